@@ -0,0 +1,108 @@
+use std::{collections::HashMap, panic};
+
+use napi::Status;
+use napi_derive::napi;
+use schematools::{
+    codegen::Codegen,
+    error::Error,
+    process::dereference::Dereferencer,
+    schema::{path_to_url, Schema},
+    storage::SchemaStorage,
+    validate, Client,
+};
+
+// errors are returned by the library, catching panics is only a last resort guard
+// against bugs which must not abort the node process
+fn call<T>(f: impl FnOnce() -> Result<T, Error>) -> napi::Result<T> {
+    panic::catch_unwind(panic::AssertUnwindSafe(f))
+        .map_err(|p| {
+            napi::Error::new(
+                Status::GenericFailure,
+                format!("processing failed: {}", panic_message(p)),
+            )
+        })?
+        .map_err(|e| napi::Error::new(Status::InvalidArg, e.to_string()))
+}
+
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_default()
+}
+
+fn load(path: &str, client: &Client) -> Result<(Schema, SchemaStorage), Error> {
+    let schema = Schema::load_url_with_client(path_to_url(path.to_string())?, client)?;
+    let storage = SchemaStorage::new(&schema, client)?;
+
+    Ok((schema, storage))
+}
+
+#[napi(object)]
+pub struct DereferenceOptions {
+    pub skip_root_internal_references: Option<bool>,
+}
+
+#[napi(object)]
+pub struct CodegenOptions {
+    pub schemas: Vec<String>,
+    pub templates: Vec<String>,
+    pub target_dir: String,
+    pub options: Option<HashMap<String, String>>,
+}
+
+/// Validates openapi or json-schema file, throws when invalid
+#[napi]
+pub fn validate(path: String) -> napi::Result<()> {
+    call(|| {
+        let schema = Schema::load_url(path_to_url(path)?)?;
+
+        if schema.get_body().get("openapi").is_some() {
+            validate::validate_openapi(&schema)
+        } else {
+            validate::validate_jsonschema(&schema)
+        }
+    })
+}
+
+/// Returns file with references replaced by their targets as json string
+#[napi]
+pub fn dereference(path: String, options: Option<DereferenceOptions>) -> napi::Result<String> {
+    call(|| {
+        let (mut schema, storage) = load(&path, &Client::new())?;
+
+        Dereferencer::options()
+            .with_skip_root_internal_references(
+                options
+                    .and_then(|o| o.skip_root_internal_references)
+                    .unwrap_or(false),
+            )
+            .process(&mut schema, &storage)?;
+
+        serde_json::to_string(schema.get_body()).map_err(Error::SerdeJsonError)
+    })
+}
+
+/// Renders templates for given schemas, returns list of generated files
+#[napi]
+pub fn codegen(opts: CodegenOptions) -> napi::Result<Vec<String>> {
+    call(|| {
+        let mut builder = Codegen::builder();
+        builder.target_dir(&opts.target_dir);
+
+        for schema in &opts.schemas {
+            builder.schema(schema);
+        }
+
+        for template in &opts.templates {
+            builder.templates(template);
+        }
+
+        for (key, value) in opts.options.unwrap_or_default() {
+            builder.option(&key, value);
+        }
+
+        builder.run()
+    })
+}
@@ -0,0 +1,103 @@
+use std::{collections::HashMap, panic};
+
+use pyo3::{exceptions::PyValueError, prelude::*};
+use schematools::{
+    codegen::Codegen,
+    error::Error,
+    process::dereference::Dereferencer,
+    schema::{path_to_url, Schema},
+    storage::SchemaStorage,
+    validate::{validate_jsonschema, validate_openapi},
+    Client,
+};
+
+// errors are returned by the library, catching panics is only a last resort guard
+// against bugs which must not abort the interpreter
+fn call<T>(f: impl FnOnce() -> Result<T, Error>) -> PyResult<T> {
+    panic::catch_unwind(panic::AssertUnwindSafe(f))
+        .map_err(|p| PyValueError::new_err(format!("processing failed: {}", panic_message(p))))?
+        .map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_default()
+}
+
+fn load(path: &str, client: &Client) -> Result<(Schema, SchemaStorage), Error> {
+    let schema = Schema::load_url_with_client(path_to_url(path.to_string())?, client)?;
+    let storage = SchemaStorage::new(&schema, client)?;
+
+    Ok((schema, storage))
+}
+
+/// Validates openapi or json-schema file, raises ValueError when invalid
+#[pyfunction]
+fn validate(path: &str) -> PyResult<()> {
+    call(|| {
+        let schema = Schema::load_url(path_to_url(path.to_string())?)?;
+
+        if schema.get_body().get("openapi").is_some() {
+            validate_openapi(&schema)
+        } else {
+            validate_jsonschema(&schema)
+        }
+    })
+}
+
+/// Returns file with references replaced by their targets as json string
+#[pyfunction]
+#[pyo3(signature = (path, skip_root_internal_references = false))]
+fn dereference(path: &str, skip_root_internal_references: bool) -> PyResult<String> {
+    call(|| {
+        let (mut schema, storage) = load(path, &Client::new())?;
+
+        Dereferencer::options()
+            .with_skip_root_internal_references(skip_root_internal_references)
+            .process(&mut schema, &storage)?;
+
+        serde_json::to_string(schema.get_body()).map_err(Error::SerdeJsonError)
+    })
+}
+
+/// Renders templates for given schemas, returns list of generated files
+#[pyfunction]
+#[pyo3(signature = (schemas, templates, target_dir, options = HashMap::new()))]
+fn codegen(
+    schemas: Vec<String>,
+    templates: Vec<String>,
+    target_dir: &str,
+    options: HashMap<String, String>,
+) -> PyResult<Vec<String>> {
+    call(|| {
+        let mut builder = Codegen::builder();
+        builder.target_dir(target_dir);
+
+        for schema in &schemas {
+            builder.schema(schema);
+        }
+
+        for template in &templates {
+            builder.templates(template);
+        }
+
+        for (key, value) in options {
+            builder.option(&key, value);
+        }
+
+        builder.run()
+    })
+}
+
+#[pymodule]
+#[pyo3(name = "schematools")]
+fn schematools_python(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(validate, m)?)?;
+    m.add_function(wrap_pyfunction!(dereference, m)?)?;
+    m.add_function(wrap_pyfunction!(codegen, m)?)?;
+
+    Ok(())
+}
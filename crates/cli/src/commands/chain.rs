@@ -1,18 +1,20 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::Serialize;
 
 use schematools::Client;
 
-use schematools::storage::SchemaStorage;
+use schematools::storage::{ResolutionCache, RetryOptions, SchemaStorage};
 use schematools::{discovery::Discovery, schema::Schema};
 
 use crate::error::Error;
 
 #[cfg(feature = "codegen")]
 use super::codegen;
+use super::hash;
 use super::process;
 use super::registry;
 use super::validate;
-use super::GetSchemaCommand;
+use super::{GetSchemaCommand, Outcome};
 
 use std::fmt::Display;
 use std::time::Instant;
@@ -30,6 +32,7 @@ pub enum ChainCommandOption {
     Process(process::Opts),
     Validate(validate::Opts),
     Registry(registry::Opts),
+    Hash(hash::Opts),
     Output(OutputOpts),
 }
 
@@ -50,15 +53,35 @@ impl Display for ChainCommandOption {
             Self::Process(p) => write!(f, "process: {p}"),
             Self::Validate(p) => write!(f, "validate: {p}"),
             Self::Registry(p) => write!(f, "registry: {p}"),
+            Self::Hash(p) => write!(f, "{p}"),
             Self::Output(p) => write!(f, "output: {p}"),
         }
     }
 }
 
-fn parse_command(cmd: &str) -> Result<ChainCommandOption, Error> {
-    let parts = schematools::tools::ArgumentsExtractor::new(cmd).collect::<Vec<String>>();
+/// Command of chain with its failure policy
+#[derive(Clone, Debug)]
+pub struct ChainStep {
+    command: ChainCommandOption,
+    continue_on_error: bool,
+}
+
+impl Display for ChainStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.command)
+    }
+}
+
+fn parse_command(cmd: &str) -> Result<ChainStep, Error> {
+    let mut parts = schematools::tools::ArgumentsExtractor::new(cmd).collect::<Vec<String>>();
 
-    match parts.first().unwrap().as_ref() {
+    // failure of step prefixed with continue-on-error is reported but doesn't stop chain
+    let continue_on_error = parts.first().is_some_and(|p| p == CONTINUE_ON_ERROR);
+    if continue_on_error {
+        parts.remove(0);
+    }
+
+    let command = match parts.first().map(String::as_str).unwrap_or_default() {
         "registry" => Ok(ChainCommandOption::Registry(
             registry::Opts::try_parse_from(parts)
                 .map_err(|e| Error::ChainWrongParameters("registry".to_string(), e))?,
@@ -76,40 +99,281 @@ fn parse_command(cmd: &str) -> Result<ChainCommandOption, Error> {
             validate::Opts::try_parse_from(parts)
                 .map_err(|e| Error::ChainWrongParameters("validate".to_string(), e))?,
         )),
+        "hash" => Ok(ChainCommandOption::Hash(
+            hash::Opts::try_parse_from(parts)
+                .map_err(|e| Error::ChainWrongParameters("hash".to_string(), e))?,
+        )),
         "output" => Ok(ChainCommandOption::Output(
             OutputOpts::try_parse_from(parts)
                 .map_err(|e| Error::ChainWrongParameters("output".to_string(), e))?,
         )),
         s => Err(Error::ChainUnknownCommand(s.to_string())),
+    }?;
+
+    Ok(ChainStep {
+        command,
+        continue_on_error,
+    })
+}
+
+const CONTINUE_ON_ERROR: &str = "continue-on-error";
+
+/// Step selected by its number (starting from 1) or name, ex. `3`, `sort`, `process: sort`
+#[derive(Clone, Debug)]
+pub struct StepSelector(String);
+
+impl StepSelector {
+    fn matches(&self, index: usize, step: &ChainStep) -> bool {
+        if let Ok(number) = self.0.parse::<usize>() {
+            return number == index + 1;
+        }
+
+        let name = step.to_string();
+
+        name == self.0 || name.split_once(": ").is_some_and(|(_, n)| n == self.0)
     }
 }
 
+fn parse_selector(s: &str) -> Result<StepSelector, String> {
+    match s.trim() {
+        "" => Err("step cannot be empty".to_string()),
+        s => Ok(StepSelector(s.to_string())),
+    }
+}
+
+fn parse_dump(s: &str) -> Result<(StepSelector, String), String> {
+    s.rsplit_once('=')
+        .filter(|(_, file)| !file.is_empty())
+        .ok_or_else(|| format!("invalid STEP=file: no `=` found in `{s}`"))
+        .and_then(|(step, file)| Ok((parse_selector(step)?, file.to_string())))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum SummaryFormat {
+    Text,
+    Json,
+    None,
+}
+
 #[derive(Debug, Parser)]
 pub struct Opts {
     #[clap(short = 'c', value_parser = parse_command, number_of_values = 1)]
-    commands: Vec<ChainCommandOption>,
+    commands: Vec<ChainStep>,
+
+    /// Format of summary printed to stderr when chain finishes
+    #[clap(long, value_enum, default_value = "text")]
+    summary: SummaryFormat,
+
+    /// Stops chain successfully after given step, selected by number or name
+    #[clap(long, value_parser = parse_selector)]
+    break_after: Option<StepSelector>,
+
+    /// Writes schema as it was after given step, ex. --dump-after 3=after-name.yaml
+    #[clap(long, value_parser = parse_dump, number_of_values = 1)]
+    dump_after: Vec<(StepSelector, String)>,
 
     #[clap(flatten)]
     verbose: crate::commands::Verbosity,
 }
 
-pub fn execute(opts: Opts, client: &Client) -> Result<(), Error> {
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum StepStatus {
+    Ok,
+    Failed,
+    // failed but step allows chain to continue
+    Ignored,
+    // not reached because of earlier failure
+    Skipped,
+}
+
+#[derive(Debug, Serialize)]
+struct StepSummary {
+    step: usize,
+    command: String,
+    status: StepStatus,
+    duration_ms: u128,
+    warnings: usize,
+    files: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct Summary {
+    success: bool,
+    duration_ms: u128,
+    warnings: usize,
+    files: usize,
+    steps: Vec<StepSummary>,
+}
+
+impl Summary {
+    fn new(steps: &[ChainStep]) -> Self {
+        Self {
+            success: false,
+            duration_ms: 0,
+            warnings: 0,
+            files: 0,
+            steps: steps
+                .iter()
+                .enumerate()
+                .map(|(i, step)| StepSummary {
+                    step: i + 1,
+                    command: step.to_string(),
+                    status: StepStatus::Skipped,
+                    duration_ms: 0,
+                    warnings: 0,
+                    files: vec![],
+                    error: None,
+                })
+                .collect(),
+        }
+    }
+
+    fn record(
+        &mut self,
+        index: usize,
+        timing: Instant,
+        result: &Result<Outcome, Error>,
+        continue_on_error: bool,
+    ) {
+        let step = &mut self.steps[index];
+        step.duration_ms = timing.elapsed().as_millis();
+
+        match result {
+            Ok(outcome) => {
+                step.status = StepStatus::Ok;
+                step.warnings = outcome.warnings;
+                step.files.clone_from(&outcome.files);
+            }
+            Err(e) => {
+                step.status = if continue_on_error {
+                    StepStatus::Ignored
+                } else {
+                    StepStatus::Failed
+                };
+                step.error = Some(e.to_string());
+            }
+        }
+    }
+
+    fn finish(&mut self, timing: Instant, success: bool) {
+        self.success = success;
+        self.duration_ms = timing.elapsed().as_millis();
+        self.warnings = self.steps.iter().map(|s| s.warnings).sum();
+        self.files = self.steps.iter().map(|s| s.files.len()).sum();
+    }
+
+    fn show(&self, format: SummaryFormat) {
+        match format {
+            SummaryFormat::Text => eprint!("{self}"),
+            SummaryFormat::Json => eprintln!("{}", serde_json::to_string_pretty(self).unwrap()),
+            SummaryFormat::None => {}
+        }
+    }
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for step in &self.steps {
+            let status = match step.status {
+                StepStatus::Ok => "\x1b[0;32mok\x1b[0m     ",
+                StepStatus::Failed => "\x1b[0;31mfailed\x1b[0m ",
+                StepStatus::Ignored => "\x1b[0;33mignored\x1b[0m",
+                StepStatus::Skipped => "skipped",
+            };
+
+            write!(
+                f,
+                "{:>3}. {status} {:>7}ms  {}",
+                step.step, step.duration_ms, step.command
+            )?;
+            if step.warnings > 0 {
+                write!(f, ", warnings: {}", step.warnings)?;
+            }
+            if !step.files.is_empty() {
+                write!(f, ", files: {}", step.files.len())?;
+            }
+            writeln!(f)?;
+
+            if let Some(error) = &step.error {
+                writeln!(f, "       {error}")?;
+            }
+        }
+
+        writeln!(
+            f,
+            "chain {}: {} steps, {} warnings, {} files, took {}ms",
+            if self.success { "succeeded" } else { "failed" },
+            self.steps.len(),
+            self.warnings,
+            self.files,
+            self.duration_ms
+        )
+    }
+}
+
+pub fn execute(opts: Opts, client: &Client, retry: &RetryOptions) -> Result<(), Error> {
     opts.verbose.start()?;
 
-    let mut schemas: Vec<(Schema, Vec<ChainCommandOption>)> = vec![];
-    let mut discovery = Discovery::default();
+    // typo in step name would silently skip debugging
+    for selector in opts
+        .break_after
+        .iter()
+        .chain(opts.dump_after.iter().map(|(selector, _)| selector))
+    {
+        if !opts
+            .commands
+            .iter()
+            .enumerate()
+            .any(|(index, step)| selector.matches(index, step))
+        {
+            return Err(Error::ChainUnknownStep(selector.0.clone()));
+        }
+    }
+
+    let timing = Instant::now();
+    let mut summary = Summary::new(&opts.commands);
+
+    let result = run(&opts, client, retry, &mut summary);
+
+    summary.finish(timing, result.is_ok());
+    summary.show(opts.summary);
+
+    result
+}
+
+fn run(
+    opts: &Opts,
+    client: &Client,
+    retry: &RetryOptions,
+    summary: &mut Summary,
+) -> Result<(), Error> {
+    let mut schemas: Vec<(Schema, Vec<(usize, &ChainStep)>)> = vec![];
+    let mut discovery = Discovery::new(client);
 
     let timing_load = Instant::now();
-    for command in opts.commands {
-        let schema = match &command {
+    for (index, step) in opts.commands.iter().enumerate() {
+        let timing_step = Instant::now();
+
+        let schema = match &step.command {
             #[cfg(feature = "codegen")]
             ChainCommandOption::Codegen(c) => c.get_schema(client),
             ChainCommandOption::Process(c) => c.get_schema(client),
             ChainCommandOption::Validate(c) => c.get_schema(client),
+            ChainCommandOption::Hash(c) => c.get_schema(client),
             ChainCommandOption::Registry(c) => {
-                c.run(&mut discovery)?;
+                let result = c
+                    .run(&mut discovery)
+                    .map(|_| Outcome::default())
+                    .map_err(Error::Schematools);
+                summary.record(index, timing_step, &result, step.continue_on_error);
 
-                Err(Error::SchemaNotApplicable)
+                match result {
+                    Err(e) if !step.continue_on_error => return Err(e),
+                    _ => Err(Error::SchemaNotApplicable),
+                }
             }
             ChainCommandOption::Output(_) => Err(Error::SchemaNotApplicable),
         };
@@ -122,12 +386,16 @@ pub fn execute(opts: Opts, client: &Client) -> Result<(), Error> {
             Err(e) => match e {
                 Error::Schematools(schematools::error::Error::SchemaAsReference) => Ok(()),
                 Error::SchemaNotApplicable => Ok(()),
-                e => Err(e),
+                e => {
+                    let result = Err(e);
+                    summary.record(index, timing_step, &result, false);
+                    result.map(|_: Outcome| ())
+                }
             },
         }?;
 
-        if let Some((_, commands)) = schemas.last_mut() {
-            commands.push(command);
+        if let Some((_, steps)) = schemas.last_mut() {
+            steps.push((index, step));
         }
     }
 
@@ -139,8 +407,12 @@ pub fn execute(opts: Opts, client: &Client) -> Result<(), Error> {
     // create resolver
     let timing_resolve = Instant::now();
 
-    let mut storage =
-        SchemaStorage::new_multi(&schemas.iter().map(|(s, _)| s).collect::<Vec<_>>(), client);
+    let cache = ResolutionCache::new(retry.clone());
+    let mut storage = SchemaStorage::new_multi_with_cache(
+        &schemas.iter().map(|(s, _)| s).collect::<Vec<_>>(),
+        client,
+        &cache,
+    )?;
 
     log::info!(
         "\x1b[1;4mresolving schema dependencies took: {:.2?}\x1b[0m",
@@ -148,32 +420,72 @@ pub fn execute(opts: Opts, client: &Client) -> Result<(), Error> {
     );
 
     let processing_time = Instant::now();
-    for (ref mut current, ref mut actions) in schemas {
-        for cmd in actions {
-            log::info!("\x1b[1;70mCHAINING: {} {}\x1b[0m", cmd, current.get_url());
+    for (ref mut current, ref mut steps) in schemas {
+        for (index, step) in steps {
+            // registries are added before schemas are loaded
+            if let ChainCommandOption::Registry(_) = step.command {
+                continue;
+            }
+
+            log::info!("\x1b[1;70mCHAINING: {} {}\x1b[0m", step, current.get_url());
             let timing_step = Instant::now();
 
-            match cmd {
+            let result = match &step.command {
                 #[cfg(feature = "codegen")]
                 ChainCommandOption::Codegen(c) => c.run(current, &discovery, &storage),
-                ChainCommandOption::Process(c) => c.run(current, &storage).inspect(|_| {
-                    storage
-                        .schemas
-                        .insert(current.get_url().clone(), current.clone());
-                }),
-                ChainCommandOption::Validate(v) => v.run(current),
+                ChainCommandOption::Process(c) => c
+                    .run(current, &storage)
+                    .inspect(|_| {
+                        // clone shares body with current, it's copied only when modified
+                        storage
+                            .schemas
+                            .insert(current.get_url().clone(), current.clone());
+                    })
+                    .map(|_| Outcome::default()),
+                ChainCommandOption::Validate(v) => {
+                    v.run(current, &storage).map(|_| Outcome::default())
+                }
+                ChainCommandOption::Hash(h) => h.run(current).map(|_| Outcome::default()),
                 ChainCommandOption::Output(o) => {
                     o.output.show(current.get_body());
-                    Ok(())
+                    Ok(Outcome {
+                        files: o.output.to_file.iter().cloned().collect(),
+                        ..Outcome::default()
+                    })
                 }
-                _ => Ok(()),
-            }?;
+                ChainCommandOption::Registry(_) => Ok(Outcome::default()),
+            };
 
             log::info!(
                 "\x1b[1;4m{} took: {:.2?}\x1b[0m",
-                cmd,
+                step,
                 timing_step.elapsed()
             );
+
+            summary.record(*index, timing_step, &result, step.continue_on_error);
+
+            match result {
+                Err(e) if !step.continue_on_error => return Err(e),
+                Err(e) => log::warn!("{step} failed, continuing: {e}"),
+                Ok(_) => {}
+            }
+
+            for (_, file) in opts
+                .dump_after
+                .iter()
+                .filter(|(selector, _)| selector.matches(*index, step))
+            {
+                dump(current, file)?;
+                summary.steps[*index].files.push(file.clone());
+                log::warn!("{step}: schema dumped to {file}");
+            }
+
+            if let Some(selector) = opts.break_after.as_ref() {
+                if selector.matches(*index, step) {
+                    log::warn!("{step}: breaking chain");
+                    return Ok(());
+                }
+            }
         }
     }
 
@@ -185,3 +497,15 @@ pub fn execute(opts: Opts, client: &Client) -> Result<(), Error> {
 
     Ok(())
 }
+
+fn dump(schema: &Schema, file: &str) -> Result<(), Error> {
+    let content = if file.ends_with(".yaml") || file.ends_with(".yml") {
+        serde_yaml::to_string(schema.get_body())
+            .map_err(|e| Error::ChainDump(file.to_string(), e.to_string()))?
+    } else {
+        serde_json::to_string_pretty(schema.get_body())
+            .map_err(|e| Error::ChainDump(file.to_string(), e.to_string()))?
+    };
+
+    std::fs::write(file, content).map_err(|e| Error::ChainDump(file.to_string(), e.to_string()))
+}
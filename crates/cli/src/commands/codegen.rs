@@ -1,19 +1,25 @@
-use schematools::codegen::jsonschema::JsonSchemaExtractOptions;
+use schematools::codegen::{
+    builder::CodegenBuilder,
+    jsonschema::{report::Report, KeepSchemaOptions},
+};
 use schematools::Client;
 use serde_json::Value;
-use std::{fmt::Display, time::Instant};
+use std::{fmt::Display, fs::File, io::Write, path::Path, time::Instant};
 
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use schematools::{
     discovery::Discovery,
     schema::{path_to_url, Schema},
-    storage::SchemaStorage,
+    storage::{ResolutionCache, RetryOptions, SchemaStorage},
 };
 
 use crate::error::Error;
-use schematools::codegen;
+use schematools::codegen::{
+    self, docs, harness::TemplateTests, ir::Ir, manifest::Manifest, profile::Profiler, project,
+    Codegen,
+};
 
-use super::GetSchemaCommand;
+use super::{GetSchemaCommand, Outcome};
 
 #[derive(Clone, Debug, Parser)]
 pub struct Opts {
@@ -26,6 +32,14 @@ impl Display for Opts {
         match &self.command {
             Command::JsonSchema(_) => write!(f, "jsonschema"),
             Command::Openapi(_) => write!(f, "openapi"),
+            Command::DumpContext(_) => write!(f, "dump-context"),
+            Command::ExportIr(_) => write!(f, "export-ir"),
+            Command::Docs(_) => write!(f, "docs"),
+            Command::Curl(_) => write!(f, "curl"),
+            Command::Render(_) => write!(f, "render"),
+            Command::TestTemplates(_) => write!(f, "test-templates"),
+            Command::Clean(_) => write!(f, "clean"),
+            Command::All(_) => write!(f, "all"),
         }
     }
 }
@@ -37,21 +51,39 @@ pub enum Command {
 
     /// Openapi
     Openapi(OpenapiOpts),
+
+    /// Writes raw render context passed to templates as json
+    DumpContext(DumpContextOpts),
+
+    /// Writes versioned extraction result (models, endpoints, security, formats) as json
+    ExportIr(ExportIrOpts),
+
+    /// Writes field tables of models and summaries of endpoints as markdown, csv or html
+    Docs(DocsOpts),
+
+    /// Writes curl snippet of every openapi endpoint with synthesized parameters and body
+    Curl(CurlOpts),
+
+    /// Renders templates from extraction result written by export-ir
+    Render(RenderOpts),
+
+    /// Renders templates against fixtures and compares output with golden files
+    TestTemplates(TestTemplatesOpts),
+
+    /// Removes files generated in target directory
+    Clean(CleanOpts),
+
+    /// Runs codegen targets described by project file
+    All(AllOpts),
 }
 
+/// Extraction flags shared by commands converting schemas to models and endpoints
 #[derive(Clone, Debug, Parser)]
-pub struct JsonSchemaOpts {
-    /// Path to json/yaml file with json-schema specification
-    pub file: Vec<String>,
-
+pub struct ExtractOpts {
     /// Wrap mixed to special wrap object which should allow to customize deserialization
     #[clap(long)]
     pub wrappers: bool,
 
-    /// Keep schema condition (allows access to original json schema in selected nodes)
-    #[clap(long, required = false)]
-    keep_schema: Vec<String>,
-
     /// Treat optional an nullable fields as models
     #[clap(long)]
     pub optional_and_nullable_as_models: bool,
@@ -60,15 +92,83 @@ pub struct JsonSchemaOpts {
     #[clap(long)]
     pub nested_arrays_as_models: bool,
 
+    /// Replace oneOf with single variant by the variant itself
+    #[clap(long)]
+    pub collapse_single_variant: bool,
+
+    /// Inline objects nested deeper than given depth are promoted
+    #[clap(long)]
+    pub flatten_depth: Option<usize>,
+
+    /// Fail on keywords which cannot be expressed by models, ex. if, not, anyOf
+    #[clap(long)]
+    pub strict: bool,
+
+    /// Rewrite nullable enums (null in enum or type, oneOf with null) to single form
+    #[clap(long)]
+    pub normalize: bool,
+
+    /// Extract typed additionalProperties next to properties as additional map of object
+    #[clap(long)]
+    pub additional_properties_model: bool,
+
+    /// Distinguish absent from null, optional nullable properties get optional_nullable presence
+    #[clap(long)]
+    pub tri_state: bool,
+
+    /// Add builder metadata (setters, required and optional properties) to objects with more properties
+    #[clap(long)]
+    pub builders_above: Option<usize>,
+
+    /// Language of property identifiers (rust, go, typescript, python) exposed as code_name
+    #[clap(long)]
+    pub code_names: Option<String>,
+
+    /// Map format decimal (and numbers with x-precision/x-scale) to decimal type instead of float
+    #[clap(long)]
+    pub decimal: bool,
+
+    /// Map date, time, date-time (with or without offset) and duration formats to distinct types
+    #[clap(long)]
+    pub temporal: bool,
+
+    /// Collect example payloads of models (own examples or composed from properties) into context
+    #[clap(long)]
+    pub examples: bool,
+
     /// Schema base name if title is absent
     #[clap(long)]
     pub base_name: Option<String>,
 
+    /// Keep schema condition (allows access to original json schema in selected nodes)
+    #[clap(long, required = false)]
+    keep_schema: Vec<String>,
+
+    /// Keyword removed from kept schemas, ex. examples or description
+    #[clap(long)]
+    keep_schema_strip: Vec<String>,
+
+    /// Sort keys of kept schemas
+    #[clap(long)]
+    keep_schema_canonical: bool,
+
+    /// Save kept schemas once in container schemas list, models refer to them by schema_index
+    #[clap(long)]
+    keep_schema_shared: bool,
+
+    /// Preferred content type used to rank endpoint content types, ex. application/vnd.company.v2+json
+    #[clap(long, required = false)]
+    content_type_preference: Vec<String>,
+}
+
+/// Rendering flags shared by commands writing templates to target directory
+#[derive(Clone, Debug, Parser)]
+pub struct TemplatesOpts {
     /// Directory with templates, name:: prefix if pointing to registry
     #[clap(long, required = true)]
     template: Vec<String>,
 
-    /// Target directory where generated files should be places
+    /// Target directory where generated files should be placed
     #[clap(long)]
     target_dir: String,
 
@@ -76,8 +176,64 @@ pub struct JsonSchemaOpts {
     #[clap(long)]
     pub format: Option<String>,
 
+    /// Command executed once after generation, receives list of generated files on stdin
+    #[clap(long)]
+    pub post_hook: Option<String>,
+
+    /// Environment variable passed to format and post hook commands
+    #[clap(long, value_parser = super::get_env, number_of_values = 1)]
+    hook_env: Vec<(String, String)>,
+
+    /// Render context version expected by templates
+    #[clap(long)]
+    context_version: Option<u32>,
+
+    /// Omit generation time from stamp so repeated generation gives identical output
+    #[clap(long)]
+    reproducible: bool,
+
+    /// Environment variable which may be read by templates using env function
+    #[clap(long)]
+    allow_env: Vec<String>,
+
+    /// Follow symlinks when discovering templates
+    #[clap(long)]
+    follow_symlinks: bool,
+
+    /// Run format, post_hook and env hooks declared by remote template packs not pinned by digest
+    #[clap(long)]
+    allow_hooks: bool,
+
+    /// Fetch remote template packs again instead of using cached ones
+    #[clap(long)]
+    no_cache: bool,
+
     #[clap(short = 'o', value_parser = super::get_options::<String>, number_of_values = 1)]
     options: Vec<(String, Value)>,
+}
+
+#[derive(Clone, Debug, Parser)]
+pub struct JsonSchemaOpts {
+    /// Path to json/yaml file with json-schema specification
+    pub file: Vec<String>,
+
+    #[clap(flatten)]
+    extract: Box<ExtractOpts>,
+
+    #[clap(flatten)]
+    templates: TemplatesOpts,
+
+    /// Print render time, context size and output size of every template
+    #[clap(long)]
+    profile_templates: bool,
+
+    /// Fail when extraction reports warnings, ex. models degraded to any type
+    #[clap(long)]
+    deny_warnings: bool,
+
+    /// Print serialized model with given name as it is passed to templates
+    #[clap(long)]
+    trace_model: Option<String>,
 
     #[clap(flatten)]
     verbose: crate::commands::Verbosity,
@@ -88,34 +244,174 @@ pub struct OpenapiOpts {
     /// Path to json/yaml file with openapi specification
     pub file: String,
 
+    #[clap(flatten)]
+    extract: Box<ExtractOpts>,
+
+    #[clap(flatten)]
+    templates: TemplatesOpts,
+
+    /// Print render time, context size and output size of every template
+    #[clap(long)]
+    profile_templates: bool,
+
+    /// Fail when extraction reports warnings, ex. models degraded to any type
+    #[clap(long)]
+    deny_warnings: bool,
+
+    /// Print serialized model with given name as it is passed to templates
+    #[clap(long)]
+    trace_model: Option<String>,
+
+    #[clap(flatten)]
+    verbose: crate::commands::Verbosity,
+}
+
+#[derive(Clone, Debug, Parser)]
+pub struct DumpContextOpts {
+    /// Path to json/yaml file with openapi or json-schema specification
+    pub file: Vec<String>,
+
+    #[clap(flatten)]
+    extract: Box<ExtractOpts>,
+
+    /// Write context to file instead of standard output
+    #[clap(long)]
+    to_file: Option<String>,
+
+    /// Render context version expected by templates
+    #[clap(long)]
+    context_version: Option<u32>,
+
+    /// Omit generation time from stamp so repeated generation gives identical output
+    #[clap(long)]
+    reproducible: bool,
+
+    #[clap(short = 'o', value_parser = super::get_options::<String>, number_of_values = 1)]
+    options: Vec<(String, Value)>,
+
+    #[clap(flatten)]
+    verbose: crate::commands::Verbosity,
+}
+
+#[derive(Clone, Debug, Parser)]
+pub struct ExportIrOpts {
+    /// Path to json/yaml file with openapi or json-schema specification
+    pub file: Vec<String>,
+
+    #[clap(flatten)]
+    extract: Box<ExtractOpts>,
+
+    /// File the extraction result is written to
+    #[clap(long)]
+    out: String,
+
+    #[clap(flatten)]
+    verbose: crate::commands::Verbosity,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum DocsFormat {
+    Markdown,
+    Csv,
+    Html,
+}
+
+impl From<DocsFormat> for docs::DocsFormat {
+    fn from(value: DocsFormat) -> Self {
+        match value {
+            DocsFormat::Markdown => Self::Markdown,
+            DocsFormat::Csv => Self::Csv,
+            DocsFormat::Html => Self::Html,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Parser)]
+pub struct DocsOpts {
+    /// Path to json/yaml file with openapi or json-schema specification
+    pub file: Vec<String>,
+
+    /// Format of documents: api.md, models.csv and endpoints.csv or static index.html
+    #[clap(long, value_enum, default_value = "markdown")]
+    format: DocsFormat,
+
+    /// Directory the documents are written to
+    #[clap(long)]
+    target_dir: String,
+
     /// Wrap mixed to special wrap object which should allow to customize deserialization
     #[clap(long)]
     wrappers: bool,
 
-    /// Treat optional an nullable fields as models
+    /// Schema base name if title is absent
     #[clap(long)]
-    pub optional_and_nullable_as_models: bool,
+    pub base_name: Option<String>,
 
-    /// Treat nested arrays as models
+    /// Preferred content type used to pick models of requests and responses
+    #[clap(long, required = false)]
+    content_type_preference: Vec<String>,
+
+    #[clap(flatten)]
+    verbose: crate::commands::Verbosity,
+}
+
+#[derive(Clone, Debug, Parser)]
+pub struct CurlOpts {
+    /// Path to json/yaml file with openapi specification
+    pub file: String,
+
+    /// Directory the snippets are written to
     #[clap(long)]
-    pub nested_arrays_as_models: bool,
+    out: String,
 
-    /// Keep schema condition (allows access to original json schema in selected nodes)
+    /// Base url prepended to paths, may refer to environment variables
+    #[clap(long, default_value = codegen::curl::DEFAULT_BASE_URL)]
+    base_url: String,
+
+    /// Preferred content type of request bodies and accepted responses
     #[clap(long, required = false)]
-    keep_schema: Vec<String>,
+    content_type_preference: Vec<String>,
 
+    #[clap(flatten)]
+    verbose: crate::commands::Verbosity,
+}
+
+#[derive(Clone, Debug, Parser)]
+pub struct RenderOpts {
+    /// File with extraction result written by export-ir
+    #[clap(long)]
+    ir: String,
+
+    #[clap(flatten)]
+    templates: TemplatesOpts,
+
+    #[clap(flatten)]
+    verbose: crate::commands::Verbosity,
+}
+
+#[derive(Clone, Debug, Parser)]
+pub struct TestTemplatesOpts {
     /// Directory with templates, name:: prefix if pointing to registry
-    #[clap(long, required = true)]
-    template: Vec<String>,
+    template: String,
 
-    /// Target directory where generated files should be placed
+    /// Directory with fixtures, each containing schema file and expected/ directory
+    fixtures: String,
+
+    /// Update golden files with rendered output
     #[clap(long)]
-    target_dir: String,
+    bless: bool,
+
+    #[clap(flatten)]
+    extract: Box<ExtractOpts>,
 
     /// Code formatting command
     #[clap(long)]
     pub format: Option<String>,
 
+    /// Render context version expected by templates
+    #[clap(long)]
+    context_version: Option<u32>,
+
     #[clap(short = 'o', value_parser = super::get_options::<String>, number_of_values = 1)]
     options: Vec<(String, Value)>,
 
@@ -123,12 +419,116 @@ pub struct OpenapiOpts {
     verbose: crate::commands::Verbosity,
 }
 
+#[derive(Clone, Debug, Parser)]
+pub struct CleanOpts {
+    /// Target directory containing generated files
+    target_dir: String,
+
+    #[clap(flatten)]
+    verbose: crate::commands::Verbosity,
+}
+
+#[derive(Clone, Debug, Parser)]
+pub struct AllOpts {
+    /// Project file with codegen targets
+    #[clap(long, default_value = project::PROJECT_FILE)]
+    config: String,
+
+    /// Runs only selected targets, all of them if none given
+    #[clap(long)]
+    target: Vec<String>,
+
+    /// Run format, post_hook and env hooks declared by remote template packs not pinned by digest
+    #[clap(long)]
+    allow_hooks: bool,
+
+    /// Fetch remote template packs again instead of using cached ones
+    #[clap(long)]
+    no_cache: bool,
+
+    #[clap(flatten)]
+    verbose: crate::commands::Verbosity,
+}
+
+impl From<&ExtractOpts> for CodegenBuilder {
+    fn from(opts: &ExtractOpts) -> Self {
+        let mut builder = Codegen::builder();
+
+        builder
+            .with_wrappers(opts.wrappers)
+            .with_optional_and_nullable_as_models(opts.optional_and_nullable_as_models)
+            .with_nested_arrays_as_models(opts.nested_arrays_as_models)
+            .with_collapse_single_variant(opts.collapse_single_variant)
+            .with_flatten_depth(opts.flatten_depth)
+            .with_strict(opts.strict)
+            .with_normalize(opts.normalize)
+            .with_additional_properties_model(opts.additional_properties_model)
+            .with_tri_state(opts.tri_state)
+            .with_builder_threshold(opts.builders_above)
+            .with_code_names(opts.code_names.clone())
+            .with_decimal(opts.decimal)
+            .with_temporal(opts.temporal)
+            .with_examples(opts.examples)
+            .with_base_name(opts.base_name.clone())
+            .with_keep_schema(opts.keep_schema.clone())
+            .with_keep_schema_options(KeepSchemaOptions {
+                strip: opts.keep_schema_strip.clone(),
+                canonical: opts.keep_schema_canonical,
+                shared: opts.keep_schema_shared,
+            });
+
+        for content_type in &opts.content_type_preference {
+            builder.content_type(content_type);
+        }
+
+        builder
+    }
+}
+
+impl TemplatesOpts {
+    /// Configures templates, target directory, hooks and render options of builder
+    fn apply(&self, builder: &mut CodegenBuilder, discovery: &Discovery) {
+        let mut discovery = discovery.clone();
+        discovery
+            .with_follow_links(self.follow_symlinks)
+            .with_allow_hooks(self.allow_hooks)
+            .with_no_cache(self.no_cache);
+
+        builder
+            .with_discovery(discovery)
+            .target_dir(&self.target_dir)
+            .with_context_version(self.context_version)
+            .with_reproducible(self.reproducible);
+
+        for template in &self.template {
+            builder.templates(template);
+        }
+        if let Some(format) = &self.format {
+            builder.format(format);
+        }
+        if let Some(post_hook) = &self.post_hook {
+            builder.post_hook(post_hook);
+        }
+        for (key, value) in &self.hook_env {
+            builder.env(key, value);
+        }
+        for name in &self.allow_env {
+            builder.allow_env(name);
+        }
+        for (key, value) in &self.options {
+            builder.option(key, value.clone());
+        }
+    }
+}
+
 impl GetSchemaCommand for Opts {
     fn get_schema(&self, client: &Client) -> Result<Schema, Error> {
         match &self.command {
-            Command::JsonSchema(opts) => {
-                let urls = opts
-                    .file
+            Command::JsonSchema(JsonSchemaOpts { file, .. })
+            | Command::DumpContext(DumpContextOpts { file, .. })
+            | Command::ExportIr(ExportIrOpts { file, .. })
+            | Command::Docs(DocsOpts { file, .. }) => {
+                let urls = file
                     .iter()
                     .map(|s| path_to_url(s.clone()))
                     .collect::<Result<Vec<_>, _>>()
@@ -136,11 +536,17 @@ impl GetSchemaCommand for Opts {
 
                 Schema::load_urls_with_client(urls, client).map_err(Error::Schematools)
             }
-            Command::Openapi(opts) => Schema::load_url_with_client(
-                path_to_url(opts.file.clone()).map_err(Error::Schematools)?,
-                client,
-            )
-            .map_err(Error::Schematools),
+            Command::Openapi(OpenapiOpts { file, .. }) | Command::Curl(CurlOpts { file, .. }) => {
+                Schema::load_url_with_client(
+                    path_to_url(file.clone()).map_err(Error::Schematools)?,
+                    client,
+                )
+                .map_err(Error::Schematools)
+            }
+            Command::Render(_)
+            | Command::TestTemplates(_)
+            | Command::Clean(_)
+            | Command::All(_) => Err(Error::SchemaNotApplicable),
         }
     }
 }
@@ -151,110 +557,385 @@ impl Opts {
         schema: &Schema,
         discovery: &Discovery,
         storage: &SchemaStorage,
-    ) -> Result<(), Error> {
+    ) -> Result<Outcome, Error> {
         match &self.command {
-            Command::JsonSchema(opts) => {
-                let timing_extraction = Instant::now();
+            Command::JsonSchema(JsonSchemaOpts {
+                extract,
+                templates,
+                profile_templates,
+                deny_warnings,
+                trace_model,
+                ..
+            })
+            | Command::Openapi(OpenapiOpts {
+                extract,
+                templates,
+                profile_templates,
+                deny_warnings,
+                trace_model,
+                ..
+            }) => {
+                let mut builder = CodegenBuilder::from(extract.as_ref());
+                builder.with_openapi(Some(matches!(self.command, Command::Openapi(_))));
+                templates.apply(&mut builder, discovery);
 
-                let models = codegen::jsonschema::extract(
-                    schema,
-                    storage,
-                    JsonSchemaExtractOptions {
-                        wrappers: opts.wrappers,
-                        optional_and_nullable_as_models: opts.optional_and_nullable_as_models,
-                        nested_arrays_as_models: opts.nested_arrays_as_models,
-                        base_name: opts.base_name.clone(),
-                        allow_list: true,
-                        keep_schema: schematools::tools::Filter::new(&opts.keep_schema)?,
-                    },
-                )?;
+                let timing_extraction = Instant::now();
+                let ir = builder.export_ir(schema, storage)?;
 
                 log::info!(
                     "\x1b[1;4mextraction took: {:.2?}\x1b[0m",
                     timing_extraction.elapsed()
                 );
 
-                let timing_rendering = Instant::now();
+                let models = ir.extracted.models();
+                let warnings = models.report().warnings().len();
+                check(models.report(), *deny_warnings)?;
 
-                let renderer = codegen::renderer::create(
-                    discovery.resolve(&opts.template)?,
-                    &[codegen::templates::TemplateType::Models],
-                    codegen::create_container(&opts.options),
-                )?;
+                if let Some(name) = trace_model {
+                    trace(models, name);
+                }
 
-                renderer
-                    .models(models, &opts.target_dir, &opts.format)
-                    .map_err(Error::Schematools)?;
+                let profiler = profile_templates.then(Profiler::default);
+                let timing_rendering = Instant::now();
+
+                let files = builder.with_profiler(profiler.clone()).render_ir(ir)?;
 
                 log::info!(
                     "\x1b[1;4mrendering took: {:.2?}\x1b[0m",
                     timing_rendering.elapsed()
                 );
 
-                Ok(())
+                if let Some(profiler) = profiler {
+                    println!("{}", profiler.report());
+                }
+
+                Ok(Outcome { warnings, files })
             }
-            Command::Openapi(opts) => {
-                let timing_extraction = Instant::now();
+            Command::DumpContext(opts) => {
+                let mut builder = CodegenBuilder::from(opts.extract.as_ref());
+
+                builder
+                    .with_context_version(opts.context_version)
+                    .with_reproducible(opts.reproducible);
+
+                for (key, value) in &opts.options {
+                    builder.option(key, value.clone());
+                }
+
+                let context =
+                    serde_json::to_string_pretty(&builder.context(schema, storage)?).unwrap();
+
+                match &opts.to_file {
+                    Some(filename) => {
+                        let mut file = File::create(filename).unwrap();
+                        file.write_all(context.as_bytes())
+                            .expect("Can't save file on disk");
+                    }
+                    None => println!("{context}"),
+                };
+
+                Ok(Outcome {
+                    files: opts.to_file.iter().cloned().collect(),
+                    ..Outcome::default()
+                })
+            }
+            Command::ExportIr(opts) => {
+                let builder = CodegenBuilder::from(opts.extract.as_ref());
+
+                builder
+                    .export_ir(schema, storage)?
+                    .save(Path::new(&opts.out))?;
+
+                Ok(Outcome {
+                    files: vec![opts.out.clone()],
+                    ..Outcome::default()
+                })
+            }
+            Command::Docs(opts) => {
+                let mut builder = Codegen::builder();
+
+                builder
+                    .with_wrappers(opts.wrappers)
+                    .with_base_name(opts.base_name.clone());
 
+                for content_type in &opts.content_type_preference {
+                    builder.content_type(content_type);
+                }
+
+                let ir = builder.export_ir(schema, storage)?;
+
+                let directory = Path::new(&opts.target_dir);
+                std::fs::create_dir_all(directory)
+                    .map_err(|e| Error::WriteFile(directory.display().to_string(), e))?;
+
+                let mut files = vec![];
+                for (name, content) in docs::render(&ir.extracted, opts.format.into()) {
+                    let path = directory.join(name);
+                    std::fs::write(&path, content)
+                        .map_err(|e| Error::WriteFile(path.display().to_string(), e))?;
+
+                    log::info!("written: {}", path.display());
+                    files.push(path.display().to_string());
+                }
+
+                Ok(Outcome {
+                    files,
+                    ..Outcome::default()
+                })
+            }
+            Command::Curl(opts) => {
                 let openapi = codegen::openapi::extract(
                     schema,
                     storage,
                     codegen::openapi::OpenapiExtractOptions {
-                        wrappers: opts.wrappers,
-                        optional_and_nullable_as_models: opts.optional_and_nullable_as_models,
-                        nested_arrays_as_models: opts.nested_arrays_as_models,
-                        keep_schema: schematools::tools::Filter::new(&opts.keep_schema)?,
+                        content_types: opts.content_type_preference.clone(),
+                        ..Default::default()
                     },
                 )?;
 
-                log::info!(
-                    "\x1b[1;4mextraction took: {:.2?}\x1b[0m",
-                    timing_extraction.elapsed()
-                );
+                std::fs::create_dir_all(&opts.out)
+                    .map_err(|e| Error::WriteFile(opts.out.clone(), e))?;
 
-                let timing_rendering = Instant::now();
+                let mut files = vec![];
+                for (name, content) in codegen::curl::render(&openapi, &opts.base_url) {
+                    let path = Path::new(&opts.out).join(name);
+                    std::fs::write(&path, content)
+                        .map_err(|e| Error::WriteFile(path.display().to_string(), e))?;
 
-                let renderer = codegen::renderer::create(
-                    discovery
-                        .resolve(&opts.template)
-                        .map_err(Error::Schematools)?,
-                    &[
-                        codegen::templates::TemplateType::Models,
-                        codegen::templates::TemplateType::Endpoints,
-                    ],
-                    codegen::create_container(&opts.options),
-                )?;
+                    log::info!("written: {}", path.display());
+                    files.push(path.display().to_string());
+                }
 
-                renderer
-                    .openapi(openapi, &opts.target_dir, &opts.format)
-                    .map_err(Error::Schematools)?;
+                Ok(Outcome {
+                    files,
+                    ..Outcome::default()
+                })
+            }
+            Command::Render(opts) => opts.run(discovery),
+            Command::TestTemplates(opts) => opts
+                .run(discovery, storage.client())
+                .map(|_| Outcome::default()),
+            Command::Clean(opts) => opts.run().map(|_| Outcome::default()),
+            Command::All(opts) => opts.run(discovery, storage.client(), storage.cache()),
+        }
+    }
+}
 
-                log::info!(
-                    "\x1b[1;4mrendering took: {:.2?}\x1b[0m",
-                    timing_rendering.elapsed()
-                );
+impl RenderOpts {
+    pub fn run(&self, discovery: &Discovery) -> Result<Outcome, Error> {
+        let mut builder = Codegen::builder();
+        self.templates.apply(&mut builder, discovery);
+
+        let files = builder.render_ir(Ir::load(Path::new(&self.ir))?)?;
+
+        Ok(Outcome {
+            files,
+            ..Outcome::default()
+        })
+    }
+}
+
+impl TestTemplatesOpts {
+    pub fn run(&self, discovery: &Discovery, client: &Client) -> Result<(), Error> {
+        let mut builder = CodegenBuilder::from(self.extract.as_ref());
+
+        builder
+            .templates(&self.template)
+            .with_context_version(self.context_version)
+            .with_reproducible(true);
+
+        if let Some(format) = &self.format {
+            builder.format(format);
+        }
+
+        for (key, value) in &self.options {
+            builder.option(key, value.clone());
+        }
 
-                Ok(())
+        let reports = TemplateTests::options().with_bless(self.bless).process(
+            &builder,
+            discovery,
+            client,
+            &self.fixtures,
+        )?;
+
+        let mut failed = 0;
+        for report in &reports {
+            if report.passed() {
+                println!("\x1b[0;32mok\x1b[0m {}", report.name);
+                continue;
             }
+
+            println!(
+                "\x1b[0;31m{}\x1b[0m {}",
+                if self.bless { "blessed" } else { "failed" },
+                report.name
+            );
+            for file in &report.changed {
+                println!("  changed: {file}");
+            }
+            for file in &report.missing {
+                println!("  missing: {file}");
+            }
+            for file in &report.unexpected {
+                println!("  unexpected: {file}");
+            }
+
+            failed += 1;
+        }
+
+        if failed > 0 && !self.bless {
+            Err(Error::TemplateTestsFailed(failed, reports.len()))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl AllOpts {
+    pub fn run(
+        &self,
+        discovery: &Discovery,
+        client: &Client,
+        cache: &ResolutionCache,
+    ) -> Result<Outcome, Error> {
+        let project = project::Project::load(Path::new(&self.config))?;
+        let mut outcome = Outcome::default();
+
+        let mut discovery = discovery.clone();
+        discovery
+            .with_allow_hooks(self.allow_hooks)
+            .with_no_cache(self.no_cache);
+
+        for (name, target) in project.select(&self.target)? {
+            let timing = Instant::now();
+
+            let files = project
+                .builder(target)?
+                .with_discovery(discovery.clone())
+                .run_with(client, cache)?;
+
+            log::info!(
+                "\x1b[1;4m{name}: {} files generated, took: {:.2?}\x1b[0m",
+                files.len(),
+                timing.elapsed()
+            );
+
+            outcome.files.extend(files);
+        }
+
+        Ok(outcome)
+    }
+}
+
+impl CleanOpts {
+    pub fn run(&self) -> Result<(), Error> {
+        for file in Manifest::clean(&self.target_dir)? {
+            println!("removed: {file}");
         }
+
+        Ok(())
     }
 }
 
-pub fn execute(opts: Opts, client: &Client) -> Result<(), Error> {
+fn check(report: &Report, deny_warnings: bool) -> Result<(), Error> {
+    if report.is_empty() {
+        return Ok(());
+    }
+
+    eprintln!("{}", report.summary());
+
+    if deny_warnings {
+        Err(Error::ExtractionWarnings(report.warnings().len()))
+    } else {
+        Ok(())
+    }
+}
+
+fn trace(models: &codegen::jsonschema::ModelContainer, name: &str) {
+    let found = codegen::profile::trace_model(models, name);
+
+    if found.is_empty() {
+        log::warn!("model {name} not found");
+    }
+
+    for model in found {
+        println!("{}", serde_json::to_string_pretty(&model).unwrap());
+    }
+}
+
+pub fn execute(opts: Opts, client: &Client, retry: &RetryOptions) -> Result<(), Error> {
+    match &opts.command {
+        Command::Render(o) => {
+            o.verbose.start()?;
+
+            return o.run(&Discovery::new(client)).map(|_| ());
+        }
+        Command::TestTemplates(o) => {
+            o.verbose.start()?;
+
+            return o.run(&Discovery::new(client), client);
+        }
+        Command::Clean(o) => {
+            o.verbose.start()?;
+
+            return o.run();
+        }
+        Command::All(o) => {
+            o.verbose.start()?;
+
+            return o
+                .run(
+                    &Discovery::new(client),
+                    client,
+                    &ResolutionCache::new(retry.clone()),
+                )
+                .map(|_| ());
+        }
+        _ => {}
+    }
+
     let schema = opts.get_schema(client)?;
-    let storage = &SchemaStorage::new(&schema, client);
-    let discovery = Discovery::default();
+    let storage = &SchemaStorage::new_multi_with_cache(
+        &[&schema],
+        client,
+        &ResolutionCache::new(retry.clone()),
+    )?;
+    let discovery = Discovery::new(client);
 
     match &opts.command {
         Command::JsonSchema(o) => {
             o.verbose.start()?;
 
-            opts.run(&schema, &discovery, storage)
+            opts.run(&schema, &discovery, storage).map(|_| ())
         }
         Command::Openapi(o) => {
             o.verbose.start()?;
 
-            opts.run(&schema, &discovery, storage)
+            opts.run(&schema, &discovery, storage).map(|_| ())
+        }
+        Command::DumpContext(o) => {
+            o.verbose.start()?;
+
+            opts.run(&schema, &discovery, storage).map(|_| ())
+        }
+        Command::ExportIr(o) => {
+            o.verbose.start()?;
+
+            opts.run(&schema, &discovery, storage).map(|_| ())
+        }
+        Command::Docs(o) => {
+            o.verbose.start()?;
+
+            opts.run(&schema, &discovery, storage).map(|_| ())
+        }
+        Command::Curl(o) => {
+            o.verbose.start()?;
+
+            opts.run(&schema, &discovery, storage).map(|_| ())
+        }
+        Command::Render(_) | Command::TestTemplates(_) | Command::Clean(_) | Command::All(_) => {
+            Ok(())
         }
     }
 }
@@ -0,0 +1,148 @@
+use clap::{Parser, Subcommand, ValueEnum};
+use schematools::infer::Inferrer;
+use schematools::scaffold::{Scaffold as Scaffolder, SecurityScheme};
+use schematools::schema::{path_to_url, Schema};
+use schematools::Client;
+
+use crate::error::Error;
+
+#[derive(Clone, Debug, Parser)]
+pub struct Opts {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Clone, Debug, Subcommand)]
+enum Command {
+    /// Starter documents following conventions of processors and codegen templates
+    #[clap(subcommand)]
+    Scaffold(Scaffold),
+
+    /// Json schema derived from example documents
+    InferSchema(InferSchemaOpts),
+}
+
+#[derive(Clone, Debug, Subcommand)]
+enum Scaffold {
+    /// Openapi specification with CRUD endpoints of given resources
+    Openapi(OpenapiOpts),
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum Security {
+    Bearer,
+    ApiKey,
+    Oauth2,
+}
+
+#[derive(Clone, Debug, Parser)]
+struct OpenapiOpts {
+    /// Title of the service
+    #[clap(long)]
+    title: String,
+
+    /// Version of the specification
+    #[clap(long, default_value = "0.1.0")]
+    version: String,
+
+    /// Plural name of resource, ex. users or order-items
+    #[clap(long)]
+    resource: Vec<String>,
+
+    /// Security scheme stubs accepted by every operation
+    #[clap(long, value_enum, default_values = ["bearer"])]
+    security: Vec<Security>,
+
+    /// Url of server
+    #[clap(long)]
+    server: Vec<String>,
+
+    #[clap(flatten)]
+    output: crate::commands::Output,
+
+    #[clap(flatten)]
+    verbose: crate::commands::Verbosity,
+}
+
+#[derive(Clone, Debug, Parser)]
+struct InferSchemaOpts {
+    /// Paths to json/yaml example documents
+    #[clap(required = true)]
+    files: Vec<String>,
+
+    /// Title of the schema
+    #[clap(long)]
+    title: Option<String>,
+
+    /// Strings with at most that many distinct, repeated values become enums, 0 disables enums
+    #[clap(long, default_value = "5")]
+    enum_threshold: usize,
+
+    /// Properties present in at least that fraction of objects are required
+    #[clap(long, default_value = "1.0")]
+    required_ratio: f64,
+
+    /// Skips detection of string formats
+    #[clap(long)]
+    no_formats: bool,
+
+    #[clap(flatten)]
+    output: crate::commands::Output,
+
+    #[clap(flatten)]
+    verbose: crate::commands::Verbosity,
+}
+
+pub fn execute(opts: Opts, client: &Client) -> Result<(), Error> {
+    match opts.command {
+        Command::Scaffold(Scaffold::Openapi(opts)) => {
+            opts.verbose.start()?;
+
+            let schema = Scaffolder::options()
+                .with_title(opts.title)
+                .with_version(opts.version)
+                .with_resources(opts.resource)
+                .with_security(
+                    opts.security
+                        .into_iter()
+                        .map(|s| match s {
+                            Security::Bearer => SecurityScheme::Bearer,
+                            Security::ApiKey => SecurityScheme::ApiKey,
+                            Security::Oauth2 => SecurityScheme::Oauth2,
+                        })
+                        .collect(),
+                )
+                .with_servers(opts.server)
+                .openapi()?;
+
+            opts.output.show(schema.get_body());
+
+            Ok(())
+        }
+        Command::InferSchema(opts) => {
+            opts.verbose.start()?;
+
+            let samples = opts
+                .files
+                .iter()
+                .map(|file| {
+                    let mut sample =
+                        Schema::load_url_with_client(path_to_url(file.clone())?, client)?;
+                    Ok(std::mem::take(sample.get_body_mut()))
+                })
+                .collect::<Result<Vec<_>, schematools::error::Error>>()?;
+
+            let schema = Inferrer::options()
+                .with_title(opts.title)
+                .with_enum_threshold(opts.enum_threshold)
+                .with_required_ratio(opts.required_ratio)
+                .with_formats(!opts.no_formats)
+                .process(&samples)?;
+
+            log::info!("schema inferred from {} samples", samples.len());
+            opts.output.show(schema.get_body());
+
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use std::fmt::Display;
+
+use clap::Parser;
+use schematools::Client;
+
+use crate::error::Error;
+use schematools::hash;
+use schematools::schema::{path_to_url, Schema};
+
+use super::GetSchemaCommand;
+
+#[derive(Clone, Debug, Parser)]
+pub struct Opts {
+    /// Path to json/yaml file with schema
+    file: String,
+
+    /// Expected digest, fails if calculated digest is different
+    #[clap(long)]
+    check: Option<String>,
+
+    #[clap(flatten)]
+    verbose: crate::commands::Verbosity,
+}
+
+impl Display for Opts {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "hash")
+    }
+}
+
+impl GetSchemaCommand for Opts {
+    fn get_schema(&self, client: &Client) -> Result<Schema, Error> {
+        Schema::load_url_with_client(
+            path_to_url(self.file.clone()).map_err(Error::Schematools)?,
+            client,
+        )
+        .map_err(Error::Schematools)
+    }
+}
+
+impl Opts {
+    pub fn run(&self, schema: &Schema) -> Result<(), Error> {
+        let calculated = format!("{:x}", hash::canonical_digest::<sha2::Sha256>(schema));
+
+        match &self.check {
+            Some(expected) if !expected.eq(&calculated) => {
+                Err(Error::HashMismatch(expected.clone(), calculated))
+            }
+            _ => {
+                println!("{calculated}");
+                Ok(())
+            }
+        }
+    }
+}
+
+pub fn execute(opts: Opts, client: &Client) -> Result<(), Error> {
+    let schema = opts.get_schema(client)?;
+
+    opts.verbose.start()?;
+    opts.run(&schema)
+}
@@ -5,14 +5,19 @@ use std::io::prelude::*;
 use clap::{Parser, ValueEnum};
 use env_logger::Builder as LoggerBuilder;
 use schematools::Client;
+use serde::Serialize;
 use serde_json::Value;
 
 pub mod chain;
 #[cfg(feature = "codegen")]
 pub mod codegen;
+pub mod generate;
+pub mod hash;
 pub mod process;
 pub mod registry;
+pub mod serve;
 pub mod validate;
+pub mod workspace;
 
 use crate::error::Error;
 use schematools::schema::Schema;
@@ -27,6 +32,13 @@ pub trait GetSchemaCommand {
     fn get_schema(&self, client: &Client) -> Result<Schema, Error>;
 }
 
+/// What a command produced, collected into chain summary
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct Outcome {
+    pub warnings: usize,
+    pub files: Vec<String>,
+}
+
 /// Parse a single key-value pair
 fn get_options<T>(
     s: &str,
@@ -48,6 +60,13 @@ where
     }
 }
 
+/// Parse a single environment variable
+fn get_env(s: &str) -> Result<(String, String), Box<dyn StdError + Send + Sync + 'static>> {
+    s.split_once('=')
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .ok_or_else(|| format!("invalid KEY=value: no `=` found in `{s}`").into())
+}
+
 #[derive(Clone, Debug, Parser)]
 pub struct Verbosity {
     /// Verbosity level, increase by multiplying v occurrences (warning, info, debug, trace)
@@ -0,0 +1,21 @@
+use clap::ValueEnum;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum Draft {
+    #[clap(name = "draft7")]
+    Draft7,
+    #[clap(name = "2019-09")]
+    Draft201909,
+    #[clap(name = "2020-12")]
+    Draft202012,
+}
+
+impl From<Draft> for schematools::process::export_schemas::Draft {
+    fn from(value: Draft) -> Self {
+        match value {
+            Draft::Draft7 => Self::Draft7,
+            Draft::Draft201909 => Self::Draft201909,
+            Draft::Draft202012 => Self::Draft202012,
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use clap::ValueEnum;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum GraphFormat {
+    Dot,
+    Mermaid,
+}
+
+impl From<GraphFormat> for schematools::process::graph::GraphFormat {
+    fn from(value: GraphFormat) -> Self {
+        match value {
+            GraphFormat::Dot => Self::Dot,
+            GraphFormat::Mermaid => Self::Mermaid,
+        }
+    }
+}
@@ -1,17 +1,21 @@
 use std::fmt::Display;
+use std::fs::File;
+use std::io::prelude::*;
 
 use crate::commands::GetSchemaCommand;
 use clap::{Parser, Subcommand};
-use schematools::storage::SchemaStorage;
+use schematools::storage::{ResolutionCache, RetryOptions, SchemaStorage};
 use schematools::tools;
 use schematools::Client;
 
 use crate::error::Error;
-use schematools::process::{dereference, merge_allof, merge_openapi, name};
+use schematools::process::{annotate, dereference, merge_allof, merge_openapi, name, servers};
 use schematools::schema::{path_to_url, Schema};
 
 #[cfg(feature = "semver")]
 pub mod bump_openapi;
+pub mod export_schemas;
+pub mod graph;
 pub mod patch;
 
 #[derive(Clone, Debug, Parser)]
@@ -29,8 +33,23 @@ impl Display for Opts {
             Command::MergeAllOf(_) => write!(f, "merge_allof"),
             Command::Dereference(_) => write!(f, "dereference"),
             Command::Name(_) => write!(f, "name"),
+            Command::NameComponents(_) => write!(f, "name_components"),
             #[cfg(feature = "json-patch")]
             Command::Patch(_) => write!(f, "patch"),
+            Command::Graph(_) => write!(f, "graph"),
+            Command::ExportSchemas(_) => write!(f, "export_schemas"),
+            Command::ImportSchemas(_) => write!(f, "import_schemas"),
+            Command::Usages(_) => write!(f, "usages"),
+            Command::RenameSchema(_) => write!(f, "rename_schema"),
+            Command::SetServers(_) => write!(f, "set_servers"),
+            Command::Redact(_) => write!(f, "redact"),
+            Command::Sort(_) => write!(f, "sort"),
+            Command::Flatten(_) => write!(f, "flatten"),
+            Command::Normalize(_) => write!(f, "normalize"),
+            Command::Include(_) => write!(f, "include"),
+            Command::Compose(_) => write!(f, "compose"),
+            Command::Transform(_) => write!(f, "transform"),
+            Command::Annotate(_) => write!(f, "annotate"),
         }
     }
 }
@@ -53,9 +72,54 @@ pub enum Command {
     /// Create missing titles for all schemas in openapi specification file
     Name(NameOpts),
 
+    /// Create missing titles for inline schemas of openapi components only
+    NameComponents(NameComponentsOpts),
+
     // Apply json patch to schema
     #[cfg(feature = "json-patch")]
     Patch(PatchOpts),
+
+    /// Exports dependency graph between schemas
+    Graph(GraphOpts),
+
+    /// Writes each of components/schemas as standalone json schema file
+    ExportSchemas(ExportSchemasOpts),
+
+    /// Inserts json schema files from directory into components/schemas
+    ImportSchemas(ImportSchemasOpts),
+
+    /// Lists json pointers referencing given node
+    Usages(UsagesOpts),
+
+    /// Renames component schema and rewrites all references to it
+    RenameSchema(RenameSchemaOpts),
+
+    /// Replaces or appends servers of openapi specification
+    SetServers(SetServersOpts),
+
+    /// Strips or masks sensitive content before publishing specification
+    Redact(RedactOpts),
+
+    /// Orders paths, methods, components and keys of openapi specification
+    Sort(SortOpts),
+
+    /// Promotes nested inline objects to named definitions
+    Flatten(FlattenOpts),
+
+    /// Rewrites nullable enums to single form: type, enum without null and nullable
+    Normalize(NormalizeOpts),
+
+    /// Inlines external documentation files, ex. markdown, into descriptions
+    Include(IncludeOpts),
+
+    /// Assembles specification from fragments referenced by $include directives
+    Compose(ComposeOpts),
+
+    /// Applies JMESPath expressions to the document, ex. `merge(@, {servers: servers[:1]})`
+    Transform(TransformOpts),
+
+    /// Backfills extensions of components (ex. x-owner) from mapping file
+    Annotate(AnnotateOpts),
 }
 
 #[derive(Clone, Debug, Parser)]
@@ -116,6 +180,10 @@ pub struct MergeAllOfOpts {
     #[clap(long, required = false)]
     filter: Vec<String>,
 
+    /// Fail with pointer of node using keywords which codegen cannot express
+    #[clap(long)]
+    strict: bool,
+
     #[clap(flatten)]
     output: crate::commands::Output,
 
@@ -140,6 +208,10 @@ pub struct DereferenceOpts {
     #[clap(long)]
     skip_references: Vec<String>,
 
+    /// Fail with pointer of node using keywords which codegen cannot express
+    #[clap(long)]
+    strict: bool,
+
     #[clap(flatten)]
     output: crate::commands::Output,
 
@@ -176,6 +248,26 @@ pub struct NameOpts {
     verbose: crate::commands::Verbosity,
 }
 
+#[derive(Clone, Debug, Parser)]
+pub struct NameComponentsOpts {
+    /// Path to json/yaml file with openapi specification
+    file: String,
+
+    /// Should overwrite existing titles
+    #[clap(long)]
+    overwrite: bool,
+
+    /// Should overwrite ambiguous titles
+    #[clap(long)]
+    overwrite_ambiguous: bool,
+
+    #[clap(flatten)]
+    output: crate::commands::Output,
+
+    #[clap(flatten)]
+    verbose: crate::commands::Verbosity,
+}
+
 #[derive(Clone, Debug, Parser)]
 pub struct PatchOpts {
     /// Path to json/yaml file with schema
@@ -191,6 +283,297 @@ pub struct PatchOpts {
     verbose: crate::commands::Verbosity,
 }
 
+#[derive(Clone, Debug, Parser)]
+pub struct GraphOpts {
+    /// Path to json/yaml file with schema
+    file: String,
+
+    /// Format of generated graph
+    #[clap(long, value_enum, default_value = "dot")]
+    format: graph::GraphFormat,
+
+    /// Skip references to external files
+    #[clap(long)]
+    skip_external: bool,
+
+    /// Path of output file, default output to stdout
+    #[clap(long)]
+    to_file: Option<String>,
+
+    #[clap(flatten)]
+    verbose: crate::commands::Verbosity,
+}
+
+#[derive(Clone, Debug, Parser)]
+pub struct ExportSchemasOpts {
+    /// Path to json/yaml file with openapi specification
+    file: String,
+
+    /// Directory where json schema files are saved
+    #[clap(long)]
+    out: String,
+
+    /// Json schema draft of exported files
+    #[clap(long, value_enum, default_value = "draft7")]
+    draft: export_schemas::Draft,
+
+    #[clap(flatten)]
+    verbose: crate::commands::Verbosity,
+}
+
+#[derive(Clone, Debug, Parser)]
+pub struct ImportSchemasOpts {
+    /// Directory with json/yaml json schema files
+    dir: String,
+
+    /// Path to json/yaml file with openapi specification
+    #[clap(long)]
+    into: String,
+
+    /// Converts json schema draft keywords to openapi 3.0 compatible form
+    #[clap(long)]
+    convert: bool,
+
+    /// Should overwrite existing schemas with the same name
+    #[clap(long)]
+    overwrite: bool,
+
+    #[clap(flatten)]
+    output: crate::commands::Output,
+
+    #[clap(flatten)]
+    verbose: crate::commands::Verbosity,
+}
+
+#[derive(Clone, Debug, Parser)]
+pub struct UsagesOpts {
+    /// Path to json/yaml file with schema
+    file: String,
+
+    /// Json pointer of referenced node, ex. /components/schemas/Foo
+    #[clap(long)]
+    pointer: String,
+
+    /// Include usages of components which depend on referenced node
+    #[clap(long)]
+    transitive: bool,
+
+    #[clap(flatten)]
+    verbose: crate::commands::Verbosity,
+}
+
+#[derive(Clone, Debug, Parser)]
+pub struct RenameSchemaOpts {
+    /// Path to json/yaml file with openapi specification
+    file: String,
+
+    /// Current name of schema in components/schemas
+    old_name: String,
+
+    /// New name of schema
+    new_name: String,
+
+    /// Directory with files referencing the specification which should be updated in place
+    #[clap(long)]
+    dir: Option<String>,
+
+    #[clap(flatten)]
+    output: crate::commands::Output,
+
+    #[clap(flatten)]
+    verbose: crate::commands::Verbosity,
+}
+
+#[derive(Clone, Debug, Parser)]
+pub struct SetServersOpts {
+    /// Path to json/yaml file with openapi specification
+    file: String,
+
+    /// Server url, may be used multiple times
+    #[clap(long, required = true)]
+    url: Vec<String>,
+
+    /// Description of server, n-th description belongs to n-th url
+    #[clap(long)]
+    description: Vec<String>,
+
+    /// Should append to existing servers instead of replacing them
+    #[clap(long)]
+    append: bool,
+
+    /// Variable substituted in {name} placeholders of url and description, ex. --variable env=staging
+    #[clap(long, value_parser = crate::commands::get_env, number_of_values = 1)]
+    variable: Vec<(String, String)>,
+
+    #[clap(flatten)]
+    output: crate::commands::Output,
+
+    #[clap(flatten)]
+    verbose: crate::commands::Verbosity,
+}
+
+#[derive(Clone, Debug, Parser)]
+pub struct RedactOpts {
+    /// Path to json/yaml file with openapi specification
+    file: String,
+
+    /// Regex of x-* extension names to remove
+    #[clap(long)]
+    strip_extension: Vec<String>,
+
+    /// Regex of descriptions to remove
+    #[clap(long)]
+    strip_description: Vec<String>,
+
+    /// Extension flagging properties which examples should be masked
+    #[clap(long, default_value = "x-sensitive")]
+    sensitive_flag: String,
+
+    /// Operations with given tag are removed
+    #[clap(long)]
+    remove_tag: Vec<String>,
+
+    #[clap(flatten)]
+    output: crate::commands::Output,
+
+    #[clap(flatten)]
+    verbose: crate::commands::Verbosity,
+}
+
+#[derive(Clone, Debug, Parser)]
+pub struct SortOpts {
+    /// Path to json/yaml file with openapi specification
+    file: String,
+
+    #[clap(flatten)]
+    output: crate::commands::Output,
+
+    #[clap(flatten)]
+    verbose: crate::commands::Verbosity,
+}
+
+#[derive(Clone, Debug, Parser)]
+pub struct FlattenOpts {
+    /// Path to json/yaml file with openapi specification or json schema
+    file: String,
+
+    /// Inline objects nested deeper than given depth are promoted
+    #[clap(long, default_value = "1")]
+    depth: usize,
+
+    /// Fail with pointer of node using keywords which codegen cannot express
+    #[clap(long)]
+    strict: bool,
+
+    #[clap(flatten)]
+    output: crate::commands::Output,
+
+    #[clap(flatten)]
+    verbose: crate::commands::Verbosity,
+}
+
+#[derive(Clone, Debug, Parser)]
+pub struct NormalizeOpts {
+    /// Path to json/yaml file with openapi specification or json schema
+    file: String,
+
+    /// Fail with pointer of node using keywords which codegen cannot express
+    #[clap(long)]
+    strict: bool,
+
+    #[clap(flatten)]
+    output: crate::commands::Output,
+
+    #[clap(flatten)]
+    verbose: crate::commands::Verbosity,
+}
+
+#[derive(Clone, Debug, Parser)]
+pub struct IncludeOpts {
+    /// Path to json/yaml file with openapi specification or json schema
+    file: String,
+
+    /// Extension holding path of included file
+    #[clap(long, default_value = "x-include")]
+    keyword: String,
+
+    /// Field receiving content of included file
+    #[clap(long, default_value = "description")]
+    target: String,
+
+    #[clap(flatten)]
+    output: crate::commands::Output,
+
+    #[clap(flatten)]
+    verbose: crate::commands::Verbosity,
+}
+
+#[derive(Clone, Debug, Parser)]
+pub struct ComposeOpts {
+    /// Path to json/yaml file with root of openapi specification or json schema
+    file: String,
+
+    /// Directive holding path (or list of paths) of included fragments
+    #[clap(long, default_value = "$include")]
+    keyword: String,
+
+    #[clap(flatten)]
+    output: crate::commands::Output,
+
+    #[clap(flatten)]
+    verbose: crate::commands::Verbosity,
+}
+
+impl ComposeOpts {
+    fn compose(&self, schema: &mut Schema, client: &Client) -> Result<(), Error> {
+        let count = ::schematools::process::compose::Composer::options()
+            .with_keyword(self.keyword.clone())
+            .process(schema, client)?;
+
+        if count > 0 {
+            log::info!("{}: {} fragments included", self.file, count);
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Parser)]
+pub struct TransformOpts {
+    /// Path to json/yaml file with openapi specification or json schema
+    file: String,
+
+    /// Expression applied to the document, may be repeated to run expressions one after another
+    #[clap(long, required = true)]
+    expr: Vec<String>,
+
+    #[clap(flatten)]
+    output: crate::commands::Output,
+
+    #[clap(flatten)]
+    verbose: crate::commands::Verbosity,
+}
+
+#[derive(Clone, Debug, Parser)]
+pub struct AnnotateOpts {
+    /// Path to json/yaml file with openapi specification
+    file: String,
+
+    /// Path to yaml/json file mapping globs of component names to extensions
+    #[clap(long)]
+    mapping: std::path::PathBuf,
+
+    /// Should replace values of extensions which are already set
+    #[clap(long)]
+    overwrite: bool,
+
+    #[clap(flatten)]
+    output: crate::commands::Output,
+
+    #[clap(flatten)]
+    verbose: crate::commands::Verbosity,
+}
+
 impl GetSchemaCommand for Opts {
     fn get_schema(&self, client: &Client) -> Result<Schema, Error> {
         match &self.command {
@@ -229,19 +612,179 @@ impl GetSchemaCommand for Opts {
                 client,
             )
             .map_err(Error::Schematools),
+            Command::NameComponents(opts) => Schema::load_url_with_client(
+                path_to_url(opts.file.clone()).map_err(Error::Schematools)?,
+                client,
+            )
+            .map_err(Error::Schematools),
             #[cfg(feature = "json-patch")]
             Command::Patch(opts) => Schema::load_url_with_client(
                 path_to_url(opts.file.clone()).map_err(Error::Schematools)?,
                 client,
             )
             .map_err(Error::Schematools),
+            Command::Graph(opts) => Schema::load_url_with_client(
+                path_to_url(opts.file.clone()).map_err(Error::Schematools)?,
+                client,
+            )
+            .map_err(Error::Schematools),
+            Command::ExportSchemas(opts) => Schema::load_url_with_client(
+                path_to_url(opts.file.clone()).map_err(Error::Schematools)?,
+                client,
+            )
+            .map_err(Error::Schematools),
+            Command::ImportSchemas(opts) => Schema::load_url_with_client(
+                path_to_url(opts.into.clone()).map_err(Error::Schematools)?,
+                client,
+            )
+            .map_err(Error::Schematools),
+            Command::Usages(opts) => Schema::load_url_with_client(
+                path_to_url(opts.file.clone()).map_err(Error::Schematools)?,
+                client,
+            )
+            .map_err(Error::Schematools),
+            Command::RenameSchema(opts) => Schema::load_url_with_client(
+                path_to_url(opts.file.clone()).map_err(Error::Schematools)?,
+                client,
+            )
+            .map_err(Error::Schematools),
+            Command::SetServers(opts) => Schema::load_url_with_client(
+                path_to_url(opts.file.clone()).map_err(Error::Schematools)?,
+                client,
+            )
+            .map_err(Error::Schematools),
+            Command::Redact(opts) => Schema::load_url_with_client(
+                path_to_url(opts.file.clone()).map_err(Error::Schematools)?,
+                client,
+            )
+            .map_err(Error::Schematools),
+            Command::Sort(opts) => Schema::load_url_with_client(
+                path_to_url(opts.file.clone()).map_err(Error::Schematools)?,
+                client,
+            )
+            .map_err(Error::Schematools),
+            Command::Flatten(opts) => Schema::load_url_with_client(
+                path_to_url(opts.file.clone()).map_err(Error::Schematools)?,
+                client,
+            )
+            .map_err(Error::Schematools),
+            Command::Normalize(opts) => Schema::load_url_with_client(
+                path_to_url(opts.file.clone()).map_err(Error::Schematools)?,
+                client,
+            )
+            .map_err(Error::Schematools),
+            Command::Include(opts) => Schema::load_url_with_client(
+                path_to_url(opts.file.clone()).map_err(Error::Schematools)?,
+                client,
+            )
+            .map_err(Error::Schematools),
+            // fragments are included before anything else, so references of fragments
+            // are resolved by storage
+            Command::Compose(opts) => {
+                let mut schema = Schema::load_url_with_client(
+                    path_to_url(opts.file.clone()).map_err(Error::Schematools)?,
+                    client,
+                )?;
+                opts.compose(&mut schema, client)?;
+
+                Ok(schema)
+            }
+            Command::Transform(opts) => Schema::load_url_with_client(
+                path_to_url(opts.file.clone()).map_err(Error::Schematools)?,
+                client,
+            )
+            .map_err(Error::Schematools),
+            Command::Annotate(opts) => Schema::load_url_with_client(
+                path_to_url(opts.file.clone()).map_err(Error::Schematools)?,
+                client,
+            )
+            .map_err(Error::Schematools),
+        }
+    }
+}
+
+impl Command {
+    // processors walking schemas may reject keywords codegen cannot express
+    fn strict(&self) -> bool {
+        match self {
+            Command::MergeAllOf(opts) => opts.strict,
+            Command::Dereference(opts) => opts.strict,
+            Command::Flatten(opts) => opts.strict,
+            Command::Normalize(opts) => opts.strict,
+            _ => false,
         }
     }
 }
 
 impl Opts {
     pub fn run(&self, schema: &mut Schema, storage: &SchemaStorage) -> Result<(), Error> {
+        if self.command.strict() {
+            ::schematools::process::strict::check(schema)?;
+        }
+
         match &self.command {
+            Command::SetServers(opts) => {
+                let servers = opts
+                    .url
+                    .iter()
+                    .enumerate()
+                    .map(|(i, url)| servers::Server {
+                        url: url.clone(),
+                        description: opts.description.get(i).cloned(),
+                    })
+                    .collect();
+
+                servers::ServersSetter::options()
+                    .with_servers(servers)
+                    .with_append(opts.append)
+                    .with_variables(opts.variable.iter().cloned().collect())
+                    .process(schema)
+                    .map_err(Error::Schematools)
+            }
+            Command::Redact(opts) => ::schematools::process::redact::Redactor::options()
+                .with_extensions(&opts.strip_extension)?
+                .with_descriptions(&opts.strip_description)?
+                .with_flag(opts.sensitive_flag.clone())
+                .with_tags(opts.remove_tag.clone())
+                .process(schema)
+                .map_err(Error::Schematools),
+            Command::Sort(_) => ::schematools::process::sort::Sorter::options()
+                .process(schema)
+                .map_err(Error::Schematools),
+            Command::Flatten(opts) => ::schematools::process::flatten::Flattener::options()
+                .with_depth(opts.depth)
+                .process(schema)
+                .map_err(Error::Schematools),
+            Command::Normalize(opts) => {
+                let count =
+                    ::schematools::process::normalize::Normalizer::options().process(schema);
+                log::info!("{}: {} nodes normalized", opts.file, count);
+
+                Ok(())
+            }
+            Command::Include(opts) => {
+                let count = ::schematools::process::include::Includer::options()
+                    .with_keyword(opts.keyword.clone())
+                    .with_target(opts.target.clone())
+                    .process(schema, storage)?;
+                log::info!("{}: {} files included", opts.file, count);
+
+                Ok(())
+            }
+            Command::Compose(opts) => opts.compose(schema, storage.client()),
+            Command::Transform(opts) => ::schematools::process::transform::Transformer::options()
+                .with_expressions(&opts.expr)?
+                .process(schema)
+                .map_err(Error::Schematools),
+            Command::Annotate(opts) => {
+                let mapping = annotate::Mapping::load(&opts.mapping)?;
+                let count = annotate::Annotator::options(mapping)
+                    .with_overwrite(opts.overwrite)
+                    .process(schema)?;
+                log::info!("{}: {} extensions added", opts.file, count);
+
+                Ok(())
+            }
             Command::MergeAllOf(opts) => {
                 merge_allof::Merger::options()
                     .with_leave_invalid_properties(opts.leave_invalid_properties)
@@ -250,7 +793,7 @@ impl Opts {
                 Ok(())
             }
             Command::MergeOpenapi(opts) => {
-                let merge = Schema::load_url(path_to_url(opts.with.clone())?)?;
+                let merge = storage.load(path_to_url(opts.with.clone())?)?;
 
                 merge_openapi::Merger::options(merge)
                     .with_retag(opts.retag.clone())
@@ -260,21 +803,19 @@ impl Opts {
             }
             #[cfg(feature = "semver")]
             Command::BumpOpenapi(opts) => {
-                let original = Schema::load_url(path_to_url(opts.original.clone())?)?;
+                let original = storage.load(path_to_url(opts.original.clone())?)?;
 
                 ::schematools::process::bump_openapi::Bumper::options(original)
                     .with_kind(opts.kind.into())
                     .process(schema)
                     .map_err(Error::Schematools)
             }
-            Command::Dereference(opts) => {
-                dereference::Dereferencer::options()
-                    .with_skip_root_internal_references(opts.skip_root_internal_references)
-                    .with_create_internal_references(opts.create_internal_references)
-                    .with_skip_references(opts.skip_references.clone())
-                    .process(schema, storage);
-                Ok(())
-            }
+            Command::Dereference(opts) => dereference::Dereferencer::options()
+                .with_skip_root_internal_references(opts.skip_root_internal_references)
+                .with_create_internal_references(opts.create_internal_references)
+                .with_skip_references(opts.skip_references.clone())
+                .process(schema, storage)
+                .map_err(Error::Schematools),
             Command::Name(opts) => {
                 //name::JsonSchemaNamer::options()
                 //    .with_base_name(opts.base_name.clone())
@@ -288,18 +829,167 @@ impl Opts {
                     .process(schema)
                     .map_err(Error::Schematools)
             }
+            Command::NameComponents(opts) => name::ComponentsNamer::options()
+                .with_overwrite(opts.overwrite)
+                .with_overwrite_ambiguous(opts.overwrite_ambiguous)
+                .process(schema)
+                .map_err(Error::Schematools),
             #[cfg(feature = "json-patch")]
             Command::Patch(opts) => {
                 let action = opts.action.clone().into();
                 ::schematools::process::patch::execute(schema, &action).map_err(Error::Schematools)
             }
+            Command::Graph(opts) => {
+                let graph = ::schematools::process::graph::Grapher::options()
+                    .with_external(!opts.skip_external)
+                    .process(schema)?
+                    .render(opts.format.into());
+
+                match &opts.to_file {
+                    Some(filename) => {
+                        let mut file = File::create(filename).unwrap();
+                        file.write_all(graph.as_bytes())
+                            .expect("Can't save file on disk");
+                    }
+                    None => println!("{graph}"),
+                };
+
+                Ok(())
+            }
+            Command::ExportSchemas(opts) => {
+                let schemas = ::schematools::process::export_schemas::Exporter::options()
+                    .with_draft(opts.draft.into())
+                    .process(schema)?;
+
+                let out = std::path::Path::new(&opts.out);
+                std::fs::create_dir_all(out).map_err(|e| Error::WriteFile(opts.out.clone(), e))?;
+
+                for (name, body) in schemas {
+                    let path = out.join(format!("{name}.json"));
+                    std::fs::write(&path, serde_json::to_string_pretty(&body).unwrap())
+                        .map_err(|e| Error::WriteFile(path.display().to_string(), e))?;
+                }
+
+                Ok(())
+            }
+            Command::ImportSchemas(opts) => {
+                let mut schemas = std::collections::BTreeMap::new();
+                for path in schema_files(&opts.dir)? {
+                    let Some(name) = path.file_stem() else {
+                        continue;
+                    };
+                    let loaded = storage.load(path_to_url(path.to_string_lossy().to_string())?)?;
+
+                    schemas.insert(
+                        name.to_string_lossy().to_string(),
+                        loaded.get_body().clone(),
+                    );
+                }
+
+                let collisions = ::schematools::process::import_schemas::Importer::options()
+                    .with_convert(opts.convert)
+                    .with_overwrite(opts.overwrite)
+                    .process(schema, schemas)?;
+
+                for name in collisions {
+                    eprintln!(
+                        "Name collision: components/schemas/{} {}",
+                        name,
+                        if opts.overwrite {
+                            "overwritten"
+                        } else {
+                            "skipped"
+                        }
+                    );
+                }
+
+                Ok(())
+            }
+            Command::Usages(opts) => {
+                let usages = ::schematools::process::usages::Usages::options()
+                    .with_transitive(opts.transitive)
+                    .process(schema, &opts.pointer)?;
+
+                for usage in usages {
+                    println!(
+                        "{}{} -> #{}",
+                        "  ".repeat(usage.depth),
+                        usage.pointer,
+                        usage.target
+                    );
+                }
+
+                Ok(())
+            }
+            Command::RenameSchema(opts) => {
+                let renamer = ::schematools::process::rename_schema::Renamer::options(
+                    opts.old_name.clone(),
+                    opts.new_name.clone(),
+                );
+
+                let count = renamer.process(schema)?;
+                log::info!("{}: {} references renamed", opts.file, count);
+
+                if let Some(dir) = &opts.dir {
+                    let document = std::path::Path::new(&opts.file);
+
+                    // every file is rewritten in memory first, so failed read leaves
+                    // directory untouched
+                    let mut renamed = vec![];
+                    for path in schema_files(dir)?
+                        .into_iter()
+                        .filter(|p| p.canonicalize().ok() != document.canonicalize().ok())
+                    {
+                        let text = std::fs::read_to_string(&path)
+                            .map_err(|e| Error::ReadFile(path.display().to_string(), e))?;
+                        let base = path.parent().unwrap_or(std::path::Path::new("."));
+                        let (result, count) = renamer.process_text(&text, base, document);
+
+                        if count > 0 {
+                            renamed.push((path, result, count));
+                        }
+                    }
+
+                    for (path, result, count) in renamed {
+                        std::fs::write(&path, result)
+                            .map_err(|e| Error::WriteFile(path.display().to_string(), e))?;
+                        log::info!("{}: {} references renamed", path.display(), count);
+                    }
+                }
+
+                Ok(())
+            }
         }
     }
 }
 
-pub fn execute(opts: Opts, client: &Client) -> Result<(), Error> {
+// json and yaml files of directory sorted by name
+fn schema_files(dir: &str) -> Result<Vec<std::path::PathBuf>, Error> {
+    let mut paths = std::fs::read_dir(dir)
+        .and_then(|entries| {
+            entries
+                .map(|e| e.map(|e| e.path()))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .map_err(|e| Error::ReadDirectory(dir.to_string(), e))?
+        .into_iter()
+        .filter(|p| {
+            p.extension()
+                .is_some_and(|e| e == "json" || e == "yaml" || e == "yml")
+        })
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    Ok(paths)
+}
+
+pub fn execute(opts: Opts, client: &Client, retry: &RetryOptions) -> Result<(), Error> {
     let mut schema = opts.get_schema(client)?;
-    let storage = &SchemaStorage::new(&schema, client);
+    let storage = &SchemaStorage::new_multi_with_cache(
+        &[&schema],
+        client,
+        &ResolutionCache::new(retry.clone()),
+    )?;
 
     // todo: ...
     match &opts.command {
@@ -339,12 +1029,108 @@ pub fn execute(opts: Opts, client: &Client) -> Result<(), Error> {
 
             Ok(())
         }
+        Command::NameComponents(o) => {
+            o.verbose.start()?;
+            opts.run(&mut schema, storage)?;
+            o.output.show(schema.get_body());
+
+            Ok(())
+        }
         #[cfg(feature = "json-patch")]
         Command::Patch(o) => {
             o.verbose.start()?;
             opts.run(&mut schema, storage)?;
             o.output.show(schema.get_body());
 
+            Ok(())
+        }
+        Command::Graph(o) => {
+            o.verbose.start()?;
+            opts.run(&mut schema, storage)
+        }
+        Command::ExportSchemas(o) => {
+            o.verbose.start()?;
+            opts.run(&mut schema, storage)
+        }
+        Command::ImportSchemas(o) => {
+            o.verbose.start()?;
+            opts.run(&mut schema, storage)?;
+            o.output.show(schema.get_body());
+
+            Ok(())
+        }
+        Command::Usages(o) => {
+            o.verbose.start()?;
+            opts.run(&mut schema, storage)
+        }
+        Command::Sort(o) => {
+            o.verbose.start()?;
+            opts.run(&mut schema, storage)?;
+            o.output.show(schema.get_body());
+
+            Ok(())
+        }
+        Command::Flatten(o) => {
+            o.verbose.start()?;
+            opts.run(&mut schema, storage)?;
+            o.output.show(schema.get_body());
+
+            Ok(())
+        }
+        Command::Normalize(o) => {
+            o.verbose.start()?;
+            opts.run(&mut schema, storage)?;
+            o.output.show(schema.get_body());
+
+            Ok(())
+        }
+        Command::Include(o) => {
+            o.verbose.start()?;
+            opts.run(&mut schema, storage)?;
+            o.output.show(schema.get_body());
+
+            Ok(())
+        }
+        Command::Compose(o) => {
+            o.verbose.start()?;
+            opts.run(&mut schema, storage)?;
+            o.output.show(schema.get_body());
+
+            Ok(())
+        }
+        Command::Transform(o) => {
+            o.verbose.start()?;
+            opts.run(&mut schema, storage)?;
+            o.output.show(schema.get_body());
+
+            Ok(())
+        }
+        Command::Annotate(o) => {
+            o.verbose.start()?;
+            opts.run(&mut schema, storage)?;
+            o.output.show(schema.get_body());
+
+            Ok(())
+        }
+        Command::Redact(o) => {
+            o.verbose.start()?;
+            opts.run(&mut schema, storage)?;
+            o.output.show(schema.get_body());
+
+            Ok(())
+        }
+        Command::SetServers(o) => {
+            o.verbose.start()?;
+            opts.run(&mut schema, storage)?;
+            o.output.show(schema.get_body());
+
+            Ok(())
+        }
+        Command::RenameSchema(o) => {
+            o.verbose.start()?;
+            opts.run(&mut schema, storage)?;
+            o.output.show(schema.get_body());
+
             Ok(())
         }
     }
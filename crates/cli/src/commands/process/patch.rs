@@ -19,6 +19,9 @@ pub enum Action {
 
     /// Apply inline patch
     Inline(PatchInlineOpts),
+
+    /// Apply inline patch to each node matched by selector
+    Select(PatchSelectOpts),
 }
 
 #[derive(Clone, Debug, Parser)]
@@ -46,12 +49,29 @@ pub struct PatchInlineOpts {
     value: Option<Value>,
 }
 
+#[derive(Clone, Debug, Parser)]
+pub struct PatchSelectOpts {
+    /// Operation add/remove/replace
+    op: Operation,
+
+    /// Path of nodes to patch, ex. /paths/*/*[has=responses][missing=x-owner]
+    selector: String,
+
+    /// Json path relative to matched node
+    path: String,
+
+    /// Json value, strings may contain {{method}}, {{path}}, {{pointer}}, {{name}} or {{N}} placeholders
+    #[clap(value_parser)]
+    value: Option<Value>,
+}
+
 impl From<Action> for schematools::process::patch::Action {
     fn from(value: Action) -> Self {
         match value {
             Action::Create(c) => Self::Create(c.into()),
             Action::Apply(a) => Self::Apply(a.into()),
             Action::Inline(i) => Self::Inline(i.into()),
+            Action::Select(s) => Self::Select(s.into()),
         }
     }
 }
@@ -80,6 +100,17 @@ impl From<PatchInlineOpts> for schematools::process::patch::PatchInlineOpts {
     }
 }
 
+impl From<PatchSelectOpts> for schematools::process::patch::PatchSelectOpts {
+    fn from(value: PatchSelectOpts) -> Self {
+        Self {
+            op: value.op.into(),
+            selector: value.selector,
+            path: value.path,
+            value: value.value,
+        }
+    }
+}
+
 impl From<Operation> for schematools::process::patch::Operation {
     fn from(value: Operation) -> Self {
         match value {
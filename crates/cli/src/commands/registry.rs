@@ -3,7 +3,7 @@ use std::{fmt::Display, fs};
 use clap::Parser;
 
 #[cfg(feature = "git2")]
-use schematools::discovery::discover_git;
+use schematools::discovery::{discover_git_with, GitFetchOptions};
 use schematools::{
     discovery::{Discovery, GitCheckoutType, Registry},
     error::Error,
@@ -20,6 +20,9 @@ impl Display for Opts {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match &self.command {
             Command::Add(_) => write!(f, "add"),
+            Command::List => write!(f, "list"),
+            Command::Show(opts) => write!(f, "show {}", opts.name),
+            Command::Verify => write!(f, "verify"),
         }
     }
 }
@@ -28,6 +31,21 @@ impl Display for Opts {
 enum Command {
     /// Adds registry
     Add(AddOpts),
+
+    /// Lists registered registries
+    List,
+
+    /// Shows source, resolved path and commit of registry
+    Show(ShowOpts),
+
+    /// Recalculates checksums of locked registries and compares them with locks
+    Verify,
+}
+
+#[derive(Clone, Debug, Parser)]
+struct ShowOpts {
+    /// Registry name
+    name: String,
 }
 
 #[derive(Clone, Debug, Parser)]
@@ -57,6 +75,14 @@ struct AddOpts {
     /// Skip cache during checkout
     #[clap(long)]
     no_cache: bool,
+
+    /// Subdirectory used as registry root, git registries check out only this directory
+    #[clap(long)]
+    subdir: Option<String>,
+
+    /// Depth of shallow git clone
+    #[clap(long)]
+    depth: Option<i32>,
 }
 
 impl Opts {
@@ -75,17 +101,50 @@ impl Opts {
                 }?;
 
                 if let Some(lock) = &opts.lock {
-                    log::info!("calculating registry hash...");
-
-                    let calculated =
-                        format!("{:x}", hash::calculate::<sha2::Sha256>(&registry.path)?);
+                    verify(&registry, lock)?;
+                }
 
-                    if !calculated.eq(lock) {
-                        return Err(Error::DiscoveryInvalidLock(lock.clone(), calculated));
+                discovery.register(opts.name.clone(), registry.with_lock(opts.lock.clone()));
+            }
+            Command::List => {
+                for (name, registry) in discovery.registries() {
+                    println!("{name}\t{}", registry.path.to_string_lossy());
+                }
+            }
+            Command::Show(opts) => {
+                let registry = discovery
+                    .get(&opts.name)
+                    .ok_or_else(|| Error::DiscoveryNoRegistry(opts.name.clone()))?;
+
+                let show =
+                    |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_string());
+
+                println!("name: {}", opts.name);
+                println!("source: {}", show(&registry.source));
+                println!("reference: {}", show(&registry.reference));
+                println!("path: {}", registry.path.to_string_lossy());
+                println!("commit: {}", show(&registry.commit));
+                println!("lock: {}", show(&registry.lock));
+            }
+            Command::Verify => {
+                let mut failed = None;
+
+                for (name, registry) in discovery.registries() {
+                    match &registry.lock {
+                        Some(lock) => match verify(registry, lock) {
+                            Ok(()) => println!("ok {name}"),
+                            Err(e) => {
+                                println!("failed {name}: {e}");
+                                failed.get_or_insert(e);
+                            }
+                        },
+                        None => println!("unlocked {name}"),
                     }
                 }
 
-                discovery.register(opts.name.clone(), registry);
+                if let Some(e) = failed {
+                    return Err(e);
+                }
             }
         }
 
@@ -93,13 +152,30 @@ impl Opts {
     }
 }
 
+fn verify(registry: &Registry, lock: &str) -> Result<(), Error> {
+    log::info!("calculating registry hash...");
+
+    let calculated = format!("{:x}", hash::calculate::<sha2::Sha256>(&registry.path)?);
+
+    if calculated.eq(lock) {
+        Ok(())
+    } else {
+        Err(Error::DiscoveryInvalidLock(lock.to_string(), calculated))
+    }
+}
+
 fn add_local_registry(opts: &AddOpts) -> Result<Registry, Error> {
     let path = fs::canonicalize(&opts.uri).map_err(Error::RegistryLocalIoError)?;
 
+    let path = match &opts.subdir {
+        Some(subdir) => fs::canonicalize(path.join(subdir)).map_err(Error::RegistryLocalIoError)?,
+        None => path,
+    };
+
     let md = fs::metadata(&path).map_err(Error::RegistryLocalIoError)?;
 
     if md.is_dir() {
-        Ok(Registry::new(path))
+        Ok(Registry::new(path).with_source(&opts.uri, None))
     } else {
         Err(Error::RegistryLocalPathNotDirError(path))
     }
@@ -117,7 +193,15 @@ fn add_git_registry(opts: &AddOpts) -> Result<Registry, Error> {
         Err(Error::RegistryMissingRevTagBranch)
     }?;
 
-    let registry = discover_git(&opts.uri, checkout, opts.no_cache)?;
+    let registry = discover_git_with(
+        &opts.uri,
+        checkout,
+        opts.no_cache,
+        &GitFetchOptions {
+            depth: opts.depth,
+            subdirectory: opts.subdir.clone(),
+        },
+    )?;
 
     Ok(registry)
 }
@@ -0,0 +1,547 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    path::PathBuf,
+    sync::{mpsc, Arc, Mutex},
+    time::{Duration, Instant, SystemTime},
+};
+
+use clap::Parser;
+use schematools::{
+    hash,
+    process::dereference::Dereferencer,
+    schema::{path_to_url, Schema},
+    storage::{ResolutionCache, RetryOptions, SchemaStorage},
+    validate, Client,
+};
+use serde_json::{json, Value};
+
+use crate::error::Error;
+
+// request line and headers, larger requests are rejected
+const MAX_HEAD_SIZE: u64 = 16 * 1024;
+
+// slow or idle clients are disconnected
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+// connections handled at the same time, others wait in queue
+const WORKERS: usize = 8;
+
+// responses kept in memory, least recently used are evicted
+const CACHE_CAPACITY: usize = 128;
+
+// loaded roots referencing remote documents are reloaded after this time
+const REMOTE_TTL: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Debug, Parser)]
+pub struct Opts {
+    /// Registered schema root as name=path, ex. orders=specs/orders.yaml
+    #[clap(long = "root", value_parser = super::get_env, number_of_values = 1, required = true)]
+    roots: Vec<(String, String)>,
+
+    /// Address the service listens on
+    #[clap(long, default_value = "127.0.0.1:8080")]
+    bind: String,
+
+    #[clap(flatten)]
+    verbose: crate::commands::Verbosity,
+}
+
+struct Response {
+    status: u16,
+    body: Value,
+    etag: Option<String>,
+}
+
+impl Response {
+    fn new(status: u16, body: Value) -> Self {
+        Self {
+            status,
+            body,
+            etag: None,
+        }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self::new(status, json!({ "error": message }))
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            304 => "Not Modified",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            422 => "Unprocessable Entity",
+            431 => "Request Header Fields Too Large",
+            _ => "Internal Server Error",
+        }
+    }
+}
+
+// content digest of root with its references and route
+type CacheKey = (String, String);
+
+// responses ordered from least to most recently used
+struct Cache {
+    entries: VecDeque<(CacheKey, (u16, Value))>,
+    capacity: usize,
+}
+
+impl Cache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    fn get(&mut self, key: &CacheKey) -> Option<(u16, Value)> {
+        let position = self.entries.iter().position(|(k, _)| k == key)?;
+        let entry = self.entries.remove(position)?;
+        let value = entry.1.clone();
+        self.entries.push_back(entry);
+
+        Some(value)
+    }
+
+    fn insert(&mut self, key: CacheKey, value: (u16, Value)) {
+        // responses of previous content are not needed anymore
+        self.entries.retain(|((_, route), _)| route != &key.1);
+
+        if self.entries.len() >= self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back((key, value));
+    }
+}
+
+// root with all referenced documents, reused until one of local files changes
+struct Loaded {
+    schema: Schema,
+    storage: SchemaStorage,
+    digest: String,
+    modified: Vec<(PathBuf, Option<SystemTime>)>,
+    remote: bool,
+    loaded_at: Instant,
+}
+
+impl Loaded {
+    fn fresh(&self) -> bool {
+        (!self.remote || self.loaded_at.elapsed() < REMOTE_TTL)
+            && self
+                .modified
+                .iter()
+                .all(|(path, time)| modified(path) == *time)
+    }
+}
+
+fn modified(path: &PathBuf) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+// shared by worker threads handling connections
+#[derive(Clone)]
+struct Service {
+    roots: Arc<Vec<(String, String)>>,
+    client: Client,
+    retry: RetryOptions,
+    cache: Arc<Mutex<Cache>>,
+    loaded: Arc<Mutex<HashMap<String, Arc<Loaded>>>>,
+}
+
+impl Service {
+    fn handle(&self, method: &str, target: &str, if_none_match: Option<&str>) -> Response {
+        if method != "GET" {
+            return Response::error(405, "only GET requests are supported");
+        }
+
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+        match path.trim_matches('/').split('/').collect::<Vec<_>>()[..] {
+            ["roots"] => Response::new(
+                200,
+                Value::Array(
+                    self.roots
+                        .iter()
+                        .map(|(name, location)| json!({"name": name, "location": location}))
+                        .collect(),
+                ),
+            ),
+            ["roots", name, action @ ("resolve" | "bundle" | "validate")] => {
+                match self.roots.iter().find(|(n, _)| n == name) {
+                    Some((_, location)) => self
+                        .root(location, action, query, if_none_match)
+                        .unwrap_or_else(|e| Response::error(500, &e.to_string())),
+                    None => Response::error(404, &format!("root {name} is not registered")),
+                }
+            }
+            _ => Response::error(404, "unknown endpoint"),
+        }
+    }
+
+    fn root(
+        &self,
+        location: &str,
+        action: &str,
+        query: &str,
+        if_none_match: Option<&str>,
+    ) -> Result<Response, Error> {
+        let loaded = self.load(location)?;
+        let etag = format!("\"{}\"", loaded.digest);
+
+        if if_none_match == Some(etag.as_str()) {
+            return Ok(Response {
+                status: 304,
+                body: Value::Null,
+                etag: Some(etag),
+            });
+        }
+
+        let key = (
+            loaded.digest.clone(),
+            format!("{location}/{action}?{query}"),
+        );
+
+        let cached = self.cache.lock().unwrap().get(&key);
+        let (status, body) = match cached {
+            Some(cached) => {
+                log::debug!("cached: {} {}", location, key.1);
+                cached
+            }
+            None => {
+                let result = run(action, query, loaded.schema.clone(), &loaded.storage)?;
+                self.cache.lock().unwrap().insert(key, result.clone());
+                result
+            }
+        };
+
+        Ok(Response {
+            status,
+            body,
+            etag: Some(etag),
+        })
+    }
+
+    // local files are checked on every request, so changes are visible without restart
+    fn load(&self, location: &str) -> Result<Arc<Loaded>, Error> {
+        if let Some(loaded) = self.loaded.lock().unwrap().get(location) {
+            if loaded.fresh() {
+                return Ok(loaded.clone());
+            }
+        }
+
+        let schema =
+            Schema::load_url_with_client(path_to_url(location.to_string())?, &self.client)?;
+        let storage = SchemaStorage::new_multi_with_cache(
+            &[&schema],
+            &self.client,
+            &ResolutionCache::new(self.retry.clone()),
+        )?;
+
+        let (local, remote): (Vec<_>, Vec<_>) = storage
+            .schemas
+            .keys()
+            .map(|url| url.to_file_path().ok())
+            .partition(Option::is_some);
+
+        let loaded = Arc::new(Loaded {
+            digest: format!("{:x}", hash::storage_digest::<sha2::Sha256>(&storage)),
+            modified: local
+                .into_iter()
+                .flatten()
+                .map(|path| {
+                    let time = modified(&path);
+                    (path, time)
+                })
+                .collect(),
+            remote: !remote.is_empty(),
+            loaded_at: Instant::now(),
+            schema,
+            storage,
+        });
+
+        self.loaded
+            .lock()
+            .unwrap()
+            .insert(location.to_string(), loaded.clone());
+
+        Ok(loaded)
+    }
+}
+
+fn run(
+    action: &str,
+    query: &str,
+    mut schema: Schema,
+    storage: &SchemaStorage,
+) -> Result<(u16, Value), Error> {
+    let response = match action {
+        "validate" => {
+            let result = if schema.get_body().get("openapi").is_some() {
+                validate::validate_openapi(&schema)
+            } else {
+                validate::validate_jsonschema(&schema)
+            };
+
+            match result {
+                Ok(_) => (200, json!({"valid": true})),
+                Err(e) => (422, json!({"valid": false, "error": e.to_string()})),
+            }
+        }
+        // external references are inlined, internal ones are kept
+        "bundle" => {
+            Dereferencer::options()
+                .with_skip_root_internal_references(true)
+                .process(&mut schema, storage)?;
+
+            (200, schema.into_body())
+        }
+        _ => {
+            Dereferencer::options().process(&mut schema, storage)?;
+
+            let pointer = query
+                .split('&')
+                .find_map(|p| p.strip_prefix("pointer="))
+                .map(|p| percent_encoding::percent_decode_str(p).decode_utf8_lossy())
+                .unwrap_or_default();
+
+            match schema.get_body().pointer(&pointer) {
+                Some(node) => (200, node.clone()),
+                None => (404, json!({ "error": format!("{pointer} not found") })),
+            }
+        }
+    };
+
+    Ok(response)
+}
+
+fn respond(stream: &TcpStream, service: &Service) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+
+    let mut reader = BufReader::new(stream.take(MAX_HEAD_SIZE));
+
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Ok(());
+    }
+
+    let mut parts = line.split_whitespace();
+    let (method, target) = (
+        parts.next().unwrap_or_default(),
+        parts.next().unwrap_or_default(),
+    );
+
+    let mut if_none_match = None;
+    let complete = loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 {
+            break false;
+        }
+        if header.trim().is_empty() {
+            break true;
+        }
+
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("if-none-match") {
+                if_none_match = Some(value.trim().to_string());
+            }
+        }
+    };
+
+    log::info!("{} {}", method, target);
+
+    let response = if reader.get_ref().limit() == 0 {
+        Response::error(431, "request line and headers are too large")
+    } else if !complete {
+        Response::error(400, "incomplete request")
+    } else {
+        service.handle(method, target, if_none_match.as_deref())
+    };
+    let body = match response.status {
+        304 => String::new(),
+        _ => serde_json::to_string_pretty(&response.body).unwrap(),
+    };
+
+    let mut head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n",
+        response.status,
+        response.reason(),
+        body.len()
+    );
+
+    if let Some(etag) = &response.etag {
+        head.push_str(&format!("ETag: {etag}\r\n"));
+    }
+
+    let mut writer = stream;
+    writer.write_all(format!("{head}\r\n{body}").as_bytes())?;
+    writer.flush()
+}
+
+pub fn execute(opts: Opts, client: &Client, retry: &RetryOptions) -> Result<(), Error> {
+    opts.verbose.start()?;
+
+    let listener = TcpListener::bind(&opts.bind).map_err(Error::ServeError)?;
+    log::warn!("listening on: {}", opts.bind);
+
+    let service = Service {
+        roots: Arc::new(opts.roots.clone()),
+        client: client.clone(),
+        retry: retry.clone(),
+        cache: Arc::new(Mutex::new(Cache::new(CACHE_CAPACITY))),
+        loaded: Arc::new(Mutex::new(HashMap::new())),
+    };
+
+    let (sender, receiver) = mpsc::channel::<TcpStream>();
+    let receiver = Arc::new(Mutex::new(receiver));
+
+    for _ in 0..WORKERS {
+        let (service, receiver) = (service.clone(), receiver.clone());
+
+        std::thread::spawn(move || loop {
+            let stream = match receiver.lock().unwrap().recv() {
+                Ok(stream) => stream,
+                Err(_) => break,
+            };
+
+            if let Err(e) = respond(&stream, &service) {
+                log::error!("cannot respond: {}", e);
+            }
+        });
+    }
+
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                if sender.send(stream).is_err() {
+                    log::error!("no worker available");
+                }
+            }
+            Err(e) => log::error!("connection failed: {}", e),
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn service(location: &str) -> Service {
+        Service {
+            roots: Arc::new(vec![("pets".to_string(), location.to_string())]),
+            client: Client::new(),
+            retry: RetryOptions::default(),
+            cache: Arc::new(Mutex::new(Cache::new(2))),
+            loaded: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    fn spec() -> String {
+        let path = std::env::temp_dir().join("schematools-serve-test.json");
+        let body = json!({
+            "definitions": {
+                "name": {"type": "string"},
+                "pet": {"type": "object", "properties": {"name": {"$ref": "#/definitions/name"}}}
+            }
+        });
+        std::fs::write(&path, body.to_string()).unwrap();
+
+        path.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn test_handle_roots() {
+        let response = service("pets.json").handle("GET", "/roots", None);
+
+        assert_eq!(response.status, 200);
+        assert_eq!(
+            response.body,
+            json!([{"name": "pets", "location": "pets.json"}])
+        );
+    }
+
+    #[test]
+    fn test_handle_resolve() {
+        let service = service(&spec());
+
+        let response = service.handle("GET", "/roots/pets/resolve?pointer=/definitions/pet", None);
+        assert_eq!(response.status, 200);
+        assert_eq!(
+            response.body,
+            json!({"type": "object", "properties": {"name": {"type": "string"}}})
+        );
+
+        let etag = response.etag.unwrap();
+        let response = service.handle(
+            "GET",
+            "/roots/pets/resolve?pointer=/definitions/pet",
+            Some(&etag),
+        );
+        assert_eq!(response.status, 304);
+
+        let response = service.handle("GET", "/roots/pets/resolve?pointer=/missing", None);
+        assert_eq!(response.status, 404);
+    }
+
+    #[test]
+    fn test_loaded_root_is_reused_until_modified() {
+        let path = std::env::temp_dir().join("schematools-serve-reload.json");
+        std::fs::write(&path, json!({"type": "string"}).to_string()).unwrap();
+        let service = service(&path.to_string_lossy());
+
+        let first = service.load(&path.to_string_lossy()).unwrap();
+        let second = service.load(&path.to_string_lossy()).unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(!first.remote);
+
+        std::fs::write(&path, json!({"type": "integer"}).to_string()).unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(10))
+            .unwrap();
+
+        let reloaded = service.load(&path.to_string_lossy()).unwrap();
+        assert!(!Arc::ptr_eq(&first, &reloaded));
+        assert_eq!(reloaded.schema.get_body(), &json!({"type": "integer"}));
+    }
+
+    #[test]
+    fn test_handle_not_found() {
+        let service = service("pets.json");
+
+        assert_eq!(
+            service.handle("GET", "/roots/cats/resolve", None).status,
+            404
+        );
+        assert_eq!(service.handle("GET", "/unknown", None).status, 404);
+        assert_eq!(service.handle("POST", "/roots", None).status, 405);
+    }
+
+    #[test]
+    fn test_cache_is_bounded() {
+        let mut cache = Cache::new(2);
+        let key = |route: &str| ("digest".to_string(), route.to_string());
+
+        cache.insert(key("a"), (200, json!(1)));
+        cache.insert(key("b"), (200, json!(2)));
+        assert!(cache.get(&key("a")).is_some());
+        cache.insert(key("c"), (200, json!(3)));
+
+        assert!(cache.get(&key("b")).is_none());
+        assert!(cache.get(&key("a")).is_some());
+        assert_eq!(cache.entries.len(), 2);
+
+        // new content of the same route replaces previous response
+        cache.insert(("other".to_string(), "a".to_string()), (200, json!(4)));
+        assert!(cache.get(&key("a")).is_none());
+        assert_eq!(cache.entries.len(), 2);
+    }
+}
@@ -1,11 +1,13 @@
 use std::fmt::Display;
 
-use clap::Parser;
+use clap::{Parser, ValueEnum};
+use schematools::storage::{ResolutionCache, RetryOptions, SchemaStorage};
 use schematools::Client;
 
 use crate::error::Error;
 use schematools::schema::{path_to_url, Schema};
 use schematools::validate;
+use schematools::validate::{baseline, compat, governance};
 
 use super::GetSchemaCommand;
 
@@ -20,6 +22,7 @@ impl Display for Opts {
         match &self.command {
             Command::Openapi(_) => write!(f, "openapi"),
             Command::JsonSchema(_) => write!(f, "jsonschema"),
+            Command::Compat(_) => write!(f, "compat"),
         }
     }
 }
@@ -31,6 +34,9 @@ enum Command {
 
     /// Performs json-schema specification validation
     JsonSchema(JsonSchemaOpts),
+
+    /// Checks compatibility of schemas with previous version of openapi specification
+    Compat(CompatOpts),
 }
 
 #[derive(Clone, Debug, Parser)]
@@ -38,6 +44,14 @@ struct OpenapiOpts {
     /// Path to json/yaml file of openapi specification
     file: String,
 
+    /// Requires governance extensions (x-owner, x-pii, x-classification) on every component
+    #[clap(long)]
+    governance: bool,
+
+    /// Extension required on every component, may be repeated, replaces governance defaults
+    #[clap(long)]
+    require_extension: Vec<String>,
+
     /// Should continue on error
     #[clap(long)]
     pub continue_on_error: bool,
@@ -59,6 +73,52 @@ struct JsonSchemaOpts {
     verbose: crate::commands::Verbosity,
 }
 
+#[derive(Clone, Debug, Parser)]
+struct CompatOpts {
+    /// Path to json/yaml file of openapi specification
+    file: String,
+
+    /// Path to previous version of openapi specification
+    #[clap(long)]
+    against: String,
+
+    /// Compatibility mode of evolution
+    #[clap(long, default_value = "backward")]
+    mode: CompatMode,
+
+    /// Should compare operations, parameters, request and response bodies too
+    #[clap(long)]
+    endpoints: bool,
+
+    /// Path to yaml/json file of acknowledged incompatibilities which don't fail validation
+    #[clap(long)]
+    baseline: Option<std::path::PathBuf>,
+
+    /// Should continue on error
+    #[clap(long)]
+    pub continue_on_error: bool,
+
+    #[clap(flatten)]
+    verbose: crate::commands::Verbosity,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum CompatMode {
+    Backward,
+    Forward,
+    Full,
+}
+
+impl From<CompatMode> for compat::CompatMode {
+    fn from(value: CompatMode) -> Self {
+        match value {
+            CompatMode::Backward => Self::Backward,
+            CompatMode::Forward => Self::Forward,
+            CompatMode::Full => Self::Full,
+        }
+    }
+}
+
 impl GetSchemaCommand for Opts {
     fn get_schema(&self, client: &Client) -> Result<Schema, Error> {
         match &self.command {
@@ -72,17 +132,65 @@ impl GetSchemaCommand for Opts {
                 client,
             )
             .map_err(Error::Schematools),
+            Command::Compat(opts) => Schema::load_url_with_client(
+                path_to_url(opts.file.clone()).map_err(Error::Schematools)?,
+                client,
+            )
+            .map_err(Error::Schematools),
         }
     }
 }
 
 impl Opts {
-    pub fn run(&self, schema: &Schema) -> Result<(), Error> {
+    pub fn run(&self, schema: &Schema, storage: &SchemaStorage) -> Result<(), Error> {
         match &self.command {
-            Command::Openapi(_) => validate::validate_openapi(schema).map_err(Error::Schematools),
+            Command::Openapi(opts) => {
+                validate::validate_openapi(schema).map_err(Error::Schematools)?;
+
+                let required = match (opts.require_extension.is_empty(), opts.governance) {
+                    (false, _) => opts.require_extension.clone(),
+                    (true, true) => governance::GOVERNANCE.map(String::from).to_vec(),
+                    (true, false) => vec![],
+                };
+                let violations = governance::governance(schema.get_body(), &required);
+                for violation in &violations {
+                    log::error!("{violation}");
+                }
+
+                match violations.is_empty() {
+                    true => Ok(()),
+                    false => Err(Error::Schematools(
+                        schematools::error::Error::SchemaValidation(schema.get_url().to_string()),
+                    )),
+                }
+            }
             Command::JsonSchema(_) => {
                 validate::validate_jsonschema(schema).map_err(Error::Schematools)
             }
+            Command::Compat(opts) => {
+                let against = storage.load(path_to_url(opts.against.clone())?)?;
+
+                let violations = compat::Compat::options(against)
+                    .with_mode(opts.mode.into())
+                    .with_endpoints(opts.endpoints)
+                    .check(schema);
+                let violations = match &opts.baseline {
+                    Some(path) => {
+                        baseline::Baseline::load(path)?.apply(violations, &baseline::today())
+                    }
+                    None => violations,
+                };
+                for violation in &violations {
+                    log::error!("{violation}");
+                }
+
+                match violations.is_empty() {
+                    true => Ok(()),
+                    false => Err(Error::Schematools(
+                        schematools::error::Error::SchemaValidation(schema.get_url().to_string()),
+                    )),
+                }
+            }
         }
         .inspect(|_| log::info!("\x1b[0;32mSuccessful validation!\x1b[0m"))
         .or_else(|e| {
@@ -100,21 +208,31 @@ impl Opts {
         match &self.command {
             Command::Openapi(o) => o.continue_on_error,
             Command::JsonSchema(o) => o.continue_on_error,
+            Command::Compat(o) => o.continue_on_error,
         }
     }
 }
 
-pub fn execute(opts: Opts, client: &Client) -> Result<(), Error> {
+pub fn execute(opts: Opts, client: &Client, retry: &RetryOptions) -> Result<(), Error> {
     let schema = opts.get_schema(client)?;
+    let storage = &SchemaStorage::new_multi_with_cache(
+        &[&schema],
+        client,
+        &ResolutionCache::new(retry.clone()),
+    )?;
 
     match &opts.command {
         Command::Openapi(o) => {
             o.verbose.start()?;
-            opts.run(&schema)
+            opts.run(&schema, storage)
         }
         Command::JsonSchema(o) => {
             o.verbose.start()?;
-            opts.run(&schema)
+            opts.run(&schema, storage)
+        }
+        Command::Compat(o) => {
+            o.verbose.start()?;
+            opts.run(&schema, storage)
         }
     }
 }
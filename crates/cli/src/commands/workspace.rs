@@ -0,0 +1,111 @@
+use std::fs::File;
+use std::io::prelude::*;
+use std::path::PathBuf;
+
+use clap::Parser;
+use schematools::process::graph;
+use schematools::storage::{ResolutionCache, RetryOptions, SchemaStorage};
+use schematools::validate::boundaries::BoundaryPolicy;
+use schematools::workspace::Workspace;
+use schematools::Client;
+
+use crate::error::Error;
+
+#[derive(Clone, Debug, Parser)]
+pub struct Opts {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Clone, Debug, Parser)]
+enum Command {
+    /// Validates all specifications of directory together with references between them
+    Validate(ValidateOpts),
+
+    /// Writes combined dependency graph of components of all specifications
+    Graph(GraphOpts),
+}
+
+#[derive(Clone, Debug, Parser)]
+struct ValidateOpts {
+    /// Directory with openapi specifications
+    dir: PathBuf,
+
+    /// Path to yaml/json policy of references allowed between files
+    #[clap(long)]
+    boundaries: Option<PathBuf>,
+
+    #[clap(flatten)]
+    verbose: crate::commands::Verbosity,
+}
+
+#[derive(Clone, Debug, Parser)]
+struct GraphOpts {
+    /// Directory with openapi specifications
+    dir: PathBuf,
+
+    /// Format of generated graph
+    #[clap(long, value_enum, default_value = "dot")]
+    format: crate::commands::process::graph::GraphFormat,
+
+    /// Path of output file, default output to stdout
+    #[clap(long)]
+    to_file: Option<String>,
+
+    #[clap(flatten)]
+    verbose: crate::commands::Verbosity,
+}
+
+pub fn execute(opts: Opts, client: &Client, retry: &RetryOptions) -> Result<(), Error> {
+    match opts.command {
+        Command::Validate(o) => {
+            o.verbose.start()?;
+
+            let workspace = Workspace::load(&o.dir, client)?;
+            let storage = SchemaStorage::new_multi_with_cache(
+                &[],
+                client,
+                &ResolutionCache::new(retry.clone()),
+            )?;
+
+            let mut violations = workspace.validate(&storage);
+            if let Some(path) = &o.boundaries {
+                violations.extend(workspace.boundaries(&BoundaryPolicy::load(path)?));
+            }
+
+            for violation in &violations {
+                log::error!("{violation}");
+            }
+
+            match violations.is_empty() {
+                true => {
+                    log::info!(
+                        "\x1b[0;32mSuccessful validation of {} specifications!\x1b[0m",
+                        workspace.specs.len()
+                    );
+                    Ok(())
+                }
+                false => Err(Error::Schematools(
+                    schematools::error::Error::SchemaValidation(o.dir.display().to_string()),
+                )),
+            }
+        }
+        Command::Graph(o) => {
+            o.verbose.start()?;
+
+            let format: graph::GraphFormat = o.format.into();
+            let graph = Workspace::load(&o.dir, client)?.graph()?.render(format);
+
+            match &o.to_file {
+                Some(filename) => {
+                    let mut file = File::create(filename).unwrap();
+                    file.write_all(graph.as_bytes())
+                        .expect("Can't save file on disk");
+                }
+                None => println!("{graph}"),
+            };
+
+            Ok(())
+        }
+    }
+}
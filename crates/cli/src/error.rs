@@ -11,9 +11,36 @@ pub enum Error {
     #[error("Unknown command: {0}")]
     ChainUnknownCommand(String),
 
+    #[error("Chain has no step {0}")]
+    ChainUnknownStep(String),
+
+    #[error("Cannot dump schema to {0}: {1}")]
+    ChainDump(String, String),
+
+    #[error("Cannot read directory {0}: {1}")]
+    ReadDirectory(String, std::io::Error),
+
+    #[error("Cannot read file {0}: {1}")]
+    ReadFile(String, std::io::Error),
+
+    #[error("Cannot write file {0}: {1}")]
+    WriteFile(String, std::io::Error),
+
     #[error("Schema not applicable")]
     SchemaNotApplicable,
 
+    #[error("Schema hash {1} doesnt match expected {0}")]
+    HashMismatch(String, String),
+
+    #[error("Template tests failed: {0} of {1} fixtures")]
+    TemplateTestsFailed(usize, usize),
+
+    #[error("Extraction produced {0} warnings")]
+    ExtractionWarnings(usize),
+
+    #[error("Cannot start service: {0}")]
+    ServeError(std::io::Error),
+
     #[error("Cannot start logger: {0}")]
     LoggerStart(String),
 }
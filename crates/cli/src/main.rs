@@ -1,5 +1,7 @@
+use std::{path::PathBuf, time::Duration};
+
 use clap::{Parser, Subcommand};
-use schematools::Client;
+use schematools::{client::ClientOptions, storage::RetryOptions};
 
 pub mod commands;
 pub mod error;
@@ -9,6 +11,40 @@ pub mod error;
 struct Opts {
     #[clap(subcommand)]
     command: Command,
+
+    #[clap(flatten)]
+    http: HttpOpts,
+}
+
+#[derive(Parser)]
+struct HttpOpts {
+    /// Proxy used to fetch schemas and remote templates, ex. http://proxy:3128
+    #[clap(long, global = true)]
+    proxy: Option<String>,
+
+    /// PEM file with additional trusted root certificates
+    #[clap(long, global = true)]
+    cacert: Option<PathBuf>,
+
+    /// Accept invalid certificates of remote schemas
+    #[clap(long, global = true)]
+    insecure: bool,
+
+    /// Timeout of http requests in seconds
+    #[clap(long, global = true)]
+    timeout: Option<u64>,
+
+    /// Connect timeout of http requests in seconds
+    #[clap(long, global = true)]
+    connect_timeout: Option<u64>,
+
+    /// Number of retries of transient failures when resolving remote references
+    #[clap(long, global = true, default_value = "0")]
+    retries: u32,
+
+    /// Delay before first retry in milliseconds, doubled after every attempt
+    #[clap(long, global = true, default_value = "500")]
+    retry_backoff: u64,
 }
 
 #[derive(Subcommand)]
@@ -25,18 +61,54 @@ enum Command {
 
     // Chain different operations in one process
     Chain(commands::chain::Opts),
+
+    /// Generation of new documents
+    Generate(commands::generate::Opts),
+
+    /// Canonical schema hash which ignores formatting and annotations
+    Hash(commands::hash::Opts),
+
+    /// Http service resolving, bundling and validating registered schemas, roots are
+    /// loaded again when local files change and at most once a minute when they
+    /// reference remote documents
+    Serve(commands::serve::Opts),
+
+    /// Several specifications of one directory processed together
+    Workspace(commands::workspace::Opts),
 }
 
 fn main() {
     let opts: Opts = Opts::parse();
-    let client = Client::new();
+    let client = match ClientOptions::new()
+        .with_proxy(opts.http.proxy)
+        .with_cacert(opts.http.cacert)
+        .with_insecure(opts.http.insecure)
+        .with_timeout(opts.http.timeout.map(Duration::from_secs))
+        .with_connect_timeout(opts.http.connect_timeout.map(Duration::from_secs))
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => {
+            println!("\x1b[0;31mError occurred:\x1b[0m {e}");
+            std::process::exit(1);
+        }
+    };
+
+    let mut retry = RetryOptions::new();
+    retry
+        .with_retries(opts.http.retries)
+        .with_backoff(Duration::from_millis(opts.http.retry_backoff));
 
     let result = match opts.command {
-        Command::Process(opts) => commands::process::execute(opts, &client),
+        Command::Process(opts) => commands::process::execute(opts, &client, &retry),
         #[cfg(feature = "codegen")]
-        Command::Codegen(opts) => commands::codegen::execute(opts, &client),
-        Command::Validate(opts) => commands::validate::execute(opts, &client),
-        Command::Chain(opts) => commands::chain::execute(opts, &client),
+        Command::Codegen(opts) => commands::codegen::execute(opts, &client, &retry),
+        Command::Validate(opts) => commands::validate::execute(opts, &client, &retry),
+        Command::Chain(opts) => commands::chain::execute(opts, &client, &retry),
+        Command::Generate(opts) => commands::generate::execute(opts, &client),
+        Command::Hash(opts) => commands::hash::execute(opts, &client),
+        Command::Serve(opts) => commands::serve::execute(opts, &client, &retry),
+        Command::Workspace(opts) => commands::workspace::execute(opts, &client, &retry),
     };
 
     std::process::exit(match result {
@@ -0,0 +1,135 @@
+use std::{path::PathBuf, time::Duration};
+
+use crate::{error::Error, Client};
+
+/// Settings of http client used to fetch schemas and remote templates, ex. proxy or
+/// certificates of internally-signed registries
+#[derive(Debug, Default, Clone)]
+pub struct ClientOptions {
+    proxy: Option<String>,
+    cacert: Option<PathBuf>,
+    insecure: bool,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+}
+
+impl ClientOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Proxy used for all requests, ex. `http://proxy.company.com:3128`
+    pub fn with_proxy(&mut self, value: Option<String>) -> &mut Self {
+        self.proxy = value;
+        self
+    }
+
+    /// PEM file with additional trusted root certificates
+    pub fn with_cacert(&mut self, value: Option<PathBuf>) -> &mut Self {
+        self.cacert = value;
+        self
+    }
+
+    /// Accepts invalid certificates, use only for debugging
+    pub fn with_insecure(&mut self, value: bool) -> &mut Self {
+        self.insecure = value;
+        self
+    }
+
+    pub fn with_timeout(&mut self, value: Option<Duration>) -> &mut Self {
+        self.timeout = value;
+        self
+    }
+
+    pub fn with_connect_timeout(&mut self, value: Option<Duration>) -> &mut Self {
+        self.connect_timeout = value;
+        self
+    }
+
+    #[cfg(feature = "http")]
+    pub fn build(&self) -> Result<Client, Error> {
+        let error = |e: reqwest::Error| Error::ClientError(e.to_string());
+
+        let mut builder = Client::builder().danger_accept_invalid_certs(self.insecure);
+
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(reqwest::Proxy::all(proxy).map_err(error)?);
+        }
+
+        if let Some(path) = &self.cacert {
+            let content = std::fs::read_to_string(path).map_err(|e| {
+                Error::ClientError(format!("cannot read {}: {e}", path.to_string_lossy()))
+            })?;
+
+            for certificate in pem_certificates(&content) {
+                builder = builder.add_root_certificate(
+                    reqwest::Certificate::from_pem(certificate.as_bytes()).map_err(error)?,
+                );
+            }
+        }
+
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+
+        builder.build().map_err(error)
+    }
+
+    #[cfg(not(feature = "http"))]
+    pub fn build(&self) -> Result<Client, Error> {
+        Ok(Client::new())
+    }
+}
+
+// splits pem bundle into separate certificates
+#[cfg(feature = "http")]
+fn pem_certificates(content: &str) -> Vec<String> {
+    const END: &str = "-----END CERTIFICATE-----";
+
+    content
+        .split_inclusive(END)
+        .filter(|block| block.contains(END))
+        .map(|block| block.trim().to_string())
+        .collect()
+}
+
+#[cfg(test)]
+#[cfg(feature = "http")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pem_certificates() {
+        let bundle = "# first\n-----BEGIN CERTIFICATE-----\nAA==\n-----END CERTIFICATE-----\n\n-----BEGIN CERTIFICATE-----\nBB==\n-----END CERTIFICATE-----\n";
+
+        assert_eq!(
+            pem_certificates(bundle),
+            vec![
+                "# first\n-----BEGIN CERTIFICATE-----\nAA==\n-----END CERTIFICATE-----",
+                "-----BEGIN CERTIFICATE-----\nBB==\n-----END CERTIFICATE-----"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build() {
+        let mut options = ClientOptions::new();
+        options
+            .with_proxy(Some("http://127.0.0.1:3128".to_string()))
+            .with_insecure(true)
+            .with_timeout(Some(Duration::from_secs(5)));
+        assert!(options.build().is_ok());
+
+        options.with_proxy(Some("not a proxy url".to_string()));
+        assert!(matches!(options.build(), Err(Error::ClientError(_))));
+
+        options
+            .with_proxy(None)
+            .with_cacert(Some(PathBuf::from("missing.pem")));
+        assert!(matches!(options.build(), Err(Error::ClientError(_))));
+    }
+}
@@ -0,0 +1,697 @@
+use std::sync::Arc;
+
+use serde_json::Value;
+
+use crate::{
+    discovery::{Discovery, Registry},
+    error::Error,
+    schema::{path_to_url, Schema},
+    storage::{ResolutionCache, SchemaStorage},
+    tools, Client,
+};
+
+use super::{
+    context,
+    hooks::Hooks,
+    ir::{Extracted, Ir},
+    jsonschema, openapi,
+    profile::Profiler,
+    renderer,
+    stamp::Stamp,
+    templates::TemplateType,
+    CodegenContainer,
+};
+
+pub struct Codegen;
+
+/// Performs whole codegen flow: loading schema, models/endpoints extraction and rendering
+#[derive(Default)]
+pub struct CodegenBuilder {
+    schemas: Vec<String>,
+    templates: Vec<String>,
+    target_dir: Option<String>,
+    hooks: Hooks,
+    options: Vec<(String, Value)>,
+    discovery: Discovery,
+    wrappers: bool,
+    nested_arrays_as_models: bool,
+    optional_and_nullable_as_models: bool,
+    collapse_single_variant: bool,
+    flatten_depth: Option<usize>,
+    strict: bool,
+    normalize: bool,
+    additional_properties_model: bool,
+    tri_state: bool,
+    builder_threshold: Option<usize>,
+    code_names: Option<String>,
+    decimal: bool,
+    temporal: bool,
+    examples: bool,
+    base_name: Option<String>,
+    keep_schema: Vec<String>,
+    keep_schema_options: jsonschema::KeepSchemaOptions,
+    context_version: Option<u32>,
+    reproducible: bool,
+    allowed_env: Vec<String>,
+    profiler: Option<Profiler>,
+    content_types: Vec<String>,
+    openapi: Option<bool>,
+}
+
+impl Codegen {
+    pub fn builder() -> CodegenBuilder {
+        CodegenBuilder::default()
+    }
+}
+
+impl CodegenBuilder {
+    pub fn schema(&mut self, path: &str) -> &mut Self {
+        self.schemas.push(path.to_string());
+        self
+    }
+
+    pub fn templates(&mut self, dir: &str) -> &mut Self {
+        self.templates.push(dir.to_string());
+        self
+    }
+
+    pub fn target_dir(&mut self, dir: &str) -> &mut Self {
+        self.target_dir = Some(dir.to_string());
+        self
+    }
+
+    pub fn format(&mut self, command: &str) -> &mut Self {
+        self.hooks.format = Some(command.to_string());
+        self
+    }
+
+    pub fn post_hook(&mut self, command: &str) -> &mut Self {
+        self.hooks.post_hook = Some(command.to_string());
+        self
+    }
+
+    pub fn env(&mut self, key: &str, value: &str) -> &mut Self {
+        self.hooks.env.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn option(&mut self, key: &str, value: impl Into<Value>) -> &mut Self {
+        self.options.push((key.to_string(), value.into()));
+        self
+    }
+
+    /// Adds preferred content type, ex. `application/vnd.company.v2+json` or `application/*`
+    pub fn content_type(&mut self, pattern: &str) -> &mut Self {
+        self.content_types.push(pattern.to_string());
+        self
+    }
+
+    pub fn registry(&mut self, name: &str, registry: Registry) -> &mut Self {
+        self.discovery.register(name.to_string(), registry);
+        self
+    }
+
+    /// Replaces discovery of templates, ex. one using configured http client
+    pub fn with_discovery(&mut self, value: Discovery) -> &mut Self {
+        self.discovery = value;
+        self
+    }
+
+    /// Forces openapi (`true`) or json-schema (`false`) extraction instead of detecting it
+    /// by `openapi` keyword of schema
+    pub fn with_openapi(&mut self, value: Option<bool>) -> &mut Self {
+        self.openapi = value;
+        self
+    }
+
+    pub fn with_wrappers(&mut self, value: bool) -> &mut Self {
+        self.wrappers = value;
+        self
+    }
+
+    pub fn with_nested_arrays_as_models(&mut self, value: bool) -> &mut Self {
+        self.nested_arrays_as_models = value;
+        self
+    }
+
+    pub fn with_optional_and_nullable_as_models(&mut self, value: bool) -> &mut Self {
+        self.optional_and_nullable_as_models = value;
+        self
+    }
+
+    /// Replaces oneOf with one variant by the variant itself
+    pub fn with_collapse_single_variant(&mut self, value: bool) -> &mut Self {
+        self.collapse_single_variant = value;
+        self
+    }
+
+    /// Promotes inline objects nested deeper than given depth to named definitions
+    pub fn with_flatten_depth(&mut self, value: Option<usize>) -> &mut Self {
+        self.flatten_depth = value;
+        self
+    }
+
+    /// Fails extraction on keywords which cannot be expressed by models, ex. `if`, `not`
+    pub fn with_strict(&mut self, value: bool) -> &mut Self {
+        self.strict = value;
+        self
+    }
+
+    /// Rewrites nullable enum idioms to single form before extraction, see process normalize
+    pub fn with_normalize(&mut self, value: bool) -> &mut Self {
+        self.normalize = value;
+        self
+    }
+
+    /// Extracts typed additionalProperties next to properties as additional model of object
+    pub fn with_additional_properties_model(&mut self, value: bool) -> &mut Self {
+        self.additional_properties_model = value;
+        self
+    }
+
+    /// Distinguishes absent from null values, see `Presence`
+    pub fn with_tri_state(&mut self, value: bool) -> &mut Self {
+        self.tri_state = value;
+        self
+    }
+
+    /// Adds builder metadata to objects with more properties than given threshold
+    pub fn with_builder_threshold(&mut self, value: Option<usize>) -> &mut Self {
+        self.builder_threshold = value;
+        self
+    }
+
+    /// Stores identifiers of given language (`rust`, `go`, `typescript`, `python`) as code
+    /// names of properties
+    pub fn with_code_names(&mut self, value: Option<String>) -> &mut Self {
+        self.code_names = value;
+        self
+    }
+
+    /// Extracts numbers of format `decimal` (or with `x-precision`/`x-scale`) as `decimal`
+    /// type, so they are not mapped to floats
+    pub fn with_decimal(&mut self, value: bool) -> &mut Self {
+        self.decimal = value;
+        self
+    }
+
+    /// Extracts strings of format `date`, `time`, `date-time`, `local-date-time` and `duration`
+    /// as types named after them, validated with pattern of format
+    pub fn with_temporal(&mut self, value: bool) -> &mut Self {
+        self.temporal = value;
+        self
+    }
+
+    /// Collects example payloads of models into render context
+    pub fn with_examples(&mut self, value: bool) -> &mut Self {
+        self.examples = value;
+        self
+    }
+
+    pub fn with_base_name(&mut self, value: Option<String>) -> &mut Self {
+        self.base_name = value;
+        self
+    }
+
+    pub fn with_keep_schema(&mut self, value: Vec<String>) -> &mut Self {
+        self.keep_schema = value;
+        self
+    }
+
+    /// Strips, canonicalizes or shares raw schemas kept on models
+    pub fn with_keep_schema_options(&mut self, value: jsonschema::KeepSchemaOptions) -> &mut Self {
+        self.keep_schema_options = value;
+        self
+    }
+
+    pub fn allow_env(&mut self, name: &str) -> &mut Self {
+        self.allowed_env.push(name.to_string());
+        self
+    }
+
+    pub fn with_profiler(&mut self, value: Option<Profiler>) -> &mut Self {
+        self.profiler = value;
+        self
+    }
+
+    pub fn with_context_version(&mut self, value: Option<u32>) -> &mut Self {
+        self.context_version = value;
+        self
+    }
+
+    /// Omits generation time from `stamp` so repeated generation gives identical output
+    pub fn with_reproducible(&mut self, value: bool) -> &mut Self {
+        self.reproducible = value;
+        self
+    }
+
+    /// Loads schemas and renders templates, returns list of generated files
+    pub fn run(&self) -> Result<Vec<String>, Error> {
+        self.run_with(&Client::new(), &ResolutionCache::default())
+    }
+
+    /// Same as [`Self::run`] but schemas and references are loaded with given client and cache
+    pub fn run_with(&self, client: &Client, cache: &ResolutionCache) -> Result<Vec<String>, Error> {
+        let urls = self
+            .schemas
+            .iter()
+            .map(|s| path_to_url(s.clone()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if urls.is_empty() {
+            return Err(Error::CodegenMissingSchema);
+        }
+
+        let schema = Schema::load_urls_with_client(urls, client)?;
+        let storage = SchemaStorage::new_multi_with_cache(&[&schema], client, cache)?;
+
+        self.run_schema(&schema, &storage)
+    }
+
+    /// Renders templates for already loaded schema, returns list of generated files
+    pub fn run_schema(
+        &self,
+        schema: &Schema,
+        storage: &SchemaStorage,
+    ) -> Result<Vec<String>, Error> {
+        let target_dir = self.target_dir.clone().unwrap_or_else(|| ".".to_string());
+
+        self.render(schema, storage, &self.discovery, &target_dir)
+    }
+
+    /// Renders templates resolved by given discovery to selected directory
+    pub fn render(
+        &self,
+        schema: &Schema,
+        storage: &SchemaStorage,
+        discovery: &Discovery,
+        target_dir: &str,
+    ) -> Result<Vec<String>, Error> {
+        self.render_extracted(
+            self.extract(schema, storage)?,
+            Stamp::new(schema),
+            discovery,
+            target_dir,
+        )
+    }
+
+    /// Renders templates from previously exported extraction result, see [`Self::export_ir`]
+    pub fn render_ir(&self, ir: Ir) -> Result<Vec<String>, Error> {
+        let target_dir = self.target_dir.clone().unwrap_or_else(|| ".".to_string());
+
+        self.render_extracted(ir.extracted, ir.stamp, &self.discovery, &target_dir)
+    }
+
+    /// Returns extraction result of already loaded schema which can be saved and rendered later
+    pub fn export_ir(&self, schema: &Schema, storage: &SchemaStorage) -> Result<Ir, Error> {
+        self.extract(schema, storage)
+            .map(|extracted| Ir::new(extracted, Stamp::new(schema)))
+    }
+
+    fn render_extracted(
+        &self,
+        extracted: Extracted,
+        stamp: Stamp,
+        discovery: &Discovery,
+        target_dir: &str,
+    ) -> Result<Vec<String>, Error> {
+        match extracted {
+            Extracted::Openapi(openapi) => renderer::create(
+                discovery.resolve(&self.templates)?,
+                &[
+                    TemplateType::Models,
+                    TemplateType::Endpoints,
+                    TemplateType::Routes,
+                    TemplateType::Tests,
+                    TemplateType::Commands,
+                ],
+                self.container(stamp),
+            )?
+            .openapi(openapi, target_dir, &self.hooks),
+            Extracted::Models(models) => renderer::create(
+                discovery.resolve(&self.templates)?,
+                &[TemplateType::Models],
+                self.container(stamp),
+            )?
+            .models(models, target_dir, &self.hooks),
+        }
+    }
+
+    /// Returns raw render context (without template specific data) for already loaded schema
+    pub fn context(&self, schema: &Schema, storage: &SchemaStorage) -> Result<Value, Error> {
+        self.context_extracted(self.extract(schema, storage)?, Stamp::new(schema))
+    }
+
+    fn context_extracted(&self, extracted: Extracted, stamp: Stamp) -> Result<Value, Error> {
+        let mut container = self.container(stamp);
+
+        match extracted {
+            Extracted::Openapi(mut openapi) => {
+                openapi.apply_credentials(&container.options)?;
+                container.data.insert(
+                    "formats".to_string(),
+                    serde_json::to_value(openapi.models.formats())
+                        .map_err(Error::SerdeJsonError)?,
+                );
+
+                context::build(openapi, &container)
+            }
+            Extracted::Models(models) => context::build(models, &container),
+        }
+    }
+
+    fn container(&self, stamp: Stamp) -> CodegenContainer {
+        let mut container = super::create_container(&self.options);
+        stamp.generated(self.reproducible).apply(&mut container);
+        container.context_version = self.context_version;
+        container.allowed_env = self.allowed_env.clone();
+        container.profiler = self.profiler.clone();
+        container
+    }
+
+    fn extract(&self, schema: &Schema, storage: &SchemaStorage) -> Result<Extracted, Error> {
+        let is_openapi = self
+            .openapi
+            .unwrap_or_else(|| schema.get_body().get("openapi").is_some());
+
+        if is_openapi {
+            openapi::extract(
+                schema,
+                storage,
+                openapi::OpenapiExtractOptions {
+                    wrappers: self.wrappers,
+                    optional_and_nullable_as_models: self.optional_and_nullable_as_models,
+                    nested_arrays_as_models: self.nested_arrays_as_models,
+                    collapse_single_variant: self.collapse_single_variant,
+                    keep_schema: tools::Filter::new(&self.keep_schema)?,
+                    keep_schema_options: self.keep_schema_options.clone(),
+                    content_types: self.content_types.clone(),
+                    flatten_depth: self.flatten_depth,
+                    strict: self.strict,
+                    normalize: self.normalize,
+                    additional_properties_model: self.additional_properties_model,
+                    tri_state: self.tri_state,
+                    builder_threshold: self.builder_threshold,
+                    code_names: self.code_names.clone(),
+                    decimal: self.decimal,
+                    temporal: self.temporal,
+                    examples: self.examples,
+                },
+            )
+            .map(Extracted::Openapi)
+        } else {
+            jsonschema::extract(
+                schema,
+                storage,
+                jsonschema::JsonSchemaExtractOptions {
+                    wrappers: self.wrappers,
+                    optional_and_nullable_as_models: self.optional_and_nullable_as_models,
+                    nested_arrays_as_models: self.nested_arrays_as_models,
+                    collapse_single_variant: self.collapse_single_variant,
+                    base_name: self.base_name.clone(),
+                    allow_list: true,
+                    keep_schema: tools::Filter::new(&self.keep_schema)?,
+                    keep_schema_options: self.keep_schema_options.clone(),
+                    flatten_depth: self.flatten_depth,
+                    strict: self.strict,
+                    normalize: self.normalize,
+                    additional_properties_model: self.additional_properties_model,
+                    tri_state: self.tri_state,
+                    builder_threshold: self.builder_threshold,
+                    code_names: self.code_names.clone(),
+                    decimal: self.decimal,
+                    temporal: self.temporal,
+                    examples: self.examples,
+                },
+            )
+            .map(|models| Extracted::Models(Arc::new(models)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codegen::ir;
+
+    #[test]
+    fn test_builder_renders_models() {
+        let mut target = std::env::temp_dir();
+        target.push("schema-tools-codegen-builder");
+
+        let files = Codegen::builder()
+            .schema(&format!(
+                "{}/resources/test/json-schemas/01-simple.json",
+                env!("CARGO_MANIFEST_DIR")
+            ))
+            .templates(&format!(
+                "{}/resources/test/codegen/models/",
+                env!("CARGO_MANIFEST_DIR")
+            ))
+            .target_dir(&target.to_string_lossy())
+            .option("namespace", "test")
+            .run()
+            .unwrap();
+
+        assert_eq!(files.len(), 1);
+        assert!(files[0].ends_with("models.txt"));
+    }
+
+    #[test]
+    fn test_builder_removes_output_of_removed_template() {
+        let dir = std::env::temp_dir().join("schema-tools-codegen-removed-template");
+        let _ = std::fs::remove_dir_all(&dir);
+        let (templates, target) = (dir.join("templates"), dir.join("target"));
+        std::fs::create_dir_all(&templates).unwrap();
+
+        for name in ["a", "b"] {
+            std::fs::write(
+                templates.join(format!("{name}.txt.j2")),
+                format!("{{# type=models,filename={name}.txt,min_version=0.19.0 #}}\n{name}"),
+            )
+            .unwrap();
+        }
+
+        let run = || {
+            Codegen::builder()
+                .schema(&format!(
+                    "{}/resources/test/json-schemas/01-simple.json",
+                    env!("CARGO_MANIFEST_DIR")
+                ))
+                .templates(&templates.to_string_lossy())
+                .target_dir(&target.to_string_lossy())
+                .run()
+                .unwrap()
+        };
+
+        assert_eq!(run().len(), 2);
+
+        std::fs::remove_file(templates.join("b.txt.j2")).unwrap();
+        assert_eq!(run().len(), 1);
+
+        assert!(target.join("a.txt").exists());
+        assert!(!target.join("b.txt").exists());
+    }
+
+    #[test]
+    fn test_builder_requires_schema() {
+        let result = Codegen::builder().templates("templates/").run();
+
+        assert!(matches!(result, Err(Error::CodegenMissingSchema)));
+    }
+
+    #[test]
+    fn test_builder_builtin_axum_server() {
+        let mut target = std::env::temp_dir();
+        target.push("schema-tools-codegen-axum-server");
+
+        let schema = Schema::from_json(serde_json::json!({
+            "openapi": "3.0.0",
+            "info": {"title": "test", "version": "1.0.0"},
+            "components": {"schemas": {"Pet": {
+                "type": "object",
+                "required": ["name"],
+                "properties": {
+                    "name": {"type": "string"},
+                    "type": {"type": "string", "enum": ["cat", "dog"]}
+                }
+            }}},
+            "paths": {"/owners/{ownerId}/pets/{petId}": {"put": {
+                "operationId": "updatePet",
+                "parameters": [
+                    {"name": "petId", "in": "path", "required": true, "schema": {"type": "integer"}},
+                    {"name": "X-Request-Id", "in": "header", "required": true, "schema": {"type": "string"}},
+                    {"name": "ownerId", "in": "path", "required": true, "schema": {"type": "string", "format": "uuid"}},
+                    {"name": "type", "in": "query", "schema": {"type": "string"}}
+                ],
+                "requestBody": {"content": {"application/json": {
+                    "schema": {"$ref": "#/components/schemas/Pet"}
+                }}},
+                "responses": {
+                    "200": {"description": "ok", "content": {"application/json": {
+                        "schema": {"$ref": "#/components/schemas/Pet"}
+                    }}},
+                    "4XX": {"description": "error"}
+                }
+            }}}
+        }));
+        let client = Client::new();
+        let storage = SchemaStorage::new(&schema, &client).unwrap();
+
+        let mut files = Codegen::builder()
+            .templates("builtin::axum-server")
+            .target_dir(&target.to_string_lossy())
+            .run_schema(&schema, &storage)
+            .unwrap();
+        files.sort();
+
+        assert_eq!(files.len(), 3);
+
+        let read = |name: &str| std::fs::read_to_string(target.join("src").join(name)).unwrap();
+
+        let models = read("models.rs");
+        assert!(models.contains("pub struct Pet {"));
+        assert!(models.contains("pub r#type: Option<PetType>,"));
+
+        let api = read("api.rs");
+        assert!(api.contains(
+            "    fn update_pet(\n        &self,\n        owner_id: uuid::Uuid,\n        pet_id: i64,\n        r#type: Option<String>,\n        x_request_id: String,\n        body: Option<Pet>,\n    )"
+        ));
+        assert!(api.contains("    Status4XX(StatusCode),"));
+
+        let router = read("router.rs");
+        assert!(router.contains("\"/owners/:ownerId/pets/:petId\""));
+        assert!(router.contains("Path((owner_id, pet_id, )): Path<(uuid::Uuid, i64, )>,"));
+    }
+
+    #[test]
+    fn test_builder_context() {
+        let schema = Schema::load_url(
+            path_to_url(format!(
+                "{}/resources/test/json-schemas/01-simple.json",
+                env!("CARGO_MANIFEST_DIR")
+            ))
+            .unwrap(),
+        )
+        .unwrap();
+        let client = Client::new();
+        let storage = SchemaStorage::new(&schema, &client).unwrap();
+
+        let context = Codegen::builder()
+            .option("namespace", "test")
+            .context(&schema, &storage)
+            .unwrap();
+
+        assert_eq!(context["schema_version"], context::CONTEXT_VERSION);
+        assert_eq!(context["options"]["namespace"], "test");
+        assert!(context["models"].is_array());
+        assert!(context["stamp"]["digest"].is_string());
+        assert!(context["stamp"]["generated_at"].is_string());
+    }
+
+    #[test]
+    fn test_builder_forced_extraction() {
+        let schema = Schema::from_json(serde_json::json!({
+            "components": {"schemas": {"Pet": {"type": "object"}}}
+        }));
+        let client = Client::new();
+        let storage = SchemaStorage::new(&schema, &client).unwrap();
+
+        let ir = Codegen::builder()
+            .with_openapi(Some(true))
+            .export_ir(&schema, &storage)
+            .unwrap();
+
+        assert!(matches!(ir.extracted, Extracted::Openapi(_)));
+        assert!(ir.extracted.models().report().is_empty());
+    }
+
+    #[test]
+    fn test_builder_context_from_ir() {
+        let openapi = Schema::from_json(serde_json::json!({
+            "openapi": "3.0.0",
+            "info": {"title": "test", "version": "1.0.0"},
+            "components": {
+                "securitySchemes": {"token": {"type": "http", "scheme": "bearer"}},
+                "schemas": {
+                    "Pet": {
+                        "type": "object",
+                        "required": ["name"],
+                        "properties": {
+                            "name": {"type": "string", "pattern": "^[a-z]+$"},
+                            "born": {"type": "string", "format": "date"},
+                            "owner": {"$ref": "#/components/schemas/Owner"}
+                        }
+                    },
+                    "Owner": {"type": "object", "properties": {"id": {"type": "integer"}}}
+                }
+            },
+            "security": [{"token": []}],
+            "paths": {
+                "/pets/{id}": {
+                    "get": {
+                        "operationId": "getPet",
+                        "tags": ["pets"],
+                        "parameters": [
+                            {"name": "id", "in": "path", "required": true, "schema": {"type": "string"}}
+                        ],
+                        "responses": {
+                            "200": {
+                                "description": "Pet",
+                                "content": {
+                                    "application/json": {"schema": {"$ref": "#/components/schemas/Pet"}},
+                                    "application/vnd.short+json": {"schema": {"$ref": "#/components/schemas/Owner"}}
+                                }
+                            },
+                            "4XX": {"description": "Error"}
+                        }
+                    }
+                }
+            }
+        }));
+        let models = Schema::load_url(
+            path_to_url(format!(
+                "{}/resources/test/json-schemas/08-with-refs-and-names.json",
+                env!("CARGO_MANIFEST_DIR")
+            ))
+            .unwrap(),
+        )
+        .unwrap();
+        let client = Client::new();
+
+        for schema in [openapi, models] {
+            let storage = SchemaStorage::new(&schema, &client).unwrap();
+
+            let mut builder = Codegen::builder();
+            builder.with_reproducible(true);
+            let ir = builder.export_ir(&schema, &storage).unwrap();
+            let content = serde_json::to_string(&ir).unwrap();
+
+            let loaded = Ir::parse(&content).unwrap();
+            let context = builder.context(&schema, &storage).unwrap();
+
+            // openapi context keeps models container under models key
+            let models = context
+                .pointer("/models/models")
+                .unwrap_or(&context["models"]);
+
+            assert!(models.as_array().is_some_and(|m| !m.is_empty()));
+            assert_eq!(
+                builder
+                    .context_extracted(loaded.extracted, loaded.stamp)
+                    .unwrap(),
+                context
+            );
+        }
+    }
+
+    #[test]
+    fn test_ir_version_is_checked() {
+        let result = Ir::parse(r#"{"version": 0, "kind": "models", "data": {}}"#);
+
+        assert!(matches!(
+            result,
+            Err(Error::CodegenUnsupportedIrVersion(0, ir::IR_VERSION))
+        ));
+    }
+}
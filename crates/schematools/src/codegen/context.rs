@@ -0,0 +1,100 @@
+//! Render context handed to templates.
+//!
+//! Every template is rendered with a single json object built from extracted data
+//! (models container or openapi) merged with codegen container (`options` and
+//! template specific data like `tag` or `formats`). The object always contains
+//! `schema_version` so templates can detect layout changes.
+//!
+//! Version 1 layout:
+//! - `schema_version`: number
+//! - `options`: object with `-o key=value` options and defaults of `schematools.toml`
+//! - `stamp`: provenance of specification, `version`, `digest` and `generated_at`
+//! - `models`, `regexps`, `formats`, `schemas`, `examples`: models container
+//! - `endpoints`, `security`, `tags`: openapi only
+//! - `tag`: endpoints templates grouped by tag
+//! - `group` and name of extension (ex. `service`): endpoints templates grouped by extension
+//! - `tags`: tags templates, list of tags with their endpoints instead of tag names
+//! - `tests`: tests templates, test cases of endpoints
+//! - `routes`: routes templates only, instead of models container and openapi
+//! - `commands`, `security`: commands templates only, instead of models container and openapi
+//!
+//! Changes of layout which break templates require new version.
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::error::Error;
+
+use super::CodegenContainer;
+
+pub const CONTEXT_VERSION: u32 = 1;
+
+pub const SUPPORTED_VERSIONS: [u32; 1] = [1];
+
+pub fn check(version: u32) -> Result<u32, Error> {
+    if SUPPORTED_VERSIONS.contains(&version) {
+        Ok(version)
+    } else {
+        Err(Error::CodegenUnsupportedContextVersion(
+            version,
+            CONTEXT_VERSION,
+        ))
+    }
+}
+
+pub fn build(data: impl Serialize, container: &CodegenContainer) -> Result<Value, Error> {
+    let mut ctx = match serde_json::to_value(data).map_err(Error::SerdeJsonError)? {
+        Value::Object(map) => map,
+        Value::Null => Map::new(),
+        _ => return Err(Error::CodegenInvalidContext),
+    };
+
+    if let Value::Object(map) = serde_json::to_value(container).map_err(Error::SerdeJsonError)? {
+        ctx.extend(map);
+    }
+
+    let version = check(container.context_version.unwrap_or(CONTEXT_VERSION))?;
+    ctx.insert("schema_version".to_string(), Value::from(version));
+
+    Ok(Value::Object(ctx))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_build_context() {
+        let mut container = super::super::create_container(&[(
+            "namespace".to_string(),
+            Value::String("test".to_string()),
+        )]);
+        container
+            .data
+            .insert("tag".to_string(), Value::String("Pets".to_string()));
+
+        let result = build(json!({"models": []}), &container).unwrap();
+
+        assert_eq!(
+            result,
+            json!({
+                "models": [],
+                "options": {"namespace": "test"},
+                "tag": "Pets",
+                "schema_version": CONTEXT_VERSION
+            })
+        );
+    }
+
+    #[test]
+    fn test_unsupported_context_version() {
+        let mut container = super::super::create_container(&[]);
+        container.context_version = Some(CONTEXT_VERSION + 1);
+
+        assert!(matches!(
+            build(json!({}), &container),
+            Err(Error::CodegenUnsupportedContextVersion(_, _))
+        ));
+    }
+}
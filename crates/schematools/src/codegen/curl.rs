@@ -0,0 +1,286 @@
+//! Curl snippets of endpoints with synthesized parameters and bodies, credentials are
+//! read from environment variables named after security schemes
+
+use inflector::Inflector;
+use serde_json::Value;
+
+use super::openapi::{endpoint::RequestSample, Openapi};
+
+pub const DEFAULT_BASE_URL: &str = "${BASE_URL}";
+
+/// Snippets (file name and content), one shell script per operation, file names are
+/// snake cased operation ids suffixed with number when not unique
+pub fn render(openapi: &Openapi, base_url: &str) -> Vec<(String, String)> {
+    let mut names = std::collections::HashSet::new();
+
+    openapi
+        .endpoints
+        .iter()
+        .map(|endpoint| {
+            let sample = endpoint.request_sample(&openapi.models);
+
+            let mut content = format!("#!/bin/sh\n# {} {}", sample.method, sample.path);
+            if let Some(summary) = &endpoint.get_docs().summary {
+                content.push_str(&format!(" - {}", summary.replace('\n', " ")));
+            }
+            content.push('\n');
+            content.push_str(&snippet(&sample, base_url));
+
+            let name = file_name(&sample);
+            let name = (1..)
+                .map(|i| match i {
+                    1 => format!("{name}.sh"),
+                    i => format!("{name}_{i}.sh"),
+                })
+                .find(|n| names.insert(n.clone()))
+                .unwrap_or_default();
+
+            (name, content)
+        })
+        .collect()
+}
+
+/// Curl command of request, `base_url` may refer to environment variables
+pub fn snippet(sample: &RequestSample, base_url: &str) -> String {
+    let mut url = format!("{}{}", base_url.trim_end_matches('/'), quoted(&sample.url));
+    let mut options = vec![];
+
+    for (name, value) in &sample.headers {
+        options.push(format!(
+            "-H {}",
+            single(&format!("{name}: {}", text(value)))
+        ));
+    }
+
+    let mut cookies = sample
+        .cookies
+        .iter()
+        .map(|(name, value)| quoted(&format!("{name}={}", text(value))))
+        .collect::<Vec<_>>();
+
+    if let Some(scheme) = &sample.security {
+        let variable = format!("${{{}}}", scheme.scheme_name.to_screaming_snake_case());
+
+        match (scheme.type_.as_str(), scheme.in_.as_deref()) {
+            ("http", _) if scheme.scheme.as_deref() == Some("basic") => {
+                options.push(format!("-u \"{variable}\""));
+            }
+            ("http", _) => options.push(format!(
+                "-H \"Authorization: {} {variable}\"",
+                scheme
+                    .scheme
+                    .as_deref()
+                    .unwrap_or("bearer")
+                    .to_pascal_case()
+            )),
+            ("apiKey", Some("header")) => options.push(format!(
+                "-H \"{}: {variable}\"",
+                quoted(scheme.name.as_deref().unwrap_or_default())
+            )),
+            ("apiKey", Some("query")) => {
+                let separator = if url.contains('?') { '&' } else { '?' };
+                url.push_str(&format!(
+                    "{separator}{}={variable}",
+                    quoted(scheme.name.as_deref().unwrap_or_default())
+                ));
+            }
+            ("apiKey", Some("cookie")) => cookies.push(format!(
+                "{}={variable}",
+                quoted(scheme.name.as_deref().unwrap_or_default())
+            )),
+            // oauth2 and openIdConnect expect obtained access token
+            _ => options.push(format!("-H \"Authorization: Bearer {variable}\"")),
+        }
+    }
+
+    if !cookies.is_empty() {
+        options.push(format!("-b \"{}\"", cookies.join("; ")));
+    }
+
+    if let Some(body) = &sample.body {
+        let data = match &body.valid {
+            Value::Object(fields) if body.content_type.contains("x-www-form-urlencoded") => fields
+                .iter()
+                .map(|(name, value)| format!("{name}={}", text(value)))
+                .collect::<Vec<_>>()
+                .join("&"),
+            value if body.content_type.contains("json") => value.to_string(),
+            value => text(value),
+        };
+
+        options.push(format!("--data-raw {}", single(&data)));
+    }
+
+    let mut command = format!("curl -X {} \"{url}\"", sample.method);
+    for option in options {
+        command.push_str(&format!(" \\\n  {option}"));
+    }
+    command.push('\n');
+
+    command
+}
+
+// operation ids may contain path separators or dots, only word characters are kept
+fn file_name(sample: &RequestSample) -> String {
+    let name = sample.operation.to_snake_case();
+    if !name.is_empty() {
+        return name;
+    }
+
+    format!("{} {}", sample.method, sample.path).to_snake_case()
+}
+
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        v => v.to_string(),
+    }
+}
+
+// content of double quoted shell string
+fn quoted(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('$', "\\$")
+        .replace('`', "\\`")
+}
+
+fn single(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        codegen::{builder::Codegen, ir::Extracted},
+        schema::Schema,
+        storage::SchemaStorage,
+        Client,
+    };
+    use serde_json::json;
+
+    fn openapi(security: Value) -> Openapi {
+        let schema = Schema::from_json(json!({
+            "openapi": "3.0.0",
+            "info": {"title": "test", "version": "1.0.0"},
+            "components": {
+                "securitySchemes": {
+                    "bearerAuth": {"type": "http", "scheme": "bearer"},
+                    "basicAuth": {"type": "http", "scheme": "basic"},
+                    "apiKey": {"type": "apiKey", "in": "query", "name": "key"}
+                },
+                "schemas": {
+                    "Pet": {
+                        "type": "object",
+                        "required": ["name"],
+                        "properties": {
+                            "name": {"type": "string", "example": "Rex's"}
+                        }
+                    }
+                }
+            },
+            "security": security,
+            "paths": {
+                "/pets/{petId}": {
+                    "put": {
+                        "operationId": "updatePet",
+                        "summary": "Update pet",
+                        "parameters": [
+                            {"name": "petId", "in": "path", "required": true, "schema": {"type": "string", "example": "a b"}},
+                            {"name": "fields", "in": "query", "required": true, "schema": {"type": "array", "items": {"type": "string", "example": "name"}}},
+                            {"name": "X-Request-Id", "in": "header", "required": true, "schema": {"type": "string", "example": "r1"}},
+                            {"name": "verbose", "in": "query", "schema": {"type": "boolean"}}
+                        ],
+                        "requestBody": {
+                            "content": {"application/json": {"schema": {"$ref": "#/components/schemas/Pet"}}}
+                        },
+                        "responses": {
+                            "200": {
+                                "description": "Pet",
+                                "content": {"application/json": {"schema": {"$ref": "#/components/schemas/Pet"}}}
+                            }
+                        }
+                    }
+                }
+            }
+        }));
+        let client = Client::new();
+        let storage = SchemaStorage::new(&schema, &client).unwrap();
+
+        match Codegen::builder()
+            .export_ir(&schema, &storage)
+            .unwrap()
+            .extracted
+        {
+            Extracted::Openapi(openapi) => openapi,
+            Extracted::Models(_) => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let snippets = render(&openapi(json!([{"bearerAuth": []}])), DEFAULT_BASE_URL);
+
+        assert_eq!(
+            snippets,
+            vec![(
+                "update_pet.sh".to_string(),
+                [
+                    "#!/bin/sh",
+                    "# PUT /pets/{petId} - Update pet",
+                    "curl -X PUT \"${BASE_URL}/pets/a%20b?fields=name\" \\",
+                    "  -H 'Accept: application/json' \\",
+                    "  -H 'Content-Type: application/json' \\",
+                    "  -H 'X-Request-Id: r1' \\",
+                    "  -H \"Authorization: Bearer ${BEARER_AUTH}\" \\",
+                    "  --data-raw '{\"name\":\"Rex'\\''s\"}'",
+                    ""
+                ]
+                .join("\n")
+            )]
+        );
+    }
+
+    #[test]
+    fn test_file_names() {
+        let mut openapi = openapi(json!([]));
+        let mut sample = openapi.endpoints[0].request_sample(&openapi.models);
+
+        sample.operation = "../pets/update.pet".to_string();
+        assert_eq!(file_name(&sample), "pets_update_pet");
+
+        sample.operation = "".to_string();
+        assert_eq!(file_name(&sample), "put_pets_pet_id");
+
+        openapi.endpoints.push(openapi.endpoints[0].clone());
+        let names = render(&openapi, DEFAULT_BASE_URL)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["update_pet.sh", "update_pet_2.sh"]);
+    }
+
+    #[test]
+    fn test_security() {
+        let openapi = openapi(json!([{"basicAuth": []}, {"apiKey": []}]));
+        let sample = openapi.endpoints[0].request_sample(&openapi.models);
+
+        assert_eq!(sample.url, "/pets/a%20b?fields=name");
+        assert_eq!(sample.headers["X-Request-Id"], json!("r1"));
+
+        let basic = snippet(&sample, "https://api.example.com/");
+        assert!(basic.starts_with("curl -X PUT \"https://api.example.com/pets/a%20b?fields=name"));
+        assert!(basic.contains("-u \"${BASIC_AUTH}\""));
+
+        let openapi = self::openapi(json!([{"apiKey": []}]));
+        let key = snippet(
+            &openapi.endpoints[0].request_sample(&openapi.models),
+            DEFAULT_BASE_URL,
+        );
+        assert!(
+            key.starts_with("curl -X PUT \"${BASE_URL}/pets/a%20b?fields=name&key=${API_KEY}\"")
+        );
+    }
+}
@@ -0,0 +1,711 @@
+//! Field tables of models and summaries of endpoints rendered straight from extraction
+//! result, for wikis which shouldn't depend on documentation templates
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::{
+    ir::Extracted,
+    jsonschema::{
+        types::{FlatModel, ModelType},
+        ModelContainer,
+    },
+    openapi::{
+        endpoint::Endpoint,
+        responses::{Response, ResponseKind},
+        MediaModelsContainer,
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocsFormat {
+    Markdown,
+    Csv,
+    Html,
+}
+
+const MODEL_COLUMNS: [&str; 6] = [
+    "model",
+    "field",
+    "type",
+    "required",
+    "constraints",
+    "description",
+];
+
+const ENDPOINT_COLUMNS: [&str; 7] = [
+    "method",
+    "path",
+    "operation",
+    "summary",
+    "parameters",
+    "request",
+    "responses",
+];
+
+/// Documents (file name and content): single `api.md` in case of markdown, `models.csv`
+/// and `endpoints.csv` (openapi only) in case of csv, static `index.html` in case of html
+pub fn render(extracted: &Extracted, format: DocsFormat) -> Vec<(String, String)> {
+    let (models, endpoints) = match extracted {
+        Extracted::Openapi(openapi) => (&openapi.models, Some(&openapi.endpoints)),
+        Extracted::Models(models) => (models, None),
+    };
+
+    let model_rows = model_rows(models);
+    let endpoint_rows = endpoints.map(|e| e.iter().map(endpoint_row).collect::<Vec<_>>());
+
+    match format {
+        DocsFormat::Markdown => {
+            let mut content = String::new();
+
+            if let Some(rows) = &endpoint_rows {
+                content.push_str("# Endpoints\n\n");
+                content.push_str(&markdown_table(&ENDPOINT_COLUMNS, rows));
+                content.push('\n');
+            }
+
+            content.push_str("# Models\n");
+
+            for (name, rows) in group(&model_rows) {
+                content.push_str(&model_section(name, &rows));
+            }
+
+            vec![("api.md".to_string(), content)]
+        }
+        DocsFormat::Csv => {
+            let mut documents = vec![("models.csv".to_string(), csv(&MODEL_COLUMNS, &model_rows))];
+
+            if let Some(rows) = &endpoint_rows {
+                documents.push(("endpoints.csv".to_string(), csv(&ENDPOINT_COLUMNS, rows)));
+            }
+
+            documents
+        }
+        DocsFormat::Html => vec![(
+            "index.html".to_string(),
+            html(models, endpoints.map(|e| e.as_slice()), &model_rows),
+        )],
+    }
+}
+
+// model rows (without model column) grouped by model in order of appearance
+fn group(model_rows: &[Vec<String>]) -> Vec<(&str, Vec<Vec<String>>)> {
+    let mut groups: Vec<(&str, Vec<Vec<String>>)> = vec![];
+
+    for row in model_rows {
+        match groups.last_mut() {
+            Some((name, rows)) if *name == row[0] => rows.push(row[1..].to_vec()),
+            _ => groups.push((&row[0], vec![row[1..].to_vec()])),
+        }
+    }
+
+    groups
+}
+
+fn model_section(name: &str, rows: &[Vec<String>]) -> String {
+    format!(
+        "\n## {name}\n\n{}",
+        markdown_table(&MODEL_COLUMNS[1..], rows)
+    )
+}
+
+// one row per field of objects, single row (without field) of other named models
+fn model_rows(container: &ModelContainer) -> Vec<Vec<String>> {
+    let mut rows = vec![];
+
+    for (_, model) in container.entries() {
+        let Ok(name) = model.name() else {
+            continue;
+        };
+        let description = model.attributes.description.clone().unwrap_or_default();
+
+        match model.inner() {
+            ModelType::ObjectType(object) => {
+                for property in &object.properties {
+                    rows.push(vec![
+                        name.to_string(),
+                        property.name.clone().unwrap_or_default(),
+                        label(property),
+                        yes_no(property.attributes.required),
+                        constraints(property),
+                        property.attributes.description.clone().unwrap_or_default(),
+                    ]);
+                }
+
+                if object.properties.is_empty() {
+                    rows.push(row(name, "object", String::new(), description));
+                }
+            }
+            ModelType::EnumType(e) => rows.push(row(
+                name,
+                &format!("enum<{}>", e.type_),
+                format!("one of: {}", e.variants.join(", ")),
+                description,
+            )),
+            ModelType::ConstType(c) => rows.push(row(
+                name,
+                &format!("const<{}>", c.type_),
+                format!("value: {}", c.value),
+                description,
+            )),
+            ModelType::WrapperType(w) => rows.push(row(
+                name,
+                &w.models.iter().map(label).collect::<Vec<_>>().join(" | "),
+                String::new(),
+                description,
+            )),
+            ModelType::ArrayType(a) => rows.push(row(
+                name,
+                &format!("array<{}>", label(&a.model)),
+                String::new(),
+                description,
+            )),
+            ModelType::MapType(m) => rows.push(row(
+                name,
+                &format!("map<{}>", label(&m.model)),
+                String::new(),
+                description,
+            )),
+            ModelType::PrimitiveType(p) => {
+                rows.push(row(name, &p.type_, String::new(), description))
+            }
+            ModelType::NullableOptionalWrapperType(o) => rows.push(row(
+                name,
+                &format!("{}?", label(&o.model)),
+                String::new(),
+                description,
+            )),
+            ModelType::AnyType(_) | ModelType::FlatModel(_) => {}
+        }
+    }
+
+    rows
+}
+
+fn row(name: &str, type_: &str, constraints: String, description: String) -> Vec<String> {
+    vec![
+        name.to_string(),
+        String::new(),
+        type_.to_string(),
+        String::new(),
+        constraints,
+        description,
+    ]
+}
+
+fn endpoint_row(endpoint: &Endpoint) -> Vec<String> {
+    let parameters = endpoint
+        .get_parameters()
+        .all
+        .iter()
+        .map(|p| format!("{}{}", p.name, if p.required { "*" } else { "" }))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let summary = endpoint
+        .get_docs()
+        .summary
+        .as_deref()
+        .or(endpoint.get_description())
+        .unwrap_or_default();
+
+    let request = endpoint
+        .requestbody
+        .as_ref()
+        .and_then(|r| r.models.as_ref())
+        .and_then(media_label)
+        .unwrap_or_default();
+
+    let responses = endpoint
+        .responses
+        .all
+        .iter()
+        .map(|r| match r.models.as_ref().and_then(media_label) {
+            Some(model) => format!("{} {model}", status(r)),
+            None => status(r),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    vec![
+        endpoint.get_method().to_uppercase(),
+        endpoint.get_path().to_string(),
+        endpoint.get_operation().to_string(),
+        summary.to_string(),
+        parameters,
+        request,
+        responses,
+    ]
+}
+
+// model of preferred content type
+fn media_label(container: &MediaModelsContainer) -> Option<String> {
+    container
+        .list
+        .iter()
+        .find(|m| m.content_type == container.default_content_type)
+        .or(container.list.first())
+        .map(|m| label(&m.model))
+}
+
+fn status(response: &Response) -> String {
+    match response.kind {
+        ResponseKind::Exact => response.status_code.to_string(),
+        ResponseKind::Range => format!("{}XX", response.status_code / 100),
+        ResponseKind::Default => "default".to_string(),
+    }
+}
+
+fn label(model: &FlatModel) -> String {
+    let label = match (&model.model, model.type_.as_str()) {
+        (Some(inner), "array") => format!("array<{}>", label(inner)),
+        (Some(inner), "map") => format!("map<{}>", label(inner)),
+        (Some(inner), "enum") => inner.name.clone().unwrap_or_else(|| label(inner)),
+        (Some(inner), _) => label(inner),
+        (None, type_) => type_.to_string(),
+    };
+
+    if model.attributes.nullable && !label.ends_with('?') {
+        format!("{label}?")
+    } else {
+        label
+    }
+}
+
+fn constraints(model: &FlatModel) -> String {
+    let mut list = model
+        .attributes
+        .validation
+        .iter()
+        .flatten()
+        .map(|(key, value)| match value {
+            serde_json::Value::String(s) => format!("{key}: {s}"),
+            // patterns are shared regexp models
+            serde_json::Value::Object(o) if o.contains_key("pattern") => {
+                format!("{key}: {}", o["pattern"].as_str().unwrap_or_default())
+            }
+            value => format!("{key}: {value}"),
+        })
+        .collect::<Vec<_>>();
+    list.sort();
+
+    if let Some(default) = &model.attributes.default {
+        list.push(format!("default: {default}"));
+    }
+
+    list.join(", ")
+}
+
+fn yes_no(value: bool) -> String {
+    if value { "yes" } else { "no" }.to_string()
+}
+
+const STYLE: &str = "
+body { margin: 0; font-family: -apple-system, 'Segoe UI', Helvetica, Arial, sans-serif; color: #1f2933; }
+nav { position: fixed; top: 0; bottom: 0; width: 260px; overflow-y: auto; background: #f5f7fa; border-right: 1px solid #e4e7eb; padding: 16px; box-sizing: border-box; font-size: 14px; }
+nav h2 { font-size: 12px; text-transform: uppercase; color: #7b8794; margin: 16px 0 4px; }
+nav a { display: block; padding: 2px 0; color: #1f2933; text-decoration: none; overflow: hidden; text-overflow: ellipsis; white-space: nowrap; }
+main { margin-left: 260px; padding: 24px 40px; max-width: 1000px; }
+section { border-bottom: 1px solid #e4e7eb; padding: 16px 0; }
+table { border-collapse: collapse; width: 100%; margin: 8px 0; font-size: 14px; }
+th, td { text-align: left; border-bottom: 1px solid #e4e7eb; padding: 6px 8px; vertical-align: top; }
+code { background: #f5f7fa; padding: 1px 4px; border-radius: 3px; }
+.method { display: inline-block; min-width: 56px; text-align: center; color: #fff; border-radius: 3px; padding: 2px 6px; font-size: 12px; font-weight: bold; background: #7b8794; }
+.get { background: #2f8132; } .post { background: #186faf; } .put { background: #95507c; } .patch { background: #bf581d; } .delete { background: #cc3333; }
+.deprecated { text-decoration: line-through; }
+.badge { color: #cc3333; font-size: 12px; }
+";
+
+// static single page reference: navigation, endpoints grouped by first tag and models
+fn html(
+    container: &ModelContainer,
+    endpoints: Option<&[Endpoint]>,
+    model_rows: &[Vec<String>],
+) -> String {
+    let groups = group(model_rows);
+    let names = groups
+        .iter()
+        .map(|(name, _)| *name)
+        .collect::<BTreeSet<_>>();
+    let descriptions = container
+        .entries()
+        .filter_map(|(_, m)| Some((m.name().ok()?, m.attributes.description.as_deref()?)))
+        .collect::<BTreeMap<_, _>>();
+
+    let mut tags: Vec<(&str, Vec<(usize, &Endpoint)>)> = vec![];
+    for (i, endpoint) in endpoints.unwrap_or_default().iter().enumerate() {
+        let tag = endpoint
+            .get_tags()
+            .first()
+            .map(|t| t.as_str())
+            .unwrap_or("default");
+
+        match tags.iter_mut().find(|(t, _)| *t == tag) {
+            Some((_, list)) => list.push((i, endpoint)),
+            None => tags.push((tag, vec![(i, endpoint)])),
+        }
+    }
+
+    let mut nav = String::new();
+    let mut content = String::new();
+
+    for (tag, list) in &tags {
+        nav.push_str(&format!("<h2>{}</h2>\n", escape(tag)));
+        content.push_str(&format!("<h1>{}</h1>\n", escape(tag)));
+
+        for (i, endpoint) in list {
+            nav.push_str(&format!(
+                "<a href=\"#endpoint-{i}\"{}><span class=\"method {}\">{}</span> {}</a>\n",
+                if endpoint.get_docs().deprecated {
+                    " class=\"deprecated\""
+                } else {
+                    ""
+                },
+                escape(endpoint.get_method()),
+                escape(&endpoint.get_method().to_uppercase()),
+                escape(endpoint.get_path())
+            ));
+            content.push_str(&endpoint_section(*i, endpoint, &names));
+        }
+    }
+
+    if !groups.is_empty() {
+        nav.push_str("<h2>Models</h2>\n");
+        content.push_str("<h1>Models</h1>\n");
+    }
+
+    for (name, rows) in &groups {
+        nav.push_str(&format!("<a href=\"#model-{0}\">{0}</a>\n", escape(name)));
+
+        content.push_str(&format!(
+            "<section id=\"model-{0}\">\n<h3>{0}</h3>\n",
+            escape(name)
+        ));
+        if let Some(description) = descriptions.get(name) {
+            content.push_str(&format!("<p>{}</p>\n", escape(description)));
+        }
+        content.push_str(&html_table(
+            &MODEL_COLUMNS[1..],
+            &rows
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .enumerate()
+                        .map(|(i, cell)| match i {
+                            1 => linked(cell, &names),
+                            _ => escape(cell),
+                        })
+                        .collect()
+                })
+                .collect::<Vec<_>>(),
+        ));
+        content.push_str("</section>\n");
+    }
+
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>API reference</title>\n<style>{STYLE}</style>\n</head>\n<body>\n<nav>\n{nav}</nav>\n<main>\n{content}</main>\n</body>\n</html>\n"
+    )
+}
+
+fn endpoint_section(i: usize, endpoint: &Endpoint, names: &BTreeSet<&str>) -> String {
+    let docs = endpoint.get_docs();
+    let mut section = format!(
+        "<section id=\"endpoint-{i}\">\n<h3><span class=\"method {}\">{}</span> <code>{}</code>{}</h3>\n",
+        escape(endpoint.get_method()),
+        escape(&endpoint.get_method().to_uppercase()),
+        escape(endpoint.get_path()),
+        if docs.deprecated {
+            " <span class=\"badge\">deprecated</span>"
+        } else {
+            ""
+        }
+    );
+
+    if let Some(summary) = &docs.summary {
+        section.push_str(&format!("<p><strong>{}</strong></p>\n", escape(summary)));
+    }
+    if let Some(description) = endpoint.get_description() {
+        section.push_str(&format!("<p>{}</p>\n", escape(description)));
+    }
+    section.push_str(&format!(
+        "<p>Operation: <code>{}</code></p>\n",
+        escape(endpoint.get_operation())
+    ));
+
+    let parameters = &endpoint.get_parameters().all;
+    if !parameters.is_empty() {
+        section.push_str("<h4>Parameters</h4>\n");
+        section.push_str(&html_table(
+            &["name", "in", "type", "required", "description"],
+            &parameters
+                .iter()
+                .map(|p| {
+                    vec![
+                        escape(&p.name),
+                        escape(&p.kind),
+                        p.model
+                            .as_ref()
+                            .map(|m| linked(&label(m), names))
+                            .unwrap_or_default(),
+                        yes_no(p.required),
+                        escape(p.description.as_deref().unwrap_or_default()),
+                    ]
+                })
+                .collect::<Vec<_>>(),
+        ));
+    }
+
+    if let Some(body) = &endpoint.requestbody {
+        section.push_str("<h4>Request</h4>\n");
+        section.push_str(&html_table(
+            &["content type", "type", "required", "description"],
+            &body
+                .models
+                .iter()
+                .flat_map(|c| &c.list)
+                .map(|m| {
+                    vec![
+                        escape(&m.content_type),
+                        linked(&label(&m.model), names),
+                        yes_no(body.required),
+                        escape(body.description.as_deref().unwrap_or_default()),
+                    ]
+                })
+                .collect::<Vec<_>>(),
+        ));
+    }
+
+    if !endpoint.responses.all.is_empty() {
+        section.push_str("<h4>Responses</h4>\n");
+        section.push_str(&html_table(
+            &["status", "type", "description"],
+            &endpoint
+                .responses
+                .all
+                .iter()
+                .map(|r| {
+                    vec![
+                        escape(&status(r)),
+                        r.models
+                            .as_ref()
+                            .and_then(media_label)
+                            .map(|l| linked(&l, names))
+                            .unwrap_or_default(),
+                        escape(r.description.as_deref().unwrap_or_default()),
+                    ]
+                })
+                .collect::<Vec<_>>(),
+        ));
+    }
+
+    section.push_str("</section>\n");
+    section
+}
+
+// type label with names of documented models turned into links
+fn linked(label: &str, names: &BTreeSet<&str>) -> String {
+    let mut result = String::new();
+    let mut token = String::new();
+
+    let flush = |token: &mut String, result: &mut String| {
+        if names.contains(token.as_str()) {
+            result.push_str(&format!("<a href=\"#model-{0}\">{0}</a>", escape(token)));
+        } else {
+            result.push_str(&escape(token));
+        }
+        token.clear();
+    };
+
+    for c in label.chars() {
+        if c.is_alphanumeric() || c == '_' || c == '-' {
+            token.push(c);
+        } else {
+            flush(&mut token, &mut result);
+            result.push_str(&escape(&c.to_string()));
+        }
+    }
+    flush(&mut token, &mut result);
+
+    result
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// cells are already escaped
+fn html_table(columns: &[&str], rows: &[Vec<String>]) -> String {
+    let mut table = format!(
+        "<table>\n<tr>{}</tr>\n",
+        columns
+            .iter()
+            .map(|c| format!("<th>{c}</th>"))
+            .collect::<String>()
+    );
+
+    for row in rows {
+        table.push_str(&format!(
+            "<tr>{}</tr>\n",
+            row.iter()
+                .map(|c| format!("<td>{c}</td>"))
+                .collect::<String>()
+        ));
+    }
+
+    table.push_str("</table>\n");
+    table
+}
+
+fn markdown_table(columns: &[&str], rows: &[Vec<String>]) -> String {
+    let escape = |cell: &str| cell.replace('|', "\\|").replace('\n', "<br>");
+
+    let mut table = format!("| {} |\n", columns.join(" | "));
+    table.push_str(&format!("|{}\n", " --- |".repeat(columns.len())));
+
+    for row in rows {
+        table.push_str(&format!(
+            "| {} |\n",
+            row.iter()
+                .map(|c| escape(c))
+                .collect::<Vec<_>>()
+                .join(" | ")
+        ));
+    }
+
+    table
+}
+
+fn csv(columns: &[&str], rows: &[Vec<String>]) -> String {
+    let escape = |cell: &str| {
+        if cell.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", cell.replace('"', "\"\""))
+        } else {
+            cell.to_string()
+        }
+    };
+
+    let mut content = format!("{}\n", columns.join(","));
+
+    for row in rows {
+        content.push_str(&row.iter().map(|c| escape(c)).collect::<Vec<_>>().join(","));
+        content.push('\n');
+    }
+
+    content
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{codegen::builder::Codegen, schema::Schema, storage::SchemaStorage, Client};
+
+    fn extracted() -> Extracted {
+        let schema = Schema::from_json(serde_json::json!({
+            "openapi": "3.0.0",
+            "info": {"title": "test", "version": "1.0.0"},
+            "components": {
+                "schemas": {
+                    "Pet": {
+                        "type": "object",
+                        "required": ["name"],
+                        "properties": {
+                            "name": {"type": "string", "pattern": "^[a-z]+$", "description": "Name, lowercase"},
+                            "tags": {"type": "array", "items": {"type": "string"}},
+                            "kind": {"$ref": "#/components/schemas/Kind"}
+                        }
+                    },
+                    "Kind": {"type": "string", "enum": ["cat", "dog"]}
+                }
+            },
+            "paths": {
+                "/pets/{id}": {
+                    "get": {
+                        "operationId": "getPet",
+                        "summary": "Get pet",
+                        "parameters": [
+                            {"name": "id", "in": "path", "required": true, "schema": {"type": "string"}},
+                            {"name": "fields", "in": "query", "schema": {"type": "string"}}
+                        ],
+                        "responses": {
+                            "200": {
+                                "description": "Pet",
+                                "content": {"application/json": {"schema": {"$ref": "#/components/schemas/Pet"}}}
+                            },
+                            "4XX": {"description": "Error"}
+                        }
+                    }
+                }
+            }
+        }));
+        let client = Client::new();
+        let storage = SchemaStorage::new(&schema, &client).unwrap();
+
+        Codegen::builder()
+            .export_ir(&schema, &storage)
+            .unwrap()
+            .extracted
+    }
+
+    #[test]
+    fn test_markdown() {
+        let documents = render(&extracted(), DocsFormat::Markdown);
+
+        assert_eq!(documents.len(), 1);
+        assert_eq!(documents[0].0, "api.md");
+
+        let content = &documents[0].1;
+        assert!(content
+            .contains("| GET | /pets/{id} | getPet | Get pet | id*, fields |  | 200 Pet, 4XX |"));
+        assert!(
+            content.contains("## Pet\n\n| field | type | required | constraints | description |")
+        );
+        assert!(content.contains("| name | string | yes | pattern: ^[a-z]+$ | Name, lowercase |"));
+        assert!(content.contains("| tags | array<string> | no |  |  |"));
+        assert!(content.contains("| kind | PetKind | no |  |  |"));
+        assert!(content.contains("|  | enum<string> |  | one of: cat, dog |  |"));
+    }
+
+    #[test]
+    fn test_html() {
+        let documents = render(&extracted(), DocsFormat::Html);
+
+        assert_eq!(documents.len(), 1);
+        assert_eq!(documents[0].0, "index.html");
+
+        let content = &documents[0].1;
+        assert!(content.starts_with("<!DOCTYPE html>"));
+        assert!(content.contains(
+            "<a href=\"#endpoint-0\"><span class=\"method get\">GET</span> /pets/{id}</a>"
+        ));
+        assert!(content
+            .contains("<tr><td>200</td><td><a href=\"#model-Pet\">Pet</a></td><td>Pet</td></tr>"));
+        assert!(content.contains("<section id=\"model-Pet\">"));
+        assert!(content.contains("<td>tags</td><td>array&lt;string&gt;</td>"));
+        assert!(content.contains("<td>kind</td><td><a href=\"#model-PetKind\">PetKind</a></td>"));
+        assert!(content.contains("<td>pattern: ^[a-z]+$</td><td>Name, lowercase</td>"));
+    }
+
+    #[test]
+    fn test_csv() {
+        let documents = render(&extracted(), DocsFormat::Csv);
+
+        let names = documents
+            .iter()
+            .map(|(n, _)| n.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["models.csv", "endpoints.csv"]);
+
+        assert!(documents[0]
+            .1
+            .starts_with("model,field,type,required,constraints,description\n"));
+        assert!(documents[0]
+            .1
+            .contains("Pet,name,string,yes,pattern: ^[a-z]+$,\"Name, lowercase\"\n"));
+        assert!(documents[1]
+            .1
+            .contains("GET,/pets/{id},getPet,Get pet,\"id*, fields\",,\"200 Pet, 4XX\"\n"));
+    }
+}
@@ -13,6 +13,7 @@ pub fn register(tera: &mut Tera) {
     tera.register_filter("pascalcase", pascalcase);
     tera.register_filter("snakecase", snakecase);
     tera.register_filter("upper_snakecase", upper_snakecase);
+    tera.register_filter("screaming", upper_snakecase);
     tera.register_filter("kebabcase", kebabcase);
     tera.register_filter("traincase", traincase);
     tera.register_filter("titlecase", titlecase);
@@ -21,12 +22,15 @@ pub fn register(tera: &mut Tera) {
     tera.register_filter("nospaces", nospaces);
 
     tera.register_filter("path_parts", path_parts);
+    tera.register_filter("path_template", path_template);
     tera.register_filter("when_numeric", when_numeric);
     tera.register_filter("filter_not", filter_not);
     tera.register_filter("filter_startswith", filter_startswith);
     tera.register_filter("filter_inarray", filter_inarray);
     tera.register_filter("filter_not_inarray", filter_not_inarray);
     tera.register_filter("plural", plural);
+    tera.register_filter("singular", singular);
+    tera.register_filter("maptype", maptype);
 }
 
 pub fn pascalcase(value: &Value, _: &HashMap<String, Value>) -> TeraResult<Value> {
@@ -114,10 +118,52 @@ pub fn path_parts(value: &Value, args: &HashMap<String, Value>) -> TeraResult<Va
     Ok(to_value(path).unwrap())
 }
 
+/// Converts `{param}` segments to syntax of router, ex. `format=":{name}"` or `format="<{name}>"`,
+/// optionally changing case of parameter names
+pub fn path_template(value: &Value, args: &HashMap<String, Value>) -> TeraResult<Value> {
+    let data = try_get_value!("path_template", "value", String, value);
+
+    let format = match args.get("format") {
+        Some(val) => try_get_value!("path_template", "format", String, val),
+        None => return Err(tera::Error::msg("Please provide format parameter")),
+    };
+
+    let case = match args.get("case") {
+        Some(val) => Some(try_get_value!("path_template", "case", String, val)),
+        None => None,
+    };
+
+    let name = |name: &str| match case.as_deref() {
+        None => Ok(name.to_string()),
+        Some("snake") => Ok(name.to_snake_case()),
+        Some("camel") => Ok(name.to_camel_case()),
+        Some("pascal") => Ok(name.to_pascal_case()),
+        Some("kebab") => Ok(name.to_kebab_case()),
+        Some(other) => Err(tera::Error::msg(format!(
+            "The `path_template` filter doesn't support case: {other}"
+        ))),
+    };
+
+    let mut path = String::new();
+    let mut rest = data.as_str();
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+
+        path.push_str(&rest[..start]);
+        path.push_str(&format.replace("{name}", &name(&rest[start + 1..start + end])?));
+        rest = &rest[start + end + 1..];
+    }
+    path.push_str(rest);
+
+    Ok(to_value(path).unwrap())
+}
+
 pub fn when_numeric(value: &Value, args: &HashMap<String, Value>) -> TeraResult<Value> {
     let value = try_get_value!("when_numeric", "value", String, value);
 
-    if value.chars().next().map_or(false, char::is_numeric) {
+    if value.chars().next().is_some_and(char::is_numeric) {
         let prefix = match args.get("prefix") {
             Some(val) => try_get_value!("when_numeric", "prefix", String, val),
             None => return Err(tera::Error::msg("Please provide prefix parameter")),
@@ -277,3 +323,151 @@ pub fn plural(value: &Value, _: &HashMap<String, Value>) -> TeraResult<Value> {
 
     Ok(to_value(plural).unwrap())
 }
+
+pub fn singular(value: &Value, _: &HashMap<String, Value>) -> TeraResult<Value> {
+    let s = try_get_value!("singular", "value", String, value);
+    let singular = pluralize(&s, 1, false);
+
+    Ok(to_value(singular).unwrap())
+}
+
+pub fn maptype(value: &Value, args: &HashMap<String, Value>) -> TeraResult<Value> {
+    let lang = match args.get("lang") {
+        Some(val) => try_get_value!("maptype", "lang", String, val),
+        None => return Err(tera::Error::msg("Please provide lang parameter")),
+    };
+
+    if !super::jsonschema::types::languages().contains(&lang.as_str()) {
+        return Err(tera::Error::msg(format!(
+            "The `maptype` filter doesn't support language: {lang}"
+        )));
+    }
+
+    // accepts type name or flattened model with optional format validation
+    let (type_, format, name) = match value {
+        Value::String(s) => (s.clone(), None, None),
+        Value::Object(o) => (
+            o.get("type")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            o.get("validation")
+                .and_then(|v| v.get("format"))
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            o.get("name")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+        ),
+        _ => {
+            return Err(tera::Error::msg(
+                "The `maptype` filter accepts type name or model",
+            ))
+        }
+    };
+
+    let format = match args.get("format") {
+        Some(val) => Some(try_get_value!("maptype", "format", String, val)),
+        None => format,
+    };
+
+    // types of target language chosen by templates, ex. `types={"date-time": "time::OffsetDateTime"}`
+    if let Some(Value::Object(types)) = args.get("types") {
+        if let Some(Value::String(target)) = types.get(&type_) {
+            return Ok(to_value(target).unwrap());
+        }
+    }
+
+    match super::jsonschema::types::language_type(&lang, &type_, format.as_deref()) {
+        Some(target) => Ok(to_value(target).unwrap()),
+        None => Ok(to_value(name.unwrap_or(type_)).unwrap()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn lang(lang: &str) -> HashMap<String, Value> {
+        HashMap::from([("lang".to_string(), json!(lang))])
+    }
+
+    #[test]
+    fn test_path_template() {
+        let args = |format: &str, case: Option<&str>| {
+            let mut args = HashMap::from([("format".to_string(), json!(format))]);
+            if let Some(case) = case {
+                args.insert("case".to_string(), json!(case));
+            }
+            args
+        };
+        let path = json!("/users/{userId}/orders/{order-id}");
+
+        assert_eq!(
+            path_template(&path, &args(":{name}", None)).unwrap(),
+            json!("/users/:userId/orders/:order-id")
+        );
+        assert_eq!(
+            path_template(&path, &args("<{name}>", Some("snake"))).unwrap(),
+            json!("/users/<user_id>/orders/<order_id>")
+        );
+        assert_eq!(
+            path_template(&path, &args("{{name}}", None)).unwrap(),
+            json!("/users/{userId}/orders/{order-id}")
+        );
+        assert!(path_template(&path, &HashMap::new()).is_err());
+        assert!(path_template(&path, &args(":{name}", Some("upper"))).is_err());
+    }
+
+    #[test]
+    fn test_singular_and_plural() {
+        assert_eq!(
+            singular(&json!("pets"), &HashMap::new()).unwrap(),
+            json!("pet")
+        );
+        assert_eq!(
+            plural(&json!("category"), &HashMap::new()).unwrap(),
+            json!("categories")
+        );
+    }
+
+    #[test]
+    fn test_maptype() {
+        assert_eq!(
+            maptype(&json!("integer"), &lang("go")).unwrap(),
+            json!("int64")
+        );
+        assert_eq!(
+            maptype(
+                &json!({"type": "string", "name": "createdAt", "validation": {"format": "date-time"}}),
+                &lang("rust")
+            )
+            .unwrap(),
+            json!("chrono::DateTime<chrono::Utc>")
+        );
+        assert_eq!(
+            maptype(
+                &json!({"type": "object", "name": "Pet"}),
+                &lang("typescript")
+            )
+            .unwrap(),
+            json!("Pet")
+        );
+        assert!(maptype(&json!("string"), &lang("cobol")).is_err());
+
+        let mut args = lang("rust");
+        args.insert(
+            "types".to_string(),
+            json!({"date-time": "time::OffsetDateTime"}),
+        );
+        assert_eq!(
+            maptype(&json!({"type": "date-time", "name": "createdAt"}), &args).unwrap(),
+            json!("time::OffsetDateTime")
+        );
+        assert_eq!(
+            maptype(&json!({"type": "date", "name": "birthday"}), &args).unwrap(),
+            json!("chrono::NaiveDate")
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{Arc, RwLock},
+};
+
+use serde_json::Value;
+use tera::{Result as TeraResult, Tera};
+
+use super::jsonschema::{ModelContainer, ModelId};
+
+// environment variables always available for templates
+const ENV_PREFIX: &str = "SCHEMATOOLS_";
+
+/// Registers functions reading auxiliary data, files are limited to the ones
+/// discovered in data directories of templates and variables to prefixed or allowed ones
+pub fn register(tera: &mut Tera, files: HashMap<String, PathBuf>, allowed_env: Vec<String>) {
+    let json_files = files.clone();
+    tera.register_function("read_json", move |args: &HashMap<String, Value>| {
+        let content = read(&json_files, args, "read_json")?;
+
+        serde_json::from_str::<Value>(&content)
+            .map_err(|e| tera::Error::msg(format!("Cannot parse json: {e}")))
+    });
+
+    tera.register_function("read_yaml", move |args: &HashMap<String, Value>| {
+        let content = read(&files, args, "read_yaml")?;
+
+        serde_yaml::from_str::<Value>(&content)
+            .map_err(|e| tera::Error::msg(format!("Cannot parse yaml: {e}")))
+    });
+
+    tera.register_function("env", move |args: &HashMap<String, Value>| {
+        env(&allowed_env, args)
+    });
+}
+
+/// Models container of current render call, set by renderer before rendering
+pub type ModelsSlot = Arc<RwLock<Option<Arc<ModelContainer>>>>;
+
+/// Registers `model(id)` function returning model exactly as it's listed in `models`
+pub fn register_models(tera: &mut Tera, models: ModelsSlot) {
+    tera.register_function("model", move |args: &HashMap<String, Value>| {
+        let id = args
+            .get("id")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| tera::Error::msg("The `model` requires numeric `id` argument"))?;
+
+        let models = models
+            .read()
+            .map_err(|e| tera::Error::msg(format!("Cannot read models: {e}")))?;
+        let entry = models
+            .as_ref()
+            .and_then(|m| m.entry(ModelId(id as u32)))
+            .ok_or_else(|| tera::Error::msg(format!("Model {id} not found")))?;
+
+        serde_json::to_value(entry).map_err(|e| tera::Error::msg(e.to_string()))
+    });
+}
+
+fn read(
+    files: &HashMap<String, PathBuf>,
+    args: &HashMap<String, Value>,
+    function: &str,
+) -> TeraResult<String> {
+    let path = args
+        .get("path")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| tera::Error::msg(format!("The `{function}` requires `path` argument")))?;
+
+    let file = files.get(path.trim_start_matches("./")).ok_or_else(|| {
+        tera::Error::msg(format!(
+            "File not found in data directory of templates: {path}"
+        ))
+    })?;
+
+    std::fs::read_to_string(file)
+        .map_err(|e| tera::Error::msg(format!("Cannot read file {path}: {e}")))
+}
+
+fn env(allowed: &[String], args: &HashMap<String, Value>) -> TeraResult<Value> {
+    let name = args
+        .get("name")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| tera::Error::msg("The `env` requires `name` argument"))?;
+
+    if !name.starts_with(ENV_PREFIX) && !allowed.iter().any(|a| a == name) {
+        return Err(tera::Error::msg(format!(
+            "Environment variable {name} is not allowed"
+        )));
+    }
+
+    match (std::env::var(name), args.get("default")) {
+        (Ok(value), _) => Ok(Value::String(value)),
+        (Err(_), Some(default)) => Ok(default.clone()),
+        (Err(_), None) => Err(tera::Error::msg(format!(
+            "Environment variable {name} is not set"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(content: &str, allowed_env: Vec<String>) -> TeraResult<String> {
+        let files = HashMap::from([(
+            "keywords.json".to_string(),
+            PathBuf::from(format!(
+                "{}/resources/test/codegen/data/keywords.json",
+                env!("CARGO_MANIFEST_DIR")
+            )),
+        )]);
+
+        let mut tera = Tera::default();
+        register(&mut tera, files, allowed_env);
+
+        tera.render_str(content, &tera::Context::new())
+    }
+
+    #[test]
+    fn test_read_json() {
+        assert_eq!(
+            render(
+                "{{ read_json(path='keywords.json') | join(sep=',') }}",
+                vec![]
+            )
+            .unwrap(),
+            "type,match"
+        );
+        assert!(render("{{ read_json(path='../secret.json') }}", vec![]).is_err());
+    }
+
+    #[test]
+    fn test_model() {
+        let schema = crate::schema::Schema::from_json(serde_json::json!({
+            "title": "Order",
+            "type": "object",
+            "properties": {"id": {"type": "string"}}
+        }));
+        let client = reqwest::blocking::Client::new();
+        let models = super::super::jsonschema::extract(
+            &schema,
+            &crate::storage::SchemaStorage::new(&schema, &client).unwrap(),
+            super::super::jsonschema::JsonSchemaExtractOptions::default(),
+        )
+        .unwrap();
+
+        let mut tera = Tera::default();
+        register_models(&mut tera, Arc::new(RwLock::new(Some(Arc::new(models)))));
+
+        assert_eq!(
+            tera.render_str(
+                "{% set m = model(id=0) %}{{ m.object.name }}",
+                &tera::Context::new()
+            )
+            .unwrap(),
+            "Order"
+        );
+        assert!(tera
+            .render_str("{{ model(id=5) }}", &tera::Context::new())
+            .is_err());
+    }
+
+    #[test]
+    fn test_env() {
+        assert_eq!(
+            render("{{ env(name='SCHEMATOOLS_MISSING', default='x') }}", vec![]).unwrap(),
+            "x"
+        );
+        assert!(render("{{ env(name='HOME') }}", vec![]).is_err());
+        assert!(render("{{ env(name='HOME') }}", vec!["HOME".to_string()]).is_ok());
+    }
+}
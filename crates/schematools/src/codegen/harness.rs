@@ -0,0 +1,232 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use walkdir::WalkDir;
+
+use crate::{
+    discovery::Discovery,
+    error::Error,
+    schema::{path_to_url, Schema},
+    storage::SchemaStorage,
+    Client,
+};
+
+use super::{builder::CodegenBuilder, manifest::MANIFEST_FILE};
+
+const SCHEMA_FILES: [&str; 3] = ["schema.json", "schema.yaml", "schema.yml"];
+
+/// Renders templates against fixtures and compares result with golden files
+///
+/// Each fixture is a directory with `schema.json` (or yaml) and `expected/` directory
+/// containing files which should be generated.
+pub struct TemplateTests;
+
+pub struct TemplateTestsOptions {
+    pub bless: bool,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct FixtureReport {
+    pub name: String,
+    pub missing: Vec<String>,
+    pub unexpected: Vec<String>,
+    pub changed: Vec<String>,
+}
+
+impl TemplateTests {
+    pub fn options() -> TemplateTestsOptions {
+        TemplateTestsOptions { bless: false }
+    }
+}
+
+impl FixtureReport {
+    pub fn passed(&self) -> bool {
+        self.missing.is_empty() && self.unexpected.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl TemplateTestsOptions {
+    pub fn with_bless(&mut self, value: bool) -> &mut Self {
+        self.bless = value;
+        self
+    }
+
+    pub fn process(
+        &self,
+        codegen: &CodegenBuilder,
+        discovery: &Discovery,
+        client: &Client,
+        fixtures: &str,
+    ) -> Result<Vec<FixtureReport>, Error> {
+        let mut reports = vec![];
+
+        let mut cases = std::fs::read_dir(fixtures)
+            .map_err(|e| Error::CodegenFileError(e.to_string()))?
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.is_dir())
+            .collect::<Vec<_>>();
+        cases.sort();
+
+        for case in cases {
+            let name = case.file_name().unwrap().to_string_lossy().to_string();
+
+            let file = SCHEMA_FILES
+                .iter()
+                .map(|f| case.join(f))
+                .find(|p| p.exists())
+                .ok_or_else(|| Error::CodegenFixtureMissingSchema(name.clone()))?;
+
+            let schema = Schema::load_url_with_client(
+                path_to_url(file.to_string_lossy().to_string())?,
+                client,
+            )?;
+            let storage = SchemaStorage::new(&schema, client)?;
+
+            let output = std::env::temp_dir().join(format!(
+                "schematools-test-templates-{}-{}",
+                std::process::id(),
+                name
+            ));
+            let _ = std::fs::remove_dir_all(&output);
+
+            let result = codegen
+                .render(&schema, &storage, discovery, &output.to_string_lossy())
+                .and_then(|_| {
+                    let expected = case.join("expected");
+                    let report = compare(&name, &read_files(&expected)?, &read_files(&output)?);
+
+                    if self.bless && !report.passed() {
+                        log::info!("Blessing fixture: {}", name);
+                        bless(&output, &expected)?;
+                    }
+
+                    Ok(report)
+                });
+
+            let _ = std::fs::remove_dir_all(&output);
+
+            reports.push(result?);
+        }
+
+        Ok(reports)
+    }
+}
+
+fn read_files(dir: &Path) -> Result<BTreeMap<String, Vec<u8>>, Error> {
+    let mut files = BTreeMap::new();
+
+    if !dir.exists() {
+        return Ok(files);
+    }
+
+    for entry in WalkDir::new(dir).into_iter().filter_map(|e| e.ok()) {
+        if entry.file_type().is_file() && entry.file_name() != MANIFEST_FILE {
+            let relative = entry
+                .path()
+                .strip_prefix(dir)
+                .unwrap()
+                .to_string_lossy()
+                .to_string();
+            let content =
+                std::fs::read(entry.path()).map_err(|e| Error::CodegenFileError(e.to_string()))?;
+
+            files.insert(relative, content);
+        }
+    }
+
+    Ok(files)
+}
+
+fn compare(
+    name: &str,
+    expected: &BTreeMap<String, Vec<u8>>,
+    rendered: &BTreeMap<String, Vec<u8>>,
+) -> FixtureReport {
+    let mut report = FixtureReport {
+        name: name.to_string(),
+        ..FixtureReport::default()
+    };
+
+    for (file, content) in expected {
+        match rendered.get(file) {
+            Some(c) if c != content => report.changed.push(file.clone()),
+            None => report.missing.push(file.clone()),
+            _ => {}
+        }
+    }
+
+    report.unexpected = rendered
+        .keys()
+        .filter(|f| !expected.contains_key(*f))
+        .cloned()
+        .collect();
+
+    report
+}
+
+fn bless(output: &Path, expected: &PathBuf) -> Result<(), Error> {
+    if expected.exists() {
+        std::fs::remove_dir_all(expected).map_err(|e| Error::CodegenFileError(e.to_string()))?;
+    }
+
+    for (relative, content) in read_files(output)? {
+        let target = expected.join(relative);
+
+        std::fs::create_dir_all(target.parent().unwrap())
+            .map_err(|e| Error::CodegenFileError(e.to_string()))?;
+        std::fs::write(target, content).map_err(|e| Error::CodegenFileError(e.to_string()))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codegen::Codegen;
+
+    #[test]
+    fn test_fixtures_pass() {
+        let reports = TemplateTests::options()
+            .process(
+                Codegen::builder().templates(&format!(
+                    "{}/resources/test/codegen/models/",
+                    env!("CARGO_MANIFEST_DIR")
+                )),
+                &Discovery::default(),
+                &Client::new(),
+                &format!(
+                    "{}/resources/test/codegen/fixtures/",
+                    env!("CARGO_MANIFEST_DIR")
+                ),
+            )
+            .unwrap();
+
+        assert_eq!(reports.len(), 1);
+        assert!(reports[0].passed(), "{:?}", reports[0]);
+    }
+
+    #[test]
+    fn test_compare_reports_differences() {
+        let expected = BTreeMap::from([
+            ("a.txt".to_string(), b"a".to_vec()),
+            ("b.txt".to_string(), b"b".to_vec()),
+        ]);
+        let rendered = BTreeMap::from([
+            ("a.txt".to_string(), b"changed".to_vec()),
+            ("c.txt".to_string(), b"c".to_vec()),
+        ]);
+
+        assert_eq!(
+            compare("test", &expected, &rendered),
+            FixtureReport {
+                name: "test".to_string(),
+                missing: vec!["b.txt".to_string()],
+                unexpected: vec!["c.txt".to_string()],
+                changed: vec!["a.txt".to_string()],
+            }
+        );
+    }
+}
@@ -0,0 +1,239 @@
+use std::{
+    collections::HashMap,
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use serde_json::Value;
+
+use crate::{error::Error, tools};
+
+/// Commands executed after rendering: `format` runs per generated file,
+/// `post_hook` runs once with list of all generated files passed to stdin
+#[derive(Debug, Default, Clone)]
+pub struct Hooks {
+    pub format: Option<String>,
+    pub post_hook: Option<String>,
+    pub env: Vec<(String, String)>,
+}
+
+impl Hooks {
+    pub fn from_header(config: &HashMap<&str, Value>) -> Result<Self, Error> {
+        let env = match config.get("env") {
+            Some(Value::String(s)) => vec![s.as_str()],
+            Some(Value::Array(list)) => list.iter().filter_map(|v| v.as_str()).collect(),
+            _ => vec![],
+        };
+
+        Ok(Self {
+            format: config
+                .get("format")
+                .and_then(|s| s.as_str())
+                .map(|s| s.to_string()),
+            post_hook: config
+                .get("post_hook")
+                .and_then(|s| s.as_str())
+                .map(|s| s.to_string()),
+            env: env
+                .into_iter()
+                .map(|kv| {
+                    kv.split_once(':')
+                        .map(|(k, v)| (k.to_string(), v.to_string()))
+                        .ok_or_else(|| Error::CodegenHookError(format!("Incorrect env: {kv}")))
+                })
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.format.is_none() && self.post_hook.is_none() && self.env.is_empty()
+    }
+
+    /// Template hooks take precedence over global ones, env variables are combined
+    pub fn merge(&self, global: &Hooks) -> Hooks {
+        Hooks {
+            format: self.format.clone().or_else(|| global.format.clone()),
+            post_hook: self.post_hook.clone(),
+            env: global.env.iter().chain(self.env.iter()).cloned().collect(),
+        }
+    }
+
+    pub fn format(&self, files: &[String]) -> Result<(), Error> {
+        if let Some(command) = &self.format {
+            for file in files {
+                let output = self
+                    .command(command)?
+                    .arg(file)
+                    .output()
+                    .map_err(Error::CodegenFormattingError)?;
+
+                if !output.status.success() {
+                    return Err(Error::CodegenFormattingCommandError(
+                        String::from_utf8_lossy(&output.stderr).to_string(),
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn post_hook(&self, files: &[String]) -> Result<(), Error> {
+        if let Some(command) = &self.post_hook {
+            log::info!("Running post hook: {}", command);
+
+            let mut child = self
+                .command(command)?
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()
+                .map_err(|e| Error::CodegenHookError(e.to_string()))?;
+
+            // written from separate thread so hook producing output before reading stdin
+            // doesn't deadlock, hooks which don't read stdin at all close the pipe early
+            let mut stdin = child.stdin.take().unwrap();
+            let list = files.iter().map(|f| format!("{f}\n")).collect::<String>();
+            let writer = std::thread::spawn(move || match stdin.write_all(list.as_bytes()) {
+                Err(e) if e.kind() != std::io::ErrorKind::BrokenPipe => Err(e),
+                _ => Ok(()),
+            });
+
+            let output = child
+                .wait_with_output()
+                .map_err(|e| Error::CodegenHookError(e.to_string()))?;
+
+            writer
+                .join()
+                .map_err(|_| Error::CodegenHookError("Writing file list failed".to_string()))?
+                .map_err(|e| Error::CodegenHookError(e.to_string()))?;
+
+            if !output.status.success() {
+                return Err(Error::CodegenHookError(
+                    String::from_utf8_lossy(&output.stderr).to_string(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn command(&self, command: &str) -> Result<Command, Error> {
+        let parts = tools::ArgumentsExtractor::new(command).collect::<Vec<String>>();
+        let (program, args) = parts
+            .split_first()
+            .ok_or_else(|| Error::CodegenHookError(format!("Incorrect command: {command}")))?;
+
+        let mut cmd = Command::new(program);
+        cmd.args(args).envs(self.env.iter().cloned());
+
+        Ok(cmd)
+    }
+}
+
+/// Runs every distinct post hook once with all generated files
+pub fn post_hooks(hooks: &[Hooks], files: &[String]) -> Result<(), Error> {
+    let mut executed: Vec<&String> = vec![];
+
+    for hook in hooks {
+        if let Some(command) = &hook.post_hook {
+            if !executed.contains(&command) {
+                hook.post_hook(files)?;
+                executed.push(command);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hooks_from_header() {
+        let config = HashMap::from([
+            ("format", Value::from("gofmt -w")),
+            ("env", Value::from(vec!["A:1", "B:x:y"])),
+        ]);
+
+        let hooks = Hooks::from_header(&config).unwrap();
+
+        assert_eq!(hooks.format, Some("gofmt -w".to_string()));
+        assert_eq!(hooks.post_hook, None);
+        assert_eq!(
+            hooks.env,
+            vec![
+                ("A".to_string(), "1".to_string()),
+                ("B".to_string(), "x:y".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hooks_merge() {
+        let global = Hooks {
+            format: Some("rustfmt".to_string()),
+            post_hook: Some("cargo check".to_string()),
+            env: vec![("A".to_string(), "1".to_string())],
+        };
+        let template = Hooks {
+            env: vec![("B".to_string(), "2".to_string())],
+            ..Hooks::default()
+        };
+
+        let merged = template.merge(&global);
+
+        assert_eq!(merged.format, Some("rustfmt".to_string()));
+        assert_eq!(merged.post_hook, None);
+        assert_eq!(merged.env.len(), 2);
+    }
+
+    #[test]
+    fn test_post_hook_receives_files_and_env() {
+        let target = std::env::temp_dir().join("schematools-post-hook-test");
+
+        let hooks = Hooks {
+            post_hook: Some(format!(
+                "sh -c 'cat > {}; echo \"$NAME\" >> {}'",
+                target.to_string_lossy(),
+                target.to_string_lossy()
+            )),
+            env: vec![("NAME".to_string(), "test".to_string())],
+            ..Hooks::default()
+        };
+
+        post_hooks(
+            &[hooks.clone(), hooks],
+            &["a.rs".to_string(), "b.rs".to_string()],
+        )
+        .unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&target).unwrap(),
+            "a.rs\nb.rs\ntest\n"
+        );
+    }
+
+    #[test]
+    fn test_post_hook_with_large_file_list() {
+        let files = (0..20000)
+            .map(|i| format!("src/model_{i}.rs"))
+            .collect::<Vec<_>>();
+
+        // doesn't read stdin at all
+        let ignoring = Hooks {
+            post_hook: Some("true".to_string()),
+            ..Hooks::default()
+        };
+        ignoring.post_hook(&files).unwrap();
+
+        // fills stdout pipe before reading stdin
+        let chatty = Hooks {
+            post_hook: Some("sh -c 'head -c 200000 /dev/zero; cat > /dev/null'".to_string()),
+            ..Hooks::default()
+        };
+        chatty.post_hook(&files).unwrap();
+    }
+}
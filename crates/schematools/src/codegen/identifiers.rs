@@ -0,0 +1,235 @@
+//! Conversion of property names to identifiers of target languages, names are converted
+//! to ascii and naming convention of language and reserved words are escaped.
+
+use inflector::Inflector;
+use regex::Regex;
+
+use crate::{error::Error, tools};
+
+// (language, reserved words)
+const RESERVED: &[(&str, &[&str])] = &[
+    (
+        "rust",
+        &[
+            "abstract", "as", "async", "await", "become", "box", "break", "const", "continue",
+            "crate", "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen",
+            "if", "impl", "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override",
+            "priv", "pub", "ref", "return", "self", "static", "struct", "super", "trait", "true",
+            "try", "type", "typeof", "unsafe", "unsized", "use", "virtual", "where", "while",
+            "yield",
+        ],
+    ),
+    (
+        "go",
+        &[
+            "break",
+            "case",
+            "chan",
+            "const",
+            "continue",
+            "default",
+            "defer",
+            "else",
+            "fallthrough",
+            "for",
+            "func",
+            "go",
+            "goto",
+            "if",
+            "import",
+            "interface",
+            "map",
+            "package",
+            "range",
+            "return",
+            "select",
+            "struct",
+            "switch",
+            "type",
+            "var",
+        ],
+    ),
+    (
+        "typescript",
+        &[
+            "break",
+            "case",
+            "catch",
+            "class",
+            "const",
+            "continue",
+            "debugger",
+            "default",
+            "delete",
+            "do",
+            "else",
+            "enum",
+            "export",
+            "extends",
+            "false",
+            "finally",
+            "for",
+            "function",
+            "if",
+            "import",
+            "in",
+            "instanceof",
+            "new",
+            "null",
+            "return",
+            "super",
+            "switch",
+            "this",
+            "throw",
+            "true",
+            "try",
+            "typeof",
+            "var",
+            "void",
+            "while",
+            "with",
+        ],
+    ),
+    (
+        "python",
+        &[
+            "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class",
+            "continue", "def", "del", "elif", "else", "except", "finally", "for", "from", "global",
+            "if", "import", "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise",
+            "return", "try", "while", "with", "yield",
+        ],
+    ),
+];
+
+// rust keywords which cannot be used as raw identifiers
+const RUST_NOT_RAW: [&str; 4] = ["crate", "self", "super", "Self"];
+
+/// Returns identifier of given name in language convention: snake case in rust and python,
+/// pascal case in go (exported fields) and camel case in typescript
+pub fn identifier(lang: &str, name: &str) -> Result<String, Error> {
+    let reserved = RESERVED
+        .iter()
+        .find(|(l, _)| *l == lang)
+        .map(|(_, words)| *words)
+        .ok_or_else(|| Error::CodegenUnsupportedLanguage(lang.to_string()))?;
+
+    let mut words: Vec<String> = vec![];
+    for segment in segments(&tools::transliterate(name)) {
+        let word = match (segment, lang) {
+            // code points are not converted, case conversion splits their digits
+            (Segment::CodePoint(c), "go") => capitalize(c),
+            (Segment::CodePoint(c), "typescript") if !words.is_empty() => capitalize(c),
+            (Segment::CodePoint(c), _) => c.to_string(),
+            (Segment::Text(t), "go") => t.to_pascal_case(),
+            (Segment::Text(t), "typescript") if !words.is_empty() => t.to_pascal_case(),
+            (Segment::Text(t), "typescript") => t.to_camel_case(),
+            (Segment::Text(t), _) => t.to_snake_case(),
+        };
+
+        if !word.is_empty() {
+            words.push(word);
+        }
+    }
+
+    let converted = match lang {
+        "go" | "typescript" => words.concat(),
+        _ => words.join("_"),
+    };
+
+    let converted = match converted.chars().next() {
+        None => "_".to_string(),
+        Some(c) if c.is_ascii_digit() && lang == "go" => format!("X{converted}"),
+        Some(c) if c.is_ascii_digit() => format!("_{converted}"),
+        _ => converted,
+    };
+
+    Ok(if !reserved.contains(&converted.as_str()) {
+        converted
+    } else if lang == "rust" && !RUST_NOT_RAW.contains(&converted.as_str()) {
+        format!("r#{converted}")
+    } else {
+        format!("{converted}_")
+    })
+}
+
+enum Segment<'a> {
+    Text(&'a str),
+    CodePoint(&'a str),
+}
+
+// transliteration puts code points of non ascii characters between spaces: ` u540d `
+fn segments(phrase: &str) -> Vec<Segment<'_>> {
+    let re = Regex::new(r" (u[0-9a-f]+) ").unwrap();
+    let mut result = vec![];
+    let mut last = 0;
+
+    for captures in re.captures_iter(phrase) {
+        let (all, code) = (captures.get(0).unwrap(), captures.get(1).unwrap());
+        result.push(Segment::Text(&phrase[last..all.start()]));
+        result.push(Segment::CodePoint(code.as_str()));
+        last = all.end();
+    }
+    result.push(Segment::Text(&phrase[last..]));
+
+    result
+}
+
+fn capitalize(word: &str) -> String {
+    word[..1].to_ascii_uppercase() + &word[1..]
+}
+
+/// Returns identifiers of names, identifiers which would collide get number suffix
+pub fn identifiers<'a>(
+    lang: &str,
+    names: impl Iterator<Item = &'a str>,
+) -> Result<Vec<String>, Error> {
+    let mut taken: Vec<String> = vec![];
+
+    for name in names {
+        let mut identifier = identifier(lang, name)?;
+        while taken.contains(&identifier) {
+            identifier = tools::bump_suffix_number(&identifier);
+        }
+
+        taken.push(identifier);
+    }
+
+    Ok(taken)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identifier() {
+        assert_eq!(identifier("rust", "match").unwrap(), "r#match");
+        assert_eq!(identifier("rust", "self").unwrap(), "self_");
+        assert_eq!(identifier("rust", "firstName").unwrap(), "first_name");
+        assert_eq!(identifier("rust", "2fa-enabled").unwrap(), "_2fa_enabled");
+        assert_eq!(identifier("go", "type").unwrap(), "Type");
+        assert_eq!(identifier("go", "2fa").unwrap(), "X2Fa");
+        assert_eq!(identifier("python", "from").unwrap(), "from_");
+        assert_eq!(identifier("python", "$ref").unwrap(), "ref");
+        assert_eq!(identifier("typescript", "delete").unwrap(), "delete_");
+        assert_eq!(identifier("typescript", "user-id").unwrap(), "userId");
+        assert_eq!(identifier("rust", "prénom").unwrap(), "prenom");
+        assert_eq!(identifier("rust", "名前").unwrap(), "u540d_u524d");
+        assert_eq!(identifier("python", "名 name").unwrap(), "u540d_name");
+        assert_eq!(identifier("go", "🚀 launch").unwrap(), "U1f680Launch");
+        assert_eq!(identifier("go", "名前").unwrap(), "U540dU524d");
+        assert_eq!(identifier("typescript", "名前").unwrap(), "u540dU524d");
+        assert_eq!(identifier("typescript", "user 名").unwrap(), "userU540d");
+        assert!(identifier("cobol", "name").is_err());
+    }
+
+    #[test]
+    fn test_identifiers_do_not_collide() {
+        let names = ["fooBar", "foo_bar", "foo-bar"];
+
+        assert_eq!(
+            identifiers("rust", names.into_iter()).unwrap(),
+            vec!["foo_bar", "foo_bar2", "foo_bar3"]
+        );
+    }
+}
@@ -0,0 +1,79 @@
+use std::{path::Path, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+use super::{jsonschema, openapi, stamp::Stamp};
+
+pub const IR_VERSION: u32 = 1;
+
+/// Result of models or endpoints extraction
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "kind", content = "data", rename_all = "lowercase")]
+pub enum Extracted {
+    Openapi(openapi::Openapi),
+    Models(Arc<jsonschema::ModelContainer>),
+}
+
+impl Extracted {
+    /// Models of extraction result, with warnings reported during extraction
+    pub fn models(&self) -> &jsonschema::ModelContainer {
+        match self {
+            Self::Openapi(openapi) => &openapi.models,
+            Self::Models(models) => models,
+        }
+    }
+}
+
+/// Versioned extraction result which can be rendered later without access to schema
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Ir {
+    pub version: u32,
+
+    #[serde(flatten)]
+    pub extracted: Extracted,
+
+    // provenance of schema, generation time is added during rendering
+    #[serde(default)]
+    pub stamp: Stamp,
+}
+
+impl Ir {
+    pub fn new(extracted: Extracted, stamp: Stamp) -> Self {
+        Self {
+            version: IR_VERSION,
+            extracted,
+            stamp,
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let content = serde_json::to_string_pretty(self).map_err(Error::SerdeJsonError)?;
+
+        std::fs::write(path, content).map_err(|e| Error::CodegenFileError(e.to_string()))
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let content =
+            std::fs::read_to_string(path).map_err(|e| Error::CodegenFileError(e.to_string()))?;
+
+        Self::parse(&content)
+    }
+
+    pub fn parse(content: &str) -> Result<Self, Error> {
+        let value: serde_json::Value =
+            serde_json::from_str(content).map_err(Error::SerdeJsonError)?;
+
+        // version is checked first so old files are reported clearly instead of parse errors
+        match value.get("version").and_then(|v| v.as_u64()) {
+            Some(version) if version == IR_VERSION as u64 => {
+                serde_json::from_value(value).map_err(Error::SerdeJsonError)
+            }
+            version => Err(Error::CodegenUnsupportedIrVersion(
+                version.unwrap_or_default() as u32,
+                IR_VERSION,
+            )),
+        }
+    }
+}
@@ -2,7 +2,7 @@ use serde_json::Map;
 use serde_json::Value;
 
 use super::{
-    types::{MapType, Model, ModelType, ObjectType},
+    types::{Constructor, FlatModel, MapType, Model, ModelType, ObjectType},
     JsonSchemaExtractOptions, ModelContainer,
 };
 use crate::{error::Error, resolver::SchemaResolver, scope::SchemaScope};
@@ -18,22 +18,19 @@ pub fn from_object_with_additional_properties(
 
     match schema.get("additionalProperties") {
         Some(value) => match value {
-            Value::Object(_) => {
-                // todo: mix of additionalProperties + properties support
-                scope.form("additionalProperties");
-                let model = super::extract_type(value, container, scope, resolver, options)
-                    .and_then(|s| s.flatten(container, scope));
-                scope.pop();
-
-                Ok(Model::new(ModelType::MapType(MapType {
-                    name: Some(name),
-                    model: Box::new(model?),
-                })))
-            }
+            Value::Object(_) => Ok(Model::new(ModelType::MapType(MapType {
+                model: Box::new(extract_value(
+                    value, &name, container, scope, resolver, options,
+                )?),
+                name: Some(name),
+            }))),
             Value::Bool(true) => Ok(Model::new(ModelType::ObjectType(ObjectType {
                 name,
                 properties: vec![],
                 additional: true,
+                additional_model: None,
+                builder: None,
+                constructor: Constructor::default(),
             }))),
             _ => Err(Error::SchemaInvalidProperty(
                 "additionalProperties".to_string(),
@@ -43,14 +40,36 @@ pub fn from_object_with_additional_properties(
             name,
             properties: vec![],
             additional: true,
+            additional_model: None,
+            builder: None,
+            constructor: Constructor::default(),
         }))),
     }
 }
 
+/// Extracts model of values described by additionalProperties schema
+pub fn extract_value(
+    value: &Value,
+    name: &str,
+    container: &mut ModelContainer,
+    scope: &mut SchemaScope,
+    resolver: &SchemaResolver,
+    options: &JsonSchemaExtractOptions,
+) -> Result<FlatModel, Error> {
+    scope.form("additionalProperties");
+    let model = super::extract_type(value, container, scope, resolver, options)
+        .and_then(|s| {
+            super::nested_array_as_model(s, Some(name), "value", container, scope, options)
+        })
+        .and_then(|s| s.flatten(container, scope));
+    scope.pop();
+
+    model
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::codegen::jsonschema::types::FlatModel;
     use serde_json::json;
 
     #[test]
@@ -76,6 +95,9 @@ mod tests {
                 name: "TestName".to_string(),
                 properties: vec![],
                 additional: true,
+                additional_model: None,
+                builder: None,
+                constructor: Constructor::default(),
             }))
         );
     }
@@ -103,6 +125,9 @@ mod tests {
                 name: "TestName".to_string(),
                 properties: vec![],
                 additional: true,
+                additional_model: None,
+                builder: None,
+                constructor: Constructor::default(),
             }))
         );
     }
@@ -32,7 +32,10 @@ pub fn from_const(
 
 #[cfg(test)]
 mod tests {
-    use crate::codegen::jsonschema::types::{FlatModel, Model};
+    use crate::codegen::jsonschema::{
+        types::{FlatModel, Model},
+        ModelId,
+    };
 
     use super::*;
     use serde_json::json;
@@ -103,7 +106,7 @@ mod tests {
                     type_: "number".to_string(),
                     ..FlatModel::default()
                 })),
-                original: Some(0),
+                original: Some(ModelId(0)),
                 ..FlatModel::default()
             }
         );
@@ -88,6 +88,7 @@ mod tests {
         let model = Model::new(ModelType::PrimitiveType(PrimitiveType {
             name: None,
             type_: "string".to_string(),
+            ..PrimitiveType::default()
         }));
 
         scope.entity("TestName");
@@ -111,6 +112,7 @@ mod tests {
         let model = Model::new(ModelType::PrimitiveType(PrimitiveType {
             name: None,
             type_: "string".to_string(),
+            ..PrimitiveType::default()
         }));
 
         scope.entity("TestName");
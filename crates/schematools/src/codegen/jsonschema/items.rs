@@ -18,6 +18,16 @@ pub fn from_array(
                 scope.form("items");
                 let name = super::title::extract_title(schema, scope, options);
                 let model = super::extract_type(items, container, scope, resolver, options)
+                    .and_then(|s| {
+                        super::nested_array_as_model(
+                            s,
+                            name.as_deref().ok(),
+                            "item",
+                            container,
+                            scope,
+                            options,
+                        )
+                    })
                     .and_then(|s| s.flatten(container, scope));
                 scope.pop();
 
@@ -74,4 +84,70 @@ mod tests {
             }))
         );
     }
+
+    #[test]
+    fn test_should_convert_nested_arrays_to_models() {
+        let schema = json!({"items": {"type": "array", "items": {"type": "number"}}});
+        let mut container = ModelContainer::default();
+        let mut scope = SchemaScope::default();
+        let resolver = SchemaResolver::empty();
+        let options = JsonSchemaExtractOptions {
+            nested_arrays_as_models: true,
+            ..JsonSchemaExtractOptions::default()
+        };
+
+        scope.entity("Matrix");
+        let result = from_array(
+            schema.as_object().unwrap(),
+            &mut container,
+            &mut scope,
+            &resolver,
+            &options,
+        )
+        .unwrap();
+
+        let row = container.find("MatrixItem").unwrap();
+
+        match result.inner() {
+            ModelType::ArrayType(a) => {
+                assert_eq!(a.model.type_, "wrapper");
+                assert_eq!(a.model.original, Some(row));
+                assert_eq!(
+                    a.model.model.as_ref().unwrap().name.as_deref(),
+                    Some("MatrixItem")
+                );
+            }
+            _ => panic!("array expected"),
+        }
+
+        match container.get(row).unwrap().inner() {
+            ModelType::ArrayType(a) => assert_eq!(a.model.type_, "number"),
+            _ => panic!("array expected"),
+        }
+    }
+
+    #[test]
+    fn test_should_inline_nested_arrays_by_default() {
+        let schema = json!({"items": {"type": "array", "items": {"type": "number"}}});
+        let mut container = ModelContainer::default();
+        let mut scope = SchemaScope::default();
+        let resolver = SchemaResolver::empty();
+        let options = JsonSchemaExtractOptions::default();
+
+        scope.entity("Matrix");
+        let result = from_array(
+            schema.as_object().unwrap(),
+            &mut container,
+            &mut scope,
+            &resolver,
+            &options,
+        )
+        .unwrap();
+
+        assert!(container.find("MatrixItem").is_none());
+        match result.inner() {
+            ModelType::ArrayType(a) => assert_eq!(a.model.type_, "array"),
+            _ => panic!("array expected"),
+        }
+    }
 }
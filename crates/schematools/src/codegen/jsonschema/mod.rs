@@ -2,7 +2,7 @@
 
 use std::collections::HashMap;
 
-use serde::{ser::SerializeStruct, Serialize};
+use serde::{ser::SerializeStruct, Deserialize, Serialize};
 use serde_json::{Map, Value};
 
 pub mod additionalproperties;
@@ -13,37 +13,134 @@ pub mod items;
 pub mod oneof;
 pub mod patternproperties;
 pub mod properties;
+pub mod report;
 pub mod required;
+pub mod samples;
 pub mod title;
 pub mod types;
 
+use crate::process::strict::{required_groups, unsupported_keyword};
 use crate::{
     error::Error, resolver::SchemaResolver, schema::Schema, scope::SchemaScope, scope::Space,
     storage::SchemaStorage, tools,
 };
 
+/// Identifier of model stored in [`ModelContainer`], stable for the whole extraction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ModelId(pub u32);
+
+impl ModelId {
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
 #[derive(Clone)]
 pub struct ModelContainer {
     regexps: Vec<types::RegexpType>,
     formats: Vec<String>,
     models: Vec<types::Model>,
-    mapping: HashMap<String, u32>,
+    // schema path -> model
+    mapping: HashMap<String, ModelId>,
+    // model name -> model, names are unique within container
+    names: HashMap<String, ModelId>,
+    // content digest -> models, precomputed once per model
+    digests: HashMap<String, Vec<ModelId>>,
+    children: Vec<Vec<ModelId>>,
+    parents: Vec<Vec<ModelId>>,
+    // raw schemas shared by models, see KeepSchemaOptions
+    schemas: Vec<String>,
+    // example payloads of models, see JsonSchemaExtractOptions examples
+    examples: Vec<types::Example>,
+    report: report::Report,
     any: types::Model,
 }
 
+#[derive(Serialize)]
+struct ModelEntry<'a> {
+    id: ModelId,
+
+    #[serde(flatten)]
+    model: &'a types::Model,
+}
+
 impl Serialize for ModelContainer {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        let mut state = serializer.serialize_struct("container", 2)?;
+        let mut state = serializer.serialize_struct("container", 5)?;
         state.serialize_field("regexps", &self.regexps)?;
         state.serialize_field("formats", &self.formats)?;
-        state.serialize_field("models", &self.models)?;
+        state.serialize_field("schemas", &self.schemas)?;
+        state.serialize_field("examples", &self.examples)?;
+        state.serialize_field(
+            "models",
+            &self
+                .entries()
+                .map(|(id, model)| ModelEntry { id, model })
+                .collect::<Vec<_>>(),
+        )?;
         state.end()
     }
 }
 
+#[derive(Deserialize)]
+struct ModelContainerData {
+    regexps: Vec<types::RegexpType>,
+    formats: Vec<String>,
+    schemas: Vec<String>,
+    #[serde(default)]
+    examples: Vec<types::Example>,
+    // ids are positions of models, so they are not read back
+    models: Vec<types::Model>,
+}
+
+// lookups are rebuilt from models, schema paths are known only during extraction
+impl<'de> Deserialize<'de> for ModelContainer {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let data = ModelContainerData::deserialize(deserializer)?;
+        let mut container = Self {
+            regexps: data.regexps,
+            formats: data.formats,
+            schemas: data.schemas,
+            examples: data.examples,
+            ..Self::default()
+        };
+
+        for (i, model) in data.models.into_iter().enumerate() {
+            let id = ModelId(i as u32);
+
+            if let Ok(name) = model.name() {
+                container.names.insert(name.to_string(), id);
+            }
+
+            container
+                .digests
+                .entry(model.digest())
+                .or_default()
+                .push(id);
+            container.children.push(model.children());
+            container.parents.push(vec![]);
+            container.models.push(model);
+        }
+
+        for (i, children) in container.children.iter().enumerate() {
+            for child in children {
+                if let Some(parents) = container.parents.get_mut(child.index()) {
+                    parents.push(ModelId(i as u32));
+                }
+            }
+        }
+
+        Ok(container)
+    }
+}
+
 impl Default for ModelContainer {
     fn default() -> Self {
         Self {
@@ -51,81 +148,157 @@ impl Default for ModelContainer {
             formats: vec![],
             models: vec![],
             mapping: HashMap::new(),
+            names: HashMap::new(),
+            digests: HashMap::new(),
+            children: vec![],
+            parents: vec![],
+            schemas: vec![],
+            examples: vec![],
+            report: report::Report::default(),
             any: types::Model::new(types::ModelType::AnyType(types::AnyType {})),
         }
     }
 }
 
 impl ModelContainer {
-    #[allow(clippy::map_entry)]
     pub fn add(
         &mut self,
         scope: &mut SchemaScope,
-        model: types::Model,
-    ) -> (Option<u32>, &types::Model) {
+        mut model: types::Model,
+    ) -> (Option<ModelId>, &types::Model) {
         if let types::ModelType::AnyType(_) = model.inner() {
             log::error!("{}: trying to save anyType as model", scope);
             return (None, &self.any);
         }
 
+        if model.source.is_none() {
+            model.source = Some(types::Source::new(scope));
+        }
+
         let key = scope.path();
-        if self.mapping.contains_key(&key) {
-            let id = self.mapping.get(&key).unwrap();
-            let model = self.models.get(*id as usize).unwrap();
-
-            (Some(*id), model)
-        } else if self.exists(&model) {
-            let id = self.models.iter().position(|s| *s == model).unwrap();
-            let model = self.models.get(id).unwrap();
-            (Some(id as u32), model)
+        let digest = model.digest();
+
+        if let Some(id) = self.mapping.get(&key).copied() {
+            (Some(id), &self.models[id.index()])
+        } else if let Some(id) = self.position(&model, &digest) {
+            log::debug!("{}: deduplicated as model {} ({})", scope, id.0, digest);
+
+            (Some(id), &self.models[id.index()])
         } else {
             let name = model.name().unwrap();
 
-            if self.models.iter().any(|c| c.name().unwrap() == name) {
+            if let Some(taken) = self.names.get(name) {
+                // different names may clash after transliteration, ex. `Straße` and `Strasse`
                 let new_name = tools::bump_suffix_number(name);
-                log::warn!(
-                    "{}: absolute: {}, conflict, renaming to: {}",
+                let owner = self.models[taken.index()]
+                    .source
+                    .as_ref()
+                    .map(|s| format!(" by {}", s.pointer))
+                    .unwrap_or_default();
+                self.report.add(
+                    report::WarningKind::Renamed,
                     scope,
-                    key,
-                    new_name
+                    format!("name {name} already taken{owner}, renaming to: {new_name}"),
                 );
 
                 self.add(scope, model.rename(new_name))
-            } else if let Some(index) = self.mapping.get(&key) {
-                (Some(*index), self.models.get(*index as usize).unwrap())
             } else {
-                self.mapping.insert(key, self.models.len() as u32);
+                let id = ModelId(self.models.len() as u32);
+                let children = model.children();
+
+                for child in &children {
+                    self.parents[child.index()].push(id);
+                }
+
+                self.mapping.insert(key, id);
+                self.names.insert(name.to_string(), id);
+                self.digests.entry(digest).or_default().push(id);
+                self.children.push(children);
+                self.parents.push(vec![]);
                 self.models.push(model);
 
-                let id = self.models.len() - 1;
-                let model = self.models.get(id).unwrap();
-                (Some(id as u32), model)
+                (Some(id), &self.models[id.index()])
             }
         }
     }
 
-    pub fn exists(&mut self, model: &types::Model) -> bool {
-        self.models.iter().any(|s| s == model)
+    pub fn exists(&self, model: &types::Model) -> bool {
+        self.position(model, &model.digest()).is_some()
     }
 
-    pub fn resolve(&mut self, scope: &mut SchemaScope) -> Option<&types::Model> {
-        if let Some(index) = self.mapping.get(&scope.path()) {
-            let ids = {
-                let s = self.models.get(*index as usize).unwrap();
-                let mut x = s.children(self);
-                x.push(*index);
-                x
-            };
+    // digest narrows candidates, full comparison confirms the match
+    fn position(&self, model: &types::Model, digest: &str) -> Option<ModelId> {
+        let candidates = self.digests.get(digest)?;
+        let found = candidates
+            .iter()
+            .copied()
+            .find(|id| self.models[id.index()] == *model);
+
+        if found.is_none() {
+            log::debug!(
+                "digest {} shared by models {:?} without structural match",
+                digest,
+                candidates
+            );
+        }
+
+        found
+    }
+
+    pub fn get(&self, id: ModelId) -> Option<&types::Model> {
+        self.models.get(id.index())
+    }
 
-            for a in ids {
-                let m = self.models.get_mut(a as usize).unwrap();
-                m.add_spaces(scope);
+    /// Model with its id, exactly as it's listed in serialized `models`
+    pub fn entry(&self, id: ModelId) -> Option<impl Serialize + '_> {
+        self.get(id).map(|model| ModelEntry { id, model })
+    }
+
+    pub fn find(&self, name: &str) -> Option<ModelId> {
+        self.names.get(name).copied()
+    }
+
+    /// Models directly and indirectly used by given model
+    pub fn children(&self, id: ModelId) -> Vec<ModelId> {
+        let mut result = vec![];
+        let mut stack = self.children.get(id.index()).cloned().unwrap_or_default();
+
+        while let Some(child) = stack.pop() {
+            if child != id && !result.contains(&child) {
+                result.push(child);
+                stack.extend(self.children[child.index()].iter().copied());
             }
+        }
 
-            Some(self.models.get(*index as usize).unwrap())
-        } else {
-            None
+        result
+    }
+
+    /// Models directly using given model
+    pub fn parents(&self, id: ModelId) -> &[ModelId] {
+        self.parents
+            .get(id.index())
+            .map(|p| p.as_slice())
+            .unwrap_or_default()
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = (ModelId, &types::Model)> + '_ {
+        self.models
+            .iter()
+            .enumerate()
+            .map(|(i, m)| (ModelId(i as u32), m))
+    }
+
+    pub fn resolve(&mut self, scope: &mut SchemaScope) -> Option<&types::Model> {
+        let id = *self.mapping.get(&scope.path())?;
+
+        let mut ids = self.children(id);
+        ids.push(id);
+
+        for a in ids {
+            self.models[a.index()].add_spaces(scope);
         }
+
+        self.get(id)
     }
 
     pub fn upsert_regexp(&mut self, regexp: types::RegexpType) -> types::RegexpType {
@@ -151,6 +324,146 @@ impl ModelContainer {
     pub fn formats(&self) -> &Vec<String> {
         &self.formats
     }
+
+    pub fn warn(&mut self, kind: report::WarningKind, scope: &SchemaScope, message: &str) {
+        self.report.add(kind, scope, message.to_string());
+    }
+
+    /// Warnings collected during extraction
+    pub fn report(&self) -> &report::Report {
+        &self.report
+    }
+
+    /// Saves serialized schema once, returns its index
+    pub fn add_schema(&mut self, schema: String) -> usize {
+        if let Some(index) = self.schemas.iter().position(|s| s == &schema) {
+            index
+        } else {
+            self.schemas.push(schema);
+            self.schemas.len() - 1
+        }
+    }
+
+    pub fn schemas(&self) -> &[String] {
+        &self.schemas
+    }
+
+    /// Collects example payloads of named models: own examples of model or, for objects
+    /// without them, payload composed of examples and defaults of properties
+    pub fn collect_examples(&mut self) {
+        self.examples = self
+            .entries()
+            .filter_map(|(id, model)| Some((id, model.name().ok()?)))
+            .flat_map(|(id, name)| {
+                let own = &self.models[id.index()].attributes.docs.examples;
+                let values = if own.is_empty() {
+                    self.composed_example(id, 0).into_iter().collect()
+                } else {
+                    own.clone()
+                };
+
+                values.into_iter().map(move |value| types::Example {
+                    id,
+                    model: name.to_string(),
+                    value,
+                })
+            })
+            .collect();
+    }
+
+    /// Marks models flagged with `x-pii` or built of such properties or models as containing
+    /// pii, properties of objects are marked the same way so templates can redact them
+    pub fn tag_pii(&mut self) {
+        let mut tagged = self
+            .models
+            .iter()
+            .map(|model| {
+                model.attributes.pii
+                    || match model.inner() {
+                        types::ModelType::ObjectType(o) => o
+                            .properties
+                            .iter()
+                            .chain(o.additional_model.iter())
+                            .any(|p| p.attributes.pii),
+                        _ => false,
+                    }
+            })
+            .collect::<Vec<_>>();
+
+        // propagated to parents until nothing changes, cycles of references included
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (i, children) in self.children.iter().enumerate() {
+                if !tagged[i] && children.iter().any(|c| tagged[c.index()]) {
+                    tagged[i] = true;
+                    changed = true;
+                }
+            }
+        }
+
+        for (model, contains_pii) in self.models.iter_mut().zip(&tagged) {
+            model.attributes.contains_pii = *contains_pii;
+
+            if let types::ModelType::ObjectType(o) = model.mut_inner() {
+                for property in o.properties.iter_mut().chain(o.additional_model.iter_mut()) {
+                    property.attributes.contains_pii = property.attributes.pii
+                        || property.original.is_some_and(|id| tagged[id.index()]);
+                }
+            }
+        }
+    }
+
+    pub fn examples(&self) -> &[types::Example] {
+        &self.examples
+    }
+
+    // example of model built from examples of its properties, required properties without
+    // example make it impossible
+    fn composed_example(&self, id: ModelId, depth: usize) -> Option<Value> {
+        const MAX_DEPTH: usize = 8;
+
+        let model = self.get(id)?;
+        if let Some(example) = model.attributes.docs.examples.first() {
+            return Some(example.clone());
+        }
+
+        let object = match model.inner() {
+            types::ModelType::ObjectType(object) if depth < MAX_DEPTH => object,
+            _ => return None,
+        };
+
+        let mut payload = Map::new();
+        for property in &object.properties {
+            let value = property
+                .attributes
+                .docs
+                .examples
+                .first()
+                .or(property.attributes.default.as_ref())
+                .cloned()
+                .or_else(|| self.flat_example(property, depth + 1));
+
+            match (value, property.name.as_ref()) {
+                (Some(value), Some(name)) => {
+                    payload.insert(name.clone(), value);
+                }
+                (None, _) if property.attributes.required => return None,
+                _ => {}
+            }
+        }
+
+        (!payload.is_empty()).then_some(Value::Object(payload))
+    }
+
+    fn flat_example(&self, model: &types::FlatModel, depth: usize) -> Option<Value> {
+        match (model.type_.as_str(), model.model.as_deref()) {
+            ("array", Some(item)) => self
+                .flat_example(item, depth)
+                .map(|item| Value::Array(vec![item])),
+            _ => self.composed_example(model.original?, depth),
+        }
+    }
 }
 
 #[derive(Default)]
@@ -158,9 +471,88 @@ pub struct JsonSchemaExtractOptions {
     pub wrappers: bool,
     pub nested_arrays_as_models: bool,
     pub optional_and_nullable_as_models: bool,
+    // oneOf with one variant is replaced with the variant itself
+    pub collapse_single_variant: bool,
     pub base_name: Option<String>,
     pub allow_list: bool,
     pub keep_schema: tools::Filter,
+    pub keep_schema_options: KeepSchemaOptions,
+    // promotes inline objects nested deeper than given depth, see process flatten
+    pub flatten_depth: Option<usize>,
+    // unsupported keywords end extraction with error instead of permissive models
+    pub strict: bool,
+    // rewrites nullable enum idioms to single form, see process normalize
+    pub normalize: bool,
+    // typed additionalProperties next to properties is extracted as additional_model of object
+    pub additional_properties_model: bool,
+    // optional nullable properties are distinguished from optional ones, see Presence
+    pub tri_state: bool,
+    // objects with more properties get builder metadata
+    pub builder_threshold: Option<usize>,
+    // language of identifiers stored as code_name of properties, see identifiers
+    pub code_names: Option<String>,
+    // numbers of format decimal or with x-precision/x-scale are extracted as decimal type
+    pub decimal: bool,
+    // date and time formats are extracted as distinct types, see TEMPORAL
+    pub temporal: bool,
+    // collects example payloads of models, see ModelContainer collect_examples
+    pub examples: bool,
+}
+
+/// Shape of raw schemas kept on models selected by `keep_schema` filter
+#[derive(Default, Clone)]
+pub struct KeepSchemaOptions {
+    // keywords removed from kept schema, ex. examples, description
+    pub strip: Vec<String>,
+    // keys sorted recursively so equal schemas are serialized equally
+    pub canonical: bool,
+    // schema serialized once to container `schemas`, models keep its index only
+    pub shared: bool,
+}
+
+impl KeepSchemaOptions {
+    fn keep(
+        &self,
+        schema: &Map<String, Value>,
+        container: &mut ModelContainer,
+    ) -> (Option<Value>, Option<usize>) {
+        let mut kept = Value::Object(schema.clone());
+
+        if !self.strip.is_empty() {
+            strip_keywords(&mut kept, &self.strip);
+        }
+
+        if self.canonical {
+            kept = crate::hash::sort_keys(&kept);
+        }
+
+        if self.shared {
+            (None, Some(container.add_schema(kept.to_string())))
+        } else {
+            (Some(kept), None)
+        }
+    }
+}
+
+// names of properties are not keywords and are left intact
+fn strip_keywords(node: &mut Value, keywords: &[String]) {
+    match node {
+        Value::Object(map) => {
+            map.retain(|key, _| !keywords.contains(key));
+
+            for (key, value) in map.iter_mut() {
+                match (key.as_str(), value) {
+                    (
+                        "properties" | "patternProperties" | "definitions" | "$defs",
+                        Value::Object(m),
+                    ) => m.values_mut().for_each(|v| strip_keywords(v, keywords)),
+                    (_, value) => strip_keywords(value, keywords),
+                }
+            }
+        }
+        Value::Array(list) => list.iter_mut().for_each(|v| strip_keywords(v, keywords)),
+        _ => {}
+    }
 }
 
 pub fn extract(
@@ -168,11 +560,27 @@ pub fn extract(
     storage: &SchemaStorage,
     options: JsonSchemaExtractOptions,
 ) -> Result<ModelContainer, Error> {
+    let normalized = options.normalize.then(|| normalize(schema, storage));
+    let (schema, storage) = match &normalized {
+        Some((schema, storage)) => (schema, storage),
+        None => (schema, storage),
+    };
+
+    let flattened = options
+        .flatten_depth
+        .map(|depth| flatten(schema, storage, depth))
+        .transpose()?;
+    let (schema, storage) = match &flattened {
+        Some((schema, storage)) => (schema, storage),
+        None => (schema, storage),
+    };
+
     let mut mcontainer = ModelContainer::default();
 
     if options.allow_list && schema.get_body().is_array() {
         let list = schema.get_body().as_array().unwrap();
         let scope = &mut SchemaScope::default();
+        scope.enter_file(schema.get_url().as_str());
 
         // todo: ... check resolve in multi
         for (i, body) in list.iter().enumerate() {
@@ -192,15 +600,63 @@ pub fn extract(
         add_types(
             schema.get_body(),
             &mut mcontainer,
-            &mut SchemaScope::default(),
+            SchemaScope::default().enter_file(schema.get_url().as_str()),
             &SchemaResolver::new(schema, storage),
             &options,
         )?;
     }
 
+    if options.strict {
+        mcontainer.report().strict()?;
+    }
+
+    if options.examples {
+        mcontainer.collect_examples();
+    }
+
+    mcontainer.tag_pii();
+
     Ok(mcontainer)
 }
 
+/// Returns copy of schema and storage (all referenced files) with nullable enums normalized
+pub fn normalize(schema: &Schema, storage: &SchemaStorage) -> (Schema, SchemaStorage) {
+    let normalizer = crate::process::normalize::Normalizer::options();
+
+    let mut normalized = schema.clone();
+    normalizer.process(&mut normalized);
+
+    let mut storage = storage.clone();
+    for (url, schema) in storage.schemas.iter_mut() {
+        if url == normalized.get_url() {
+            *schema = normalized.clone();
+        } else {
+            normalizer.process(schema);
+        }
+    }
+
+    (normalized, storage)
+}
+
+/// Returns copy of schema and storage with nested inline objects promoted to definitions
+pub fn flatten(
+    schema: &Schema,
+    storage: &SchemaStorage,
+    depth: usize,
+) -> Result<(Schema, SchemaStorage), Error> {
+    let mut flattened = schema.clone();
+    crate::process::flatten::Flattener::options()
+        .with_depth(depth)
+        .process(&mut flattened)?;
+
+    let mut storage = storage.clone();
+    storage
+        .schemas
+        .insert(flattened.get_url().clone(), flattened.clone());
+
+    Ok((flattened, storage))
+}
+
 pub fn add_types(
     node: &Value,
     container: &mut ModelContainer,
@@ -224,7 +680,11 @@ pub fn extract_type(
         if let Some(model) = container.resolve(scope) {
             return Ok(model.clone());
         } else if scope.recurse() {
-            log::warn!("{}: circular refs not implemented yet", scope);
+            container.warn(
+                report::WarningKind::CircularRef,
+                scope,
+                "circular refs not implemented yet",
+            );
 
             return Ok(types::Model::new(types::ModelType::AnyType(
                 types::AnyType {},
@@ -233,9 +693,17 @@ pub fn extract_type(
 
         match node {
             Value::Object(schema) => {
+                if let Some(keyword) = unsupported_keyword(schema) {
+                    container.warn(
+                        report::WarningKind::Unsupported,
+                        scope,
+                        &format!("unsupported keyword {keyword}"),
+                    );
+                }
+
                 title::extract_title(schema, scope, options).inspect(|s| scope.entity(s))?;
 
-                log::trace!("{}", scope);
+                span!("extract", pointer = %scope.pointer());
 
                 let has_id = schema
                     .get("$id")
@@ -307,13 +775,14 @@ pub fn extract_type(
                         .or_else(|_| {
                             const_::from_const(schema, container, scope, resolver, options)
                         })
-                        .or_else(|_| Ok(types::AnyType::model(schema, scope))),
+                        .or_else(|_| Ok(types::AnyType::model(schema, container, scope))),
                 };
 
                 scope.pop();
 
                 let with_spaces = result.map(|mut s| {
                     s.add_spaces(scope);
+                    s.source.get_or_insert_with(|| types::Source::new(scope));
                     s
                 });
 
@@ -324,7 +793,10 @@ pub fn extract_type(
                     with_spaces?,
                     schema,
                     container,
-                    options.keep_schema.check(node, false),
+                    options
+                        .keep_schema
+                        .check(node, false)
+                        .then_some(&options.keep_schema_options),
                 ))
             }
             _ => {
@@ -336,11 +808,42 @@ pub fn extract_type(
     })
 }
 
+/// Arrays nested in arrays, maps and oneOf variants become named models when
+/// `nested_arrays_as_models` is enabled, unnamed ones are named after parent with suffix
+pub fn nested_array_as_model(
+    model: types::Model,
+    parent: Option<&str>,
+    suffix: &str,
+    container: &mut ModelContainer,
+    scope: &mut SchemaScope,
+    options: &JsonSchemaExtractOptions,
+) -> Result<types::Model, Error> {
+    let name = match model.inner() {
+        types::ModelType::ArrayType(a) if options.nested_arrays_as_models => a.name.clone(),
+        _ => return Ok(model),
+    };
+
+    let model = if name.is_none() || name.as_deref() == parent {
+        let mut renamed = model
+            .clone()
+            .rename(scope.namer().decorate(vec![suffix.to_string()]))
+            .with_attributes(&model.attributes);
+        renamed.spaces = model.spaces.clone();
+        renamed
+    } else {
+        model
+    };
+
+    let flat = model.flatten_as_model(container, scope)?;
+
+    Ok(types::Model::new(types::ModelType::FlatModel(flat)).with_attributes(&model.attributes))
+}
+
 fn add_validation_and_nullable(
     model: types::Model,
     schema: &Map<String, Value>,
     mcontainer: &mut ModelContainer,
-    keep_schema: bool,
+    keep_schema: Option<&KeepSchemaOptions>,
 ) -> types::Model {
     if model.attributes.validation.is_some() {
         return model;
@@ -382,6 +885,15 @@ fn add_validation_and_nullable(
         })
         .collect::<HashMap<String, Value>>();
 
+    // temporal types are validated with pattern of their format unless schema has its own
+    if let types::ModelType::PrimitiveType(primitive) = model.inner() {
+        if let Some((_, pattern)) = types::TEMPORAL.iter().find(|(t, _)| *t == primitive.type_) {
+            result
+                .entry("pattern".to_string())
+                .or_insert_with(|| Value::from(*pattern));
+        }
+    }
+
     if let Some(pattern) = result.get("pattern") {
         let model = mcontainer.upsert_regexp(types::RegexpType {
             name: "Regexp".to_string(),
@@ -391,6 +903,27 @@ fn add_validation_and_nullable(
         result.insert("pattern".to_string(), serde_json::to_value(model).unwrap());
     }
 
+    // maps built from patternProperties expose patterns of their keys
+    if let (Some(Value::Object(patterns)), types::ModelType::MapType(_)) =
+        (schema.get("patternProperties"), model.inner())
+    {
+        let regexps = patterns
+            .keys()
+            .map(|pattern| {
+                serde_json::to_value(types::as_regexp_type(mcontainer, pattern)).unwrap()
+            })
+            .collect();
+
+        result.insert("key_patterns".to_string(), Value::Array(regexps));
+    }
+
+    // oneOf/anyOf listing only required fields, ex. exactly one of email or phone
+    for (keyword, key) in [("oneOf", "required_one_of"), ("anyOf", "required_any_of")] {
+        if let Some(groups) = required_groups(schema, keyword) {
+            result.insert(key.to_string(), groups);
+        }
+    }
+
     if let Some(serde_json::Value::String(fmt)) = result.get("format") {
         mcontainer.add_format(fmt);
     }
@@ -414,6 +947,10 @@ fn add_validation_and_nullable(
 
     let default = schema.get("default").cloned();
 
+    let (kept, schema_index) = keep_schema
+        .map(|k| k.keep(schema, mcontainer))
+        .unwrap_or_default();
+
     let mut mmodel = model;
 
     mmodel.attributes = types::Attributes {
@@ -421,12 +958,14 @@ fn add_validation_and_nullable(
         default,
         nullable,
         validation,
+        pii: schema
+            .get("x-pii")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
         x,
-        schema: if keep_schema {
-            Some(Value::Object(schema.clone()))
-        } else {
-            None
-        },
+        schema: kept,
+        schema_index,
+        docs: types::Docs::new(schema),
         ..types::Attributes::default()
     };
 
@@ -485,6 +1024,761 @@ mod tests {
     use super::*;
     use serde_json::json;
 
+    #[test]
+    fn test_container_relations() {
+        let schema = Schema::from_json(json!({
+            "title": "Order",
+            "type": "object",
+            "properties": {
+                "customer": {
+                    "title": "Customer",
+                    "type": "object",
+                    "properties": {
+                        "address": {
+                            "title": "Address",
+                            "type": "object",
+                            "properties": {"city": {"type": "string"}}
+                        }
+                    }
+                }
+            }
+        }));
+
+        let client = reqwest::blocking::Client::new();
+        let container = extract(
+            &schema,
+            &SchemaStorage::new(&schema, &client).unwrap(),
+            JsonSchemaExtractOptions::default(),
+        )
+        .unwrap();
+
+        let order = container.find("Order").unwrap();
+        let customer = container.find("Customer").unwrap();
+        let address = container.find("Address").unwrap();
+
+        assert_eq!(container.get(order).unwrap().name().unwrap(), "Order");
+        assert_eq!(container.children(order), vec![customer, address]);
+        assert_eq!(container.parents(address), &[customer]);
+        assert!(container.exists(container.get(customer).unwrap()));
+        assert_eq!(
+            serde_json::to_value(&container).unwrap()["models"][address.index()]["id"],
+            json!(address.0)
+        );
+    }
+
+    #[test]
+    fn test_container_deduplicates_by_digest() {
+        let schema = Schema::from_json(json!({
+            "title": "Order",
+            "type": "object",
+            "properties": {
+                "billing": {
+                    "title": "Address",
+                    "type": "object",
+                    "properties": {"city": {"type": "string", "x-a": 1, "x-b": 2}}
+                },
+                "shipping": {
+                    "title": "Address",
+                    "type": "object",
+                    "properties": {"city": {"type": "string", "x-b": 2, "x-a": 1}}
+                }
+            }
+        }));
+
+        let client = reqwest::blocking::Client::new();
+        let container = extract(
+            &schema,
+            &SchemaStorage::new(&schema, &client).unwrap(),
+            JsonSchemaExtractOptions::default(),
+        )
+        .unwrap();
+
+        let address = container.find("Address").unwrap();
+
+        assert!(container.find("Address2").is_none());
+        assert_eq!(
+            container.children(container.find("Order").unwrap()),
+            vec![address]
+        );
+    }
+
+    #[test]
+    fn test_flatten_depth_names_models_by_property_path() {
+        let schema = Schema::from_json(json!({
+            "title": "Order",
+            "type": "object",
+            "properties": {
+                "customer": {
+                    "type": "object",
+                    "properties": {
+                        "address": {
+                            "type": "object",
+                            "properties": {"city": {"type": "string"}}
+                        }
+                    }
+                }
+            }
+        }));
+
+        let client = reqwest::blocking::Client::new();
+        let container = extract(
+            &schema,
+            &SchemaStorage::new(&schema, &client).unwrap(),
+            JsonSchemaExtractOptions {
+                flatten_depth: Some(1),
+                ..JsonSchemaExtractOptions::default()
+            },
+        )
+        .unwrap();
+
+        assert!(container.find("OrderCustomerAddress").is_some());
+        assert!(container.find("CustomerAddress").is_none());
+    }
+
+    #[test]
+    fn test_keep_schema_shared_stripped_and_canonical() {
+        let schema = Schema::from_json(json!({
+            "title": "Order",
+            "type": "object",
+            "x-keep": true,
+            "properties": {
+                "description": {"type": "string", "description": "Description", "examples": ["a"]},
+                "billing": {"title": "Address", "x-keep": true, "type": "object", "properties": {"city": {"type": "string"}}},
+                "shipping": {"title": "Address", "type": "object", "x-keep": true, "properties": {"city": {"type": "string"}}}
+            }
+        }));
+
+        let client = reqwest::blocking::Client::new();
+        let container = extract(
+            &schema,
+            &SchemaStorage::new(&schema, &client).unwrap(),
+            JsonSchemaExtractOptions {
+                keep_schema: tools::Filter::new(&["x-keep=true".to_string()]).unwrap(),
+                keep_schema_options: KeepSchemaOptions {
+                    strip: vec!["description".to_string(), "examples".to_string()],
+                    canonical: true,
+                    shared: true,
+                },
+                ..JsonSchemaExtractOptions::default()
+            },
+        )
+        .unwrap();
+
+        let order = container.get(container.find("Order").unwrap()).unwrap();
+        let address = container.get(container.find("Address").unwrap()).unwrap();
+
+        assert_eq!(order.attributes.schema, None);
+        assert_eq!(container.schemas().len(), 2);
+
+        let kept: Value =
+            serde_json::from_str(&container.schemas()[order.attributes.schema_index.unwrap()])
+                .unwrap();
+        assert_eq!(kept["properties"]["description"], json!({"type": "string"}));
+        assert_eq!(
+            container.schemas()[address.attributes.schema_index.unwrap()],
+            r#"{"properties":{"city":{"type":"string"}},"title":"Address","type":"object","x-keep":true}"#
+        );
+    }
+
+    #[test]
+    fn test_docs_keep_line_breaks_and_examples() {
+        let schema = Schema::from_json(json!({
+            "title": "Order",
+            "type": "object",
+            "description": "First line\nsecond line",
+            "deprecated": true,
+            "externalDocs": {"url": "https://example.com/orders"},
+            "examples": [{"id": "1"}],
+            "example": {"id": "2"},
+            "properties": {"id": {"type": "string"}}
+        }));
+
+        let client = reqwest::blocking::Client::new();
+        let container = extract(
+            &schema,
+            &SchemaStorage::new(&schema, &client).unwrap(),
+            JsonSchemaExtractOptions::default(),
+        )
+        .unwrap();
+
+        let order = container.get(container.find("Order").unwrap()).unwrap();
+
+        assert_eq!(
+            order.attributes.description.as_deref(),
+            Some("First line second line")
+        );
+        assert_eq!(
+            order.attributes.docs,
+            types::Docs {
+                summary: None,
+                description: Some("First line\nsecond line".to_string()),
+                external_docs: Some(json!({"url": "https://example.com/orders"})),
+                deprecated: true,
+                examples: vec![json!({"id": "1"}), json!({"id": "2"})],
+            }
+        );
+    }
+
+    #[test]
+    fn test_strict_fails_on_unsupported_keywords() {
+        let schema = Schema::from_json(json!({
+            "title": "Order",
+            "type": "object",
+            "properties": {
+                "status": {"type": "string"},
+                "discount": {
+                    "type": "object",
+                    "properties": {"code": {"type": "string"}},
+                    "not": {"required": ["code"]}
+                }
+            }
+        }));
+
+        let client = reqwest::blocking::Client::new();
+        let storage = SchemaStorage::new(&schema, &client).unwrap();
+
+        assert!(extract(&schema, &storage, JsonSchemaExtractOptions::default()).is_ok());
+
+        let result = extract(
+            &schema,
+            &storage,
+            JsonSchemaExtractOptions {
+                strict: true,
+                ..JsonSchemaExtractOptions::default()
+            },
+        );
+
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "schema://inline#/properties/discount: unsupported keyword not"
+        );
+    }
+
+    #[test]
+    fn test_object_constraints_are_exposed_in_validation() {
+        let schema = Schema::from_json(json!({
+            "title": "Contact",
+            "type": "object",
+            "minProperties": 1,
+            "maxProperties": 3,
+            "properties": {
+                "email": {"type": "string"},
+                "phone": {"type": "string"},
+                "fax": {"type": "string"}
+            },
+            "oneOf": [{"required": ["email"]}, {"required": ["phone", "fax"]}],
+            "anyOf": [{"required": ["email"]}, {"required": ["phone"]}]
+        }));
+
+        let client = reqwest::blocking::Client::new();
+        let storage = SchemaStorage::new(&schema, &client).unwrap();
+
+        let container = extract(
+            &schema,
+            &storage,
+            JsonSchemaExtractOptions {
+                strict: true,
+                ..JsonSchemaExtractOptions::default()
+            },
+        )
+        .unwrap();
+        let value = serde_json::to_value(container).unwrap();
+
+        assert_eq!(value.pointer("/models/0/object/name").unwrap(), "Contact");
+        assert_eq!(
+            value.pointer("/models/0/attributes/validation").unwrap(),
+            &json!({
+                "minProperties": 1,
+                "maxProperties": 3,
+                "required_one_of": [["email"], ["phone", "fax"]],
+                "required_any_of": [["email"], ["phone"]]
+            })
+        );
+    }
+
+    #[test]
+    fn test_decimal_numbers_keep_precision() {
+        let schema = Schema::from_json(json!({
+            "title": "Payment",
+            "type": "object",
+            "properties": {
+                "amount": {"type": "number", "format": "decimal", "x-precision": 18, "x-scale": 2},
+                "rate": {"type": "string", "format": "decimal"},
+                "fee": {"type": "number"}
+            }
+        }));
+
+        let client = reqwest::blocking::Client::new();
+        let storage = SchemaStorage::new(&schema, &client).unwrap();
+
+        let types = |decimal: bool| {
+            let options = JsonSchemaExtractOptions {
+                decimal,
+                ..JsonSchemaExtractOptions::default()
+            };
+            let container = extract(&schema, &storage, options).unwrap();
+            let value = serde_json::to_value(container).unwrap();
+
+            value
+                .pointer("/models/0/object/properties")
+                .unwrap()
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|p| {
+                    (
+                        p["type"].as_str().unwrap().to_string(),
+                        p["validation"].get("precision").cloned(),
+                        p["validation"].get("scale").cloned(),
+                        p["validation"].get("encoding").cloned(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            types(true),
+            vec![
+                (
+                    "decimal".to_string(),
+                    Some(json!(18)),
+                    Some(json!(2)),
+                    Some(json!("number"))
+                ),
+                ("decimal".to_string(), None, None, Some(json!("string"))),
+                ("number".to_string(), None, None, None),
+            ]
+        );
+        assert_eq!(types(false)[0], ("number".to_string(), None, None, None));
+    }
+
+    #[test]
+    fn test_temporal_formats_are_distinct_types() {
+        let schema = Schema::from_json(json!({
+            "title": "Booking",
+            "type": "object",
+            "properties": {
+                "day": {"type": "string", "format": "date"},
+                "starts": {"type": "string", "format": "date-time"},
+                "local": {"type": "string", "format": "date-time", "x-offset": false},
+                "length": {"type": "string", "format": "duration"},
+                "ticket": {"type": "string", "format": "date", "pattern": "^2024-"}
+            }
+        }));
+
+        let client = reqwest::blocking::Client::new();
+        let storage = SchemaStorage::new(&schema, &client).unwrap();
+
+        let options = JsonSchemaExtractOptions {
+            temporal: true,
+            ..JsonSchemaExtractOptions::default()
+        };
+        let container = extract(&schema, &storage, options).unwrap();
+        let value = serde_json::to_value(container).unwrap();
+
+        let regexps = value["regexps"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| (r["name"].clone(), r["pattern"].as_str().unwrap()))
+            .collect::<Vec<_>>();
+        let properties = value
+            .pointer("/models/0/object/properties")
+            .unwrap()
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| {
+                let name = &p["validation"]["pattern"]["name"];
+                let pattern = regexps.iter().find(|r| &r.0 == name).map(|r| r.1);
+                (p["type"].as_str().unwrap(), pattern)
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            properties,
+            vec![
+                ("date", Some(types::TEMPORAL[0].1)),
+                ("date-time", Some(types::TEMPORAL[2].1)),
+                ("local-date-time", Some(types::TEMPORAL[3].1)),
+                ("duration", Some(types::TEMPORAL[4].1)),
+                ("date", Some("^2024-")),
+            ]
+        );
+
+        let date_time = regex::Regex::new(types::TEMPORAL[2].1).unwrap();
+        assert!(date_time.is_match("2024-01-01T10:00:00+02:00"));
+        assert!(!date_time.is_match("2024-01-01T10:00:00"));
+        assert!(regex::Regex::new(types::TEMPORAL[4].1)
+            .unwrap()
+            .is_match("P1DT12H30M"));
+    }
+
+    #[test]
+    fn test_exotic_property_names_produce_valid_model_names() {
+        let schema = Schema::from_json(json!({
+            "title": "Café",
+            "type": "object",
+            "properties": {
+                "名前": {"type": "object", "properties": {"a": {"type": "string"}}},
+                "🚀 launch": {"type": "object", "properties": {"b": {"type": "string"}}},
+                "straße": {"type": "object", "properties": {"c": {"type": "string"}}}
+            }
+        }));
+
+        let client = reqwest::blocking::Client::new();
+        let storage = SchemaStorage::new(&schema, &client).unwrap();
+
+        let container = extract(&schema, &storage, JsonSchemaExtractOptions::default()).unwrap();
+        let value = serde_json::to_value(container).unwrap();
+
+        let names = value["models"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| m["object"]["name"].as_str().unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            names,
+            vec!["CafeU540dU524d", "CafeU1f680Launch", "CafeStrasse", "Cafe"]
+        );
+        assert_eq!(
+            value["models"][3]["object"]["properties"][0]["name"],
+            "名前"
+        );
+    }
+
+    #[test]
+    fn test_transliterated_names_which_collide_are_renamed() {
+        let schema = Schema::from_json(json!({
+            "title": "Address",
+            "type": "object",
+            "properties": {
+                "straße": {"type": "object", "properties": {"a": {"type": "string"}}},
+                "strasse": {"type": "object", "properties": {"b": {"type": "string"}}},
+                "名": {"type": "object", "properties": {"c": {"type": "string"}}},
+                "u540d": {"type": "object", "properties": {"d": {"type": "string"}}}
+            }
+        }));
+
+        let client = reqwest::blocking::Client::new();
+        let storage = SchemaStorage::new(&schema, &client).unwrap();
+
+        let container = extract(&schema, &storage, JsonSchemaExtractOptions::default()).unwrap();
+        let report = container.report().clone();
+        let value = serde_json::to_value(container).unwrap();
+
+        let names = value["models"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| m["object"]["name"].as_str().unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            names,
+            vec![
+                "AddressStrasse",
+                "AddressStrasse2",
+                "AddressU540d",
+                "AddressU540d2",
+                "Address"
+            ]
+        );
+        assert_eq!(
+            report
+                .warnings()
+                .iter()
+                .map(|w| w.message.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "name AddressStrasse already taken by /properties/straße, renaming to: AddressStrasse2",
+                "name AddressU540d already taken by /properties/名, renaming to: AddressU540d2"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_examples_of_models_are_collected() {
+        let schema = Schema::from_json(json!({
+            "title": "Order",
+            "type": "object",
+            "required": ["id", "customer"],
+            "properties": {
+                "id": {"type": "integer", "example": 7},
+                "status": {"type": "string", "default": "new"},
+                "note": {"type": "string"},
+                "customer": {
+                    "type": "object",
+                    "examples": [{"name": "Alice"}, {"name": "Bob"}],
+                    "properties": {"name": {"type": "string"}}
+                },
+                "items": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {"sku": {"type": "string", "examples": ["A-1"]}}
+                    }
+                },
+                "address": {
+                    "type": "object",
+                    "required": ["city"],
+                    "properties": {"city": {"type": "string"}}
+                }
+            }
+        }));
+
+        let client = reqwest::blocking::Client::new();
+        let storage = SchemaStorage::new(&schema, &client).unwrap();
+
+        let options = JsonSchemaExtractOptions {
+            examples: true,
+            ..Default::default()
+        };
+        let container = extract(&schema, &storage, options).unwrap();
+        let value = serde_json::to_value(container).unwrap();
+
+        let examples = value["examples"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| (e["model"].as_str().unwrap(), e["value"].clone()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            examples,
+            vec![
+                ("OrderCustomer", json!({"name": "Alice"})),
+                ("OrderCustomer", json!({"name": "Bob"})),
+                ("OrderItems", json!({"sku": "A-1"})),
+                (
+                    "Order",
+                    json!({
+                        "id": 7,
+                        "status": "new",
+                        "customer": {"name": "Alice"},
+                        "items": [{"sku": "A-1"}]
+                    })
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pii_is_propagated_to_models() {
+        let schema = Schema::from_json(json!({
+            "title": "Order",
+            "type": "object",
+            "properties": {
+                "id": {"type": "integer"},
+                "customer": {
+                    "type": "object",
+                    "properties": {
+                        "email": {"type": "string", "x-pii": true},
+                        "tier": {"type": "string"}
+                    }
+                },
+                "items": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {"sku": {"type": "string"}}
+                    }
+                }
+            }
+        }));
+
+        let client = reqwest::blocking::Client::new();
+        let storage = SchemaStorage::new(&schema, &client).unwrap();
+
+        let container = extract(&schema, &storage, JsonSchemaExtractOptions::default()).unwrap();
+        let value = serde_json::to_value(container).unwrap();
+
+        let models = value["models"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| {
+                let object = &m["object"];
+                let properties = object["properties"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|p| {
+                        (
+                            p["name"].as_str().unwrap(),
+                            p["pii"].as_bool().unwrap(),
+                            p["contains_pii"].as_bool().unwrap(),
+                        )
+                    })
+                    .collect::<Vec<_>>();
+
+                (
+                    object["name"].as_str().unwrap(),
+                    m["attributes"]["contains_pii"].as_bool().unwrap(),
+                    properties,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            models,
+            vec![
+                (
+                    "OrderCustomer",
+                    true,
+                    vec![("email", true, true), ("tier", false, false)]
+                ),
+                ("OrderItems", false, vec![("sku", false, false)]),
+                (
+                    "Order",
+                    true,
+                    vec![
+                        ("id", false, false),
+                        ("customer", false, true),
+                        ("items", false, false)
+                    ]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_normalize_nullable_enum_idioms() {
+        let schema = Schema::from_json(json!({
+            "title": "Order",
+            "type": "object",
+            "properties": {
+                "a": {"type": "string", "enum": ["x", "y"], "nullable": true},
+                "b": {"type": "string", "enum": ["x", "y", null]},
+                "c": {"anyOf": [{"type": "string", "enum": ["x", "y"]}, {"type": "null"}]},
+                "d": {"enum": ["x", "y", null]}
+            }
+        }));
+
+        let client = reqwest::blocking::Client::new();
+        let storage = SchemaStorage::new(&schema, &client).unwrap();
+
+        let container = extract(
+            &schema,
+            &storage,
+            JsonSchemaExtractOptions {
+                normalize: true,
+                ..JsonSchemaExtractOptions::default()
+            },
+        )
+        .unwrap();
+        let value = serde_json::to_value(container).unwrap();
+
+        let properties = value
+            .pointer("/models/4/object/properties")
+            .and_then(|p| p.as_array())
+            .unwrap();
+
+        assert_eq!(properties.len(), 4);
+        for property in properties {
+            assert_eq!(property["nullable"], json!(true), "{}", property["name"]);
+            assert_eq!(property["type"], json!("enum"), "{}", property["name"]);
+        }
+    }
+
+    #[test]
+    fn test_report_any_type_and_circular_refs() {
+        let schema = Schema::from_json(json!({
+            "definitions": {
+                "node": {
+                    "title": "Node",
+                    "type": "object",
+                    "properties": {"next": {"$ref": "#/definitions/node"}}
+                }
+            },
+            "title": "Tree",
+            "type": "object",
+            "properties": {
+                "root": {"$ref": "#/definitions/node"},
+                "payload": {"type": "object"},
+                "anything": {},
+                "limited": {"minimum": 1}
+            }
+        }));
+
+        let client = reqwest::blocking::Client::new();
+        let container = extract(
+            &schema,
+            &SchemaStorage::new(&schema, &client).unwrap(),
+            JsonSchemaExtractOptions::default(),
+        )
+        .unwrap();
+
+        let report = container.report();
+        assert_eq!(report.count(report::WarningKind::CircularRef), 1);
+        assert_eq!(report.count(report::WarningKind::AnyType), 1);
+        assert_eq!(
+            report
+                .warnings()
+                .iter()
+                .find(|w| w.kind == report::WarningKind::AnyType)
+                .unwrap()
+                .source
+                .pointer,
+            "/properties/limited"
+        );
+        assert!(report
+            .summary()
+            .ends_with("2 warnings: 0 unsupported, 1 any type, 1 circular ref, 0 renamed"));
+    }
+
+    #[test]
+    fn test_models_keep_source_location() {
+        let schema = Schema::from_json(json!({
+            "definitions": {
+                "address": {
+                    "title": "Address",
+                    "type": "object",
+                    "properties": {"city": {"type": "string"}}
+                }
+            },
+            "title": "Order",
+            "type": "object",
+            "properties": {
+                "shipping": {"$ref": "#/definitions/address"},
+                "customer": {
+                    "title": "Customer",
+                    "type": "object",
+                    "properties": {"name": {"type": "string"}}
+                }
+            }
+        }));
+
+        let client = reqwest::blocking::Client::new();
+        let container = extract(
+            &schema,
+            &SchemaStorage::new(&schema, &client).unwrap(),
+            JsonSchemaExtractOptions::default(),
+        )
+        .unwrap();
+
+        let source = |name: &str| {
+            container
+                .get(container.find(name).unwrap())
+                .unwrap()
+                .source
+                .clone()
+                .unwrap()
+        };
+
+        assert_eq!(
+            source("Address"),
+            types::Source {
+                file: Some("schema://inline".to_string()),
+                pointer: "/definitions/address".to_string()
+            }
+        );
+        assert_eq!(source("Customer").pointer, "/properties/customer");
+        assert_eq!(source("Order").pointer, "");
+    }
+
     #[test]
     fn test_nullable_one_of_should_inherit_additional_from_detected_type() {
         let schema = Schema::from_json(json!({
@@ -514,7 +1808,7 @@ mod tests {
             schema.get_body(),
             &mut mcontainer,
             &mut SchemaScope::default(),
-            &SchemaResolver::new(&schema, &SchemaStorage::new(&schema, &client)),
+            &SchemaResolver::new(&schema, &SchemaStorage::new(&schema, &client).unwrap()),
             &options,
         )
         .unwrap();
@@ -522,7 +1816,8 @@ mod tests {
         assert_eq!(
             types::Model::new(types::ModelType::PrimitiveType(types::PrimitiveType {
                 name: Some("MySecretName".to_string()),
-                type_: "string".to_string()
+                type_: "string".to_string(),
+                ..types::PrimitiveType::default()
             }))
             .with_attributes(&types::Attributes {
                 nullable: true,
@@ -613,7 +1908,11 @@ mod tests {
         let options = JsonSchemaExtractOptions::default();
 
         let client = reqwest::blocking::Client::new();
-        let result = extract(&schema, &SchemaStorage::new(&schema, &client), options);
+        let result = extract(
+            &schema,
+            &SchemaStorage::new(&schema, &client).unwrap(),
+            options,
+        );
 
         assert!(result.is_ok());
     }
@@ -649,7 +1948,11 @@ mod tests {
         let options = JsonSchemaExtractOptions::default();
 
         let client = reqwest::blocking::Client::new();
-        let result = extract(&schema, &SchemaStorage::new(&schema, &client), options);
+        let result = extract(
+            &schema,
+            &SchemaStorage::new(&schema, &client).unwrap(),
+            options,
+        );
 
         assert!(result.is_ok());
 
@@ -1,7 +1,7 @@
 use std::borrow::Cow;
 
 use super::{
-    types::{Model, ModelType, WrapperType},
+    types::{EnumType, FlatModel, Model, ModelType, WrapperStrategy, WrapperType, WrapperTypeKind},
     JsonSchemaExtractOptions, ModelContainer,
 };
 use serde_json::{Map, Value};
@@ -37,6 +37,24 @@ pub fn from_oneof(
                     return converted;
                 }
 
+                if let Some(converted) =
+                    nullable_one_of(schema, variants, container, scope, resolver, options)
+                {
+                    return converted;
+                }
+
+                if let Some(converted) = singletons_to_enum(variants, scope, resolver) {
+                    return converted;
+                }
+
+                if let [variant] = &variants[..] {
+                    if options.collapse_single_variant {
+                        log::debug!("{}: collapsing oneOf with single variant", scope);
+
+                        return super::extract_type(variant, container, scope, resolver, options);
+                    }
+                }
+
                 scope.form("oneOf");
 
                 let models = extractor
@@ -47,6 +65,11 @@ pub fn from_oneof(
                         scope.index(i);
                         let result =
                             super::extract_type(value, container, scope, resolver, options)
+                                .and_then(|m| {
+                                    super::nested_array_as_model(
+                                        m, None, "item", container, scope, options,
+                                    )
+                                })
                                 .and_then(|mut m| {
                                     // all object names in oneOf of enum structure are changed
                                     // to avoid collisions, in many cases extractor will modify
@@ -59,6 +82,7 @@ pub fn from_oneof(
                                     extractor.extract(value, m, container, scope)
                                 })
                                 .map(|mut s| {
+                                    reference_siblings(value, &mut s);
                                     s.attributes.required = true;
                                     s.name = Some(
                                         scope
@@ -75,13 +99,22 @@ pub fn from_oneof(
 
                 scope.pop();
 
-                // todo: wrapper to only flattened
-                Ok(Model::new(ModelType::WrapperType(WrapperType {
+                let mut wrapper = WrapperType {
                     name: scope.namer().decorate(vec!["Variant".to_string()]),
                     models: models?,
                     strategy: extractor.strategy(),
                     ..WrapperType::default()
-                })))
+                };
+
+                if wrapper.strategy == WrapperStrategy::BruteForce && mixed(&mut wrapper.models) {
+                    log::debug!("{}: oneOf of primitives and models", scope);
+
+                    wrapper.kind = WrapperTypeKind::Mixed;
+                    wrapper.strategy = WrapperStrategy::Untagged;
+                }
+
+                // todo: wrapper to only flattened
+                Ok(Model::new(ModelType::WrapperType(wrapper)))
             }
             _ => Err(Error::SchemaInvalidProperty("oneOf".to_string())),
         },
@@ -120,7 +153,10 @@ fn simplify_one_of(
                             m,
                             node.as_object().unwrap(),
                             container,
-                            options.keep_schema.check(node, false),
+                            options
+                                .keep_schema
+                                .check(node, false)
+                                .then_some(&options.keep_schema_options),
                         )
                         .with_attributes(&attributes)
                     }),
@@ -130,11 +166,165 @@ fn simplify_one_of(
     })
 }
 
+// description and extensions placed next to $ref of variant describe the variant itself
+fn reference_siblings(variant: &Value, model: &mut FlatModel) {
+    let siblings = match variant.as_object() {
+        Some(map) if map.contains_key("$ref") => map,
+        _ => return,
+    };
+
+    if let Some(Value::String(description)) = siblings.get("description") {
+        model.attributes.description = Some(description.lines().collect::<Vec<_>>().join(" "));
+    }
+
+    for (key, value) in siblings {
+        if let Some(name) = key.strip_prefix("x-") {
+            model.attributes.x.insert(name.to_string(), value.clone());
+        }
+    }
+}
+
+const VARIANT_META: &str = "_variant";
+const PRIMITIVES: [&str; 4] = ["integer", "number", "boolean", "string"];
+
+// flags variants of oneOf mixing primitives and models and orders them for untagged
+// deserialization: models first, then primitives from the most specific one
+pub(super) fn mixed(models: &mut [FlatModel]) -> bool {
+    let position = |m: &FlatModel| {
+        PRIMITIVES
+            .iter()
+            .position(|p| *p == m.type_)
+            .map(|p| p + 1)
+            .unwrap_or(if m.type_ == "any" {
+                PRIMITIVES.len() + 1
+            } else {
+                0
+            })
+    };
+
+    let primitives = models
+        .iter()
+        .filter(|m| PRIMITIVES.contains(&m.type_.as_str()))
+        .count();
+
+    if primitives == 0 || primitives == models.len() {
+        return false;
+    }
+
+    models.sort_by_key(position);
+
+    for model in models.iter_mut() {
+        let kind = if PRIMITIVES.contains(&model.type_.as_str()) {
+            "primitive"
+        } else {
+            "model"
+        };
+
+        model
+            .attributes
+            .x
+            .insert(VARIANT_META.to_string(), Value::String(kind.to_string()));
+    }
+
+    true
+}
+
+// oneOf of single value string enums or consts is plain enum of those values
+fn singletons_to_enum(
+    variants: &[Value],
+    scope: &mut SchemaScope,
+    resolver: &SchemaResolver,
+) -> Option<Result<Model, Error>> {
+    let mut values: Vec<String> = vec![];
+
+    for variant in variants {
+        let value = resolver
+            .resolve(variant, scope, |node, _| Ok(singleton(node)))
+            .ok()
+            .flatten()?;
+
+        if !values.contains(&value) {
+            values.push(value);
+        }
+    }
+
+    if values.is_empty() {
+        return None;
+    }
+
+    log::debug!("{}: mapping oneOf of singletons to enum", scope);
+
+    Some(scope.namer().simple().map(|name| {
+        Model::new(ModelType::EnumType(EnumType {
+            name,
+            type_: "string".to_string(),
+            variants: values,
+        }))
+    }))
+}
+
+fn singleton(node: &Value) -> Option<String> {
+    let schema = node.as_object()?;
+
+    if schema.get("type").is_some_and(|t| t != "string") {
+        return None;
+    }
+
+    match (schema.get("const"), schema.get("enum")) {
+        (Some(Value::String(value)), _) => Some(value.clone()),
+        (None, Some(Value::Array(values))) => match &values[..] {
+            [Value::String(value)] => Some(value.clone()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// oneOf with null and many other variants is nullable oneOf of remaining variants
+fn nullable_one_of(
+    schema: &Map<String, Value>,
+    variants: &[Value],
+    container: &mut ModelContainer,
+    scope: &mut SchemaScope,
+    resolver: &SchemaResolver,
+    options: &JsonSchemaExtractOptions,
+) -> Option<Result<Model, Error>> {
+    let null_type = serde_json::json!({"type":"null"});
+
+    if variants.len() < 3 || !variants.contains(&null_type) {
+        return None;
+    }
+
+    log::debug!("{}: mapping oneOf with null to nullable oneOf", scope);
+
+    let mut without_null = schema.clone();
+    without_null.insert(
+        "oneOf".to_string(),
+        Value::Array(
+            variants
+                .iter()
+                .filter(|v| *v != &null_type)
+                .cloned()
+                .collect(),
+        ),
+    );
+
+    Some(
+        from_oneof(&without_null, container, scope, resolver, options).map(|mut m| {
+            m.attributes.nullable = true;
+            m
+        }),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
 
-    use crate::codegen::jsonschema::types::{Attributes, FlatModel, ObjectType, WrapperStrategy};
+    use crate::codegen::jsonschema::{
+        types::{Attributes, Constructor, FlatModel, ObjectType, WrapperStrategy},
+        ModelId,
+    };
 
     use super::*;
     use serde_json::json;
@@ -187,10 +377,22 @@ mod tests {
                                             "model": null,
                                             "required": true,
                                             "nullable": false,
+                                            "presence": "required",
                                             "validation": null,
                                             "x": {},
+                                            "pii": false,
+                                            "contains_pii": false,
                                             "description": null,
-                                            "default": null
+                                            "default": null,
+                                            "has_default": false,
+                                            "code_name": null,
+                                            "docs": {
+                                                "summary": null,
+                                                "description": null,
+                                                "external_docs": null,
+                                                "deprecated": false,
+                                                "examples": []
+                                            }
                                         }
                                     },
                                     "properties": 1
@@ -202,7 +404,7 @@ mod tests {
                             reference: true,
                             ..Attributes::default()
                         },
-                        original: Some(0),
+                        original: Some(ModelId(0)),
                         ..FlatModel::default()
                     },
                     FlatModel {
@@ -225,10 +427,22 @@ mod tests {
                                             "model": null,
                                             "required": true,
                                             "nullable": false,
+                                            "presence": "required",
                                             "validation": null,
                                             "x": {},
+                                            "pii": false,
+                                            "contains_pii": false,
                                             "description": null,
-                                            "default": null
+                                            "default": null,
+                                            "has_default": false,
+                                            "code_name": null,
+                                            "docs": {
+                                                "summary": null,
+                                                "description": null,
+                                                "external_docs": null,
+                                                "deprecated": false,
+                                                "examples": []
+                                            }
                                         }
                                     },
                                     "properties": 1
@@ -240,7 +454,7 @@ mod tests {
                             reference: true,
                             ..Attributes::default()
                         },
-                        original: Some(1),
+                        original: Some(ModelId(1)),
                         ..FlatModel::default()
                     }
                 ],
@@ -301,7 +515,7 @@ mod tests {
                             reference: true,
                             ..Attributes::default()
                         },
-                        original: Some(1),
+                        original: Some(ModelId(1)),
                         ..FlatModel::default()
                     },
                     FlatModel {
@@ -327,7 +541,7 @@ mod tests {
                             reference: true,
                             ..Attributes::default()
                         },
-                        original: Some(3),
+                        original: Some(ModelId(3)),
                         ..FlatModel::default()
                     }
                 ],
@@ -368,7 +582,13 @@ mod tests {
                     ..FlatModel::default()
                 },],
                 additional: true,
-                ..ObjectType::default()
+                additional_model: None,
+                builder: None,
+                constructor: Constructor {
+                    required: vec![],
+                    defaulted: vec![],
+                    optional: vec!["test".to_string()],
+                },
             }))
             .with_attributes(&Attributes {
                 nullable: true,
@@ -414,4 +634,300 @@ mod tests {
             }))
         );
     }
+
+    #[test]
+    fn test_should_convert_array_variant_to_model() {
+        let schema = json!({
+            "oneOf": [
+                {"type": "array", "items": {"type": "string"}},
+                {"type": "string"}
+            ]
+        });
+
+        let mut container = ModelContainer::default();
+        let mut scope = SchemaScope::default();
+        let resolver = SchemaResolver::empty();
+        let options = JsonSchemaExtractOptions {
+            nested_arrays_as_models: true,
+            ..JsonSchemaExtractOptions::default()
+        };
+
+        scope.entity("TestName");
+        let result = from_oneof(
+            schema.as_object().unwrap(),
+            &mut container,
+            &mut scope,
+            &resolver,
+            &options,
+        )
+        .unwrap();
+
+        let list = container.find("TestNameOption1").unwrap();
+
+        match result.inner() {
+            ModelType::WrapperType(w) => {
+                assert_eq!(w.models[0].type_, "wrapper");
+                assert_eq!(w.models[0].original, Some(list));
+                assert_eq!(w.models[1].type_, "string");
+            }
+            _ => panic!("wrapper expected"),
+        }
+    }
+
+    #[test]
+    fn test_should_collapse_single_variant() {
+        let schema = json!({
+            "oneOf": [
+                {"type": "object", "properties": {"a": {"type": "string"}}}
+            ]
+        });
+
+        let mut container = ModelContainer::default();
+        let mut scope = SchemaScope::default();
+        let resolver = SchemaResolver::empty();
+        let options = JsonSchemaExtractOptions {
+            collapse_single_variant: true,
+            ..JsonSchemaExtractOptions::default()
+        };
+
+        scope.entity("TestName");
+        let result = from_oneof(
+            schema.as_object().unwrap(),
+            &mut container,
+            &mut scope,
+            &resolver,
+            &options,
+        )
+        .unwrap();
+
+        match result.inner() {
+            ModelType::ObjectType(o) => assert_eq!(o.name, "TestName"),
+            _ => panic!("object expected"),
+        }
+
+        let result = from_oneof(
+            schema.as_object().unwrap(),
+            &mut container,
+            &mut scope,
+            &resolver,
+            &JsonSchemaExtractOptions::default(),
+        )
+        .unwrap();
+
+        assert!(matches!(result.inner(), ModelType::WrapperType(_)));
+    }
+
+    #[test]
+    fn test_should_convert_null_and_many_variants_to_nullable_wrapper() {
+        let schema = json!({
+            "oneOf": [
+                {"type": "string"},
+                {"type": "null"},
+                {"type": "number"}
+            ]
+        });
+
+        let mut container = ModelContainer::default();
+        let mut scope = SchemaScope::default();
+        let resolver = SchemaResolver::empty();
+        let options = JsonSchemaExtractOptions::default();
+
+        scope.entity("TestName");
+        let result = from_oneof(
+            schema.as_object().unwrap(),
+            &mut container,
+            &mut scope,
+            &resolver,
+            &options,
+        )
+        .unwrap();
+
+        assert!(result.attributes.nullable);
+
+        match result.inner() {
+            ModelType::WrapperType(w) => {
+                assert_eq!(w.name, "TestNameVariant");
+                assert_eq!(
+                    w.models
+                        .iter()
+                        .map(|m| m.type_.as_str())
+                        .collect::<Vec<_>>(),
+                    vec!["string", "number"]
+                );
+            }
+            _ => panic!("wrapper expected"),
+        }
+    }
+
+    #[test]
+    fn test_should_collapse_singletons_to_enum() {
+        let schema = json!({
+            "definitions": {
+                "failed": {"type": "string", "enum": ["failed"], "description": "Failed"}
+            },
+            "oneOf": [
+                {"const": "pending", "title": "Pending"},
+                {"type": "string", "enum": ["done"]},
+                {"$ref": "#/definitions/failed"}
+            ]
+        });
+
+        let mut container = ModelContainer::default();
+        let mut scope = SchemaScope::default();
+        let options = JsonSchemaExtractOptions::default();
+
+        let schema = crate::schema::Schema::from_json(schema);
+        let client = reqwest::blocking::Client::new();
+        let storage = crate::storage::SchemaStorage::new(&schema, &client).unwrap();
+        let resolver = SchemaResolver::new(&schema, &storage);
+
+        scope.entity("Status");
+        let result = from_oneof(
+            schema.get_body().as_object().unwrap(),
+            &mut container,
+            &mut scope,
+            &resolver,
+            &options,
+        )
+        .unwrap();
+
+        assert_eq!(
+            result,
+            Model::new(ModelType::EnumType(EnumType {
+                name: "Status".to_string(),
+                type_: "string".to_string(),
+                variants: vec![
+                    "pending".to_string(),
+                    "done".to_string(),
+                    "failed".to_string()
+                ],
+            }))
+        );
+    }
+
+    #[test]
+    fn test_should_model_string_or_object_as_mixed() {
+        let schema = json!({
+            "oneOf": [
+                {"type": "string"},
+                {"type": "object", "properties": {"url": {"type": "string"}, "token": {"type": "string"}}},
+                {"type": "integer"}
+            ]
+        });
+
+        let mut container = ModelContainer::default();
+        let mut scope = SchemaScope::default();
+        let resolver = SchemaResolver::empty();
+        let options = JsonSchemaExtractOptions::default();
+
+        scope.entity("Source");
+        let result = from_oneof(
+            schema.as_object().unwrap(),
+            &mut container,
+            &mut scope,
+            &resolver,
+            &options,
+        )
+        .unwrap();
+
+        match result.inner() {
+            ModelType::WrapperType(w) => {
+                assert_eq!(w.kind, WrapperTypeKind::Mixed);
+                assert_eq!(w.strategy, WrapperStrategy::Untagged);
+                assert_eq!(
+                    w.models
+                        .iter()
+                        .map(|m| (
+                            m.type_.as_str(),
+                            m.attributes.x["_variant"].as_str().unwrap()
+                        ))
+                        .collect::<Vec<_>>(),
+                    vec![
+                        ("object", "model"),
+                        ("integer", "primitive"),
+                        ("string", "primitive")
+                    ]
+                );
+            }
+            _ => panic!("wrapper expected"),
+        }
+    }
+
+    #[test]
+    fn test_should_not_model_primitives_only_as_mixed() {
+        let schema = json!({
+            "oneOf": [
+                {"type": "string"},
+                {"type": "integer"}
+            ]
+        });
+
+        let mut container = ModelContainer::default();
+        let mut scope = SchemaScope::default();
+        let resolver = SchemaResolver::empty();
+        let options = JsonSchemaExtractOptions::default();
+
+        scope.entity("Source");
+        let result = from_oneof(
+            schema.as_object().unwrap(),
+            &mut container,
+            &mut scope,
+            &resolver,
+            &options,
+        )
+        .unwrap();
+
+        match result.inner() {
+            ModelType::WrapperType(w) => {
+                assert_eq!(w.kind, WrapperTypeKind::OneOf);
+                assert_eq!(w.strategy, WrapperStrategy::BruteForce);
+                assert!(w.models[0].attributes.x.is_empty());
+            }
+            _ => panic!("wrapper expected"),
+        }
+    }
+
+    #[test]
+    fn test_should_keep_reference_siblings_on_variant() {
+        let schema = crate::schema::Schema::from_json(json!({
+            "definitions": {
+                "card": {"title": "Card", "type": "object", "description": "Card", "properties": {"number": {"type": "string"}}},
+                "cash": {"title": "Cash", "type": "object", "properties": {"currency": {"type": "string"}}}
+            },
+            "oneOf": [
+                {"$ref": "#/definitions/card", "description": "Paid with\ncard", "x-rank": 1},
+                {"$ref": "#/definitions/cash"}
+            ]
+        }));
+
+        let mut container = ModelContainer::default();
+        let mut scope = SchemaScope::default();
+        let options = JsonSchemaExtractOptions::default();
+
+        let client = reqwest::blocking::Client::new();
+        let storage = crate::storage::SchemaStorage::new(&schema, &client).unwrap();
+        let resolver = SchemaResolver::new(&schema, &storage);
+
+        scope.entity("Payment");
+        let result = from_oneof(
+            schema.get_body().as_object().unwrap(),
+            &mut container,
+            &mut scope,
+            &resolver,
+            &options,
+        )
+        .unwrap();
+
+        match result.inner() {
+            ModelType::WrapperType(w) => {
+                assert_eq!(
+                    w.models[0].attributes.description.as_deref(),
+                    Some("Paid with card")
+                );
+                assert_eq!(w.models[0].attributes.x["rank"], json!(1));
+                assert_eq!(w.models[1].attributes.description, None);
+            }
+            _ => panic!("wrapper expected"),
+        }
+    }
 }
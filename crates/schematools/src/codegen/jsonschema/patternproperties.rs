@@ -1,7 +1,12 @@
+use std::collections::HashMap;
+
 use serde_json::{Map, Value};
 
 use super::{
-    types::{AnyType, FlatModel, Model, ModelType},
+    types::{
+        as_regexp_type, FlatModel, MapType, Model, ModelType, WrapperStrategy, WrapperType,
+        WrapperTypeKind,
+    },
     JsonSchemaExtractOptions, ModelContainer,
 };
 use crate::{error::Error, resolver::SchemaResolver, scope::SchemaScope};
@@ -25,7 +30,18 @@ pub fn from_pattern_properties(
                         scope.form(pattern);
                         let result =
                             super::extract_type(value, container, scope, resolver, options)
-                                .and_then(|m| m.flatten(container, scope));
+                                .and_then(|m| {
+                                    super::nested_array_as_model(
+                                        m,
+                                        Some(&name),
+                                        "value",
+                                        container,
+                                        scope,
+                                        options,
+                                    )
+                                })
+                                .and_then(|m| m.flatten(container, scope))
+                                .map(|m| (pattern.as_str(), m));
                         scope.pop();
 
                         result
@@ -35,26 +51,25 @@ pub fn from_pattern_properties(
                 types
             }?;
 
-            let model = {
-                let first_type = types
-                    .first()
-                    .map(|s| s.type_.clone())
-                    .unwrap_or_else(|| "string".to_string());
-                let filtered = types.iter().filter(|f| f.type_ == first_type).count();
-
-                if filtered != types.len() {
-                    log::warn!("{}: patternProperties is mixed", scope);
-                    AnyType::model(map, scope).flatten(container, scope)?
-                } else {
-                    types.first().unwrap().clone()
+            let model = match &types[..] {
+                [] => FlatModel {
+                    name: Some(name.clone()),
+                    type_: "string".to_string(),
+                    ..FlatModel::default()
+                },
+                [(_, first), rest @ ..]
+                    if rest
+                        .iter()
+                        .all(|(_, m)| String::from(m) == String::from(first)) =>
+                {
+                    first.clone()
                 }
+                _ => union(types, container, scope)?,
             };
 
-            Ok(Model::new(ModelType::FlatModel(FlatModel {
+            Ok(Model::new(ModelType::MapType(MapType {
                 name: Some(name),
-                type_: "map".to_string(),
-                model: Some(Box::new(model)),
-                ..FlatModel::default()
+                model: Box::new(model),
             })))
         }
         _ => Err(Error::SchemaInvalidProperty(
@@ -63,6 +78,54 @@ pub fn from_pattern_properties(
     }
 }
 
+// values of keys matching different patterns have different types, map value becomes
+// a wrapper with variant per pattern, each variant carries its key pattern
+fn union(
+    types: Vec<(&str, FlatModel)>,
+    container: &mut ModelContainer,
+    scope: &mut SchemaScope,
+) -> Result<FlatModel, Error> {
+    log::debug!("{}: patternProperties with different value types", scope);
+
+    let mut models = types
+        .into_iter()
+        .enumerate()
+        .map(|(i, (pattern, mut model))| {
+            let regexp = serde_json::to_value(as_regexp_type(container, pattern))
+                .map_err(Error::SerdeJsonError)?;
+
+            model.attributes.required = true;
+            model
+                .attributes
+                .validation
+                .get_or_insert_with(HashMap::new)
+                .insert("key_pattern".to_string(), regexp);
+            model.name = Some(
+                scope
+                    .namer()
+                    .build(vec!["variant".to_string(), i.to_string()]),
+            );
+
+            Ok(model)
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let kind = if super::oneof::mixed(&mut models) {
+        WrapperTypeKind::Mixed
+    } else {
+        WrapperTypeKind::OneOf
+    };
+
+    let wrapper = WrapperType {
+        name: scope.namer().decorate(vec!["Value".to_string()]),
+        models,
+        strategy: WrapperStrategy::Untagged,
+        kind,
+    };
+
+    Model::new(ModelType::WrapperType(wrapper)).flatten(container, scope)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::codegen::jsonschema::types::Model;
@@ -93,24 +156,22 @@ mod tests {
 
         assert_eq!(
             result.unwrap(),
-            Model::new(ModelType::FlatModel(FlatModel {
+            Model::new(ModelType::MapType(MapType {
                 name: Some("TestName".to_string()),
-                type_: "map".to_string(),
-                model: Some(Box::new(FlatModel {
+                model: Box::new(FlatModel {
                     name: Some("TestName".to_string()),
                     type_: "string".to_string(),
                     ..FlatModel::default()
-                })),
-                ..FlatModel::default()
+                }),
             }))
         );
     }
 
     #[test]
-    fn test_should_convert_to_any_on_mixed() {
+    fn test_should_convert_to_union_on_mixed() {
         let schema = json!({"patternProperties": {
-            "[A-z]+": { "type": "string"},
-            "[0-9]+": { "type": "number"}
+            "^[a-z]+$": { "type": "object", "properties": {"id": {"type": "string"}}},
+            "^[0-9]+$": { "type": "number"}
         }});
 
         let mut container = ModelContainer::default();
@@ -125,20 +186,35 @@ mod tests {
             &mut scope,
             &resolver,
             &options,
+        )
+        .unwrap();
+
+        let value = match result.inner() {
+            ModelType::MapType(map) => &map.model,
+            _ => unreachable!(),
+        };
+        assert_eq!(value.type_, "wrapper");
+
+        let context = serde_json::to_value(&container).unwrap();
+        let wrapper = context["models"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find_map(|m| m.get("wrapper"))
+            .unwrap();
+
+        assert_eq!(wrapper["name"], "TestNameValue");
+        assert_eq!(wrapper["kind"], "Mixed");
+        assert_eq!(wrapper["models"][0]["type"], "object");
+        assert_eq!(
+            wrapper["models"][0]["validation"]["key_pattern"]["pattern"],
+            "^[a-z]+$"
         );
-
+        assert_eq!(wrapper["models"][1]["type"], "number");
         assert_eq!(
-            result.unwrap(),
-            Model::new(ModelType::FlatModel(FlatModel {
-                name: Some("TestName".to_string()),
-                type_: "map".to_string(),
-                model: Some(Box::new(FlatModel {
-                    name: None,
-                    type_: "any".to_string(),
-                    ..FlatModel::default()
-                })),
-                ..FlatModel::default()
-            }))
+            wrapper["models"][1]["validation"]["key_pattern"]["pattern"],
+            "^[0-9]+$"
         );
+        assert_eq!(context["regexps"].as_array().unwrap().len(), 2);
     }
 }
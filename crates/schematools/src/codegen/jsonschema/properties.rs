@@ -1,5 +1,8 @@
 use super::{
-    types::{AnyType, FlatModel, Model, ModelType, NullableOptionalWrapperType, ObjectType},
+    types::{
+        AnyType, Builder, Constructor, FlatModel, Model, ModelType, NullableOptionalWrapperType,
+        ObjectType, Presence,
+    },
     JsonSchemaExtractOptions, ModelContainer,
 };
 use crate::{
@@ -34,8 +37,22 @@ pub fn from_object_with_properties(
 
                     model.name = Some(name.clone());
                     model.attributes.required = required.contains(name);
-
-                    let model = if model.attributes.nullable
+                    model.attributes.has_default = model
+                        .attributes
+                        .default
+                        .as_ref()
+                        .is_some_and(|default| default_fits(default, &model, container));
+
+                    let (default, has_default) = (
+                        model.attributes.default.clone(),
+                        model.attributes.has_default,
+                    );
+                    let presence = Presence::new(
+                        model.attributes.required,
+                        model.attributes.nullable,
+                        options.tri_state,
+                    );
+                    let mut model = if model.attributes.nullable
                         && !model.attributes.required
                         && options.optional_and_nullable_as_models
                     {
@@ -44,6 +61,10 @@ pub fn from_object_with_properties(
                     } else {
                         model
                     };
+                    model.attributes.default = default;
+                    model.attributes.has_default = has_default;
+                    // kept only when it differs from one derived from required and nullable
+                    model.attributes.presence = Some(presence).filter(|p| *p != model.presence());
 
                     scope.pop();
 
@@ -53,8 +74,46 @@ pub fn from_object_with_properties(
 
             scope.pop();
 
+            let mut properties = properties;
+            if let Some(lang) = &options.code_names {
+                let names = properties.iter().filter_map(|p| p.name.as_deref());
+                let code_names = crate::codegen::identifiers::identifiers(lang, names)?;
+
+                for (property, code_name) in properties.iter_mut().zip(code_names) {
+                    property.attributes.code_name = Some(code_name);
+                }
+            }
+
+            // values are named after object with suffix to not collide with object itself
+            let additional_model = match schema.get("additionalProperties") {
+                Some(value @ Value::Object(_)) if options.additional_properties_model => {
+                    let value_name = scope.namer().decorate(vec!["value".to_string()]);
+
+                    scope.entity(&value_name);
+                    let model = super::additionalproperties::extract_value(
+                        value,
+                        &value_name,
+                        container,
+                        scope,
+                        resolver,
+                        options,
+                    );
+                    scope.pop();
+
+                    Some(model?)
+                }
+                _ => None,
+            };
+
+            let builder = options
+                .builder_threshold
+                .filter(|threshold| properties.len() > *threshold)
+                .map(|_| Builder::new(&name, &properties));
+
             Ok(Model::new(ModelType::ObjectType(ObjectType {
                 name,
+                constructor: Constructor::new(&properties),
+                builder,
                 properties,
                 additional: schema
                     .get("additionalProperties")
@@ -63,12 +122,49 @@ pub fn from_object_with_properties(
                         _ => true,
                     })
                     .unwrap_or(true),
+                additional_model,
             })))
         }
         _ => Err(Error::SchemaInvalidProperty("properties".to_string())),
     }
 }
 
+// default is usable in constructors only when it matches type of property
+fn default_fits(default: &Value, model: &FlatModel, container: &ModelContainer) -> bool {
+    let nested = |value: &Value| {
+        model
+            .model
+            .as_deref()
+            .is_none_or(|m| default_fits(value, m, container))
+    };
+
+    match (default, model.type_.as_str()) {
+        (Value::Null, _) => model.attributes.nullable,
+        (_, "any") => true,
+        (Value::String(_), "string") | (Value::Bool(_), "boolean") => true,
+        (Value::Number(n), "integer") => n.is_i64() || n.is_u64(),
+        (Value::Number(_), "number") => true,
+        (Value::Array(items), "array") => items.iter().all(nested),
+        (Value::Object(values), "map") => values.values().all(nested),
+        (Value::Object(_), "object") => true,
+        (_, "enum") => {
+            let value = default
+                .as_str()
+                .map(|s| s.to_string())
+                .unwrap_or_else(|| default.to_string());
+
+            model
+                .original
+                .and_then(|id| container.get(id))
+                .is_some_and(|m| match m.inner() {
+                    ModelType::EnumType(e) => e.variants.contains(&value),
+                    _ => false,
+                })
+        }
+        _ => false,
+    }
+}
+
 fn convert_to_nullable_optional_wrapper(
     mut model: FlatModel,
     container: &mut ModelContainer,
@@ -110,17 +206,17 @@ pub fn from_object(
                 schema, container, scope, resolver, options,
             )
         })
-        .or_else(|_| Ok(AnyType::model(schema, scope)))
+        .or_else(|_| Ok(AnyType::model(schema, container, scope)))
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::codegen::jsonschema::types::Attributes;
+    use crate::codegen::jsonschema::types::{Attributes, MapType, Setter};
 
     use super::*;
     use serde_json::json;
     // use crate::codegen::jsonschema::types::ModelType::FlatModel;
-    use crate::codegen::jsonschema::types::FlatModel;
+    use crate::codegen::jsonschema::{types::FlatModel, ModelId};
 
     #[test]
     fn test_should_convert_to_object_with_additional_properties() {
@@ -168,10 +264,298 @@ mod tests {
                     }
                 ],
                 additional: true,
+                additional_model: None,
+                builder: None,
+                constructor: Constructor {
+                    required: vec!["a".to_string()],
+                    defaulted: vec![],
+                    optional: vec!["b".to_string()],
+                },
             }))
         );
     }
 
+    #[test]
+    fn test_should_set_presence_of_properties() {
+        let schema = json!({
+            "required": ["a", "c"],
+            "properties": {
+                "a": { "type": "string" },
+                "b": { "type": "string" },
+                "c": { "type": "string", "nullable": true },
+                "d": { "type": "string", "nullable": true }
+            }
+        });
+
+        let resolver = SchemaResolver::empty();
+        let presence = |options: &JsonSchemaExtractOptions| {
+            let mut scope = SchemaScope::default();
+            scope.entity("TestName");
+
+            match from_object_with_properties(
+                schema.as_object().unwrap(),
+                &mut ModelContainer::default(),
+                &mut scope,
+                &resolver,
+                options,
+            )
+            .unwrap()
+            .inner()
+            {
+                ModelType::ObjectType(object) => object
+                    .properties
+                    .iter()
+                    .map(|p| p.presence())
+                    .collect::<Vec<_>>(),
+                _ => unreachable!(),
+            }
+        };
+
+        assert_eq!(
+            presence(&JsonSchemaExtractOptions::default()),
+            [
+                Presence::Required,
+                Presence::Optional,
+                Presence::Nullable,
+                Presence::Optional
+            ]
+        );
+        assert_eq!(
+            presence(&JsonSchemaExtractOptions {
+                tri_state: true,
+                ..JsonSchemaExtractOptions::default()
+            }),
+            [
+                Presence::Required,
+                Presence::Optional,
+                Presence::Nullable,
+                Presence::OptionalNullable
+            ]
+        );
+        assert_eq!(
+            presence(&JsonSchemaExtractOptions {
+                tri_state: true,
+                optional_and_nullable_as_models: true,
+                ..JsonSchemaExtractOptions::default()
+            })[3],
+            Presence::OptionalNullable
+        );
+    }
+
+    #[test]
+    fn test_should_extract_additional_properties_model_next_to_properties() {
+        let schema = json!({
+            "properties": {
+                "a": { "type": "string"}
+            },
+            "additionalProperties": { "type": "integer" },
+        });
+
+        let mut container = ModelContainer::default();
+        let mut scope = SchemaScope::default();
+        let resolver = SchemaResolver::empty();
+
+        scope.entity("TestName");
+        let extract = |options: &JsonSchemaExtractOptions,
+                       container: &mut ModelContainer,
+                       scope: &mut SchemaScope| {
+            match from_object_with_properties(
+                schema.as_object().unwrap(),
+                container,
+                scope,
+                &resolver,
+                options,
+            )
+            .unwrap()
+            .inner()
+            {
+                ModelType::ObjectType(object) => object.clone(),
+                _ => unreachable!(),
+            }
+        };
+
+        let ignored = extract(
+            &JsonSchemaExtractOptions::default(),
+            &mut container,
+            &mut scope,
+        );
+        let hybrid = extract(
+            &JsonSchemaExtractOptions {
+                additional_properties_model: true,
+                ..JsonSchemaExtractOptions::default()
+            },
+            &mut container,
+            &mut scope,
+        );
+
+        assert_eq!(ignored.additional_model, None);
+        assert_eq!(hybrid.properties, ignored.properties);
+        assert!(hybrid.additional);
+        assert_eq!(
+            hybrid.additional_model,
+            Some(FlatModel {
+                name: Some("TestNameValue".to_string()),
+                type_: "integer".to_string(),
+                ..FlatModel::default()
+            })
+        );
+    }
+
+    #[test]
+    fn test_should_group_properties_for_constructor() {
+        let schema = json!({
+            "required": ["id", "status"],
+            "properties": {
+                "id": { "type": "string"},
+                "status": { "type": "string", "enum": ["active", "blocked"], "default": "active"},
+                "retries": { "type": "integer", "default": "5"},
+                "tags": { "type": "array", "items": {"type": "string"}, "default": ["new"]},
+                "note": { "type": "string"}
+            }
+        });
+
+        let mut container = ModelContainer::default();
+        let mut scope = SchemaScope::default();
+        let resolver = SchemaResolver::empty();
+        let options = JsonSchemaExtractOptions::default();
+
+        scope.entity("TestName");
+        let result = from_object_with_properties(
+            schema.as_object().unwrap(),
+            &mut container,
+            &mut scope,
+            &resolver,
+            &options,
+        )
+        .unwrap();
+
+        let object = match result.inner() {
+            ModelType::ObjectType(object) => object,
+            _ => unreachable!(),
+        };
+
+        assert_eq!(
+            object
+                .properties
+                .iter()
+                .map(|p| p.attributes.has_default)
+                .collect::<Vec<_>>(),
+            vec![false, true, false, true, false]
+        );
+        assert_eq!(
+            object.constructor,
+            Constructor {
+                required: vec!["id".to_string()],
+                defaulted: vec!["status".to_string(), "tags".to_string()],
+                optional: vec!["retries".to_string(), "note".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn test_should_add_builder_to_objects_above_threshold() {
+        let schema = json!({
+            "required": ["id", "fooBar"],
+            "properties": {
+                "id": { "type": "string"},
+                "fooBar": { "type": "string"},
+                "foo_bar": { "type": "string"},
+                "enabled": { "type": "boolean", "default": true}
+            }
+        });
+
+        let mut container = ModelContainer::default();
+        let mut scope = SchemaScope::default();
+        let resolver = SchemaResolver::empty();
+
+        scope.entity("TestName");
+        let mut builder = |threshold| {
+            let options = JsonSchemaExtractOptions {
+                builder_threshold: Some(threshold),
+                ..JsonSchemaExtractOptions::default()
+            };
+
+            match from_object_with_properties(
+                schema.as_object().unwrap(),
+                &mut container,
+                &mut scope,
+                &resolver,
+                &options,
+            )
+            .unwrap()
+            .inner()
+            {
+                ModelType::ObjectType(object) => object.builder.clone(),
+                _ => unreachable!(),
+            }
+        };
+
+        assert_eq!(builder(4), None);
+
+        let setter = |property: &str, setter: &str| Setter {
+            property: property.to_string(),
+            setter: setter.to_string(),
+        };
+        assert_eq!(
+            builder(3),
+            Some(Builder {
+                name: "TestNameBuilder".to_string(),
+                required: vec![setter("id", "with_id"), setter("fooBar", "with_foo_bar")],
+                optional: vec![
+                    setter("foo_bar", "with_foo_bar2"),
+                    setter("enabled", "with_enabled")
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn test_should_add_code_names_of_properties() {
+        let schema = json!({
+            "properties": {
+                "type": { "type": "string"},
+                "match": { "type": "string"},
+                "firstName": { "type": "string"}
+            }
+        });
+
+        let mut container = ModelContainer::default();
+        let mut scope = SchemaScope::default();
+        let resolver = SchemaResolver::empty();
+        let options = JsonSchemaExtractOptions {
+            code_names: Some("rust".to_string()),
+            ..JsonSchemaExtractOptions::default()
+        };
+
+        scope.entity("TestName");
+        let result = from_object_with_properties(
+            schema.as_object().unwrap(),
+            &mut container,
+            &mut scope,
+            &resolver,
+            &options,
+        )
+        .unwrap();
+
+        let object = match result.inner() {
+            ModelType::ObjectType(object) => object,
+            _ => unreachable!(),
+        };
+
+        assert_eq!(
+            object
+                .properties
+                .iter()
+                .map(|p| (p.name.as_deref(), p.attributes.code_name.as_deref()))
+                .collect::<Vec<_>>(),
+            vec![
+                (Some("type"), Some("r#type")),
+                (Some("match"), Some("r#match")),
+                (Some("firstName"), Some("first_name"))
+            ]
+        );
+    }
+
     #[test]
     fn test_should_convert_to_object_without_additional_properties() {
         let schema = json!({
@@ -218,6 +602,13 @@ mod tests {
                     }
                 ],
                 additional: false,
+                additional_model: None,
+                builder: None,
+                constructor: Constructor {
+                    required: vec!["a".to_string()],
+                    defaulted: vec![],
+                    optional: vec!["b".to_string()],
+                },
             }))
         );
     }
@@ -267,6 +658,13 @@ mod tests {
                     }
                 ],
                 additional: true,
+                additional_model: None,
+                builder: None,
+                constructor: Constructor {
+                    required: vec!["a".to_string()],
+                    defaulted: vec![],
+                    optional: vec!["b".to_string()],
+                },
             }))
         );
     }
@@ -285,8 +683,10 @@ mod tests {
         let mut container = ModelContainer::default();
         let mut scope = SchemaScope::default();
         let resolver = SchemaResolver::empty();
-        let mut options = JsonSchemaExtractOptions::default();
-        options.optional_and_nullable_as_models = true;
+        let options = JsonSchemaExtractOptions {
+            optional_and_nullable_as_models: true,
+            ..JsonSchemaExtractOptions::default()
+        };
 
         scope.entity("TestName");
 
@@ -320,7 +720,7 @@ mod tests {
                             type_: "number".to_string(),
                             ..FlatModel::default()
                         })),
-                        original: Some(0),
+                        original: Some(ModelId(0)),
                         attributes: Attributes {
                             required: false,
                             nullable: false,
@@ -330,6 +730,13 @@ mod tests {
                     }
                 ],
                 additional: true,
+                additional_model: None,
+                builder: None,
+                constructor: Constructor {
+                    required: vec!["property1".to_string()],
+                    defaulted: vec![],
+                    optional: vec!["property2".to_string()],
+                },
             }))
         );
 
@@ -380,10 +787,9 @@ mod tests {
 
         assert_eq!(
             result.unwrap(),
-            Model::new(ModelType::FlatModel(FlatModel {
+            Model::new(ModelType::MapType(MapType {
                 name: Some("TestName".to_string()),
-                type_: "map".to_string(),
-                model: Some(Box::from(FlatModel {
+                model: Box::from(FlatModel {
                     name: Some("TestName".to_string()),
                     type_: "number".to_string(),
                     model: None,
@@ -393,13 +799,7 @@ mod tests {
                     },
                     spaces: Default::default(),
                     original: None,
-                })),
-                attributes: Attributes {
-                    required: true,
-                    ..Attributes::default()
-                },
-                spaces: Default::default(),
-                original: None
+                }),
             }))
         );
     }
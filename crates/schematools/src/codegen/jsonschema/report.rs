@@ -0,0 +1,120 @@
+use serde::Serialize;
+
+use crate::{error::Error, scope::SchemaScope};
+
+use super::types::Source;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningKind {
+    /// Model name was already taken and got a numeric suffix
+    Renamed,
+    /// Schema could not be mapped and became `any`
+    AnyType,
+    /// Circular reference replaced with `any`
+    CircularRef,
+    /// Keyword ignored by extraction, see strict mode
+    Unsupported,
+}
+
+impl std::fmt::Display for WarningKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Renamed => write!(f, "renamed"),
+            Self::AnyType => write!(f, "any type"),
+            Self::CircularRef => write!(f, "circular ref"),
+            Self::Unsupported => write!(f, "unsupported"),
+        }
+    }
+}
+
+const KINDS: [WarningKind; 4] = [
+    WarningKind::Unsupported,
+    WarningKind::AnyType,
+    WarningKind::CircularRef,
+    WarningKind::Renamed,
+];
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct Warning {
+    pub kind: WarningKind,
+    pub source: Source,
+    pub message: String,
+}
+
+/// Warnings collected during extraction, models degraded to `any` are reported here
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Report {
+    warnings: Vec<Warning>,
+}
+
+impl Report {
+    pub fn add(&mut self, kind: WarningKind, scope: &SchemaScope, message: String) {
+        log::warn!("{}: {}", scope, message);
+
+        self.warnings.push(Warning {
+            kind,
+            source: Source::new(scope),
+            message,
+        });
+    }
+
+    pub fn warnings(&self) -> &[Warning] {
+        &self.warnings
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.warnings.is_empty()
+    }
+
+    /// Fails on first keyword ignored by extraction
+    pub fn strict(&self) -> Result<(), Error> {
+        match self
+            .warnings
+            .iter()
+            .find(|w| w.kind == WarningKind::Unsupported)
+        {
+            Some(w) => Err(Error::JsonSchemaUnsupportedKeyword(
+                format!(
+                    "{}#{}",
+                    w.source.file.as_deref().unwrap_or_default(),
+                    w.source.pointer
+                ),
+                w.message.clone(),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    pub fn count(&self, kind: WarningKind) -> usize {
+        self.warnings.iter().filter(|w| w.kind == kind).count()
+    }
+
+    /// Table of warnings grouped by kind
+    pub fn summary(&self) -> String {
+        let mut lines = vec![format!("{:>14}  {:<40}  {}", "kind", "pointer", "message")];
+
+        for kind in KINDS {
+            for w in self.warnings.iter().filter(|w| w.kind == kind) {
+                lines.push(format!(
+                    "{:>14}  {:<40}  {}",
+                    w.kind.to_string(),
+                    w.source.pointer,
+                    w.message
+                ));
+            }
+        }
+
+        lines.push(format!(
+            "{} warnings: {}",
+            self.warnings.len(),
+            KINDS
+                .iter()
+                .map(|kind| format!("{} {}", self.count(*kind), kind))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+
+        lines.join("\n")
+    }
+}
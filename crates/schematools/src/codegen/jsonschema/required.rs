@@ -23,8 +23,8 @@ mod tests {
             "required": ["a", "b", "c"]
         });
 
-        let mut scope = SchemaScope::default();
-        let result = extract_required(schema.as_object().unwrap(), &mut scope);
+        let scope = SchemaScope::default();
+        let result = extract_required(schema.as_object().unwrap(), &scope);
 
         assert_eq!(
             result,
@@ -36,8 +36,8 @@ mod tests {
     fn test_required_missing() {
         let schema = json!({});
 
-        let mut scope = SchemaScope::default();
-        let result = extract_required(schema.as_object().unwrap(), &mut scope);
+        let scope = SchemaScope::default();
+        let result = extract_required(schema.as_object().unwrap(), &scope);
 
         let expected: Vec<String> = vec![];
         assert_eq!(result, expected);
@@ -0,0 +1,265 @@
+//! Synthesized payloads of models: valid sample prefers examples and defaults and falls
+//! back to minimal values satisfying type and basic constraints, invalid sample breaks
+//! type or drops required property.
+
+use serde_json::{json, Map, Value};
+
+use super::{types, ModelContainer};
+
+const MAX_DEPTH: usize = 8;
+
+/// Valid payload of model
+pub fn valid(container: &ModelContainer, model: &types::FlatModel) -> Value {
+    sample(container, model, 0)
+}
+
+/// Payload rejected by model, none if model accepts anything
+pub fn invalid(container: &ModelContainer, model: &types::FlatModel) -> Option<Value> {
+    let object = model
+        .original
+        .and_then(|id| container.get(id))
+        .and_then(|m| match m.inner() {
+            types::ModelType::ObjectType(o) => Some(o),
+            _ => None,
+        });
+
+    match (model.type_.as_str(), object) {
+        ("any", _) => None,
+        (_, Some(object)) => {
+            let mut payload = match sample(container, model, 0) {
+                Value::Object(payload) => payload,
+                _ => Map::new(),
+            };
+
+            match object.properties.iter().find(|p| p.attributes.required) {
+                Some(required) => payload.remove(required.name.as_deref().unwrap_or_default()),
+                None => return Some(Value::from("invalid")),
+            };
+
+            Some(Value::Object(payload))
+        }
+        ("string", _) => Some(Value::from(0)),
+        _ => Some(Value::from("invalid")),
+    }
+}
+
+fn sample(container: &ModelContainer, model: &types::FlatModel, depth: usize) -> Value {
+    if let Some(example) = model.attributes.docs.examples.first() {
+        return example.clone();
+    }
+
+    if let (true, Some(default)) = (model.attributes.has_default, &model.attributes.default) {
+        return default.clone();
+    }
+
+    if depth > MAX_DEPTH {
+        return Value::Null;
+    }
+
+    let validation = |key: &str| {
+        model
+            .attributes
+            .validation
+            .as_ref()
+            .and_then(|v| v.get(key))
+    };
+
+    match model.type_.as_str() {
+        "string" => string(validation("format").and_then(Value::as_str), validation),
+        "integer" => bound(validation)
+            .map(|n| json!(n.ceil() as i64))
+            .unwrap_or_else(|| json!(0)),
+        "number" => bound(validation)
+            .map(|n| json!(n))
+            .unwrap_or_else(|| json!(0.0)),
+        "decimal" => {
+            let value = bound(validation).unwrap_or(0.0);
+            match validation("encoding").and_then(Value::as_str) {
+                Some("string") => json!(value.to_string()),
+                _ => json!(value),
+            }
+        }
+        "date" | "time" | "date-time" | "local-date-time" | "duration" => {
+            string(Some(model.type_.as_str()), validation)
+        }
+        "boolean" => json!(true),
+        "array" => Value::Array(
+            model
+                .model
+                .as_deref()
+                .map(|item| vec![sample(container, item, depth + 1)])
+                .unwrap_or_default(),
+        ),
+        "map" => json!({
+            "key": model
+                .model
+                .as_deref()
+                .map(|value| sample(container, value, depth + 1))
+                .unwrap_or(Value::Null)
+        }),
+        _ => model
+            .original
+            .and_then(|id| container.get(id))
+            .map(|original| original_sample(container, original, depth))
+            .unwrap_or_else(|| json!({})),
+    }
+}
+
+fn original_sample(container: &ModelContainer, model: &types::Model, depth: usize) -> Value {
+    if let Some(example) = model.attributes.docs.examples.first() {
+        return example.clone();
+    }
+
+    match model.inner() {
+        types::ModelType::ObjectType(object) => Value::Object(
+            object
+                .properties
+                .iter()
+                .filter(|p| p.attributes.required || !p.attributes.docs.examples.is_empty())
+                .filter_map(|p| {
+                    p.name
+                        .clone()
+                        .map(|name| (name, sample(container, p, depth + 1)))
+                })
+                .collect(),
+        ),
+        types::ModelType::EnumType(e) => e
+            .variants
+            .first()
+            .map(|v| typed(&e.type_, v))
+            .unwrap_or(Value::Null),
+        types::ModelType::ConstType(c) => typed(&c.type_, &c.value),
+        types::ModelType::WrapperType(w) => w
+            .models
+            .first()
+            .map(|m| sample(container, m, depth + 1))
+            .unwrap_or(Value::Null),
+        types::ModelType::NullableOptionalWrapperType(w) => sample(container, &w.model, depth + 1),
+        types::ModelType::ArrayType(a) => {
+            Value::Array(vec![sample(container, &a.model, depth + 1)])
+        }
+        types::ModelType::FlatModel(f) => sample(container, f, depth + 1),
+        _ => json!({}),
+    }
+}
+
+fn string<'a>(format: Option<&str>, validation: impl Fn(&str) -> Option<&'a Value>) -> Value {
+    let value = match format {
+        Some("date") => "2024-01-01".to_string(),
+        Some("date-time") => "2024-01-01T00:00:00Z".to_string(),
+        Some("time") => "00:00:00".to_string(),
+        Some("local-date-time") => "2024-01-01T00:00:00".to_string(),
+        Some("duration") => "P1D".to_string(),
+        Some("uuid") => "00000000-0000-0000-0000-000000000000".to_string(),
+        Some("email") => "user@example.com".to_string(),
+        Some("uri") | Some("url") => "https://example.com".to_string(),
+        Some("ipv4") => "127.0.0.1".to_string(),
+        Some("ipv6") => "::1".to_string(),
+        _ => {
+            let min = validation("minLength").and_then(Value::as_u64).unwrap_or(0);
+            let max = validation("maxLength").and_then(Value::as_u64);
+
+            let mut value = "string".to_string();
+            if let Some(max) = max {
+                value.truncate(max as usize);
+            }
+            while (value.len() as u64) < min {
+                value.push('x');
+            }
+
+            value
+        }
+    };
+
+    Value::from(value)
+}
+
+// lowest accepted number, ex. minimum or exclusive minimum
+fn bound<'a>(validation: impl Fn(&str) -> Option<&'a Value>) -> Option<f64> {
+    match (
+        validation("minimum").and_then(Value::as_f64),
+        validation("exclusiveMinimum").and_then(Value::as_f64),
+        validation("maximum").and_then(Value::as_f64),
+    ) {
+        (Some(min), _, _) => Some(min),
+        (_, Some(min), _) => Some(min + 1.0),
+        (_, _, Some(max)) if max < 0.0 => Some(max),
+        _ => None,
+    }
+}
+
+// enum variants and const values are kept as strings
+fn typed(type_: &str, value: &str) -> Value {
+    match type_ {
+        "integer" | "number" | "boolean" => {
+            serde_json::from_str(value).unwrap_or_else(|_| Value::from(value))
+        }
+        _ => Value::from(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{codegen::jsonschema, schema::Schema, storage::SchemaStorage};
+
+    #[test]
+    fn test_samples() {
+        let schema = Schema::from_json(json!({
+            "title": "Order",
+            "type": "object",
+            "required": ["id", "status", "created", "items"],
+            "properties": {
+                "id": {"type": "integer", "minimum": 10},
+                "status": {"type": "string", "enum": ["new", "paid"]},
+                "created": {"type": "string", "format": "date-time"},
+                "code": {"type": "string", "minLength": 8},
+                "note": {"type": "string", "examples": ["fragile"]},
+                "items": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "required": ["sku"],
+                        "properties": {"sku": {"type": "string", "minLength": 8}}
+                    }
+                }
+            }
+        }));
+
+        let client = reqwest::blocking::Client::new();
+        let storage = SchemaStorage::new(&schema, &client).unwrap();
+        let container = jsonschema::extract(
+            &schema,
+            &storage,
+            jsonschema::JsonSchemaExtractOptions::default(),
+        )
+        .unwrap();
+
+        let id = container.find("Order").unwrap();
+        let model = types::FlatModel {
+            type_: "object".to_string(),
+            original: Some(id),
+            ..types::FlatModel::default()
+        };
+
+        assert_eq!(
+            valid(&container, &model),
+            json!({
+                "id": 10,
+                "status": "new",
+                "created": "2024-01-01T00:00:00Z",
+                "note": "fragile",
+                "items": [{"sku": "stringxx"}]
+            })
+        );
+        assert_eq!(
+            invalid(&container, &model),
+            Some(json!({
+                "status": "new",
+                "created": "2024-01-01T00:00:00Z",
+                "note": "fragile",
+                "items": [{"sku": "stringxx"}]
+            }))
+        );
+    }
+}
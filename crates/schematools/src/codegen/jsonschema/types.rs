@@ -1,11 +1,15 @@
-use serde::{ser::SerializeStruct, Serialize};
+use serde::{ser::SerializeStruct, Deserialize, Serialize};
 use serde_json::{Map, Value};
 
-use crate::{error::Error, resolver::SchemaResolver, scope::SchemaScope, scope::Space};
+use inflector::Inflector;
 
-use super::{title, JsonSchemaExtractOptions, ModelContainer};
+use crate::{
+    error::Error, hash, resolver::SchemaResolver, scope::SchemaScope, scope::Space, tools,
+};
 
-#[derive(Debug, Serialize, Clone)]
+use super::{title, JsonSchemaExtractOptions, ModelContainer, ModelId};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Model {
     #[serde(flatten)]
     inner: ModelType,
@@ -14,6 +18,24 @@ pub struct Model {
 
     #[serde(flatten)]
     pub spaces: SpacesContainer,
+
+    pub source: Option<Source>,
+}
+
+/// Location of schema node model or endpoint was extracted from
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq, Default)]
+pub struct Source {
+    pub file: Option<String>,
+    pub pointer: String,
+}
+
+impl Source {
+    pub fn new(scope: &SchemaScope) -> Self {
+        Self {
+            file: scope.file().map(String::from),
+            pointer: scope.pointer(),
+        }
+    }
 }
 
 impl PartialEq for Model {
@@ -28,6 +50,7 @@ impl Model {
             inner,
             attributes: Attributes::default(),
             spaces: SpacesContainer::default(),
+            source: None,
         }
     }
 
@@ -43,9 +66,16 @@ impl Model {
     pub fn mut_inner(&mut self) -> &mut ModelType {
         &mut self.inner
     }
+
+    /// Digest of the part of model taking part in comparison, equal models have equal digests
+    pub fn digest(&self) -> String {
+        let value = serde_json::to_value(&self.inner).unwrap_or_default();
+
+        format!("{:x}", md5::compute(hash::sort_keys(&value).to_string()))
+    }
 }
 
-#[derive(Debug, Serialize, Clone, Eq, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
 pub enum ModelType {
     // common types
     #[serde(rename = "primitive")]
@@ -77,7 +107,7 @@ pub enum ModelType {
     MapType(MapType),
 
     // flat type
-    #[serde(skip_serializing)]
+    #[serde(skip)]
     FlatModel(FlatModel),
 }
 
@@ -89,7 +119,7 @@ pub struct FlatModel {
 
     pub attributes: Attributes,
     pub spaces: SpacesContainer,
-    pub original: Option<u32>,
+    pub original: Option<ModelId>,
 }
 
 impl From<&FlatModel> for String {
@@ -98,7 +128,7 @@ impl From<&FlatModel> for String {
     }
 }
 
-#[derive(Debug, Eq, Serialize, Clone, Default)]
+#[derive(Debug, Eq, Serialize, Deserialize, Clone, Default)]
 pub struct SpacesContainer {
     #[serde(rename = "spaces")]
     pub list: Vec<Space>,
@@ -121,23 +151,249 @@ impl SpacesContainer {
     }
 }
 
-#[derive(Debug, Serialize, Clone, Eq, PartialEq, Default)]
+/// Date and time formats extracted as distinct types with patterns validating them,
+/// `local-date-time` is date-time without offset (format or `date-time` with `x-offset: false`)
+pub const TEMPORAL: [(&str, &str); 5] = [
+    ("date", r"^\d{4}-\d{2}-\d{2}$"),
+    (
+        "time",
+        r"^\d{2}:\d{2}:\d{2}(\.\d+)?([Zz]|[+-]\d{2}:\d{2})?$",
+    ),
+    (
+        "date-time",
+        r"^\d{4}-\d{2}-\d{2}[Tt ]\d{2}:\d{2}:\d{2}(\.\d+)?([Zz]|[+-]\d{2}:\d{2})$",
+    ),
+    (
+        "local-date-time",
+        r"^\d{4}-\d{2}-\d{2}[Tt ]\d{2}:\d{2}:\d{2}(\.\d+)?$",
+    ),
+    (
+        "duration",
+        r"^-?P(\d+Y)?(\d+M)?(\d+W)?(\d+D)?(T(\d+H)?(\d+M)?(\d+(\.\d+)?S)?)?$",
+    ),
+];
+
+/// Registry of primitive types and formats mapped to types of target languages used by
+/// `maptype` filter: (language, type, format, target) where empty format matches any format
+pub const FORMATS: &[(&str, &str, &str, &str)] = &[
+    (
+        "rust",
+        "string",
+        "date-time",
+        "chrono::DateTime<chrono::Utc>",
+    ),
+    ("rust", "string", "date", "chrono::NaiveDate"),
+    ("rust", "string", "uuid", "uuid::Uuid"),
+    ("rust", "string", "decimal", "rust_decimal::Decimal"),
+    ("rust", "string", "byte", "Vec<u8>"),
+    ("rust", "string", "", "String"),
+    ("rust", "integer", "int32", "i32"),
+    ("rust", "integer", "", "i64"),
+    ("rust", "number", "float", "f32"),
+    ("rust", "number", "", "f64"),
+    ("rust", "boolean", "", "bool"),
+    ("rust", "decimal", "", "rust_decimal::Decimal"),
+    ("rust", "date", "", "chrono::NaiveDate"),
+    ("rust", "time", "", "chrono::NaiveTime"),
+    (
+        "rust",
+        "date-time",
+        "",
+        "chrono::DateTime<chrono::FixedOffset>",
+    ),
+    ("rust", "local-date-time", "", "chrono::NaiveDateTime"),
+    // chrono cannot parse iso 8601 durations
+    ("rust", "duration", "", "String"),
+    ("rust", "any", "", "serde_json::Value"),
+    ("go", "string", "date-time", "time.Time"),
+    ("go", "string", "date", "time.Time"),
+    ("go", "string", "byte", "[]byte"),
+    ("go", "string", "", "string"),
+    ("go", "integer", "int32", "int32"),
+    ("go", "integer", "", "int64"),
+    ("go", "number", "float", "float32"),
+    ("go", "number", "", "float64"),
+    ("go", "boolean", "", "bool"),
+    ("go", "decimal", "", "decimal.Decimal"),
+    ("go", "date", "", "time.Time"),
+    ("go", "time", "", "string"),
+    ("go", "date-time", "", "time.Time"),
+    ("go", "local-date-time", "", "time.Time"),
+    ("go", "duration", "", "string"),
+    ("go", "any", "", "interface{}"),
+    ("typescript", "string", "", "string"),
+    ("typescript", "integer", "", "number"),
+    ("typescript", "number", "", "number"),
+    ("typescript", "boolean", "", "boolean"),
+    // number would lose precision
+    ("typescript", "decimal", "", "string"),
+    ("typescript", "date", "", "string"),
+    ("typescript", "time", "", "string"),
+    ("typescript", "date-time", "", "string"),
+    ("typescript", "local-date-time", "", "string"),
+    ("typescript", "duration", "", "string"),
+    ("typescript", "any", "", "unknown"),
+    ("python", "string", "date-time", "datetime.datetime"),
+    ("python", "string", "date", "datetime.date"),
+    ("python", "string", "uuid", "uuid.UUID"),
+    ("python", "string", "decimal", "decimal.Decimal"),
+    ("python", "string", "byte", "bytes"),
+    ("python", "string", "", "str"),
+    ("python", "integer", "", "int"),
+    ("python", "number", "", "float"),
+    ("python", "boolean", "", "bool"),
+    ("python", "decimal", "", "decimal.Decimal"),
+    ("python", "date", "", "datetime.date"),
+    ("python", "time", "", "datetime.time"),
+    ("python", "date-time", "", "datetime.datetime"),
+    ("python", "local-date-time", "", "datetime.datetime"),
+    ("python", "duration", "", "datetime.timedelta"),
+    ("python", "any", "", "typing.Any"),
+];
+
+/// Returns languages with types registered in formats registry
+pub fn languages() -> Vec<&'static str> {
+    let mut list = FORMATS.iter().map(|(l, _, _, _)| *l).collect::<Vec<_>>();
+    list.dedup();
+    list
+}
+
+/// Returns language type of primitive type, format specific mapping takes precedence
+pub fn language_type(lang: &str, type_: &str, format: Option<&str>) -> Option<&'static str> {
+    let candidates = FORMATS
+        .iter()
+        .filter(|(l, t, _, _)| *l == lang && *t == type_);
+
+    candidates
+        .clone()
+        .find(|(_, _, f, _)| format.is_some_and(|format| format == *f))
+        .or_else(|| candidates.clone().find(|(_, _, f, _)| f.is_empty()))
+        .map(|(_, _, _, target)| *target)
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq, Default)]
 pub struct PrimitiveType {
     #[serde(rename = "name")]
     pub name: Option<String>,
 
     #[serde(rename = "type")]
     pub type_: String,
+
+    // total and fractional digits of decimal type, see JsonSchemaExtractOptions decimal
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub precision: Option<u64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scale: Option<u64>,
+
+    // json type decimal is sent as, number or string
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<String>,
 }
 
-#[derive(Debug, Serialize, Clone, Eq, PartialEq, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq, Default)]
 pub struct ObjectType {
     pub name: String,
     pub properties: Vec<FlatModel>,
     pub additional: bool,
+    // values of properties not listed in properties, typed by additionalProperties schema
+    #[serde(default)]
+    pub additional_model: Option<FlatModel>,
+    #[serde(default)]
+    pub constructor: Constructor,
+    // present only for objects with more properties than builder threshold
+    #[serde(default)]
+    pub builder: Option<Builder>,
+}
+
+/// Names of object properties grouped for generated constructors and builders: required
+/// ones without usable default are arguments, defaulted ones are initialized with their
+/// defaults and optional ones are left empty, each group keeps order of properties
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq, Default)]
+pub struct Constructor {
+    pub required: Vec<String>,
+    pub defaulted: Vec<String>,
+    pub optional: Vec<String>,
+}
+
+impl Constructor {
+    pub fn new(properties: &[FlatModel]) -> Self {
+        let mut constructor = Self::default();
+
+        for property in properties {
+            let name = property.name.clone().unwrap_or_default();
+
+            if property.attributes.has_default {
+                constructor.defaulted.push(name);
+            } else if property.attributes.required {
+                constructor.required.push(name);
+            } else {
+                constructor.optional.push(name);
+            }
+        }
+
+        constructor
+    }
+}
+
+/// Metadata of builder generated for large objects, required properties have to be set
+/// before build, optional and defaulted ones may be skipped
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq, Default)]
+pub struct Builder {
+    pub name: String,
+    pub required: Vec<Setter>,
+    pub optional: Vec<Setter>,
+}
+
+/// Example payload of model, usable in doc tests and usage snippets
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Example {
+    pub id: ModelId,
+    pub model: String,
+    pub value: Value,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq, Default)]
+pub struct Setter {
+    pub property: String,
+    pub setter: String,
+}
+
+impl Builder {
+    pub fn new(name: &str, properties: &[FlatModel]) -> Self {
+        let mut builder = Self {
+            name: format!("{name}Builder"),
+            ..Self::default()
+        };
+        let mut taken: Vec<String> = vec![];
+
+        for property in properties {
+            let name = property.name.clone().unwrap_or_default();
+
+            // setter names of properties differing only in case would collide
+            let mut setter = format!("with_{}", name.to_snake_case());
+            while taken.contains(&setter) {
+                setter = tools::bump_suffix_number(&setter);
+            }
+            taken.push(setter.clone());
+
+            let setter = Setter {
+                property: name,
+                setter,
+            };
+
+            if property.attributes.required && !property.attributes.has_default {
+                builder.required.push(setter);
+            } else {
+                builder.optional.push(setter);
+            }
+        }
+
+        builder
+    }
 }
 
-#[derive(Debug, Serialize, Clone, Eq, PartialEq, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq, Default)]
 pub struct ArrayType {
     #[serde(rename = "name")]
     pub name: Option<String>,
@@ -146,7 +402,7 @@ pub struct ArrayType {
     pub model: Box<FlatModel>,
 }
 
-#[derive(Debug, Serialize, Clone, Eq, PartialEq, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq, Default)]
 pub struct EnumType {
     #[serde(rename = "name")]
     pub name: String,
@@ -158,7 +414,7 @@ pub struct EnumType {
     pub variants: Vec<String>,
 }
 
-#[derive(Debug, Serialize, Clone, Eq, PartialEq, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq, Default)]
 pub struct ConstType {
     #[serde(rename = "name")]
     pub name: String,
@@ -170,16 +426,16 @@ pub struct ConstType {
     pub value: String,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Default, Serialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Default, Serialize, Deserialize)]
 pub struct MapType {
     pub name: Option<String>,
     pub model: Box<FlatModel>,
 }
 
-#[derive(Debug, Serialize, Clone, Eq, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
 pub struct AnyType {}
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct RegexpType {
     #[serde(rename = "name")]
     pub name: String,
@@ -188,7 +444,7 @@ pub struct RegexpType {
     pub pattern: String,
 }
 
-#[derive(Debug, Serialize, Clone, Eq, PartialEq, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq, Default)]
 pub struct WrapperType {
     #[serde(rename = "name")]
     pub name: String,
@@ -203,33 +459,27 @@ pub struct WrapperType {
     pub strategy: WrapperStrategy,
 }
 
-#[derive(Debug, Clone, Serialize, Eq, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, Default)]
 pub enum WrapperTypeKind {
     AllOf,
+    #[default]
     OneOf,
+    // oneOf mixing primitive and model variants
+    Mixed,
 }
 
-impl Default for WrapperTypeKind {
-    fn default() -> Self {
-        Self::OneOf
-    }
-}
-
-#[derive(Debug, Clone, Serialize, Eq, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, Default)]
 #[serde(rename_all = "camelCase")]
 pub enum WrapperStrategy {
+    #[default]
     BruteForce,
     Internally(String),
     Externally,
+    // variants are tried in order they are listed
+    Untagged,
 }
 
-impl Default for WrapperStrategy {
-    fn default() -> Self {
-        Self::BruteForce
-    }
-}
-
-#[derive(Debug, Serialize, Clone, Eq, PartialEq, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq, Default)]
 pub struct NullableOptionalWrapperType {
     #[serde(rename = "name")]
     pub name: String,
@@ -238,7 +488,29 @@ pub struct NullableOptionalWrapperType {
     pub model: FlatModel,
 }
 
-#[derive(Debug, Serialize, Clone, Eq, PartialEq)]
+/// Whether value of property may be absent, null or both
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Presence {
+    Required,
+    Optional,
+    Nullable,
+    OptionalNullable,
+}
+
+impl Presence {
+    /// Absent and null values of optional nullable properties are the same unless tri-state
+    pub fn new(required: bool, nullable: bool, tri_state: bool) -> Self {
+        match (required, nullable) {
+            (true, false) => Self::Required,
+            (true, true) => Self::Nullable,
+            (false, true) if tri_state => Self::OptionalNullable,
+            (false, _) => Self::Optional,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
 pub struct Attributes {
     #[serde(rename = "description")]
     pub description: Option<String>,
@@ -246,12 +518,24 @@ pub struct Attributes {
     #[serde(rename = "default")]
     pub default: Option<Value>,
 
+    // default matches type of model so it is safe to materialize
+    #[serde(rename = "has_default", default)]
+    pub has_default: bool,
+
+    // identifier of property in target language, name keeps original wire name
+    #[serde(rename = "code_name", default)]
+    pub code_name: Option<String>,
+
     #[serde(rename = "nullable")]
     pub nullable: bool,
 
     #[serde(rename = "required")]
     pub required: bool,
 
+    // set when it cannot be derived from required and nullable, ex. tri-state properties
+    #[serde(rename = "presence", default)]
+    pub presence: Option<Presence>,
+
     #[serde(rename = "reference")]
     pub reference: bool,
 
@@ -261,12 +545,64 @@ pub struct Attributes {
     #[serde(rename = "schema")]
     pub schema: Option<Value>,
 
+    // index of schema in container `schemas` when schemas are shared
+    #[serde(rename = "schema_index")]
+    pub schema_index: Option<usize>,
+
     #[serde(rename = "x")]
     pub x: std::collections::HashMap<String, Value>,
+
+    // schema flagged with `x-pii: true`
+    #[serde(rename = "pii", default)]
+    pub pii: bool,
+
+    // flagged itself or through any model it is built of, see ModelContainer::tag_pii
+    #[serde(rename = "contains_pii", default)]
+    pub contains_pii: bool,
+
+    #[serde(rename = "docs")]
+    pub docs: Docs,
+}
+
+/// Documentation of model or endpoint, description keeps original line breaks
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq, Default)]
+pub struct Docs {
+    pub summary: Option<String>,
+    pub description: Option<String>,
+    pub external_docs: Option<Value>,
+    pub deprecated: bool,
+    pub examples: Vec<Value>,
+}
+
+impl Docs {
+    pub fn new(data: &Map<String, Value>) -> Self {
+        let text = |key: &str| data.get(key).and_then(|v| v.as_str()).map(String::from);
+
+        // json schema examples list and openapi single example are merged
+        let mut examples = match data.get("examples") {
+            Some(Value::Array(list)) => list.clone(),
+            _ => vec![],
+        };
+        if let Some(example) = data.get("example") {
+            examples.push(example.clone());
+        }
+
+        Self {
+            summary: text("summary"),
+            description: text("description"),
+            external_docs: data.get("externalDocs").cloned(),
+            deprecated: data
+                .get("deprecated")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            examples,
+        }
+    }
 }
 
 impl Model {
-    pub fn children(&self, container: &ModelContainer) -> Vec<u32> {
+    /// Models referenced directly by this model
+    pub fn children(&self) -> Vec<ModelId> {
         let children = match self.inner() {
             ModelType::ArrayType(a) => {
                 vec![a.model.original]
@@ -274,7 +610,12 @@ impl Model {
             ModelType::MapType(s) => {
                 vec![s.model.original]
             }
-            ModelType::ObjectType(o) => o.properties.iter().map(|p| p.original).collect(),
+            ModelType::ObjectType(o) => o
+                .properties
+                .iter()
+                .chain(o.additional_model.iter())
+                .map(|p| p.original)
+                .collect(),
             ModelType::WrapperType(w) => w.models.iter().map(|p| p.original).collect(),
             ModelType::NullableOptionalWrapperType(s) => {
                 vec![s.model.original]
@@ -282,20 +623,7 @@ impl Model {
             _ => vec![],
         };
 
-        let mut ids = children.iter().cloned().flatten().collect::<Vec<_>>();
-        let mut additional: Vec<u32> = vec![];
-        for id in ids.iter() {
-            additional.append(
-                &mut container
-                    .models
-                    .get(*id as usize)
-                    .unwrap()
-                    .children(container),
-            );
-        }
-
-        ids.append(&mut additional);
-        ids
+        children.into_iter().flatten().collect()
     }
 
     pub fn flatten(
@@ -323,6 +651,22 @@ impl Model {
         })
     }
 
+    /// Saves array in container as named model instead of flattening it inline
+    pub fn flatten_as_model(
+        &self,
+        container: &mut ModelContainer,
+        scope: &mut SchemaScope,
+    ) -> Result<FlatModel, Error> {
+        match self.inner() {
+            ModelType::ArrayType(a) => a.flatten_as_model(container.add(scope, self.clone())),
+            _ => return self.flatten(container, scope),
+        }
+        .map(|mut s| {
+            s.spaces = self.spaces.clone();
+            s.customize_attributes(&self.attributes)
+        })
+    }
+
     pub fn add_spaces(&mut self, scope: &mut SchemaScope) {
         let spaces = scope.get_spaces();
 
@@ -410,21 +754,90 @@ impl Serialize for FlatModel {
     where
         S: serde::Serializer,
     {
-        let mut state = serializer.serialize_struct("FlattenedType", 9)?;
+        let mut state = serializer.serialize_struct("FlattenedType", 16)?;
+        match self.original {
+            Some(id) => state.serialize_field("id", &id)?,
+            None => state.skip_field("id")?,
+        }
         state.serialize_field("name", &self.name)?;
         state.serialize_field("type", &self.type_)?;
         state.serialize_field("model", &self.model)?;
         state.serialize_field("required", &self.attributes.required)?;
         state.serialize_field("nullable", &self.attributes.nullable)?;
+        state.serialize_field("presence", &self.presence())?;
         state.serialize_field("validation", &self.attributes.validation)?;
         state.serialize_field("x", &self.attributes.x)?;
+        state.serialize_field("pii", &self.attributes.pii)?;
+        state.serialize_field("contains_pii", &self.attributes.contains_pii)?;
         state.serialize_field("description", &self.attributes.description)?;
         state.serialize_field("default", &self.attributes.default)?;
+        state.serialize_field("has_default", &self.attributes.has_default)?;
+        state.serialize_field("code_name", &self.attributes.code_name)?;
+        state.serialize_field("docs", &self.attributes.docs)?;
         // state.serialize_field("spaces", &self.spaces)?; // todo: ???
         state.end()
     }
 }
 
+// mirrors serialized form, attributes not exposed to templates stay default
+#[derive(Deserialize)]
+struct FlatModelData {
+    id: Option<ModelId>,
+    name: Option<String>,
+    #[serde(rename = "type")]
+    type_: String,
+    model: Option<Box<FlatModel>>,
+    required: bool,
+    nullable: bool,
+    #[serde(default)]
+    presence: Option<Presence>,
+    validation: Option<std::collections::HashMap<String, Value>>,
+    x: std::collections::HashMap<String, Value>,
+    #[serde(default)]
+    pii: bool,
+    #[serde(default)]
+    contains_pii: bool,
+    description: Option<String>,
+    default: Option<Value>,
+    #[serde(default)]
+    has_default: bool,
+    #[serde(default)]
+    code_name: Option<String>,
+    docs: Docs,
+}
+
+impl<'de> Deserialize<'de> for FlatModel {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let data = FlatModelData::deserialize(deserializer)?;
+
+        Ok(Self {
+            name: data.name,
+            type_: data.type_,
+            model: data.model,
+            original: data.id,
+            attributes: Attributes {
+                description: data.description,
+                default: data.default,
+                has_default: data.has_default,
+                code_name: data.code_name,
+                nullable: data.nullable,
+                required: data.required,
+                presence: data.presence,
+                validation: data.validation,
+                x: data.x,
+                pii: data.pii,
+                contains_pii: data.contains_pii,
+                docs: data.docs,
+                ..Attributes::default()
+            },
+            spaces: SpacesContainer::default(),
+        })
+    }
+}
+
 impl Default for FlatModel {
     fn default() -> Self {
         Self {
@@ -439,6 +852,12 @@ impl Default for FlatModel {
 }
 
 impl FlatModel {
+    pub fn presence(&self) -> Presence {
+        self.attributes.presence.unwrap_or_else(|| {
+            Presence::new(self.attributes.required, self.attributes.nullable, false)
+        })
+    }
+
     // Modifies customizable attributes when referred type is resolved
     pub fn customize_attributes(mut self, attributes: &Attributes) -> Self {
         self.attributes.required = attributes.required;
@@ -449,17 +868,34 @@ impl FlatModel {
 
 impl PrimitiveType {
     pub fn flatten(&self, added: &Model) -> Result<FlatModel, Error> {
+        let mut attributes = added.attributes.clone();
+
+        // flattened models carry precision and encoding of decimals as validation
+        let digits = [
+            ("precision", self.precision.map(Value::from)),
+            ("scale", self.scale.map(Value::from)),
+            ("encoding", self.encoding.clone().map(Value::from)),
+        ];
+        for (key, value) in digits {
+            if let Some(value) = value {
+                attributes
+                    .validation
+                    .get_or_insert_with(Default::default)
+                    .insert(key.to_string(), value);
+            }
+        }
+
         Ok(FlatModel {
             name: self.name.clone(),
             type_: self.type_.clone(),
-            attributes: added.attributes.clone(),
+            attributes,
             ..FlatModel::default()
         })
     }
 }
 
 impl ObjectType {
-    pub fn flatten(&self, added: (Option<u32>, &Model)) -> Result<FlatModel, Error> {
+    pub fn flatten(&self, added: (Option<ModelId>, &Model)) -> Result<FlatModel, Error> {
         if let ModelType::ObjectType(linked) = added.1.inner() {
             Ok(FlatModel {
                 name: None,
@@ -500,8 +936,30 @@ impl ArrayType {
     }
 }
 
+impl ArrayType {
+    pub fn flatten_as_model(&self, added: (Option<ModelId>, &Model)) -> Result<FlatModel, Error> {
+        if let ModelType::ArrayType(linked) = added.1.inner() {
+            Ok(FlatModel {
+                name: None,
+                type_: "wrapper".to_string(),
+                model: Some(Box::new(FlatModel {
+                    name: linked.name.clone(),
+                    type_: "array".to_string(),
+                    model: None,
+                    ..FlatModel::default()
+                })),
+                attributes: added.1.attributes.clone(),
+                original: added.0,
+                ..FlatModel::default()
+            })
+        } else {
+            Err(Error::FlatteningTypeError)
+        }
+    }
+}
+
 impl EnumType {
-    pub fn flatten(&self, added: (Option<u32>, &Model)) -> Result<FlatModel, Error> {
+    pub fn flatten(&self, added: (Option<ModelId>, &Model)) -> Result<FlatModel, Error> {
         if let ModelType::EnumType(linked) = added.1.inner() {
             Ok(FlatModel {
                 name: None,
@@ -529,7 +987,7 @@ impl EnumType {
 }
 
 impl ConstType {
-    pub fn flatten(&self, added: (Option<u32>, &Model)) -> Result<FlatModel, Error> {
+    pub fn flatten(&self, added: (Option<ModelId>, &Model)) -> Result<FlatModel, Error> {
         if let ModelType::ConstType(linked) = added.1.inner() {
             Ok(FlatModel {
                 name: Some(linked.name.clone()),
@@ -577,20 +1035,59 @@ impl Default for Attributes {
         Self {
             description: None,
             default: None,
+            has_default: false,
+            code_name: None,
             nullable: false,
             required: true,
+            presence: None,
             validation: None,
             reference: false,
             schema: None,
+            schema_index: None,
             x: std::collections::HashMap::new(),
+            pii: false,
+            contains_pii: false,
+            docs: Docs::default(),
         }
     }
 }
 
 impl AnyType {
-    pub fn model(schema: &Map<String, Value>, scope: &SchemaScope) -> Model {
+    pub fn model(
+        schema: &Map<String, Value>,
+        container: &mut ModelContainer,
+        scope: &SchemaScope,
+    ) -> Model {
         log::debug!("{}: {:?} may be invalid json schema", scope, schema);
 
+        // free-form schemas are any on purpose
+        let annotations = [
+            "type",
+            "title",
+            "description",
+            "nullable",
+            "default",
+            "example",
+            "examples",
+            "deprecated",
+            "readOnly",
+            "writeOnly",
+            "$id",
+            "$schema",
+            "$comment",
+        ];
+
+        if schema
+            .keys()
+            .any(|k| !annotations.contains(&k.as_str()) && !k.starts_with("x-"))
+        {
+            container.warn(
+                super::report::WarningKind::AnyType,
+                scope,
+                "schema cannot be mapped, falling back to any",
+            );
+        }
+
         Model::new(ModelType::AnyType(Self {}))
     }
 
@@ -612,7 +1109,7 @@ impl PartialEq for RegexpType {
 }
 
 impl WrapperType {
-    pub fn flatten(&self, added: (Option<u32>, &Model)) -> Result<FlatModel, Error> {
+    pub fn flatten(&self, added: (Option<ModelId>, &Model)) -> Result<FlatModel, Error> {
         if let ModelType::WrapperType(linked) = added.1.inner() {
             Ok(FlatModel {
                 name: None,
@@ -634,7 +1131,7 @@ impl WrapperType {
 }
 
 impl NullableOptionalWrapperType {
-    pub fn flatten(&self, added: (Option<u32>, &Model)) -> Result<FlatModel, Error> {
+    pub fn flatten(&self, added: (Option<ModelId>, &Model)) -> Result<FlatModel, Error> {
         if let ModelType::NullableOptionalWrapperType(linked) = added.1.inner() {
             let mut flat = linked.model.clone();
             flat.name = Some(linked.name.clone());
@@ -665,7 +1162,44 @@ impl PrimitiveType {
             .map(Some)
             .unwrap();
 
-        PrimitiveType { name, type_ }
+        let digits = |key: &str| schema.get(key).and_then(|v| v.as_u64());
+        let (precision, scale) = (digits("x-precision"), digits("x-scale"));
+
+        // financial amounts cannot be silently converted to floats
+        let decimal = schema.get("format").and_then(|v| v.as_str()) == Some("decimal")
+            || precision.is_some()
+            || scale.is_some();
+        if options.decimal && decimal && (type_ == "number" || type_ == "string") {
+            return PrimitiveType {
+                name,
+                type_: "decimal".to_string(),
+                precision,
+                scale,
+                encoding: Some(type_),
+            };
+        }
+
+        let format = match schema.get("format").and_then(|v| v.as_str()) {
+            Some("date-time") if schema.get("x-offset") == Some(&Value::Bool(false)) => {
+                Some("local-date-time")
+            }
+            format => format,
+        };
+        if let Some((temporal, _)) = TEMPORAL.iter().find(|(t, _)| Some(*t) == format) {
+            if options.temporal && type_ == "string" {
+                return PrimitiveType {
+                    name,
+                    type_: temporal.to_string(),
+                    ..PrimitiveType::default()
+                };
+            }
+        }
+
+        PrimitiveType {
+            name,
+            type_,
+            ..PrimitiveType::default()
+        }
     }
 }
 
@@ -675,3 +1209,30 @@ pub fn as_regexp_type(container: &mut ModelContainer, pattern: &str) -> RegexpTy
         pattern: pattern.to_string(),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_type() {
+        assert_eq!(language_type("rust", "integer", Some("int32")), Some("i32"));
+        assert_eq!(language_type("rust", "integer", Some("int64")), Some("i64"));
+        assert_eq!(language_type("go", "string", None), Some("string"));
+        assert_eq!(language_type("go", "object", None), None);
+        assert_eq!(
+            language_type("rust", "decimal", Some("decimal")),
+            Some("rust_decimal::Decimal")
+        );
+        assert_eq!(language_type("cobol", "string", None), None);
+    }
+
+    #[test]
+    fn test_temporal_registered() {
+        for lang in languages() {
+            for (temporal, _) in TEMPORAL {
+                assert!(language_type(lang, temporal, None).is_some());
+            }
+        }
+    }
+}
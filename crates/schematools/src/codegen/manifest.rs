@@ -0,0 +1,340 @@
+use std::{
+    collections::BTreeMap,
+    path::{Component, Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+pub const MANIFEST_FILE: &str = ".schematools-manifest.json";
+
+/// List of files generated in target directory
+///
+/// Every file is stored with hash of its content and identifier of templates set
+/// which produced it, so consecutive runs with different templates may share directory.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct Manifest {
+    pub files: BTreeMap<String, ManifestEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ManifestEntry {
+    pub hash: String,
+    pub templates: String,
+}
+
+impl Manifest {
+    pub fn load(target_dir: &str) -> Result<Self, Error> {
+        let path = Path::new(target_dir).join(MANIFEST_FILE);
+
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content =
+            std::fs::read_to_string(path).map_err(|e| Error::CodegenFileError(e.to_string()))?;
+
+        let manifest: Self = serde_json::from_str(&content).map_err(Error::SerdeJsonError)?;
+
+        // entries are joined with target directory on removal
+        for file in manifest.files.keys() {
+            check(file)?;
+        }
+
+        Ok(manifest)
+    }
+
+    pub fn save(&self, target_dir: &str) -> Result<(), Error> {
+        let path = Path::new(target_dir).join(MANIFEST_FILE);
+
+        if self.files.is_empty() {
+            if path.exists() {
+                std::fs::remove_file(path).map_err(|e| Error::CodegenFileError(e.to_string()))?;
+            }
+
+            return Ok(());
+        }
+
+        std::fs::create_dir_all(target_dir).map_err(|e| Error::CodegenFileError(e.to_string()))?;
+        std::fs::write(
+            path,
+            serde_json::to_string_pretty(self).map_err(Error::SerdeJsonError)?,
+        )
+        .map_err(|e| Error::CodegenFileError(e.to_string()))
+    }
+
+    /// Replaces entries of given templates set with new list of generated files,
+    /// returns files which are not produced anymore
+    pub fn update(
+        &mut self,
+        target_dir: &str,
+        templates: &str,
+        generated: &[String],
+    ) -> Result<Vec<String>, Error> {
+        let mut current = BTreeMap::new();
+
+        for file in generated {
+            current.insert(
+                relative(target_dir, file)?,
+                ManifestEntry {
+                    hash: hash(Path::new(file))?,
+                    templates: templates.to_string(),
+                },
+            );
+        }
+
+        let stale = self
+            .files
+            .iter()
+            .filter(|(f, e)| e.templates == templates && !current.contains_key(*f))
+            .map(|(f, _)| f.clone())
+            .collect::<Vec<_>>();
+
+        for file in &stale {
+            self.files.remove(file);
+        }
+
+        self.files.extend(current);
+
+        Ok(stale)
+    }
+
+    /// Removes files which were not modified since generation, returns list of removed files
+    ///
+    /// Hash of file covers content preserved in keep regions, so files with non-empty
+    /// keep regions are never removed.
+    pub fn remove(&self, target_dir: &str, files: &[String]) -> Result<Vec<String>, Error> {
+        let mut removed = vec![];
+
+        for file in files {
+            let path = Path::new(target_dir).join(file);
+            let regions = kept(&path);
+
+            match (self.files.get(file), path.exists()) {
+                (Some(_), true) if !regions.is_empty() => {
+                    log::warn!(
+                        "Skipping removal of file with kept regions {}: {:?}",
+                        regions.join(", "),
+                        path
+                    );
+                }
+                (Some(entry), true) if entry.hash == hash(&path)? => {
+                    log::info!("Removing: {:?}", path);
+
+                    std::fs::remove_file(&path)
+                        .map_err(|e| Error::CodegenFileError(e.to_string()))?;
+                    remove_empty_parents(&path, Path::new(target_dir));
+
+                    removed.push(file.clone());
+                }
+                (Some(_), true) => {
+                    log::warn!("Skipping removal of modified file: {:?}", path);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(removed)
+    }
+
+    /// Removes generated files which were not modified, modified ones are kept in manifest
+    /// and manifest itself is removed when nothing is left
+    pub fn clean(target_dir: &str) -> Result<Vec<String>, Error> {
+        let mut manifest = Self::load(target_dir)?;
+        let removed = manifest.remove(
+            target_dir,
+            &manifest.files.keys().cloned().collect::<Vec<_>>(),
+        )?;
+
+        manifest
+            .files
+            .retain(|file, _| !removed.contains(file) && Path::new(target_dir).join(file).exists());
+        manifest.save(target_dir)?;
+
+        Ok(removed)
+    }
+}
+
+/// Updates manifest in target directory and removes stale files generated by same templates
+pub fn process(target_dir: &str, templates: &str, generated: &[String]) -> Result<(), Error> {
+    let mut manifest = Manifest::load(target_dir)?;
+    let previous = Manifest {
+        files: manifest.files.clone(),
+    };
+
+    let stale = manifest.update(target_dir, templates, generated)?;
+    previous.remove(target_dir, &stale)?;
+
+    manifest.save(target_dir)
+}
+
+fn relative(target_dir: &str, file: &str) -> Result<String, Error> {
+    let relative = Path::new(file)
+        .strip_prefix(target_dir)
+        .map(|p| p.to_string_lossy().to_string())
+        .map_err(|_| Error::CodegenManifestInvalidPath(file.to_string()))?;
+
+    check(&relative)?;
+
+    Ok(relative)
+}
+
+// only plain segments, absolute paths or `..` would point outside of target directory
+fn check(file: &str) -> Result<(), Error> {
+    let path = Path::new(file);
+
+    if path.as_os_str().is_empty() || !path.components().all(|c| matches!(c, Component::Normal(_)))
+    {
+        return Err(Error::CodegenManifestInvalidPath(file.to_string()));
+    }
+
+    Ok(())
+}
+
+fn hash(path: &Path) -> Result<String, Error> {
+    let content = std::fs::read(path).map_err(|e| Error::CodegenFileError(e.to_string()))?;
+
+    Ok(format!("{:x}", md5::compute(content)))
+}
+
+// binary files cannot contain keep regions
+fn kept(path: &Path) -> Vec<String> {
+    std::fs::read_to_string(path)
+        .map(|content| super::regions::kept(&content))
+        .unwrap_or_default()
+}
+
+fn remove_empty_parents(path: &Path, root: &Path) {
+    let mut directory = PathBuf::from(path);
+
+    while directory.pop() && directory.starts_with(root) && directory != root {
+        if std::fs::remove_dir(&directory).is_err() {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(target_dir: &str, file: &str, content: &str) -> String {
+        let path = Path::new(target_dir).join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, content).unwrap();
+
+        path.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn test_stale_files_are_removed() {
+        let dir = std::env::temp_dir().join("schematools-manifest-stale");
+        let _ = std::fs::remove_dir_all(&dir);
+        let target_dir = dir.to_string_lossy().to_string();
+
+        let first = vec![
+            write(&target_dir, "a.rs", "a"),
+            write(&target_dir, "models/b.rs", "b"),
+        ];
+        let other = vec![write(&target_dir, "other.rs", "o")];
+
+        process(&target_dir, "first", &first).unwrap();
+        process(&target_dir, "second", &other).unwrap();
+        process(&target_dir, "first", &first[..1]).unwrap();
+
+        assert!(dir.join("a.rs").exists());
+        assert!(dir.join("other.rs").exists());
+        assert!(!dir.join("models").exists());
+
+        let manifest = Manifest::load(&target_dir).unwrap();
+        assert_eq!(
+            manifest.files.keys().collect::<Vec<_>>(),
+            vec!["a.rs", "other.rs"]
+        );
+    }
+
+    #[test]
+    fn test_clean_skips_modified_files() {
+        let dir = std::env::temp_dir().join("schematools-manifest-clean");
+        let _ = std::fs::remove_dir_all(&dir);
+        let target_dir = dir.to_string_lossy().to_string();
+
+        let files = vec![
+            write(&target_dir, "a.rs", "a"),
+            write(&target_dir, "b.rs", "b"),
+        ];
+        process(&target_dir, "t", &files).unwrap();
+        write(&target_dir, "b.rs", "modified");
+
+        assert_eq!(Manifest::clean(&target_dir).unwrap(), vec!["a.rs"]);
+        assert!(dir.join("b.rs").exists());
+        assert_eq!(
+            Manifest::load(&target_dir)
+                .unwrap()
+                .files
+                .keys()
+                .collect::<Vec<_>>(),
+            vec!["b.rs"]
+        );
+
+        write(&target_dir, "b.rs", "b");
+        assert_eq!(Manifest::clean(&target_dir).unwrap(), vec!["b.rs"]);
+        assert!(!dir.join(MANIFEST_FILE).exists());
+    }
+
+    #[test]
+    fn test_clean_skips_files_with_kept_regions() {
+        let dir = std::env::temp_dir().join("schematools-manifest-kept");
+        let _ = std::fs::remove_dir_all(&dir);
+        let target_dir = dir.to_string_lossy().to_string();
+
+        let files = vec![
+            write(
+                &target_dir,
+                "a.rs",
+                "// schematools:keep-start\n// schematools:keep-end\n",
+            ),
+            write(
+                &target_dir,
+                "b.rs",
+                "// schematools:keep-start\nuse foo;\n// schematools:keep-end\n",
+            ),
+        ];
+        process(&target_dir, "t", &files).unwrap();
+
+        assert_eq!(Manifest::clean(&target_dir).unwrap(), vec!["a.rs"]);
+        assert!(dir.join("b.rs").exists());
+    }
+
+    #[test]
+    fn test_paths_outside_target_dir_are_rejected() {
+        let dir = std::env::temp_dir().join("schematools-manifest-outside");
+        let _ = std::fs::remove_dir_all(&dir);
+        let target_dir = dir.join("out").to_string_lossy().to_string();
+
+        let outside = write(&dir.to_string_lossy(), "victim.rs", "v");
+        std::fs::create_dir_all(&target_dir).unwrap();
+
+        for key in ["../victim.rs", outside.as_str(), "a/../../victim.rs"] {
+            std::fs::write(
+                Path::new(&target_dir).join(MANIFEST_FILE),
+                serde_json::json!({"files": {key: {"hash": hash(Path::new(&outside)).unwrap(), "templates": "t"}}})
+                    .to_string(),
+            )
+            .unwrap();
+
+            assert!(matches!(
+                Manifest::clean(&target_dir),
+                Err(Error::CodegenManifestInvalidPath(_))
+            ));
+        }
+        assert!(dir.join("victim.rs").exists());
+
+        std::fs::remove_file(Path::new(&target_dir).join(MANIFEST_FILE)).unwrap();
+        assert!(matches!(
+            process(&target_dir, "t", &[outside]),
+            Err(Error::CodegenManifestInvalidPath(_))
+        ));
+    }
+}
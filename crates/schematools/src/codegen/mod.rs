@@ -1,20 +1,45 @@
-use crate::error::Error;
 use serde::Serialize;
 use serde_json::Value;
 use std::collections::HashMap;
 
+pub mod builder;
+pub mod context;
+pub mod curl;
+pub mod docs;
 pub mod filters;
+pub mod functions;
+pub mod harness;
+pub mod hooks;
+pub mod identifiers;
+pub mod ir;
 pub mod jsonschema;
+pub mod manifest;
 pub mod openapi;
+pub mod options;
+pub mod profile;
+pub mod project;
+pub mod regions;
 pub mod renderer;
+pub mod stamp;
 pub mod templates;
 
+pub use self::builder::Codegen;
+
 #[derive(Default, Debug, Clone, Serialize)]
 pub struct CodegenContainer {
     pub options: HashMap<String, Value>,
 
     #[serde(flatten)]
     pub data: HashMap<String, Value>,
+
+    #[serde(skip)]
+    pub context_version: Option<u32>,
+
+    #[serde(skip)]
+    pub allowed_env: Vec<String>,
+
+    #[serde(skip)]
+    pub profiler: Option<profile::Profiler>,
 }
 
 pub fn create_container(options: &[(String, serde_json::Value)]) -> CodegenContainer {
@@ -23,10 +48,13 @@ pub fn create_container(options: &[(String, serde_json::Value)]) -> CodegenConta
     CodegenContainer {
         options,
         data: HashMap::new(),
+        context_version: None,
+        allowed_env: vec![],
+        profiler: None,
     }
 }
 
-pub fn format(data: &str) -> Result<HashMap<&str, Value>, Error> {
+pub fn format(data: &str) -> Result<HashMap<&str, Value>, String> {
     let (values, errors): (Vec<_>, Vec<_>) = data
         .split(',')
         .filter(|s| !s.is_empty())
@@ -43,17 +71,17 @@ pub fn format(data: &str) -> Result<HashMap<&str, Value>, Error> {
 
                     Ok((key_value[0], Value::from(key_value[1])))
                 }
-                _ => Err(Error::CodegenFileHeaderParseError(format!(
-                    "Cannot parse: {s}"
-                ))),
+                _ => Err(format!("cannot parse `{}`", s.trim())),
             }
         })
         .partition(|result| result.is_ok());
 
     if !errors.is_empty() {
-        return Err(Error::CodegenFileHeaderParseError(format!(
-            "Errors occurred: {errors:?}"
-        )));
+        return Err(errors
+            .into_iter()
+            .filter_map(|e| e.err())
+            .collect::<Vec<_>>()
+            .join(", "));
     }
 
     Ok(values.into_iter().map(|s| s.unwrap()).collect())
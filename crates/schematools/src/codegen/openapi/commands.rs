@@ -0,0 +1,337 @@
+//! Command-line client layout of operations: subcommands grouped by first tag with typed
+//! flags of parameters and fields of object request bodies
+
+use inflector::Inflector;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::codegen::jsonschema::{
+    types::{FlatModel, ModelType},
+    ModelContainer,
+};
+
+use super::endpoint::Endpoint;
+
+const UNTAGGED: &str = "default";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CommandGroup {
+    // kebab case of tag
+    pub name: String,
+    pub tag: String,
+    pub commands: Vec<Command>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Command {
+    // kebab case of operation
+    pub name: String,
+    pub operation: String,
+    pub method: String,
+    pub path: String,
+    pub summary: Option<String>,
+    pub description: Option<String>,
+    pub deprecated: bool,
+    pub flags: Vec<Flag>,
+    pub body: Option<Body>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Body {
+    pub content_type: String,
+    pub required: bool,
+    // fields of object bodies are flags, other bodies are passed as raw json
+    pub flattened: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Flag {
+    // kebab case of field, body fields colliding with parameters get body- prefix
+    pub name: String,
+    pub field: String,
+
+    // path, query, header, cookie or body
+    #[serde(rename = "in")]
+    pub in_: String,
+
+    // string, integer, number, boolean or json for values which don't fit in plain flag
+    #[serde(rename = "type")]
+    pub type_: String,
+
+    // flag may be repeated, array of plain values
+    pub multiple: bool,
+    pub required: bool,
+    pub options: Vec<String>,
+    pub default: Option<Value>,
+    pub description: Option<String>,
+}
+
+/// Commands of endpoints grouped by first tag, untagged ones land in `default` group, request
+/// bodies are taken in given content type (default one if not given)
+pub fn commands<'a>(
+    endpoints: impl IntoIterator<Item = &'a Endpoint>,
+    container: &ModelContainer,
+    content_type: Option<&str>,
+) -> Vec<CommandGroup> {
+    let mut groups: Vec<CommandGroup> = vec![];
+
+    for endpoint in endpoints {
+        let tag = endpoint
+            .get_tags()
+            .first()
+            .map(|t| t.as_str())
+            .unwrap_or(UNTAGGED);
+        let command = command(endpoint, container, content_type);
+
+        match groups.iter_mut().find(|g| g.tag == tag) {
+            Some(group) => group.commands.push(command),
+            None => groups.push(CommandGroup {
+                name: tag.to_kebab_case(),
+                tag: tag.to_string(),
+                commands: vec![command],
+            }),
+        }
+    }
+
+    groups
+}
+
+fn command(endpoint: &Endpoint, container: &ModelContainer, content_type: Option<&str>) -> Command {
+    let parameters = endpoint.get_parameters();
+
+    let mut flags = parameters
+        .path
+        .iter()
+        .chain(parameters.query.iter())
+        .chain(parameters.header.iter())
+        .chain(parameters.cookie.iter())
+        .flat_map(|p| match p.properties.is_empty() {
+            true => vec![p],
+            false => p.properties.iter().collect(),
+        })
+        .map(|p| {
+            let mut flag = flag(&p.name, p.model.as_ref(), container);
+            flag.in_ = p.kind.clone();
+            flag.required = p.required;
+            flag.description = p.description.clone().or(flag.description);
+            flag
+        })
+        .collect::<Vec<_>>();
+
+    let media = endpoint
+        .requestbody
+        .as_ref()
+        .and_then(|r| Some((r, r.models.as_ref()?)))
+        .and_then(|(r, models)| models.select(content_type).map(|m| (r, m)));
+
+    let body = media.map(|(requestbody, media)| {
+        let object = media
+            .model
+            .original
+            .and_then(|id| container.get(id))
+            .and_then(|m| match m.inner() {
+                ModelType::ObjectType(o) => Some(o),
+                _ => None,
+            });
+
+        if let Some(object) = object {
+            for property in &object.properties {
+                let field = property.name.clone().unwrap_or_default();
+                let mut flag = flag(&field, Some(property), container);
+                flag.in_ = "body".to_string();
+                flag.required = requestbody.required && property.attributes.required;
+
+                if flags.iter().any(|f| f.name == flag.name) {
+                    flag.name = format!("body-{}", flag.name);
+                }
+
+                flags.push(flag);
+            }
+        }
+
+        Body {
+            content_type: media.content_type.clone(),
+            required: requestbody.required,
+            flattened: object.is_some(),
+        }
+    });
+
+    let docs = endpoint.get_docs();
+
+    Command {
+        name: endpoint.get_operation().to_kebab_case(),
+        operation: endpoint.get_operation().to_string(),
+        method: endpoint.get_method().to_uppercase(),
+        path: endpoint.get_path().to_string(),
+        summary: docs.summary.clone(),
+        description: endpoint.get_description().map(String::from),
+        deprecated: docs.deprecated,
+        flags,
+        body,
+    }
+}
+
+fn flag(field: &str, model: Option<&FlatModel>, container: &ModelContainer) -> Flag {
+    let (type_, multiple, options) = match model {
+        Some(model) if model.type_ == "array" => {
+            match model.model.as_deref().map(|i| plain(i, container)) {
+                Some(Some((type_, options))) => (type_, true, options),
+                _ => ("json".to_string(), false, vec![]),
+            }
+        }
+        Some(model) => plain(model, container)
+            .map(|(type_, options)| (type_, false, options))
+            .unwrap_or_else(|| ("json".to_string(), false, vec![])),
+        None => ("string".to_string(), false, vec![]),
+    };
+
+    Flag {
+        name: field.to_kebab_case(),
+        field: field.to_string(),
+        in_: String::new(),
+        type_,
+        multiple,
+        required: false,
+        options,
+        default: model.and_then(|m| m.attributes.default.clone()),
+        description: model.and_then(|m| m.attributes.description.clone()),
+    }
+}
+
+// type and allowed values of models which fit in single flag value
+fn plain(model: &FlatModel, container: &ModelContainer) -> Option<(String, Vec<String>)> {
+    match (model.type_.as_str(), model.model.as_deref()) {
+        (
+            "string" | "integer" | "number" | "decimal" | "boolean" | "date" | "time" | "date-time"
+            | "local-date-time" | "duration",
+            _,
+        ) => Some((model.type_.clone(), vec![])),
+        ("enum", Some(inner)) => {
+            let options = inner
+                .original
+                .and_then(|id| container.get(id))
+                .and_then(|m| match m.inner() {
+                    ModelType::EnumType(e) => Some(e.variants.clone()),
+                    _ => None,
+                })
+                .unwrap_or_default();
+
+            Some((inner.type_.clone(), options))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{schema::Schema, storage::SchemaStorage, Client};
+    use serde_json::json;
+
+    #[test]
+    fn test_commands() {
+        let schema = Schema::from_json(json!({
+            "openapi": "3.0.0",
+            "info": {"title": "test", "version": "1.0.0"},
+            "components": {"schemas": {
+                "Pet": {
+                    "type": "object",
+                    "required": ["name"],
+                    "properties": {
+                        "name": {"type": "string", "description": "Name"},
+                        "kind": {"type": "string", "enum": ["cat", "dog"]},
+                        "tags": {"type": "array", "items": {"type": "string"}},
+                        "owner": {"type": "object", "properties": {"id": {"type": "integer"}}},
+                        "petId": {"type": "string"}
+                    }
+                }
+            }},
+            "paths": {
+                "/pets/{petId}": {
+                    "put": {
+                        "operationId": "updatePet",
+                        "tags": ["pets"],
+                        "summary": "Update pet",
+                        "parameters": [
+                            {"name": "petId", "in": "path", "required": true, "schema": {"type": "string"}},
+                            {"name": "dryRun", "in": "query", "schema": {"type": "boolean", "default": false}}
+                        ],
+                        "requestBody": {
+                            "required": true,
+                            "content": {"application/json": {"schema": {"$ref": "#/components/schemas/Pet"}}}
+                        },
+                        "responses": {"204": {"description": "ok"}}
+                    }
+                },
+                "/health": {
+                    "post": {
+                        "operationId": "checkHealth",
+                        "requestBody": {
+                            "content": {"application/json": {"schema": {"type": "array", "items": {"type": "string"}}}}
+                        },
+                        "responses": {"204": {"description": "ok"}}
+                    }
+                }
+            }
+        }));
+        let client = Client::new();
+        let openapi = super::super::extract(
+            &schema,
+            &SchemaStorage::new(&schema, &client).unwrap(),
+            super::super::OpenapiExtractOptions::default(),
+        )
+        .unwrap();
+
+        let groups = commands(&openapi.endpoints, &openapi.models, None);
+
+        assert_eq!(
+            groups.iter().map(|g| g.name.as_str()).collect::<Vec<_>>(),
+            ["pets", "default"]
+        );
+
+        let update = &groups[0].commands[0];
+        assert_eq!(update.name, "update-pet");
+        assert_eq!(update.method, "PUT");
+        assert_eq!(
+            update.body,
+            Some(Body {
+                content_type: "application/json".to_string(),
+                required: true,
+                flattened: true
+            })
+        );
+
+        let flags = update
+            .flags
+            .iter()
+            .map(|f| {
+                (
+                    f.name.as_str(),
+                    f.in_.as_str(),
+                    f.type_.as_str(),
+                    f.multiple,
+                    f.required,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            flags,
+            [
+                ("pet-id", "path", "string", false, true),
+                ("dry-run", "query", "boolean", false, false),
+                ("name", "body", "string", false, true),
+                ("kind", "body", "string", false, false),
+                ("tags", "body", "string", true, false),
+                ("owner", "body", "json", false, false),
+                ("body-pet-id", "body", "string", false, false),
+            ]
+        );
+        assert_eq!(update.flags[1].default, Some(json!(false)));
+        assert_eq!(update.flags[2].description.as_deref(), Some("Name"));
+        assert_eq!(update.flags[3].options, ["cat", "dog"]);
+
+        let health = &groups[1].commands[0];
+        assert!(health.flags.is_empty());
+        assert!(!health.body.as_ref().unwrap().flattened);
+    }
+}
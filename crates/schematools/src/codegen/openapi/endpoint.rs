@@ -1,8 +1,14 @@
-use serde::Serialize;
+use inflector::Inflector;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
 
 use crate::{
-    codegen::jsonschema::{JsonSchemaExtractOptions, ModelContainer},
+    codegen::jsonschema::{
+        samples,
+        types::{Docs, FlatModel, Source},
+        JsonSchemaExtractOptions, ModelContainer,
+    },
     error::Error,
     process::name::endpoint,
     resolver::SchemaResolver,
@@ -10,28 +16,476 @@ use crate::{
 };
 
 use super::{
+    media::{self, MediaType},
     parameters::{self, Parameters},
-    requestbody, responses, security,
+    policy::Policy,
+    requestbody, responses, security, MediaModelsContainer,
 };
 
-#[derive(Serialize, Clone)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct Endpoint {
     security: Vec<security::SecurityScheme>,
     path: String,
     method: String,
     operation: String,
     description: Option<String>,
+    docs: Docs,
+    source: Source,
     tags: Vec<String>,
     parameters: parameters::Parameters,
     pub requestbody: Option<requestbody::RequestBody>,
     pub responses: responses::Responses,
+    pub request_content_types: Vec<MediaType>,
+    pub response_content_types: Vec<MediaType>,
+    pub policy: Policy,
+    #[serde(default)]
+    pub handler: Handler,
+    // request body of patch operation is merge patch of partial model
+    #[serde(default)]
+    pub merge_patch: bool,
     x: std::collections::HashMap<String, Value>,
 }
 
+/// Endpoint serialized as if [`Endpoint::set_content_type`] was applied, without copying it
+#[derive(Serialize)]
+pub struct EndpointView<'a> {
+    security: &'a [security::SecurityScheme],
+    path: &'a str,
+    method: &'a str,
+    operation: &'a str,
+    description: &'a Option<String>,
+    docs: &'a Docs,
+    source: &'a Source,
+    tags: &'a [String],
+    parameters: &'a parameters::Parameters,
+    requestbody: Option<requestbody::RequestBodyView<'a>>,
+    responses: responses::ResponsesView<'a>,
+    request_content_types: &'a [MediaType],
+    response_content_types: &'a [MediaType],
+    policy: &'a Policy,
+    handler: Handler,
+    merge_patch: bool,
+    x: &'a std::collections::HashMap<String, Value>,
+}
+
+/// Server handler signature of operation, arguments in order they are extracted from
+/// request: path parameters in order of path segments, query, header, cookie and body last
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct Handler {
+    // snake case of operation
+    pub name: String,
+    pub arguments: Vec<HandlerArgument>,
+    // success response in selected content type
+    pub status_code: Option<u32>,
+    pub returns: Option<FlatModel>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct HandlerArgument {
+    // snake case of parameter, body for request body
+    pub name: String,
+    pub field: String,
+    // path, query, header, cookie or body
+    #[serde(rename = "in")]
+    pub in_: String,
+    pub required: bool,
+    pub model: Option<FlatModel>,
+    pub content_type: Option<String>,
+}
+
+/// Routing table entry of operation, independent of models
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Route {
+    pub method: String,
+    pub path: String,
+    pub operation: String,
+    pub tags: Vec<String>,
+    pub security: Vec<RouteSecurity>,
+    // union of scopes of all security requirements
+    pub scopes: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RouteSecurity {
+    pub scheme: String,
+    pub scopes: Vec<String>,
+}
+
+/// Test skeleton of operation and single response code with synthesized payloads
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TestCase {
+    pub operation: String,
+    pub method: String,
+    pub path: String,
+    // exact code, first code of range or 0 for default response
+    pub status_code: u32,
+    pub description: Option<String>,
+    // valid values of required path and query parameters
+    pub parameters: serde_json::Map<String, Value>,
+    pub request: Option<Payload>,
+    pub response: Option<Payload>,
+}
+
+/// Request of operation with synthesized values of path and required query, header and
+/// cookie parameters, ex. for curl snippets
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RequestSample {
+    pub operation: String,
+    pub method: String,
+    // path template, ex. /users/{userId}
+    pub path: String,
+    // path with encoded values of parameters and query string, ex. /users/1?limit=10
+    pub url: String,
+    pub headers: serde_json::Map<String, Value>,
+    pub cookies: serde_json::Map<String, Value>,
+    pub body: Option<Payload>,
+    // scheme of first security requirement, credentials are up to the consumer
+    pub security: Option<security::SecurityScheme>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Payload {
+    pub content_type: String,
+    pub valid: Value,
+    pub invalid: Option<Value>,
+}
+
+impl Payload {
+    fn from(
+        models: &MediaModelsContainer,
+        container: &ModelContainer,
+        content_type: Option<&str>,
+    ) -> Option<Self> {
+        models.select(content_type).map(|m| Self {
+            content_type: m.content_type.clone(),
+            valid: samples::valid(container, &m.model),
+            invalid: samples::invalid(container, &m.model),
+        })
+    }
+}
+
 impl Endpoint {
+    /// Test cases of every response code, payloads are synthesized from models in given
+    /// content type (default one if not given)
+    pub fn test_cases(
+        &self,
+        container: &ModelContainer,
+        content_type: Option<&str>,
+    ) -> Vec<TestCase> {
+        let parameters = self
+            .parameters
+            .path
+            .iter()
+            .chain(self.parameters.query.iter().filter(|p| p.required))
+            .filter_map(|p| {
+                p.model
+                    .as_ref()
+                    .map(|m| (p.name.clone(), samples::valid(container, m)))
+            })
+            .collect::<serde_json::Map<_, _>>();
+
+        let request = self
+            .requestbody
+            .as_ref()
+            .and_then(|r| r.models.as_ref())
+            .and_then(|m| Payload::from(m, container, content_type));
+
+        self.responses
+            .all
+            .iter()
+            .map(|response| TestCase {
+                operation: self.operation.clone(),
+                method: self.method.to_uppercase(),
+                path: self.path.clone(),
+                status_code: response.status_code,
+                description: response.description.clone(),
+                parameters: parameters.clone(),
+                request: request.clone(),
+                response: response
+                    .models
+                    .as_ref()
+                    .and_then(|m| Payload::from(m, container, content_type)),
+            })
+            .collect()
+    }
+
+    /// Request with values synthesized from models of parameters and request body
+    pub fn request_sample(&self, container: &ModelContainer) -> RequestSample {
+        let value = |p: &parameters::Parameter| {
+            p.model
+                .as_ref()
+                .map(|m| samples::valid(container, m))
+                .unwrap_or(Value::Null)
+        };
+        // parameters with json content are serialized as a whole
+        let text = |p: &parameters::Parameter| match p.content_type.as_deref() {
+            Some(content_type) if content_type.contains("json") => value(p).to_string(),
+            _ => plain(&value(p)),
+        };
+
+        let mut url = self.path.clone();
+        for parameter in &self.parameters.path {
+            url = url.replace(
+                &format!("{{{}}}", parameter.name),
+                &encode(&text(parameter)),
+            );
+        }
+
+        let query = self
+            .parameters
+            .query
+            .iter()
+            .flat_map(|p| match p.properties.is_empty() {
+                true => vec![p],
+                false => p.properties.iter().collect(),
+            })
+            .filter(|p| p.required)
+            .flat_map(|p| match value(p) {
+                Value::Array(items) if p.content_type.is_none() => items
+                    .iter()
+                    .map(|i| format!("{}={}", encode(&p.name), encode(&plain(i))))
+                    .collect::<Vec<_>>(),
+                _ => vec![format!("{}={}", encode(&p.name), encode(&text(p)))],
+            })
+            .collect::<Vec<_>>();
+        if !query.is_empty() {
+            url = format!("{url}?{}", query.join("&"));
+        }
+
+        let body = self
+            .requestbody
+            .as_ref()
+            .and_then(|r| r.models.as_ref())
+            .and_then(|m| Payload::from(m, container, None));
+
+        let mut headers = serde_json::Map::new();
+        if let Some(accept) = self
+            .responses
+            .success
+            .as_ref()
+            .and_then(|r| r.models.as_ref())
+            .map(|m| m.default_content_type.clone())
+        {
+            headers.insert("Accept".to_string(), Value::String(accept));
+        }
+        if let Some(body) = &body {
+            headers.insert(
+                "Content-Type".to_string(),
+                Value::String(body.content_type.clone()),
+            );
+        }
+        for parameter in self.parameters.header.iter().filter(|p| p.required) {
+            headers.insert(parameter.name.clone(), Value::String(text(parameter)));
+        }
+
+        let cookies = self
+            .parameters
+            .cookie
+            .iter()
+            .filter(|p| p.required)
+            .map(|p| (p.name.clone(), Value::String(text(p))))
+            .collect();
+
+        RequestSample {
+            operation: self.operation.clone(),
+            method: self.method.to_uppercase(),
+            path: self.path.clone(),
+            url,
+            headers,
+            cookies,
+            body,
+            security: self.security.first().cloned(),
+        }
+    }
+
+    pub fn route(&self) -> Route {
+        let mut scopes = self
+            .security
+            .iter()
+            .flat_map(|s| s.scopes.clone())
+            .collect::<Vec<_>>();
+        scopes.sort();
+        scopes.dedup();
+
+        Route {
+            method: self.method.to_uppercase(),
+            path: self.path.clone(),
+            operation: self.operation.clone(),
+            tags: self.tags.clone(),
+            security: self
+                .security
+                .iter()
+                .map(|s| RouteSecurity {
+                    scheme: s.scheme_name.clone(),
+                    scopes: s.scopes.clone(),
+                })
+                .collect(),
+            scopes,
+        }
+    }
+
+    pub fn apply_credentials(&mut self, credentials: &HashMap<String, security::Credential>) {
+        for scheme in self.security.iter_mut() {
+            scheme.apply_credentials(credentials);
+        }
+    }
+
+    pub fn get_method(&self) -> &str {
+        &self.method
+    }
+
+    pub fn get_path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn get_docs(&self) -> &Docs {
+        &self.docs
+    }
+
+    pub fn get_description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub fn get_parameters(&self) -> &parameters::Parameters {
+        &self.parameters
+    }
+
     pub fn get_tags(&self) -> &Vec<String> {
         &self.tags
     }
+
+    pub fn get_operation(&self) -> &str {
+        &self.operation
+    }
+
+    /// Extension value by name without `x-` prefix
+    pub fn get_extension(&self, name: &str) -> Option<&Value> {
+        self.x.get(name)
+    }
+
+    pub fn rank_content_types(&mut self, preference: &[String]) {
+        media::rank(&mut self.request_content_types, preference);
+        media::rank(&mut self.response_content_types, preference);
+    }
+
+    pub fn set_content_type(mut self, content_type: &str) -> Self {
+        self.responses.all.iter_mut().for_each(|r| {
+            if let Some(ref mut c) = r.models {
+                c.default_content_type = content_type.to_string();
+            }
+        });
+
+        if let Some(ref mut rb) = self.requestbody {
+            if let Some(ref mut c) = rb.models {
+                c.default_content_type = content_type.to_string();
+            }
+        }
+
+        self.handler = self.handler_signature();
+
+        self
+    }
+
+    pub fn view<'a>(&'a self, content_type: &'a str) -> EndpointView<'a> {
+        EndpointView {
+            security: &self.security,
+            path: &self.path,
+            method: &self.method,
+            operation: &self.operation,
+            description: &self.description,
+            docs: &self.docs,
+            source: &self.source,
+            tags: &self.tags,
+            parameters: &self.parameters,
+            requestbody: self.requestbody.as_ref().map(|r| r.view(content_type)),
+            responses: self.responses.view(content_type),
+            request_content_types: &self.request_content_types,
+            response_content_types: &self.response_content_types,
+            policy: &self.policy,
+            handler: self.handler_with(Some(content_type)),
+            merge_patch: self.merge_patch,
+            x: &self.x,
+        }
+    }
+
+    fn handler_signature(&self) -> Handler {
+        self.handler_with(None)
+    }
+
+    // content type overrides default one of request body, success response keeps its own
+    fn handler_with(&self, content_type: Option<&str>) -> Handler {
+        let mut path = self.parameters.path.iter().collect::<Vec<_>>();
+        path.sort_by_key(|p| {
+            self.path
+                .find(&format!("{{{}}}", p.name))
+                .unwrap_or(usize::MAX)
+        });
+
+        let mut arguments = path
+            .into_iter()
+            .chain(self.parameters.query.iter())
+            .chain(self.parameters.header.iter())
+            .chain(self.parameters.cookie.iter())
+            .map(|p| HandlerArgument {
+                name: p.name.to_snake_case(),
+                field: p.name.clone(),
+                in_: p.kind.clone(),
+                required: p.required,
+                model: p.model.clone(),
+                content_type: p.content_type.clone(),
+            })
+            .collect::<Vec<_>>();
+
+        if let Some((requestbody, media)) = self
+            .requestbody
+            .as_ref()
+            .and_then(|r| Some((r, r.models.as_ref()?.select(content_type)?.clone())))
+        {
+            arguments.push(HandlerArgument {
+                name: "body".to_string(),
+                field: "body".to_string(),
+                in_: "body".to_string(),
+                required: requestbody.required,
+                model: Some(media.model),
+                content_type: Some(media.content_type),
+            });
+        }
+
+        let success = self.responses.success.as_ref();
+
+        Handler {
+            name: self.operation.to_snake_case(),
+            arguments,
+            status_code: success.map(|r| r.status_code),
+            returns: success
+                .and_then(|r| r.models.as_ref())
+                .and_then(|m| m.select(None))
+                .map(|m| m.model.clone()),
+        }
+    }
+}
+
+// value of parameter as text, arrays in form style
+fn plain(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        Value::Array(items) => items.iter().map(plain).collect::<Vec<_>>().join(","),
+        v => v.to_string(),
+    }
+}
+
+// percent encoding of everything except unreserved characters
+fn encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            b => format!("%{b:02X}"),
+        })
+        .collect()
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -56,6 +510,8 @@ pub fn extract_endpoints(
             };
             scope.pop();
 
+            let x = super::extensions(details);
+
             let mut endpoints = vec![];
             for method in &[
                 "get", "put", "post", "delete", "options", "head", "patch", "trace",
@@ -65,6 +521,7 @@ pub fn extract_endpoints(
                     endpoints.push(new_endpoint(
                         method_details,
                         parameters.as_ref(),
+                        &x,
                         path,
                         method,
                         scope,
@@ -89,6 +546,7 @@ pub fn extract_endpoints(
 fn new_endpoint(
     node: &Value,
     parameters: Option<&Parameters>,
+    shared_x: &std::collections::HashMap<String, Value>,
     path: &str,
     method: &str,
     scope: &mut SchemaScope,
@@ -138,13 +596,9 @@ fn new_endpoint(
                 tags
             };
 
-            let x = data
-                .iter()
-                .filter_map(|(key, val)| {
-                    key.strip_prefix("x-")
-                        .map(|stripped| (stripped.to_string(), val.clone()))
-                })
-                .collect::<std::collections::HashMap<String, Value>>();
+            // operation extensions override path item ones
+            let mut x = shared_x.clone();
+            x.extend(super::extensions(data));
 
             scope.glue(&operation);
             scope.add_spaces(&mut tags.clone().into_iter().map(Space::Tag).collect());
@@ -157,19 +611,44 @@ fn new_endpoint(
                 endpoint_parameters.merge(shared)
             }
 
-            let endpoint = Endpoint {
+            let responses = responses::extract(data, scope, mcontainer, resolver, options)?;
+            let mut requestbody = requestbody::extract(data, scope, mcontainer, resolver, options)?;
+
+            let merge_patch = match requestbody.as_mut() {
+                Some(requestbody) if method.eq_ignore_ascii_case("patch") => {
+                    requestbody::merge_patch(requestbody, scope, mcontainer)?
+                }
+                _ => false,
+            };
+
+            let policy = Policy::from_extensions(&x, &scope.to_string())?;
+
+            let mut endpoint = Endpoint {
+                handler: Handler::default(),
+                merge_patch,
+                policy,
                 security,
                 description,
+                docs: Docs::new(data),
+                source: Source::new(scope),
                 operation,
                 method: method.to_string(),
                 path: path.to_string(),
                 tags,
-                responses: responses::extract(data, scope, mcontainer, resolver, options)?,
-                requestbody: requestbody::extract(data, scope, mcontainer, resolver, options)?,
+                request_content_types: content_types(
+                    requestbody.iter().filter_map(|r| r.models.as_ref()),
+                ),
+                response_content_types: content_types(
+                    responses.all.iter().filter_map(|r| r.models.as_ref()),
+                ),
+                responses,
+                requestbody,
                 parameters: endpoint_parameters,
                 x,
             };
 
+            endpoint.handler = endpoint.handler_signature();
+
             scope.clear_spaces();
             scope.pop();
 
@@ -179,9 +658,23 @@ fn new_endpoint(
     }
 }
 
+// unique media types in order of appearance
+fn content_types<'a>(containers: impl Iterator<Item = &'a MediaModelsContainer>) -> Vec<MediaType> {
+    let mut result: Vec<MediaType> = vec![];
+
+    for media in containers.flat_map(|c| c.list.iter().map(|m| &m.media_type)) {
+        if !result.iter().any(|m| m.content_type == media.content_type) {
+            result.push(media.clone());
+        }
+    }
+
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::codegen::jsonschema::types::{ModelType, Presence};
     use serde_json::json;
 
     #[test]
@@ -224,7 +717,7 @@ mod tests {
         });
 
         let mut mcontainer = ModelContainer::default();
-        let mut scontainer = super::security::SecuritySchemes::new();
+        let scontainer = super::security::SecuritySchemes::new();
         let mut scope = SchemaScope::default();
         let resolver = SchemaResolver::empty();
         let options = JsonSchemaExtractOptions::default();
@@ -234,7 +727,7 @@ mod tests {
             "/users/{userId}",
             &mut scope,
             &mut mcontainer,
-            &mut scontainer,
+            &scontainer,
             &resolver,
             &options,
         );
@@ -283,7 +776,7 @@ mod tests {
         });
 
         let mut mcontainer = ModelContainer::default();
-        let mut scontainer = super::security::SecuritySchemes::new();
+        let scontainer = super::security::SecuritySchemes::new();
         let mut scope = SchemaScope::default();
         let resolver = SchemaResolver::empty();
         let options = JsonSchemaExtractOptions::default();
@@ -293,7 +786,7 @@ mod tests {
             "/users/{userId}",
             &mut scope,
             &mut mcontainer,
-            &mut scontainer,
+            &scontainer,
             &resolver,
             &options,
         );
@@ -319,4 +812,215 @@ mod tests {
         // let serialized = serde_json::to_string_pretty(&endpoints).unwrap();
         // println!("serialized: {}", serialized);
     }
+
+    #[test]
+    fn test_merge_patch() {
+        let body = json!({
+            "required": true,
+            "content": {
+                "application/merge-patch+json": { "schema": {
+                    "type": "object",
+                    "title": "Pet",
+                    "required": ["name"],
+                    "properties": {
+                        "name": { "type": "string" },
+                        "age": { "type": "integer", "default": 1 }
+                    }
+                }}
+            }
+        });
+        let schema = json!({
+            "patch": { "requestBody": body, "responses": {"204": {"description": "ok"}} },
+            "put": { "requestBody": body, "responses": {"204": {"description": "ok"}} }
+        });
+
+        let mut mcontainer = ModelContainer::default();
+        let scontainer = super::security::SecuritySchemes::new();
+        let mut scope = SchemaScope::default();
+        let resolver = SchemaResolver::empty();
+        let options = JsonSchemaExtractOptions::default();
+
+        let endpoints = extract_endpoints(
+            &schema,
+            "/pets/{petId}",
+            &mut scope,
+            &mut mcontainer,
+            &scontainer,
+            &resolver,
+            &options,
+        )
+        .unwrap();
+
+        let object = |endpoint: &Endpoint| {
+            let model = &endpoint
+                .requestbody
+                .as_ref()
+                .unwrap()
+                .models
+                .as_ref()
+                .unwrap()
+                .list[0]
+                .model;
+
+            match mcontainer.get(model.original.unwrap()).unwrap().inner() {
+                ModelType::ObjectType(o) => o.clone(),
+                _ => unreachable!(),
+            }
+        };
+
+        let patch = endpoints.iter().find(|e| e.method == "patch").unwrap();
+        let put = endpoints.iter().find(|e| e.method == "put").unwrap();
+
+        assert!(patch.merge_patch);
+        assert!(!put.merge_patch);
+        assert_eq!(object(put).name, "Pet");
+
+        let partial = object(patch);
+        assert_eq!(partial.name, "PetPatch");
+        assert_eq!(partial.constructor.optional, ["name", "age"]);
+        assert!(partial.properties.iter().all(|p| {
+            p.presence() == Presence::OptionalNullable && p.attributes.default.is_none()
+        }));
+    }
+
+    #[test]
+    fn test_deep_object_parameters() {
+        let schema = json!({
+            "get": {
+                "parameters": [{
+                    "in": "query",
+                    "name": "filter",
+                    "style": "deepObject",
+                    "explode": true,
+                    "required": true,
+                    "schema": {
+                        "type": "object",
+                        "required": ["status"],
+                        "properties": {
+                            "status": { "type": "string", "enum": ["open"] },
+                            "created": {
+                                "type": "object",
+                                "properties": {
+                                    "from": { "type": "string", "format": "date" }
+                                }
+                            }
+                        }
+                    }
+                }],
+                "responses": {"204": {"description": "ok"}}
+            }
+        });
+
+        let mut mcontainer = ModelContainer::default();
+        let scontainer = super::security::SecuritySchemes::new();
+        let mut scope = SchemaScope::default();
+        let resolver = SchemaResolver::empty();
+        let options = JsonSchemaExtractOptions::default();
+
+        let endpoints = extract_endpoints(
+            &schema,
+            "/issues",
+            &mut scope,
+            &mut mcontainer,
+            &scontainer,
+            &resolver,
+            &options,
+        )
+        .unwrap();
+
+        let filter = &endpoints[0].parameters.query[0];
+        let properties = filter
+            .properties
+            .iter()
+            .map(|p| (p.name.as_str(), p.required))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            properties,
+            [("filter[status]", true), ("filter[created][from]", false)]
+        );
+        assert_eq!(
+            endpoints[0].request_sample(&mcontainer).url,
+            "/issues?filter%5Bstatus%5D=open"
+        );
+    }
+
+    #[test]
+    fn test_header_and_cookie_parameters() {
+        let schema = json!({
+            "get": {
+                "parameters": [
+                    {
+                        "in": "header",
+                        "name": "X-Tags",
+                        "required": true,
+                        "schema": { "type": "array", "items": { "type": "string", "enum": ["a"] } }
+                    },
+                    {
+                        "in": "header",
+                        "name": "X-Filter",
+                        "required": true,
+                        "content": { "application/json": { "schema": {
+                            "type": "object",
+                            "required": ["status"],
+                            "properties": { "status": { "type": "string", "enum": ["open"] } }
+                        }}}
+                    },
+                    {
+                        "in": "cookie",
+                        "name": "session",
+                        "required": true,
+                        "schema": { "type": "string", "enum": ["s1"] }
+                    }
+                ],
+                "responses": {"204": {"description": "ok"}}
+            }
+        });
+
+        let mut mcontainer = ModelContainer::default();
+        let scontainer = super::security::SecuritySchemes::new();
+        let mut scope = SchemaScope::default();
+        let resolver = SchemaResolver::empty();
+        let options = JsonSchemaExtractOptions::default();
+
+        let endpoints = extract_endpoints(
+            &schema,
+            "/issues",
+            &mut scope,
+            &mut mcontainer,
+            &scontainer,
+            &resolver,
+            &options,
+        )
+        .unwrap();
+
+        let parameters = &endpoints[0].parameters;
+        let serialization = parameters
+            .all
+            .iter()
+            .map(|p| (p.style.as_deref(), p.explode, p.content_type.as_deref()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            serialization,
+            [
+                (Some("simple"), Some(false), None),
+                (Some("simple"), Some(false), Some("application/json")),
+                (Some("form"), Some(true), None)
+            ]
+        );
+        assert_eq!(parameters.header[0].model.as_ref().unwrap().type_, "array");
+        assert_eq!(parameters.header[1].model.as_ref().unwrap().type_, "object");
+
+        let arguments = endpoints[0].handler_signature().arguments;
+        assert_eq!(
+            arguments[1].content_type.as_deref(),
+            Some("application/json")
+        );
+
+        let sample = endpoints[0].request_sample(&mcontainer);
+        assert_eq!(sample.headers["X-Tags"], json!("a"));
+        assert_eq!(sample.headers["X-Filter"], json!("{\"status\":\"open\"}"));
+        assert_eq!(sample.cookies["session"], json!("s1"));
+    }
 }
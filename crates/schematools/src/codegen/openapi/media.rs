@@ -0,0 +1,175 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaType {
+    // content type as written in specification
+    pub content_type: String,
+
+    // type/subtype without parameters
+    pub essence: String,
+
+    // vendor tree subtype without prefix and suffix, ex. company.v2
+    pub vendor: Option<String>,
+
+    // structured syntax suffix, ex. json
+    pub suffix: Option<String>,
+
+    pub version: Option<String>,
+
+    pub charset: Option<String>,
+
+    pub parameters: BTreeMap<String, String>,
+}
+
+// content types delivered as a stream of chunks or records
+const STREAMING: [&str; 3] = [
+    "text/event-stream",
+    "application/x-ndjson",
+    "application/octet-stream",
+];
+
+// content types where schema describes single record of the stream
+const RECORDS: [&str; 1] = ["application/x-ndjson"];
+
+impl MediaType {
+    pub fn is_streaming(&self) -> bool {
+        STREAMING.contains(&self.essence.as_str())
+    }
+
+    pub fn is_records(&self) -> bool {
+        RECORDS.contains(&self.essence.as_str())
+    }
+
+    pub fn parse(content_type: &str) -> Self {
+        let mut parts = content_type.split(';');
+        let essence = parts.next().unwrap_or_default().trim().to_lowercase();
+
+        let parameters = parts
+            .filter_map(|p| p.split_once('='))
+            .map(|(k, v)| {
+                (
+                    k.trim().to_lowercase(),
+                    v.trim().trim_matches('"').to_string(),
+                )
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        let subtype = essence.split_once('/').map(|(_, s)| s).unwrap_or_default();
+        let (tree, suffix) = match subtype.rsplit_once('+') {
+            Some((tree, suffix)) => (tree, Some(suffix.to_string())),
+            None => (subtype, None),
+        };
+        let vendor = tree.strip_prefix("vnd.").map(|s| s.to_string());
+
+        // version parameter takes precedence over version encoded in vendor tree
+        let version = parameters.get("version").cloned().or_else(|| {
+            vendor.as_ref().and_then(|v| {
+                v.rsplit('.')
+                    .next()
+                    .and_then(|last| last.strip_prefix('v'))
+                    .filter(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+                    .map(|n| n.to_string())
+            })
+        });
+
+        Self {
+            content_type: content_type.to_string(),
+            charset: parameters.get("charset").cloned(),
+            essence,
+            vendor,
+            suffix,
+            version,
+            parameters,
+        }
+    }
+
+    /// Checks if media type matches pattern like `application/json`, `application/*` or `*/*`,
+    /// parameters of pattern have to be present in media type
+    pub fn matches(&self, pattern: &str) -> bool {
+        let pattern = MediaType::parse(pattern);
+
+        let essence = match pattern.essence.as_str() {
+            "*/*" => true,
+            p => match p.strip_suffix("/*") {
+                Some(type_) => self.essence.split('/').next() == Some(type_),
+                None => p == self.essence,
+            },
+        };
+
+        essence
+            && pattern
+                .parameters
+                .iter()
+                .all(|(k, v)| self.parameters.get(k) == Some(v))
+    }
+}
+
+/// Sorts media types by the first matching preference, not matched ones keep
+/// their order at the end of list
+pub fn rank(list: &mut [MediaType], preference: &[String]) {
+    list.sort_by_key(|m| {
+        preference
+            .iter()
+            .position(|p| m.matches(p))
+            .unwrap_or(preference.len())
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_vendor_media_type() {
+        let media = MediaType::parse("application/vnd.company.v2+json; charset=UTF-8");
+
+        assert_eq!(media.essence, "application/vnd.company.v2+json");
+        assert_eq!(media.vendor, Some("company.v2".to_string()));
+        assert_eq!(media.suffix, Some("json".to_string()));
+        assert_eq!(media.version, Some("2".to_string()));
+        assert_eq!(media.charset, Some("UTF-8".to_string()));
+    }
+
+    #[test]
+    fn test_parse_version_parameter() {
+        let media = MediaType::parse("application/json;version=3");
+
+        assert_eq!(media.vendor, None);
+        assert_eq!(media.version, Some("3".to_string()));
+        assert!(media.matches("application/json"));
+        assert!(media.matches("application/*"));
+        assert!(media.matches("application/json; version=3"));
+        assert!(!media.matches("application/json; version=2"));
+    }
+
+    #[test]
+    fn test_rank() {
+        let mut list = vec![
+            MediaType::parse("text/plain"),
+            MediaType::parse("application/json"),
+            MediaType::parse("application/vnd.company.v2+json"),
+        ];
+
+        rank(
+            &mut list,
+            &[
+                "application/vnd.company.v2+json".to_string(),
+                "application/*".to_string(),
+            ],
+        );
+
+        assert_eq!(
+            list.iter()
+                .map(|m| m.content_type.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "application/vnd.company.v2+json",
+                "application/json",
+                "text/plain"
+            ]
+        );
+    }
+}
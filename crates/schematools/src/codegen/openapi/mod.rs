@@ -1,23 +1,47 @@
+use std::{collections::HashMap, sync::Arc};
+
 use crate::storage::SchemaStorage;
 use crate::{error::Error, resolver::SchemaResolver, schema::Schema, scope::SchemaScope, tools};
 use serde::ser::SerializeMap;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Map;
 use serde_json::Value;
 
 use super::jsonschema::{add_types, extract_type, JsonSchemaExtractOptions, ModelContainer};
 
+pub mod commands;
 pub mod endpoint;
+pub mod media;
 pub mod parameters;
+pub mod policy;
 pub mod requestbody;
 pub mod responses;
 pub mod security;
 
+#[derive(Default)]
 pub struct OpenapiExtractOptions {
     pub wrappers: bool,
     pub nested_arrays_as_models: bool,
     pub optional_and_nullable_as_models: bool,
+    pub collapse_single_variant: bool,
     pub keep_schema: tools::Filter,
+    pub keep_schema_options: super::jsonschema::KeepSchemaOptions,
+    // preferred content types, ex. application/vnd.company.v2+json, application/*
+    pub content_types: Vec<String>,
+    // promotes inline objects nested deeper than given depth, see process flatten
+    pub flatten_depth: Option<usize>,
+    pub strict: bool,
+    // rewrites nullable enum idioms to single form, see process normalize
+    pub normalize: bool,
+    pub additional_properties_model: bool,
+    pub tri_state: bool,
+    pub builder_threshold: Option<usize>,
+    pub code_names: Option<String>,
+    // numbers of format decimal or with x-precision/x-scale are extracted as decimal type
+    pub decimal: bool,
+    // date and time formats are extracted as distinct types, see TEMPORAL
+    pub temporal: bool,
+    pub examples: bool,
 }
 #[derive(Default)]
 pub struct EndpointContainer {
@@ -34,13 +58,15 @@ impl EndpointContainer {
     }
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct MediaModel {
     pub model: crate::codegen::jsonschema::types::FlatModel,
 
     pub content_type: String,
 
+    pub media_type: media::MediaType,
+
     pub is_unique: bool,
 }
 
@@ -50,12 +76,58 @@ pub struct MediaModelsContainer {
     pub default_content_type: String,
 }
 
+impl MediaModelsContainer {
+    pub fn streaming(&self) -> bool {
+        self.list.iter().any(|m| m.media_type.is_streaming())
+    }
+
+    /// Model of single stream record (ndjson line)
+    pub fn stream_item(&self) -> Option<&crate::codegen::jsonschema::types::FlatModel> {
+        self.list
+            .iter()
+            .find(|m| m.media_type.is_records())
+            .map(|m| &m.model)
+    }
+
+    /// Model of given content type (default one if not given), first model otherwise
+    pub fn select(&self, content_type: Option<&str>) -> Option<&MediaModel> {
+        let content_type = content_type.unwrap_or(&self.default_content_type);
+
+        self.list
+            .iter()
+            .find(|m| m.content_type == content_type)
+            .or_else(|| self.list.first())
+    }
+
+    /// Borrowed container serialized with given default content type
+    pub fn view<'a>(&'a self, content_type: &'a str) -> MediaModelsView<'a> {
+        MediaModelsView {
+            list: &self.list,
+            default_content_type: content_type,
+        }
+    }
+}
+
+pub struct MediaModelsView<'a> {
+    list: &'a [MediaModel],
+    default_content_type: &'a str,
+}
+
 impl Serialize for MediaModelsContainer {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        let mut models = self.list.clone();
+        self.view(&self.default_content_type).serialize(serializer)
+    }
+}
+
+impl Serialize for MediaModelsView<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut models = self.list.iter().collect::<Vec<_>>();
         models.dedup_by(|a, b| a.model == b.model);
 
         // different serialization depending on scenario
@@ -71,12 +143,9 @@ impl Serialize for MediaModelsContainer {
 
                         let m = v.as_object_mut().unwrap();
 
-                        let re = regex::Regex::new(r"/vnd\.|\+").unwrap();
-                        let parts: Vec<&str> = re.split(&s.content_type).collect();
-
                         m.insert(
                             "vnd".to_string(),
-                            serde_json::to_value(parts.get(1)).unwrap(),
+                            serde_json::to_value(&s.media_type.vendor).unwrap(),
                         );
 
                         v
@@ -100,20 +169,89 @@ impl Serialize for MediaModelsContainer {
     }
 }
 
-#[derive(Serialize, Clone)]
+#[derive(Deserialize)]
+struct MediaModelsData {
+    default: Option<MediaModel>,
+    all: Vec<MediaModel>,
+}
+
+// content types sharing the same model are already collapsed by serialization
+impl<'de> Deserialize<'de> for MediaModelsContainer {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let data = MediaModelsData::deserialize(deserializer)?;
+
+        Ok(Self {
+            default_content_type: data.default.map(|m| m.content_type).unwrap_or_default(),
+            list: data.all,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
 pub struct Openapi {
-    pub models: ModelContainer,
+    pub models: Arc<ModelContainer>,
     pub endpoints: Vec<endpoint::Endpoint>,
     pub security: security::SecuritySchemes,
     pub tags: Vec<String>,
 }
 
+/// Borrowed openapi used during rendering, content type is applied to endpoints when serialized
+pub struct OpenapiView<'a> {
+    pub models: &'a ModelContainer,
+    pub endpoints: Vec<&'a endpoint::Endpoint>,
+    pub content_type: &'a str,
+    pub security: &'a security::SecuritySchemes,
+    pub tags: &'a [String],
+}
+
+impl Serialize for OpenapiView<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(4))?;
+        map.serialize_entry("models", self.models)?;
+        map.serialize_entry(
+            "endpoints",
+            &self
+                .endpoints
+                .iter()
+                .map(|e| e.view(self.content_type))
+                .collect::<Vec<_>>(),
+        )?;
+        map.serialize_entry("security", self.security)?;
+        map.serialize_entry("tags", self.tags)?;
+        map.end()
+    }
+}
+
 pub fn extract(
     schema: &Schema,
     storage: &SchemaStorage,
     options: OpenapiExtractOptions,
 ) -> Result<Openapi, Error> {
+    let normalized = options
+        .normalize
+        .then(|| super::jsonschema::normalize(schema, storage));
+    let (schema, storage) = match &normalized {
+        Some((schema, storage)) => (schema, storage),
+        None => (schema, storage),
+    };
+
+    let flattened = options
+        .flatten_depth
+        .map(|depth| super::jsonschema::flatten(schema, storage, depth))
+        .transpose()?;
+    let (schema, storage) = match &flattened {
+        Some((schema, storage)) => (schema, storage),
+        None => (schema, storage),
+    };
+
     let mut scope = SchemaScope::default();
+    scope.enter_file(schema.get_url().as_str());
     let mut mcontainer = ModelContainer::default();
     let mut econtainer = EndpointContainer::new();
     let mut scontainer = security::SecuritySchemes::new();
@@ -121,9 +259,24 @@ pub fn extract(
 
     let root = schema.get_body();
     let resolver = &SchemaResolver::new(schema, storage);
+    let content_types = options.content_types;
+    let policy = match root {
+        Value::Object(data) => policy::Policy::from_extensions(&extensions(data), "#")?,
+        _ => policy::Policy::default(),
+    };
     let options = &JsonSchemaExtractOptions {
         optional_and_nullable_as_models: options.optional_and_nullable_as_models,
+        nested_arrays_as_models: options.nested_arrays_as_models,
+        collapse_single_variant: options.collapse_single_variant,
         keep_schema: options.keep_schema,
+        keep_schema_options: options.keep_schema_options,
+        strict: options.strict,
+        additional_properties_model: options.additional_properties_model,
+        tri_state: options.tri_state,
+        builder_threshold: options.builder_threshold,
+        code_names: options.code_names.clone(),
+        decimal: options.decimal,
+        temporal: options.temporal,
         ..Default::default()
     };
 
@@ -240,7 +393,7 @@ pub fn extract(
         "path:paths/any:*",
         |node, parts, scope| {
             if let [path] = parts {
-                log::trace!("{}", scope);
+                span!("extract", path = %path);
 
                 let endpoints = endpoint::extract_endpoints(
                     node,
@@ -252,7 +405,9 @@ pub fn extract(
                     options,
                 )?;
 
-                for endpoint in endpoints.into_iter() {
+                for mut endpoint in endpoints.into_iter() {
+                    endpoint.rank_content_types(&content_types);
+                    endpoint.policy.inherit(&policy);
                     tags.append(&mut endpoint.get_tags().clone());
                     econtainer.add(endpoint);
                 }
@@ -265,8 +420,18 @@ pub fn extract(
     tags.sort();
     tags.dedup();
 
+    if options.strict {
+        mcontainer.report().strict()?;
+    }
+
+    if options.examples {
+        mcontainer.collect_examples();
+    }
+
+    mcontainer.tag_pii();
+
     Ok(Openapi {
-        models: mcontainer,
+        models: Arc::new(mcontainer),
         endpoints: econtainer.endpoints,
         security: scontainer,
         tags,
@@ -297,6 +462,7 @@ pub fn get_content(
                                         .map(|model| MediaModel {
                                             model,
                                             content_type: content_type.to_string(),
+                                            media_type: media::MediaType::parse(content_type),
                                             is_unique: false,
                                         }),
                                 );
@@ -323,22 +489,328 @@ pub fn get_content(
     })
 }
 
+/// Collects `x-*` extensions of openapi object, prefix is stripped
+pub fn extensions(data: &Map<String, Value>) -> HashMap<String, Value> {
+    data.iter()
+        .filter_map(|(key, val)| {
+            key.strip_prefix("x-")
+                .map(|stripped| (stripped.to_string(), val.clone()))
+        })
+        .collect()
+}
+
 impl Openapi {
+    /// Materializes credentials of security schemes configured by `auth.<scheme>` options
+    pub fn apply_credentials(&mut self, options: &HashMap<String, Value>) -> Result<(), Error> {
+        let credentials = self.security.credentials(options)?;
+
+        self.security.apply_credentials(&credentials);
+        for endpoint in self.endpoints.iter_mut() {
+            endpoint.apply_credentials(&credentials);
+        }
+
+        Ok(())
+    }
+
     pub fn set_content_type(mut self, content_type: &str) -> Self {
-        self.endpoints.iter_mut().for_each(|f| {
-            f.responses.all.iter_mut().for_each(|r| {
-                if let Some(ref mut c) = r.models {
-                    c.default_content_type = content_type.to_string();
-                }
-            });
+        self.endpoints = self
+            .endpoints
+            .into_iter()
+            .map(|e| e.set_content_type(content_type))
+            .collect();
 
-            if let Some(ref mut rb) = f.requestbody {
-                if let Some(ref mut c) = rb.models {
-                    c.default_content_type = content_type.to_string();
+        self
+    }
+
+    /// Borrowed view used during rendering, only endpoints accepted by filter are listed
+    pub fn view<'a, F>(&'a self, content_type: &'a str, filter: F) -> OpenapiView<'a>
+    where
+        F: Fn(&endpoint::Endpoint) -> bool,
+    {
+        OpenapiView {
+            models: &self.models,
+            endpoints: self.endpoints.iter().filter(|e| filter(e)).collect(),
+            content_type,
+            security: &self.security,
+            tags: &self.tags,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Client;
+    use serde_json::json;
+
+    #[test]
+    fn test_view_borrows_only_filtered_endpoints() {
+        let schema = Schema::from_json(json!({
+            "openapi": "3.0.0",
+            "info": {"title": "test", "version": "1.0.0"},
+            "paths": {
+                "/orders": {
+                    "post": {
+                        "operationId": "createOrder",
+                        "tags": ["orders"],
+                        "requestBody": {
+                            "content": {
+                                "application/json": {
+                                    "schema": {"title": "Order", "type": "object", "properties": {"id": {"type": "string"}}}
+                                },
+                                "application/xml": {
+                                    "schema": {"title": "OrderXml", "type": "object", "properties": {"id": {"type": "integer"}}}
+                                }
+                            }
+                        },
+                        "responses": {"204": {"description": "created"}}
+                    }
+                },
+                "/users": {
+                    "get": {
+                        "operationId": "listUsers",
+                        "tags": ["users"],
+                        "responses": {"204": {"description": "ok"}}
+                    }
                 }
             }
+        }));
+        let client = Client::new();
+        let storage = SchemaStorage::new(&schema, &client).unwrap();
+
+        let openapi = extract(&schema, &storage, OpenapiExtractOptions::default()).unwrap();
+
+        let view = openapi.view("application/xml", |e| {
+            e.get_tags().contains(&"orders".to_string())
         });
+        let value = serde_json::to_value(&view).unwrap();
+
+        assert_eq!(view.endpoints.len(), 1);
+        assert!(std::ptr::eq(view.endpoints[0], &openapi.endpoints[0]));
+        assert_eq!(
+            value["endpoints"][0]["requestbody"]["models"]["default"]["contentType"],
+            "application/xml"
+        );
+        assert_eq!(
+            value["endpoints"][0],
+            serde_json::to_value(
+                openapi.endpoints[0]
+                    .clone()
+                    .set_content_type("application/xml")
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            value["models"],
+            serde_json::to_value(&openapi.models).unwrap()
+        );
+        assert!(openapi
+            .view("application/json", |_| false)
+            .endpoints
+            .is_empty());
+    }
 
-        self
+    #[test]
+    fn test_extensions_are_passed_to_models() {
+        let schema = Schema::from_json(json!({
+            "openapi": "3.0.0",
+            "info": {"title": "test", "version": "1.0.0"},
+            "components": {
+                "securitySchemes": {
+                    "token": {"type": "http", "scheme": "bearer", "x-issuer": "auth"}
+                }
+            },
+            "paths": {
+                "/orders": {
+                    "x-rate-limit": 10,
+                    "x-gateway": "public",
+                    "post": {
+                        "operationId": "createOrder",
+                        "x-rate-limit": 5,
+                        "parameters": [
+                            {"name": "id", "in": "query", "schema": {"type": "string"}, "x-internal": true}
+                        ],
+                        "requestBody": {
+                            "x-audit": true,
+                            "content": {
+                                "application/json": {
+                                    "schema": {"title": "Order", "type": "object", "properties": {"id": {"type": "string"}}}
+                                }
+                            }
+                        },
+                        "responses": {"204": {"description": "created", "x-cache": "none"}}
+                    }
+                }
+            }
+        }));
+        let client = Client::new();
+        let storage = SchemaStorage::new(&schema, &client).unwrap();
+
+        let openapi = extract(&schema, &storage, OpenapiExtractOptions::default()).unwrap();
+
+        let value = serde_json::to_value(&openapi).unwrap();
+        let endpoint = &value["endpoints"][0];
+
+        assert_eq!(endpoint["x"], json!({"rate-limit": 5, "gateway": "public"}));
+        assert_eq!(
+            endpoint["parameters"]["query"][0]["x"],
+            json!({"internal": true})
+        );
+        assert_eq!(endpoint["requestbody"]["x"], json!({"audit": true}));
+        assert_eq!(
+            endpoint["responses"]["all"][0]["x"],
+            json!({"cache": "none"})
+        );
+        assert_eq!(value["security"]["all"][0]["x"], json!({"issuer": "auth"}));
+    }
+
+    #[test]
+    fn test_pii_is_propagated_to_models() {
+        let schema = Schema::from_json(json!({
+            "openapi": "3.0.0",
+            "info": {"title": "test", "version": "1.0.0"},
+            "paths": {
+                "/orders": {
+                    "post": {
+                        "operationId": "createOrder",
+                        "requestBody": {
+                            "content": {
+                                "application/json": {
+                                    "schema": {
+                                        "title": "Order",
+                                        "type": "object",
+                                        "properties": {
+                                            "id": {"type": "string"},
+                                            "customer": {
+                                                "type": "object",
+                                                "properties": {
+                                                    "email": {"type": "string", "x-pii": true}
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        },
+                        "responses": {"204": {"description": "created"}}
+                    }
+                }
+            }
+        }));
+        let client = Client::new();
+        let storage = SchemaStorage::new(&schema, &client).unwrap();
+
+        let openapi = extract(&schema, &storage, OpenapiExtractOptions::default()).unwrap();
+
+        let value = serde_json::to_value(&openapi.models).unwrap();
+        let contains_pii = value["models"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| {
+                (
+                    m["object"]["name"].as_str().unwrap(),
+                    m["attributes"]["contains_pii"].as_bool().unwrap(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(contains_pii, vec![("OrderCustomer", true), ("Order", true)]);
+    }
+
+    #[test]
+    fn test_endpoint_content_types_are_ranked() {
+        let schema = Schema::from_json(json!({
+            "openapi": "3.0.0",
+            "info": {"title": "test", "version": "1.0.0"},
+            "paths": {
+                "/orders": {
+                    "get": {
+                        "operationId": "getOrders",
+                        "responses": {
+                            "200": {
+                                "description": "orders",
+                                "content": {
+                                    "application/json": {"schema": {"type": "string"}},
+                                    "application/vnd.company.v2+json": {"schema": {"type": "integer"}}
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }));
+        let client = Client::new();
+        let storage = SchemaStorage::new(&schema, &client).unwrap();
+
+        let openapi = extract(
+            &schema,
+            &storage,
+            OpenapiExtractOptions {
+                content_types: vec!["application/vnd.company.v2+json".to_string()],
+                ..OpenapiExtractOptions::default()
+            },
+        )
+        .unwrap();
+
+        let value = serde_json::to_value(&openapi.endpoints[0]).unwrap();
+
+        assert_eq!(value["request_content_types"], json!([]));
+        assert_eq!(
+            value["response_content_types"][0]["contentType"],
+            "application/vnd.company.v2+json"
+        );
+        assert_eq!(value["response_content_types"][0]["version"], "2");
+        assert_eq!(
+            value["responses"]["all"][0]["models"]["all"][1]["vnd"],
+            "company.v2"
+        );
+    }
+
+    #[test]
+    fn test_credentials_are_applied_to_security_schemes() {
+        let schema = Schema::from_json(json!({
+            "openapi": "3.0.0",
+            "info": {"title": "test", "version": "1.0.0"},
+            "components": {"securitySchemes": {
+                "apiKey": {"type": "apiKey", "in": "header", "name": "X-Key"},
+                "token": {"type": "http", "scheme": "bearer"}
+            }},
+            "security": [{"apiKey": []}],
+            "paths": {"/orders": {"get": {
+                "security": [{"token": []}],
+                "responses": {"204": {"description": "ok"}}
+            }}}
+        }));
+
+        let client = Client::new();
+        let storage = SchemaStorage::new(&schema, &client).unwrap();
+        let mut openapi = extract(&schema, &storage, OpenapiExtractOptions::default()).unwrap();
+
+        let options = HashMap::from([
+            ("auth.apiKey".to_string(), json!("env:API_KEY")),
+            ("auth.token".to_string(), json!("file:/run/secrets/token")),
+            ("name".to_string(), json!("client")),
+        ]);
+        openapi.apply_credentials(&options).unwrap();
+
+        let value = serde_json::to_value(&openapi).unwrap();
+        assert_eq!(
+            value["security"]["default"][0]["credential"],
+            json!({"provider": "env", "value": "API_KEY"})
+        );
+        assert_eq!(
+            value["endpoints"][0]["security"][0]["credential"],
+            json!({"provider": "file", "value": "/run/secrets/token"})
+        );
+
+        for (key, value) in [("auth.unknown", "env:X"), ("auth.apiKey", "API_KEY")] {
+            let options = HashMap::from([(key.to_string(), json!(value))]);
+
+            assert!(matches!(
+                openapi.apply_credentials(&options),
+                Err(Error::CodegenInvalidCredential(_, _))
+            ));
+        }
     }
 }
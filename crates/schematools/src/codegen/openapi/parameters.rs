@@ -1,18 +1,20 @@
 use crate::{
     codegen::jsonschema::{
-        extract_type, types::FlatModel, JsonSchemaExtractOptions, ModelContainer,
+        extract_type,
+        types::{FlatModel, ModelType},
+        JsonSchemaExtractOptions, ModelContainer, ModelId,
     },
     error::Error,
     resolver::SchemaResolver,
     scope::Space,
 };
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Map;
 use serde_json::Value;
 
 use crate::scope::SchemaScope;
 
-#[derive(Serialize, Default, Clone)]
+#[derive(Serialize, Deserialize, Default, Clone)]
 pub struct Parameters {
     #[serde(rename = "path")]
     pub path: Vec<Parameter>,
@@ -30,7 +32,7 @@ pub struct Parameters {
     pub all: Vec<Parameter>,
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Parameter {
     #[serde(rename = "model")]
     pub model: Option<FlatModel>,
@@ -52,6 +54,17 @@ pub struct Parameter {
 
     #[serde(rename = "kind")]
     pub kind: String,
+
+    // media type of parameters defined with content instead of schema, ex. application/json
+    #[serde(rename = "content_type", default)]
+    pub content_type: Option<String>,
+
+    // leaf properties of deepObject query parameter named with brackets, ex. filter[status]
+    #[serde(rename = "properties", default)]
+    pub properties: Vec<Parameter>,
+
+    #[serde(rename = "x")]
+    pub x: std::collections::HashMap<String, Value>,
 }
 
 pub fn extract(
@@ -134,18 +147,48 @@ pub fn extract_parameter(
                     .unwrap()
             });
 
-            let schema = data.get("schema").ok_or_else(|| {
-                Error::CodegenInvalidEndpointProperty("schema".to_string(), scope.to_string())
-            })?;
+            let (schema, content_type) = match (data.get("schema"), data.get("content")) {
+                (Some(schema), _) => (schema, None),
+                (None, Some(Value::Object(content))) if content.len() == 1 => content
+                    .iter()
+                    .next()
+                    .and_then(|(content_type, media)| {
+                        Some((media.get("schema")?, Some(content_type.clone())))
+                    })
+                    .ok_or_else(|| {
+                        Error::CodegenInvalidEndpointProperty(
+                            "content".to_string(),
+                            scope.to_string(),
+                        )
+                    })?,
+                _ => {
+                    return Err(Error::CodegenInvalidEndpointProperty(
+                        "schema".to_string(),
+                        scope.to_string(),
+                    ))
+                }
+            };
 
             let required = data
                 .get("required")
                 .map(|s| s.as_bool().unwrap())
                 .unwrap_or(false);
 
-            let explode = data.get("explode").map(|s| s.as_bool().unwrap());
-
-            let style = data.get("style").map(|s| s.as_str().unwrap().to_string());
+            // defaults of serialization by location
+            let style = data
+                .get("style")
+                .and_then(|s| s.as_str())
+                .or(match kind.as_str() {
+                    "query" | "cookie" => Some("form"),
+                    "path" | "header" => Some("simple"),
+                    _ => None,
+                })
+                .map(|s| s.to_string());
+
+            let explode = data
+                .get("explode")
+                .and_then(|s| s.as_bool())
+                .or_else(|| style.as_ref().map(|s| s == "form"));
 
             scope.any("schema").glue(&name).glue(&kind);
 
@@ -154,6 +197,13 @@ pub fn extract_parameter(
 
             scope.reduce(3);
 
+            let model = model?;
+            let properties = if kind == "query" && style.as_deref() == Some("deepObject") {
+                deep_object(&name, &model, required, mcontainer, &mut vec![])
+            } else {
+                vec![]
+            };
+
             Ok(Parameter {
                 required,
                 name,
@@ -161,7 +211,10 @@ pub fn extract_parameter(
                 kind,
                 explode,
                 style,
-                model: Some(model?),
+                model: Some(model),
+                content_type,
+                properties,
+                x: super::extensions(data),
             })
         }
         _ => Err(Error::CodegenInvalidEndpointProperty(
@@ -171,6 +224,61 @@ pub fn extract_parameter(
     })
 }
 
+// nested objects are flattened too, recursive ones only once
+fn deep_object(
+    name: &str,
+    model: &FlatModel,
+    required: bool,
+    mcontainer: &ModelContainer,
+    visited: &mut Vec<ModelId>,
+) -> Vec<Parameter> {
+    let object = match model
+        .original
+        .and_then(|id| Some((id, mcontainer.get(id)?)))
+    {
+        Some((id, m)) if model.type_ == "object" && !visited.contains(&id) => match m.inner() {
+            ModelType::ObjectType(o) => {
+                visited.push(id);
+                o
+            }
+            _ => return vec![],
+        },
+        _ => return vec![],
+    };
+
+    let properties = object
+        .properties
+        .iter()
+        .flat_map(|property| {
+            let field = property.name.clone().unwrap_or_default();
+            let name = format!("{name}[{field}]");
+            let required = required && property.attributes.required;
+
+            let nested = deep_object(&name, property, required, mcontainer, visited);
+            if !nested.is_empty() {
+                return nested;
+            }
+
+            vec![Parameter {
+                model: Some(property.clone()),
+                required,
+                name,
+                description: property.attributes.description.clone(),
+                style: None,
+                explode: None,
+                kind: "query".to_string(),
+                content_type: None,
+                properties: vec![],
+                x: property.attributes.x.clone(),
+            }]
+        })
+        .collect();
+
+    visited.pop();
+
+    properties
+}
+
 impl Parameters {
     pub fn add(&mut self, param: Parameter) {
         self.all.push(param.clone());
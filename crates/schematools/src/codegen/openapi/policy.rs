@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::Error;
+
+/// Client call policy built from `x-timeout` and `x-retry` extensions,
+/// durations are normalized to milliseconds
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct Policy {
+    pub timeout: Option<u64>,
+    pub retry: Option<RetryPolicy>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub attempts: u32,
+    pub backoff: Option<u64>,
+    pub on: Vec<u32>,
+}
+
+impl Policy {
+    /// Reads policy from extensions (without `x-` prefix)
+    pub fn from_extensions(x: &HashMap<String, Value>, scope: &str) -> Result<Self, Error> {
+        let timeout = x
+            .get("timeout")
+            .map(|v| duration(v).ok_or_else(|| invalid("x-timeout", scope)))
+            .transpose()?;
+
+        let retry = x.get("retry").map(|v| retry(v, scope)).transpose()?;
+
+        Ok(Self { timeout, retry })
+    }
+
+    /// Fills values missing in operation policy with root level ones
+    pub fn inherit(&mut self, root: &Policy) {
+        if self.timeout.is_none() {
+            self.timeout = root.timeout;
+        }
+
+        if self.retry.is_none() {
+            self.retry = root.retry.clone();
+        }
+    }
+}
+
+fn retry(value: &Value, scope: &str) -> Result<RetryPolicy, Error> {
+    match value {
+        Value::Number(n) => n
+            .as_u64()
+            .map(|attempts| RetryPolicy {
+                attempts: attempts as u32,
+                backoff: None,
+                on: vec![],
+            })
+            .ok_or_else(|| invalid("x-retry", scope)),
+        Value::Object(o) => Ok(RetryPolicy {
+            attempts: o
+                .get("attempts")
+                .and_then(|v| v.as_u64())
+                .ok_or_else(|| invalid("x-retry.attempts", scope))? as u32,
+            backoff: o
+                .get("backoff")
+                .map(|v| duration(v).ok_or_else(|| invalid("x-retry.backoff", scope)))
+                .transpose()?,
+            on: match o.get("on") {
+                Some(Value::Array(codes)) => codes
+                    .iter()
+                    .map(|c| c.as_u64().map(|c| c as u32))
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(|| invalid("x-retry.on", scope))?,
+                Some(_) => return Err(invalid("x-retry.on", scope)),
+                None => vec![],
+            },
+        }),
+        _ => Err(invalid("x-retry", scope)),
+    }
+}
+
+// number of milliseconds or string with ms, s or m unit
+fn duration(value: &Value) -> Option<u64> {
+    match value {
+        Value::Number(n) => n.as_u64(),
+        Value::String(s) => {
+            let s = s.trim();
+
+            if let Some(ms) = s.strip_suffix("ms") {
+                ms.trim().parse().ok()
+            } else if let Some(secs) = s.strip_suffix('s') {
+                secs.trim().parse::<u64>().ok().map(|v| v * 1000)
+            } else if let Some(mins) = s.strip_suffix('m') {
+                mins.trim().parse::<u64>().ok().map(|v| v * 60_000)
+            } else {
+                s.parse().ok()
+            }
+        }
+        _ => None,
+    }
+}
+
+fn invalid(property: &str, scope: &str) -> Error {
+    Error::CodegenInvalidEndpointProperty(property.to_string(), scope.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn extensions(value: Value) -> HashMap<String, Value> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_policy_from_extensions() {
+        let policy = Policy::from_extensions(
+            &extensions(json!({
+                "timeout": "2s",
+                "retry": {"attempts": 3, "backoff": "150ms", "on": [502, 503]}
+            })),
+            "",
+        )
+        .unwrap();
+
+        assert_eq!(
+            policy,
+            Policy {
+                timeout: Some(2000),
+                retry: Some(RetryPolicy {
+                    attempts: 3,
+                    backoff: Some(150),
+                    on: vec![502, 503]
+                })
+            }
+        );
+    }
+
+    #[test]
+    fn test_policy_inherit() {
+        let root =
+            Policy::from_extensions(&extensions(json!({"timeout": 1000, "retry": 2})), "").unwrap();
+        let mut policy =
+            Policy::from_extensions(&extensions(json!({"timeout": "1m"})), "").unwrap();
+
+        policy.inherit(&root);
+
+        assert_eq!(policy.timeout, Some(60_000));
+        assert_eq!(policy.retry.unwrap().attempts, 2);
+    }
+
+    #[test]
+    fn test_policy_invalid() {
+        assert!(Policy::from_extensions(&extensions(json!({"timeout": "soon"})), "").is_err());
+        assert!(Policy::from_extensions(&extensions(json!({"retry": {"on": [500]}})), "").is_err());
+    }
+}
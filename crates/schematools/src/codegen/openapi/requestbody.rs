@@ -1,14 +1,17 @@
 use crate::{
-    codegen::jsonschema::{JsonSchemaExtractOptions, ModelContainer},
+    codegen::jsonschema::{
+        types::{Builder, Constructor, FlatModel, Model, ModelType, ObjectType, Presence},
+        JsonSchemaExtractOptions, ModelContainer,
+    },
     error::Error,
     resolver::SchemaResolver,
     scope::SchemaScope,
 };
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Map;
 use serde_json::Value;
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct RequestBody {
     #[serde(rename = "models")]
     pub models: Option<super::MediaModelsContainer>,
@@ -18,8 +21,44 @@ pub struct RequestBody {
 
     #[serde(rename = "description")]
     pub description: Option<String>,
+
+    #[serde(rename = "streaming")]
+    pub streaming: bool,
+
+    #[serde(rename = "stream_item")]
+    pub stream_item: Option<FlatModel>,
+
+    #[serde(rename = "x")]
+    pub x: std::collections::HashMap<String, Value>,
+}
+
+/// Request body serialized with given default content type, see [`RequestBody::view`]
+#[derive(Serialize)]
+pub struct RequestBodyView<'a> {
+    models: Option<super::MediaModelsView<'a>>,
+    required: bool,
+    description: &'a Option<String>,
+    streaming: bool,
+    stream_item: &'a Option<FlatModel>,
+    x: &'a std::collections::HashMap<String, Value>,
+}
+
+impl RequestBody {
+    pub fn view<'a>(&'a self, content_type: &'a str) -> RequestBodyView<'a> {
+        RequestBodyView {
+            models: self.models.as_ref().map(|m| m.view(content_type)),
+            required: self.required,
+            description: &self.description,
+            streaming: self.streaming,
+            stream_item: &self.stream_item,
+            x: &self.x,
+        }
+    }
 }
 
+/// Media type of JSON Merge Patch (RFC 7396) documents
+pub const MERGE_PATCH: &str = "application/merge-patch+json";
+
 pub fn extract(
     node: &Map<String, Value>,
     scope: &mut SchemaScope,
@@ -48,7 +87,7 @@ pub fn extract_requestbody(
 ) -> Result<Option<RequestBody>, Error> {
     resolver.resolve(node, scope, |node, scope| match node {
         Value::Object(ref data) => {
-            log::trace!("{}", scope);
+            span!("extract", pointer = %scope.pointer());
 
             let required = data
                 .get("required")
@@ -68,10 +107,15 @@ pub fn extract_requestbody(
 
             scope.reduce(2);
 
+            let model = model?;
+
             Ok(Some(RequestBody {
-                models: model?,
+                streaming: model.as_ref().is_some_and(|m| m.streaming()),
+                stream_item: model.as_ref().and_then(|m| m.stream_item()).cloned(),
+                models: model,
                 description,
                 required,
+                x: super::extensions(data),
             }))
         }
         _ => Err(Error::CodegenInvalidEndpointProperty(
@@ -80,3 +124,69 @@ pub fn extract_requestbody(
         )),
     })
 }
+
+/// Replaces objects of merge patch bodies with partial models named `<Object>Patch`, every
+/// property is optional and nullable: absent one is left unchanged and null one is removed
+pub fn merge_patch(
+    requestbody: &mut RequestBody,
+    scope: &mut SchemaScope,
+    mcontainer: &mut ModelContainer,
+) -> Result<bool, Error> {
+    let mut patched = false;
+
+    let Some(models) = requestbody.models.as_mut() else {
+        return Ok(false);
+    };
+
+    for media in models
+        .list
+        .iter_mut()
+        .filter(|m| m.media_type.essence == MERGE_PATCH)
+    {
+        let Some(base) = media.model.original.and_then(|id| mcontainer.get(id)) else {
+            continue;
+        };
+        let ModelType::ObjectType(object) = base.inner() else {
+            continue;
+        };
+
+        let name = format!("{}Patch", object.name);
+        let properties = object
+            .properties
+            .iter()
+            .cloned()
+            .map(|mut property| {
+                property.attributes.required = false;
+                property.attributes.nullable = true;
+                property.attributes.presence = Some(Presence::OptionalNullable);
+                // defaults would overwrite values which are not meant to change
+                property.attributes.default = None;
+                property.attributes.has_default = false;
+                property
+            })
+            .collect::<Vec<_>>();
+
+        let mut patch = Model::new(ModelType::ObjectType(ObjectType {
+            constructor: Constructor::new(&properties),
+            builder: object
+                .builder
+                .as_ref()
+                .map(|_| Builder::new(&name, &properties)),
+            additional: object.additional,
+            additional_model: object.additional_model.clone(),
+            properties,
+            name,
+        }))
+        .with_attributes(&base.attributes);
+        patch.source = base.source.clone();
+
+        scope.any("patch");
+        let flat = patch.flatten(mcontainer, scope);
+        scope.pop();
+
+        media.model = flat?.customize_attributes(&media.model.attributes);
+        patched = true;
+    }
+
+    Ok(patched)
+}
@@ -7,29 +7,103 @@ use crate::{
     resolver::SchemaResolver,
     scope::SchemaScope,
 };
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Map;
 use serde_json::Value;
 
 use super::parameters::Parameter;
+use crate::codegen::jsonschema::types::FlatModel;
 
-#[derive(Debug, Serialize, Default, Clone)]
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Responses {
     pub success: Option<Response>,
     pub all: Vec<Response>,
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ResponseKind {
+    Exact,
+    Range,
+    Default,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct StatusRange {
+    pub from: u32,
+    pub to: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Response {
+    // exact code, first code of range or 0 for default response
     pub status_code: u32,
 
+    pub kind: ResponseKind,
+
+    pub range: Option<StatusRange>,
+
     pub models: Option<super::MediaModelsContainer>,
 
     pub description: Option<String>,
 
     pub headers: Option<Vec<Parameter>>,
+
+    pub streaming: bool,
+
+    pub stream_item: Option<FlatModel>,
+
+    pub x: HashMap<String, Value>,
+}
+
+/// Responses serialized with given default content type of every response except success,
+/// see [`Responses::view`]
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponsesView<'a> {
+    success: &'a Option<Response>,
+    all: Vec<ResponseView<'a>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseView<'a> {
+    status_code: u32,
+    kind: ResponseKind,
+    range: &'a Option<StatusRange>,
+    models: Option<super::MediaModelsView<'a>>,
+    description: &'a Option<String>,
+    headers: &'a Option<Vec<Parameter>>,
+    streaming: bool,
+    stream_item: &'a Option<FlatModel>,
+    x: &'a HashMap<String, Value>,
+}
+
+impl Responses {
+    pub fn view<'a>(&'a self, content_type: &'a str) -> ResponsesView<'a> {
+        ResponsesView {
+            success: &self.success,
+            all: self.all.iter().map(|r| r.view(content_type)).collect(),
+        }
+    }
+}
+
+impl Response {
+    pub fn view<'a>(&'a self, content_type: &'a str) -> ResponseView<'a> {
+        ResponseView {
+            status_code: self.status_code,
+            kind: self.kind,
+            range: &self.range,
+            models: self.models.as_ref().map(|m| m.view(content_type)),
+            description: &self.description,
+            headers: &self.headers,
+            streaming: self.streaming,
+            stream_item: &self.stream_item,
+            x: &self.x,
+        }
+    }
 }
 
 pub fn extract(
@@ -109,22 +183,22 @@ pub fn extract_responses(
                 }
             }
 
-            for response in parsed {
-                scope.property(&response.status_code.to_string());
-
-                if responses.success.is_none()
-                    && response.status_code >= 200
-                    && response.status_code < 300
-                {
-                    log::info!("{} -> success status code: {}", scope, response.status_code);
-                    responses.success = Some(response.clone());
-                }
-
-                responses.all.push(response);
+            // exact success code is preferred over 2XX range
+            responses.success = [ResponseKind::Exact, ResponseKind::Range]
+                .iter()
+                .find_map(|kind| {
+                    parsed
+                        .iter()
+                        .find(|r| r.kind == *kind && r.status_code >= 200 && r.status_code < 300)
+                })
+                .cloned();
 
-                scope.pop();
+            if let Some(success) = &responses.success {
+                log::info!("{} -> success status code: {}", scope, success.status_code);
             }
 
+            responses.all = parsed;
+
             Ok(responses)
         }
         _ => Err(Error::CodegenInvalidEndpointProperty(
@@ -144,7 +218,7 @@ pub fn extract_response(
 ) -> Result<Response, Error> {
     resolver.resolve(node, scope, |node, scope| match node {
         Value::Object(data) => {
-            log::trace!("{}", scope);
+            span!("extract", pointer = %scope.pointer());
 
             let description = data.get("description").map(|v| {
                 v.as_str()
@@ -152,18 +226,14 @@ pub fn extract_response(
                     .unwrap()
             });
 
-            let status_code = if code == "default" {
-                0
-            } else {
-                code.parse::<u32>().map_err(|_| {
-                    Error::CodegenInvalidEndpointProperty(
-                        format!("response:{code}"),
-                        scope.to_string(),
-                    )
-                })?
-            };
+            let (kind, status_code, range) = status(code).ok_or_else(|| {
+                Error::CodegenInvalidEndpointProperty(format!("response:{code}"), scope.to_string())
+            })?;
 
-            scope.glue(&status_code.to_string());
+            match kind {
+                ResponseKind::Range => scope.glue(&code.to_lowercase()),
+                _ => scope.glue(&status_code.to_string()),
+            };
 
             let model = super::get_content(data, scope, mcontainer, resolver, options)
                 .map_or(Ok(None), |v| v.map(Some));
@@ -195,11 +265,18 @@ pub fn extract_response(
                 })
                 .map_or(Ok(None), |v| v.map(Some))?;
 
+            let model = model?;
+
             Ok(Response {
-                models: model?,
+                streaming: model.as_ref().is_some_and(|m| m.streaming()),
+                stream_item: model.as_ref().and_then(|m| m.stream_item()).cloned(),
+                models: model,
                 headers,
                 description,
                 status_code,
+                kind,
+                range,
+                x: super::extensions(data),
             })
         }
         _ => Err(Error::CodegenInvalidEndpointProperty(
@@ -209,6 +286,32 @@ pub fn extract_response(
     })
 }
 
+// parses response key: exact code, 1XX-5XX range or default
+fn status(code: &str) -> Option<(ResponseKind, u32, Option<StatusRange>)> {
+    if code == "default" {
+        return Some((ResponseKind::Default, 0, None));
+    }
+
+    match code.as_bytes() {
+        [class @ b'1'..=b'5', b'X' | b'x', b'X' | b'x'] => {
+            let from = u32::from(class - b'0') * 100;
+
+            Some((
+                ResponseKind::Range,
+                from,
+                Some(StatusRange {
+                    from,
+                    to: from + 99,
+                }),
+            ))
+        }
+        _ => code
+            .parse::<u32>()
+            .ok()
+            .map(|c| (ResponseKind::Exact, c, None)),
+    }
+}
+
 fn as_header_node(
     name: &str,
     node: &Value,
@@ -313,4 +416,98 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_status_kinds() {
+        let schema = json!({
+            "default": {"description": "Unexpected"},
+            "4XX": {"description": "Client error"},
+            "2XX": {"description": "Any success"},
+            "201": {"description": "Created"}
+        });
+
+        let mut mcontainer = ModelContainer::default();
+        let mut scope = SchemaScope::default();
+        let resolver = SchemaResolver::empty();
+        let options = JsonSchemaExtractOptions::default();
+
+        let responses =
+            extract_responses(&schema, &mut scope, &mut mcontainer, &resolver, &options).unwrap();
+
+        let kinds = responses
+            .all
+            .iter()
+            .map(|r| (r.kind, r.status_code, r.range.clone()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            kinds,
+            vec![
+                (ResponseKind::Default, 0, None),
+                (
+                    ResponseKind::Range,
+                    400,
+                    Some(StatusRange { from: 400, to: 499 })
+                ),
+                (
+                    ResponseKind::Range,
+                    200,
+                    Some(StatusRange { from: 200, to: 299 })
+                ),
+                (ResponseKind::Exact, 201, None),
+            ]
+        );
+        assert_eq!(responses.success.unwrap().status_code, 201);
+        assert!(status("6XX").is_none());
+    }
+
+    #[test]
+    fn test_streaming_responses() {
+        let schema = json!({
+            "200": {
+                "description": "Events",
+                "content": {
+                    "application/x-ndjson": { "schema" : {"title": "Event", "type": "object", "properties": { "id" : {"type": "string"}}} },
+                },
+            },
+            "201": {
+                "description": "File",
+                "content": {
+                    "application/octet-stream": { "schema" : {"type": "string", "format": "binary"} },
+                },
+            },
+            "202": {
+                "description": "Json",
+                "content": {
+                    "application/json": { "schema" : {"type": "string"} },
+                },
+            }
+        });
+
+        let mut mcontainer = ModelContainer::default();
+        let mut scope = SchemaScope::default();
+        let resolver = SchemaResolver::empty();
+        let options = JsonSchemaExtractOptions::default();
+
+        let responses =
+            extract_responses(&schema, &mut scope, &mut mcontainer, &resolver, &options).unwrap();
+
+        let events = &responses.all[0];
+        assert!(events.streaming);
+        assert_eq!(
+            events
+                .stream_item
+                .as_ref()
+                .unwrap()
+                .model
+                .as_ref()
+                .unwrap()
+                .name,
+            Some("Event".to_string())
+        );
+
+        assert!(responses.all[1].streaming);
+        assert!(responses.all[1].stream_item.is_none());
+        assert!(!responses.all[2].streaming);
+    }
 }
@@ -1,8 +1,12 @@
 use crate::{error::Error, scope::SchemaScope};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
 
-#[derive(Debug, Serialize, Default, Clone)]
+// prefix of options mapping security schemes to credentials, ex. auth.apiKey=env:API_KEY
+pub const CREDENTIAL_OPTION: &str = "auth.";
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
 pub struct SecuritySchemes {
     #[serde(rename = "default")]
     pub default: Vec<SecurityScheme>,
@@ -23,9 +27,65 @@ impl SecuritySchemes {
     pub fn add_default(&mut self, scheme: SecurityScheme) {
         self.default.push(scheme);
     }
+
+    /// Credentials configured by `auth.<scheme>=<provider>:<value>` options
+    pub fn credentials(
+        &self,
+        options: &HashMap<String, Value>,
+    ) -> Result<HashMap<String, Credential>, Error> {
+        options
+            .iter()
+            .filter_map(|(key, value)| {
+                key.strip_prefix(CREDENTIAL_OPTION)
+                    .map(|scheme| (key, scheme, value))
+            })
+            .map(|(key, scheme, value)| {
+                if !self.all.iter().any(|s| s.scheme_name == scheme) {
+                    return Err(Error::CodegenInvalidCredential(
+                        key.clone(),
+                        format!("unknown security scheme {scheme}"),
+                    ));
+                }
+
+                value
+                    .as_str()
+                    .and_then(|v| v.split_once(':'))
+                    .filter(|(provider, value)| !provider.is_empty() && !value.is_empty())
+                    .map(|(provider, value)| {
+                        (
+                            scheme.to_string(),
+                            Credential {
+                                provider: provider.to_string(),
+                                value: value.to_string(),
+                            },
+                        )
+                    })
+                    .ok_or_else(|| {
+                        Error::CodegenInvalidCredential(
+                            key.clone(),
+                            "expected provider:value, ex. env:API_KEY".to_string(),
+                        )
+                    })
+            })
+            .collect()
+    }
+
+    pub fn apply_credentials(&mut self, credentials: &HashMap<String, Credential>) {
+        for scheme in self.all.iter_mut().chain(self.default.iter_mut()) {
+            scheme.apply_credentials(credentials);
+        }
+    }
 }
 
-#[derive(Debug, Serialize, Clone)]
+/// Source of secret used by security scheme, ex. environment variable
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Credential {
+    // ex. env, file or name of custom provider known to templates
+    pub provider: String,
+    pub value: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SecurityScheme {
     #[serde(rename = "scheme_name")]
     pub scheme_name: String,
@@ -41,9 +101,25 @@ pub struct SecurityScheme {
 
     #[serde(rename = "name")]
     pub name: Option<String>,
+
+    #[serde(rename = "x")]
+    pub x: std::collections::HashMap<String, Value>,
+
+    // scopes required by security requirement, empty in list of all schemes
+    #[serde(rename = "scopes", default)]
+    pub scopes: Vec<String>,
+
+    #[serde(rename = "credential", default)]
+    pub credential: Option<Credential>,
     // todo: openId and oauth2
 }
 
+impl SecurityScheme {
+    pub fn apply_credentials(&mut self, credentials: &HashMap<String, Credential>) {
+        self.credential = credentials.get(&self.scheme_name).cloned();
+    }
+}
+
 pub fn new_scheme(
     node: &Value,
     scheme_name: &str,
@@ -74,6 +150,9 @@ pub fn new_scheme(
                 scheme,
                 in_,
                 name,
+                x: super::extensions(data),
+                scopes: vec![],
+                credential: None,
             };
 
             scope.pop();
@@ -118,12 +197,22 @@ pub fn extract_default(
     match node {
         Value::Object(data) => {
             let mut security_scheme: Option<SecurityScheme> = None;
-            for (scheme_name, _) in data {
+            for (scheme_name, scopes) in data {
                 security_scheme = scontainer
-                    .clone()
                     .all
-                    .into_iter()
-                    .find(|scheme| scheme.scheme_name == *scheme_name);
+                    .iter()
+                    .find(|scheme| scheme.scheme_name == *scheme_name)
+                    .map(|scheme| SecurityScheme {
+                        scopes: scopes
+                            .as_array()
+                            .map(|list| {
+                                list.iter()
+                                    .filter_map(|v| v.as_str().map(String::from))
+                                    .collect()
+                            })
+                            .unwrap_or_default(),
+                        ..scheme.clone()
+                    });
             }
 
             Ok(security_scheme)
@@ -0,0 +1,207 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{discovery::Discovered, error::Error};
+
+use super::CodegenContainer;
+
+pub const OPTIONS_FILE: &str = "schematools.toml";
+
+/// Options declared by template pack in `schematools.toml`
+///
+/// ```toml
+/// [options.name]
+/// type = "string"
+/// required = true
+///
+/// [options.async]
+/// type = "boolean"
+/// default = false
+/// ```
+#[derive(Debug, Default, Deserialize)]
+pub struct OptionsSpec {
+    #[serde(default)]
+    pub options: BTreeMap<String, OptionSpec>,
+
+    #[serde(default)]
+    pub files: BTreeMap<String, FileSpec>,
+}
+
+/// Copying rules of static file
+///
+/// ```toml
+/// [files."Cargo.toml"]
+/// if = "%options.crate%:true"
+/// target = "{{name}}/Cargo.toml"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+pub struct FileSpec {
+    #[serde(rename = "if")]
+    pub condition: Option<String>,
+
+    pub target: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OptionSpec {
+    #[serde(rename = "type", default)]
+    pub type_: OptionType,
+
+    #[serde(default)]
+    pub required: bool,
+
+    pub default: Option<Value>,
+
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum OptionType {
+    #[default]
+    String,
+    Integer,
+    Number,
+    Boolean,
+    Array,
+    Object,
+}
+
+impl OptionsSpec {
+    pub fn from_discovered(discovered: &Discovered) -> Result<Option<Self>, Error> {
+        match discovered.files.get(OPTIONS_FILE) {
+            Some(path) => {
+                let content = std::fs::read_to_string(path).map_err(Error::DiscoveryReadFile)?;
+
+                toml::from_str(&content)
+                    .map(Some)
+                    .map_err(|e| Error::CodegenOptionsError(format!("{OPTIONS_FILE}: {e}")))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Validates and coerces provided options, fills defaults of missing ones
+    pub fn apply(&self, container: &mut CodegenContainer) -> Result<(), Error> {
+        for (name, spec) in &self.options {
+            match container.options.get(name) {
+                Some(value) => {
+                    let value = spec.type_.coerce(value).ok_or_else(|| {
+                        Error::CodegenOptionsError(format!(
+                            "option {name} has to be of type {:?}",
+                            spec.type_
+                        ))
+                    })?;
+
+                    container.options.insert(name.clone(), value);
+                }
+                None if spec.required => {
+                    return Err(Error::CodegenOptionsError(format!(
+                        "option {name} is required"
+                    )))
+                }
+                None => {
+                    if let Some(default) = &spec.default {
+                        container.options.insert(name.clone(), default.clone());
+                    }
+                }
+            }
+        }
+
+        for name in container.options.keys() {
+            if !self.options.contains_key(name)
+                && !name.starts_with(super::openapi::security::CREDENTIAL_OPTION)
+            {
+                log::warn!("Option {} is not declared by templates", name);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl OptionType {
+    pub fn coerce(&self, value: &Value) -> Option<Value> {
+        match (self, value) {
+            (Self::String, Value::String(_)) => Some(value.clone()),
+            (Self::String, Value::Number(n)) => Some(Value::String(n.to_string())),
+            (Self::String, Value::Bool(b)) => Some(Value::String(b.to_string())),
+            (Self::Integer, Value::Number(n)) if n.is_i64() || n.is_u64() => Some(value.clone()),
+            (Self::Integer, Value::String(s)) => s.trim().parse::<i64>().ok().map(Value::from),
+            (Self::Number, Value::Number(_)) => Some(value.clone()),
+            (Self::Number, Value::String(s)) => s.trim().parse::<f64>().ok().map(Value::from),
+            (Self::Boolean, Value::Bool(_)) => Some(value.clone()),
+            (Self::Boolean, Value::String(s)) => match s.trim() {
+                "true" | "1" | "yes" => Some(Value::Bool(true)),
+                "false" | "0" | "no" => Some(Value::Bool(false)),
+                _ => None,
+            },
+            (Self::Array, Value::Array(_)) | (Self::Object, Value::Object(_)) => {
+                Some(value.clone())
+            }
+            (Self::Array, Value::String(s)) => serde_json::from_str::<Value>(s)
+                .ok()
+                .filter(Value::is_array),
+            (Self::Object, Value::String(s)) => serde_json::from_str::<Value>(s)
+                .ok()
+                .filter(Value::is_object),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn spec() -> OptionsSpec {
+        toml::from_str(
+            r#"
+            [options.name]
+            required = true
+
+            [options.port]
+            type = "integer"
+            default = 8080
+
+            [options.async]
+            type = "boolean"
+
+            [options.endpoints]
+            type = "array"
+            "#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_apply_coerces_and_fills_defaults() {
+        let mut container = super::super::create_container(&[
+            ("name".to_string(), json!("client")),
+            ("async".to_string(), json!("true")),
+            ("endpoints".to_string(), json!("[\"/pets\"]")),
+        ]);
+
+        spec().apply(&mut container).unwrap();
+
+        assert_eq!(container.options["name"], json!("client"));
+        assert_eq!(container.options["port"], json!(8080));
+        assert_eq!(container.options["async"], json!(true));
+        assert_eq!(container.options["endpoints"], json!(["/pets"]));
+    }
+
+    #[test]
+    fn test_apply_validates() {
+        let mut missing = super::super::create_container(&[]);
+        assert!(spec().apply(&mut missing).is_err());
+
+        let mut invalid = super::super::create_container(&[
+            ("name".to_string(), json!("client")),
+            ("port".to_string(), json!("http")),
+        ]);
+        assert!(spec().apply(&mut invalid).is_err());
+    }
+}
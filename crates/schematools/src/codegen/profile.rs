@@ -0,0 +1,98 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use serde_json::Value;
+
+use super::jsonschema::ModelContainer;
+
+#[derive(Debug, Clone)]
+pub struct RenderProfile {
+    pub template: String,
+    pub target: String,
+    pub duration: Duration,
+    pub context_size: usize,
+    pub output_size: usize,
+}
+
+/// Collects render statistics of every template, shared between cloned containers
+#[derive(Debug, Clone, Default)]
+pub struct Profiler {
+    entries: Arc<Mutex<Vec<RenderProfile>>>,
+}
+
+impl Profiler {
+    pub fn record(&self, profile: RenderProfile) {
+        self.entries.lock().unwrap().push(profile);
+    }
+
+    pub fn entries(&self) -> Vec<RenderProfile> {
+        self.entries.lock().unwrap().clone()
+    }
+
+    /// Table of renders sorted from the slowest one
+    pub fn report(&self) -> String {
+        let mut entries = self.entries();
+        entries.sort_by_key(|e| std::cmp::Reverse(e.duration));
+
+        let mut lines = vec![format!(
+            "{:>10} {:>12} {:>12}  {}",
+            "time", "context", "output", "template -> target"
+        )];
+
+        for e in entries {
+            lines.push(format!(
+                "{:>10.2?} {:>12} {:>12}  {} -> {}",
+                e.duration, e.context_size, e.output_size, e.template, e.target
+            ));
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// Returns serialized models with given name exactly as they are passed to templates
+pub fn trace_model(models: &ModelContainer, name: &str) -> Vec<Value> {
+    let serialized = serde_json::to_value(models).unwrap_or_default();
+
+    serialized
+        .get("models")
+        .and_then(|m| m.as_array())
+        .map(|list| {
+            list.iter()
+                .filter(|model| {
+                    model.as_object().is_some_and(|o| {
+                        o.values()
+                            .any(|v| v.get("name").and_then(|n| n.as_str()) == Some(name))
+                    })
+                })
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profiler_shared_between_clones() {
+        let profiler = Profiler::default();
+        let cloned = profiler.clone();
+
+        for (template, millis) in [("a.j2", 1), ("b.j2", 5)] {
+            cloned.record(RenderProfile {
+                template: template.to_string(),
+                target: "out".to_string(),
+                duration: Duration::from_millis(millis),
+                context_size: 10,
+                output_size: 20,
+            });
+        }
+
+        assert_eq!(profiler.entries().len(), 2);
+        assert!(profiler.report().lines().nth(1).unwrap().contains("b.j2"));
+    }
+}
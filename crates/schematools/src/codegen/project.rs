@@ -0,0 +1,263 @@
+//! Project file (`schematools.yaml`) describing many codegen targets, so one command
+//! regenerates every client, server and model package of repository.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+
+use crate::error::Error;
+
+use super::builder::{Codegen, CodegenBuilder};
+
+pub const PROJECT_FILE: &str = "schematools.yaml";
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Project {
+    #[serde(default)]
+    pub targets: BTreeMap<String, Target>,
+
+    // directory of project file, relative paths of targets start there
+    #[serde(skip)]
+    pub root: PathBuf,
+}
+
+/// Codegen target, keys are named after flags of `codegen openapi` and `codegen jsonschema`
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Target {
+    #[serde(deserialize_with = "one_or_many")]
+    pub schema: Vec<String>,
+    #[serde(deserialize_with = "one_or_many")]
+    pub template: Vec<String>,
+    pub target_dir: Option<String>,
+    pub format: Option<String>,
+    pub post_hook: Option<String>,
+    pub hook_env: BTreeMap<String, String>,
+    pub options: BTreeMap<String, Value>,
+    pub content_type_preference: Vec<String>,
+    pub keep_schema: Vec<String>,
+    pub wrappers: bool,
+    pub nested_arrays_as_models: bool,
+    pub optional_and_nullable_as_models: bool,
+    pub collapse_single_variant: bool,
+    pub flatten_depth: Option<usize>,
+    pub strict: bool,
+    pub normalize: bool,
+    pub additional_properties_model: bool,
+    pub tri_state: bool,
+    pub builders_above: Option<usize>,
+    pub code_names: Option<String>,
+    pub decimal: bool,
+    pub temporal: bool,
+    pub examples: bool,
+    pub base_name: Option<String>,
+    pub allow_env: Vec<String>,
+    pub context_version: Option<u32>,
+    pub reproducible: bool,
+}
+
+impl Project {
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            Error::CodegenProjectError(format!("cannot read {}: {e}", path.display()))
+        })?;
+
+        let mut project: Project = serde_yaml::from_str(&content).map_err(|e| {
+            Error::CodegenProjectError(format!("cannot parse {}: {e}", path.display()))
+        })?;
+        project.root = path.parent().map(Path::to_path_buf).unwrap_or_default();
+
+        Ok(project)
+    }
+
+    /// Targets with given names in order of arguments, all targets ordered by name if none given
+    pub fn select(&self, names: &[String]) -> Result<Vec<(&str, &Target)>, Error> {
+        if names.is_empty() {
+            return Ok(self
+                .targets
+                .iter()
+                .map(|(name, target)| (name.as_str(), target))
+                .collect());
+        }
+
+        names
+            .iter()
+            .map(|name| {
+                self.targets
+                    .get_key_value(name)
+                    .map(|(name, target)| (name.as_str(), target))
+                    .ok_or_else(|| Error::CodegenProjectError(format!("unknown target {name}")))
+            })
+            .collect()
+    }
+
+    /// Builder of target with schemas, templates and target directory relative to project file
+    pub fn builder(&self, target: &Target) -> Result<CodegenBuilder, Error> {
+        if target.template.is_empty() {
+            return Err(Error::CodegenProjectError(
+                "template is required by every target".to_string(),
+            ));
+        }
+
+        let mut builder = Codegen::builder();
+
+        for schema in &target.schema {
+            builder.schema(&self.path(schema));
+        }
+
+        for template in &target.template {
+            // registries and archives are left untouched
+            if template.contains("::") || template.contains("://") {
+                builder.templates(template);
+            } else {
+                builder.templates(&self.path(template));
+            }
+        }
+
+        builder.target_dir(&self.path(target.target_dir.as_deref().unwrap_or(".")));
+
+        if let Some(format) = &target.format {
+            builder.format(format);
+        }
+
+        if let Some(post_hook) = &target.post_hook {
+            builder.post_hook(post_hook);
+        }
+
+        for (key, value) in &target.hook_env {
+            builder.env(key, value);
+        }
+
+        for (key, value) in &target.options {
+            builder.option(key, value.clone());
+        }
+
+        for pattern in &target.content_type_preference {
+            builder.content_type(pattern);
+        }
+
+        for name in &target.allow_env {
+            builder.allow_env(name);
+        }
+
+        builder
+            .with_keep_schema(target.keep_schema.clone())
+            .with_wrappers(target.wrappers)
+            .with_nested_arrays_as_models(target.nested_arrays_as_models)
+            .with_optional_and_nullable_as_models(target.optional_and_nullable_as_models)
+            .with_collapse_single_variant(target.collapse_single_variant)
+            .with_flatten_depth(target.flatten_depth)
+            .with_strict(target.strict)
+            .with_normalize(target.normalize)
+            .with_additional_properties_model(target.additional_properties_model)
+            .with_tri_state(target.tri_state)
+            .with_builder_threshold(target.builders_above)
+            .with_code_names(target.code_names.clone())
+            .with_decimal(target.decimal)
+            .with_temporal(target.temporal)
+            .with_examples(target.examples)
+            .with_base_name(target.base_name.clone())
+            .with_context_version(target.context_version)
+            .with_reproducible(target.reproducible);
+
+        Ok(builder)
+    }
+
+    fn path(&self, path: &str) -> String {
+        if path.starts_with("http") {
+            path.to_string()
+        } else {
+            self.root.join(path).to_string_lossy().to_string()
+        }
+    }
+}
+
+fn one_or_many<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(value) => vec![value],
+        OneOrMany::Many(values) => values,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_project() {
+        let path = std::env::temp_dir()
+            .join("schematools-project")
+            .join(PROJECT_FILE);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(
+            &path,
+            r#"
+targets:
+  rust-client:
+    schema: openapi.yaml
+    template: [templates/rust, shared::rust/]
+    target-dir: clients/rust
+    format: rustfmt --edition 2021
+    options:
+      crate: orders
+      async: true
+    wrappers: true
+    content-type-preference: [application/json]
+  models:
+    schema: [schemas/order.json, schemas/user.json]
+    template: templates/models
+"#,
+        )
+        .unwrap();
+
+        let project = Project::load(&path).unwrap();
+
+        let names = |targets: Vec<(&str, &Target)>| {
+            targets
+                .into_iter()
+                .map(|(name, _)| name.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            names(project.select(&[]).unwrap()),
+            ["models", "rust-client"]
+        );
+        assert_eq!(
+            names(project.select(&["rust-client".to_string()]).unwrap()),
+            ["rust-client"]
+        );
+        assert!(project.select(&["missing".to_string()]).is_err());
+
+        let rust = &project.targets["rust-client"];
+        assert_eq!(rust.schema, ["openapi.yaml"]);
+        assert_eq!(rust.template, ["templates/rust", "shared::rust/"]);
+        assert_eq!(rust.options["async"], Value::Bool(true));
+        assert!(rust.wrappers);
+        assert_eq!(project.targets["models"].schema.len(), 2);
+
+        assert!(project.builder(rust).is_ok());
+        assert!(project.builder(&Target::default()).is_err());
+
+        std::fs::write(&path, "targets:\n  rust:\n    unknown: true\n").unwrap();
+        assert!(matches!(
+            Project::load(&path),
+            Err(Error::CodegenProjectError(_))
+        ));
+    }
+}
@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+
+const KEEP_START: &str = "schematools:keep-start";
+const KEEP_END: &str = "schematools:keep-end";
+
+/// Splices regions between `schematools:keep-start` and `schematools:keep-end` markers
+/// from previously generated file into new output
+///
+/// Regions are matched by name following the start marker or by position if name is absent.
+/// Region content from new output is used when file didn't contain such region yet.
+pub fn preserve(existing: &str, generated: &str) -> String {
+    let regions = extract(existing);
+
+    if regions.is_empty() {
+        return generated.to_string();
+    }
+
+    let mut result = String::with_capacity(generated.len());
+    let mut index = 0;
+    let mut current: Option<String> = None;
+    let mut kept = false;
+
+    for line in generated.split_inclusive('\n') {
+        match &current {
+            None => {
+                if let Some(name) = marker(line, KEEP_START) {
+                    let name = name.unwrap_or_else(|| format!("#{index}"));
+                    index += 1;
+
+                    result.push_str(line);
+
+                    if let Some(content) = regions.get(&name) {
+                        result.push_str(content);
+                        kept = true;
+                    }
+
+                    current = Some(name);
+                } else {
+                    result.push_str(line);
+                }
+            }
+            Some(_) => {
+                if marker(line, KEEP_END).is_some() {
+                    result.push_str(line);
+                    current = None;
+                    kept = false;
+                } else if !kept {
+                    result.push_str(line);
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// Returns names of non-empty regions of previously generated file which are not present
+/// in new output, their content would be lost by [`preserve`]
+pub fn missing(existing: &str, generated: &str) -> Vec<String> {
+    let regions = extract(generated);
+
+    let mut names = extract(existing)
+        .into_iter()
+        .filter(|(name, content)| !content.trim().is_empty() && !regions.contains_key(name))
+        .map(|(name, _)| name)
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+}
+
+/// Returns names of non-empty regions of file, content of such regions is not generated
+pub fn kept(content: &str) -> Vec<String> {
+    let mut names = extract(content)
+        .into_iter()
+        .filter(|(_, content)| !content.trim().is_empty())
+        .map(|(name, _)| name)
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+}
+
+fn extract(content: &str) -> HashMap<String, String> {
+    let mut regions = HashMap::new();
+    let mut index = 0;
+    let mut current: Option<(String, String)> = None;
+
+    for line in content.split_inclusive('\n') {
+        match current.as_mut() {
+            None => {
+                if let Some(name) = marker(line, KEEP_START) {
+                    current = Some((name.unwrap_or_else(|| format!("#{index}")), String::new()));
+                    index += 1;
+                }
+            }
+            Some((name, body)) => {
+                if marker(line, KEEP_END).is_some() {
+                    regions.insert(name.clone(), body.clone());
+                    current = None;
+                } else {
+                    body.push_str(line);
+                }
+            }
+        }
+    }
+
+    regions
+}
+
+// returns region name if line contains marker, None as inner value if marker is unnamed
+fn marker(line: &str, marker: &str) -> Option<Option<String>> {
+    line.find(marker).map(|pos| {
+        line[pos + marker.len()..]
+            .split_whitespace()
+            .next()
+            .filter(|s| !["*/", "-->", "#}", "%}"].contains(s))
+            .map(|s| s.to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_regions() {
+        let existing = "// schematools:keep-start a
+x
+// schematools:keep-end
+// schematools:keep-start b
+use foo;
+// schematools:keep-end
+// schematools:keep-start c
+\n// schematools:keep-end
+";
+        let generated = "// schematools:keep-start a
+// schematools:keep-end
+";
+
+        assert_eq!(missing(existing, generated), vec!["b".to_string()]);
+        assert!(missing(existing, existing).is_empty());
+    }
+
+    #[test]
+    fn test_preserve_named_regions() {
+        let existing = "struct A;\n// schematools:keep-start imports\nuse foo;\n// schematools:keep-end\nold\n";
+        let generated =
+            "struct B;\n// schematools:keep-start imports\n// schematools:keep-end\nnew\n";
+
+        assert_eq!(
+            preserve(existing, generated),
+            "struct B;\n// schematools:keep-start imports\nuse foo;\n// schematools:keep-end\nnew\n"
+        );
+    }
+
+    #[test]
+    fn test_preserve_unnamed_regions_by_position() {
+        let existing = "/* schematools:keep-start */\na\n/* schematools:keep-end */\n/* schematools:keep-start */\nb\n/* schematools:keep-end */\n";
+        let generated = "/* schematools:keep-start */\n/* schematools:keep-end */\nx\n/* schematools:keep-start */\ndefault\n/* schematools:keep-end */\n";
+
+        assert_eq!(
+            preserve(existing, generated),
+            "/* schematools:keep-start */\na\n/* schematools:keep-end */\nx\n/* schematools:keep-start */\nb\n/* schematools:keep-end */\n"
+        );
+    }
+
+    #[test]
+    fn test_new_region_keeps_generated_content() {
+        let existing = "// schematools:keep-start a\nkept\n// schematools:keep-end\n";
+        let generated = "// schematools:keep-start a\n// schematools:keep-end\n// schematools:keep-start b\ndefault\n// schematools:keep-end\n";
+
+        assert_eq!(
+            preserve(existing, generated),
+            "// schematools:keep-start a\nkept\n// schematools:keep-end\n// schematools:keep-start b\ndefault\n// schematools:keep-end\n"
+        );
+    }
+}
@@ -1,3 +1,6 @@
+use std::sync::Arc;
+
+use super::{functions::ModelsSlot, hooks::Hooks};
 use crate::{discovery::Discovered, error::Error};
 use tera::Tera;
 
@@ -5,14 +8,29 @@ pub struct Renderer {
     pub tera: Tera,
     pub templates: super::templates::Templates,
     pub container: super::CodegenContainer,
+    pub id: String,
+    models: ModelsSlot,
 }
 
 // todo: refactor, it should allocate templates only once if same templates are used
 pub fn create(
     discovered: Discovered,
     required: &[super::templates::TemplateType],
-    container: super::CodegenContainer,
+    mut container: super::CodegenContainer,
 ) -> Result<Renderer, Error> {
+    if let Some(version) = container.context_version {
+        super::context::check(version)?;
+    }
+
+    if let Some(spec) = super::options::OptionsSpec::from_discovered(&discovered)? {
+        spec.apply(&mut container)?;
+    }
+
+    // manifest entries of the same templates are replaced, even if their files changed
+    let mut sources = discovered.sources.clone();
+    sources.sort();
+    let id = sources.join(", ");
+
     let mut tera = Tera::default();
 
     // todo: more borrowing, less allocating
@@ -20,6 +38,15 @@ pub fn create(
         .map_err(Error::CodegenTemplatesParseError)?;
 
     super::filters::register(&mut tera);
+    super::functions::register(
+        &mut tera,
+        discovered.data.clone(),
+        container.allowed_env.clone(),
+    );
+
+    // models are known only during rendering, lookup reads the ones of current call
+    let models = ModelsSlot::default();
+    super::functions::register_models(&mut tera, models.clone());
 
     let templates = super::templates::get(discovered)?;
     if !templates.includes(required) {
@@ -30,16 +57,19 @@ pub fn create(
         tera,
         templates,
         container,
+        id,
+        models,
     })
 }
 
 impl Renderer {
     pub fn models(
         &self,
-        models: super::jsonschema::ModelContainer,
+        models: Arc<super::jsonschema::ModelContainer>,
         target_dir: &str,
-        format: &Option<String>,
-    ) -> Result<(), Error> {
+        hooks: &Hooks,
+    ) -> Result<Vec<String>, Error> {
+        let mut generated = vec![];
         let files = self
             .templates
             .list
@@ -52,9 +82,11 @@ impl Renderer {
             })
             .collect::<Vec<_>>();
 
-        for template in files {
+        let _models = self.use_models(models.clone());
+
+        for template in &files {
             let files = match template {
-                super::templates::Template::File(t) => t.copy(target_dir),
+                super::templates::Template::File(t) => t.copy(target_dir, &self.container),
                 super::templates::Template::Models(t) => {
                     t.render(&self.tera, target_dir, &models, &self.container)
                 }
@@ -64,25 +96,31 @@ impl Renderer {
                 _ => Ok(vec![]),
             }?;
 
-            if let Some(command) = format {
-                template.format(command, files)?
-            }
+            template.hooks().merge(hooks).format(&files)?;
+
+            generated.extend(files);
         }
 
-        Ok(())
+        self.post_hooks(files.into_iter(), hooks, &generated)?;
+        super::manifest::process(target_dir, &self.id, &generated)?;
+
+        Ok(generated)
     }
 
     pub fn openapi(
         &self,
-        openapi: super::openapi::Openapi,
+        mut openapi: super::openapi::Openapi,
         target_dir: &str,
-        format: &Option<String>,
-    ) -> Result<(), Error> {
+        hooks: &Hooks,
+    ) -> Result<Vec<String>, Error> {
+        openapi.apply_credentials(&self.container.options)?;
+
         let mut files: Vec<Vec<String>> = vec![];
+        let _models = self.use_models(openapi.models.clone());
 
         for template in &self.templates.list {
             files.push(match template {
-                super::templates::Template::File(t) => t.copy(target_dir),
+                super::templates::Template::File(t) => t.copy(target_dir, &self.container),
                 super::templates::Template::Static(t) => {
                     t.render(&self.tera, target_dir, &self.container)
                 }
@@ -92,20 +130,58 @@ impl Renderer {
                 super::templates::Template::Tags(t) => {
                     t.render(&self.tera, target_dir, &openapi, &self.container)
                 }
+                super::templates::Template::Routes(t) => {
+                    t.render(&self.tera, target_dir, &openapi, &self.container)
+                }
+                super::templates::Template::Tests(t) => {
+                    t.render(&self.tera, target_dir, &openapi, &self.container)
+                }
+                super::templates::Template::Commands(t) => {
+                    t.render(&self.tera, target_dir, &openapi, &self.container)
+                }
                 super::templates::Template::Models(t) => {
                     t.render(&self.tera, target_dir, &openapi.models, &self.container)
                 }
             }?);
         }
 
-        if let Some(command) = format {
-            for (i, list) in files.iter().enumerate() {
-                let template = &self.templates.list.get(i).unwrap();
+        for (i, list) in files.iter().enumerate() {
+            let template = &self.templates.list.get(i).unwrap();
 
-                template.format(command, list.clone())?
-            }
+            template.hooks().merge(hooks).format(list)?
         }
 
-        Ok(())
+        let generated = files.into_iter().flatten().collect::<Vec<_>>();
+
+        self.post_hooks(self.templates.list.iter(), hooks, &generated)?;
+        super::manifest::process(target_dir, &self.id, &generated)?;
+
+        Ok(generated)
+    }
+
+    // models are released by returned guard when rendering is finished
+    fn use_models(&self, models: Arc<super::jsonschema::ModelContainer>) -> ModelsGuard<'_> {
+        *self.models.write().unwrap() = Some(models);
+        ModelsGuard(&self.models)
+    }
+
+    fn post_hooks<'a>(
+        &self,
+        templates: impl Iterator<Item = &'a super::templates::Template>,
+        hooks: &Hooks,
+        generated: &[String],
+    ) -> Result<(), Error> {
+        let mut list = vec![hooks.clone()];
+        list.extend(templates.map(|t| t.hooks().merge(hooks)));
+
+        super::hooks::post_hooks(&list, generated)
+    }
+}
+
+struct ModelsGuard<'a>(&'a ModelsSlot);
+
+impl Drop for ModelsGuard<'_> {
+    fn drop(&mut self) {
+        *self.0.write().unwrap() = None;
     }
 }
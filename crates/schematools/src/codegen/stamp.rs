@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::schema::Schema;
+use crate::tools::timestamp;
+
+use super::CodegenContainer;
+
+/// Provenance of generated code available as `stamp` in render context
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Stamp {
+    // info.version of openapi specification
+    pub version: Option<String>,
+    // md5 of specification content
+    pub digest: String,
+    // rfc3339 time of generation, omitted in reproducible builds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generated_at: Option<String>,
+}
+
+impl Stamp {
+    pub fn new(schema: &Schema) -> Self {
+        let body = schema.get_body();
+
+        Self {
+            version: body
+                .pointer("/info/version")
+                .and_then(Value::as_str)
+                .map(String::from),
+            digest: format!("{:x}", md5::compute(body.to_string())),
+            generated_at: None,
+        }
+    }
+
+    /// Sets generation time to current time unless build has to be reproducible
+    pub fn generated(mut self, reproducible: bool) -> Self {
+        self.generated_at = (!reproducible).then(|| {
+            timestamp(
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_secs())
+                    .unwrap_or_default(),
+            )
+        });
+        self
+    }
+
+    pub fn apply(&self, container: &mut CodegenContainer) {
+        container.data.insert(
+            "stamp".to_string(),
+            serde_json::to_value(self).unwrap_or_default(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timestamp() {
+        assert_eq!(timestamp(0), "1970-01-01T00:00:00Z");
+        assert_eq!(timestamp(951782400), "2000-02-29T00:00:00Z");
+        assert_eq!(timestamp(1700000000), "2023-11-14T22:13:20Z");
+    }
+
+    #[test]
+    fn test_stamp() {
+        let schema = Schema::from_json(serde_json::json!({
+            "openapi": "3.0.0",
+            "info": {"title": "test", "version": "2.1.0"},
+            "paths": {}
+        }));
+
+        let stamp = Stamp::new(&schema);
+        assert_eq!(stamp.version.as_deref(), Some("2.1.0"));
+        assert_eq!(stamp.digest.len(), 32);
+        assert_eq!(stamp.clone().generated(true), stamp);
+        assert!(stamp.generated(false).generated_at.is_some());
+    }
+}
@@ -4,10 +4,40 @@ use tera::Context;
 use tera::Tera;
 
 use crate::{discovery::Discovered, error::Error, tools};
-use std::{collections::HashMap, fs::File, io::Write, path::PathBuf, process::Command};
+use std::{collections::HashMap, fs::File, io::Write, path::PathBuf, time::Instant};
 
-use super::openapi::Openapi;
+use super::{hooks::Hooks, openapi::Openapi};
 use inflector::Inflector;
+use regex::Regex;
+
+// line starting new output file when rendered by template
+const FILE_MARKER: &str = "@schematools:file ";
+
+const TEMPLATE_TYPES: [&str; 7] = [
+    "endpoints",
+    "models",
+    "tags",
+    "routes",
+    "tests",
+    "commands",
+    "static",
+];
+
+// allowed header options with template types supporting them (empty means all)
+const HEADER_OPTIONS: [(&str, &[&str]); 9] = [
+    ("type", &[]),
+    ("filename", &[]),
+    ("min_version", &[]),
+    ("if", &[]),
+    ("content_type", &["endpoints", "tags", "tests", "commands"]),
+    ("group_by", &["endpoints"]),
+    ("format", &[]),
+    ("post_hook", &[]),
+    ("env", &[]),
+];
+
+// header options which accept list of values separated by `;`
+const MULTI_VALUE_OPTIONS: [&str; 1] = ["env"];
 
 #[derive(Debug)]
 pub struct Templates {
@@ -19,6 +49,9 @@ pub enum Template {
     Models(ModelsTemplate),
     Endpoints(EndpointsTemplate),
     Tags(TagsTemplate),
+    Routes(RoutesTemplate),
+    Tests(TestsTemplate),
+    Commands(CommandsTemplate),
     Static(StaticTemplate),
     File(FileTemplate),
 }
@@ -30,6 +63,7 @@ pub struct EndpointsTemplate {
     content_type: String,
     condition: Option<Condition>,
     group_by: GroupBy,
+    hooks: Hooks,
 }
 
 #[derive(Debug)]
@@ -38,6 +72,7 @@ pub struct TagsTemplate {
     filename: Filename,
     content_type: String,
     condition: Option<Condition>,
+    hooks: Hooks,
 }
 
 #[derive(Debug)]
@@ -45,6 +80,33 @@ pub struct ModelsTemplate {
     relative: PathBuf,
     filename: Filename,
     condition: Option<Condition>,
+    hooks: Hooks,
+}
+
+#[derive(Debug)]
+pub struct RoutesTemplate {
+    relative: PathBuf,
+    filename: Filename,
+    condition: Option<Condition>,
+    hooks: Hooks,
+}
+
+#[derive(Debug)]
+pub struct TestsTemplate {
+    relative: PathBuf,
+    filename: Filename,
+    content_type: String,
+    condition: Option<Condition>,
+    hooks: Hooks,
+}
+
+#[derive(Debug)]
+pub struct CommandsTemplate {
+    relative: PathBuf,
+    filename: Filename,
+    content_type: String,
+    condition: Option<Condition>,
+    hooks: Hooks,
 }
 
 #[derive(Debug)]
@@ -52,6 +114,7 @@ pub struct StaticTemplate {
     relative: PathBuf,
     filename: Filename,
     condition: Option<Condition>,
+    hooks: Hooks,
 }
 
 #[derive(Debug)]
@@ -64,25 +127,32 @@ pub struct GroupBy {
     pub kind: Option<String>,
 }
 #[derive(Serialize)]
-pub struct TagContainer {
+pub struct TagContainer<'a> {
     tag: String,
-    endpoints: Vec<super::openapi::endpoint::Endpoint>,
+    endpoints: Vec<&'a super::openapi::endpoint::Endpoint>,
 }
 
 pub trait Group {
-    fn process(&self, openapi: &mut Openapi, container: &mut super::CodegenContainer);
+    fn process(&self, container: &mut super::CodegenContainer);
+
+    fn includes(&self, endpoint: &super::openapi::endpoint::Endpoint) -> bool;
 }
 
 #[derive(Debug)]
 pub struct FileTemplate {
     relative: String,
     path: PathBuf,
+    filename: Filename,
+    condition: Option<Condition>,
 }
 
 #[derive(Eq, PartialEq, Debug)]
 pub enum TemplateType {
     Models,
     Endpoints,
+    Routes,
+    Tests,
+    Commands,
 }
 
 #[derive(Debug, Clone)]
@@ -96,7 +166,14 @@ impl Filename {
     }
 
     pub fn resolve(&self, container: &super::CodegenContainer) -> Result<String, Error> {
-        tools::fill_parameters(&self.filename, container)
+        lazy_static! {
+            static ref OPTION_PLACEHOLDER: Regex = Regex::new(r"\{\{\s*([\w.\-]+)\s*\}\}").unwrap();
+        }
+
+        // {{name}} is a shorthand of %options.name%
+        let filename = OPTION_PLACEHOLDER.replace_all(&self.filename, "%options.$1%");
+
+        tools::fill_parameters(&filename, container)
     }
 }
 
@@ -121,22 +198,26 @@ impl Condition {
 
 impl GroupBy {
     pub fn from(group_by: &str) -> Result<Self, Error> {
-        if group_by != "tag" {
-            Err(Error::CodegenNotAllowedGroupBy(group_by.to_string()))
-        } else {
+        if group_by == "tag" || group_by.strip_prefix("x-").is_some_and(|s| !s.is_empty()) {
             Ok(Self {
                 kind: Some(group_by.to_string()),
             })
+        } else {
+            Err(Error::CodegenNotAllowedGroupBy(group_by.to_string()))
         }
     }
 
-    pub fn split(&self, openapi: &Openapi) -> impl IntoIterator<Item = impl Group> {
-        match &self.kind {
-            Some(_) => TagGroup::produce(openapi)
+    pub fn split(&self, openapi: &Openapi) -> Result<Vec<GroupType>, Error> {
+        match self.kind.as_deref() {
+            Some("tag") => Ok(TagGroup::produce(openapi)
                 .into_iter()
                 .map(GroupType::TagGroup)
-                .collect::<Vec<_>>(),
-            None => vec![GroupType::NoGroup],
+                .collect()),
+            Some(kind) => Ok(ExtensionGroup::produce(openapi, &kind[2..])?
+                .into_iter()
+                .map(GroupType::ExtensionGroup)
+                .collect()),
+            None => Ok(vec![GroupType::NoGroup]),
         }
     }
 }
@@ -145,15 +226,15 @@ pub struct TagGroup {
 }
 
 impl Group for TagGroup {
-    fn process(&self, openapi: &mut Openapi, container: &mut super::CodegenContainer) {
+    fn process(&self, container: &mut super::CodegenContainer) {
         container.data.insert(
             "tag".to_string(),
             Value::String(self.tag.clone().to_pascal_case()),
         );
+    }
 
-        openapi
-            .endpoints
-            .retain(|s| s.get_tags().contains(&self.tag));
+    fn includes(&self, endpoint: &super::openapi::endpoint::Endpoint) -> bool {
+        endpoint.get_tags().contains(&self.tag)
     }
 }
 
@@ -172,30 +253,91 @@ impl TagGroup {
             .collect::<Vec<_>>()
     }
 
-    pub fn filter(
+    pub fn filter<'a>(
         &self,
-        endpoints: &[super::openapi::endpoint::Endpoint],
-    ) -> Vec<super::openapi::endpoint::Endpoint> {
-        endpoints
+        endpoints: &'a [super::openapi::endpoint::Endpoint],
+    ) -> Vec<&'a super::openapi::endpoint::Endpoint> {
+        endpoints.iter().filter(|e| self.includes(e)).collect()
+    }
+}
+
+/// Groups endpoints by value of operation extension, ex. x-service
+pub struct ExtensionGroup {
+    name: String,
+    value: String,
+}
+
+impl Group for ExtensionGroup {
+    fn process(&self, container: &mut super::CodegenContainer) {
+        container.data.insert(
+            "group".to_string(),
+            Value::String(self.value.clone().to_pascal_case()),
+        );
+        container
+            .data
+            .insert(self.name.clone(), Value::String(self.value.clone()));
+    }
+
+    fn includes(&self, endpoint: &super::openapi::endpoint::Endpoint) -> bool {
+        endpoint.get_extension(&self.name).and_then(|v| v.as_str()) == Some(self.value.as_str())
+    }
+}
+
+impl ExtensionGroup {
+    // every operation has to declare the extension
+    pub fn produce(openapi: &Openapi, name: &str) -> Result<Vec<ExtensionGroup>, Error> {
+        let mut values = openapi
+            .endpoints
             .iter()
-            .filter(|e| e.get_tags().contains(&self.tag))
-            .cloned()
-            .collect()
+            .map(|e| {
+                e.get_extension(name)
+                    .and_then(|v| v.as_str())
+                    .map(|v| v.to_string())
+                    .ok_or_else(|| {
+                        Error::CodegenGroupByError(format!(
+                            "operation {} doesn't have string x-{} extension",
+                            e.get_operation(),
+                            name
+                        ))
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        values.sort();
+        values.dedup();
+
+        Ok(values
+            .into_iter()
+            .map(|value| ExtensionGroup {
+                name: name.to_string(),
+                value,
+            })
+            .collect())
     }
 }
 
 pub enum GroupType {
     TagGroup(TagGroup),
+    ExtensionGroup(ExtensionGroup),
     NoGroup,
 }
 
 impl Group for GroupType {
-    fn process(&self, openapi: &mut Openapi, container: &mut super::CodegenContainer) {
+    fn process(&self, container: &mut super::CodegenContainer) {
         match &self {
-            Self::TagGroup(t) => t.process(openapi, container),
+            Self::TagGroup(t) => t.process(container),
+            Self::ExtensionGroup(e) => e.process(container),
             Self::NoGroup => {}
         }
     }
+
+    fn includes(&self, endpoint: &super::openapi::endpoint::Endpoint) -> bool {
+        match &self {
+            Self::TagGroup(t) => t.includes(endpoint),
+            Self::ExtensionGroup(e) => e.includes(endpoint),
+            Self::NoGroup => true,
+        }
+    }
 }
 
 impl Templates {
@@ -205,6 +347,9 @@ impl Templates {
             .filter_map(|t| match *t {
                 Template::Models(_) => Some(TemplateType::Models),
                 Template::Endpoints(_) => Some(TemplateType::Endpoints),
+                Template::Routes(_) => Some(TemplateType::Routes),
+                Template::Tests(_) => Some(TemplateType::Tests),
+                Template::Commands(_) => Some(TemplateType::Commands),
                 _ => None,
             })
             .filter(|f| types.contains(f))
@@ -214,14 +359,43 @@ impl Templates {
 }
 
 impl Template {
-    fn from_file(relative: String, path: PathBuf) -> Self {
-        Template::File(FileTemplate { relative, path })
+    fn from_file(
+        relative: String,
+        path: PathBuf,
+        spec: Option<&super::options::FileSpec>,
+    ) -> Result<Self, Error> {
+        let filename = Filename::from(
+            spec.and_then(|s| s.target.clone())
+                .unwrap_or_else(|| relative.clone()),
+        );
+
+        let condition = spec
+            .and_then(|s| s.condition.as_ref())
+            .map(|s| Condition::from(s))
+            .map_or(Ok(None), |v| v.map(Some))?;
+
+        Ok(Template::File(FileTemplate {
+            relative,
+            path,
+            filename,
+            condition,
+        }))
     }
 
     fn from_content(relative: String, content: String) -> Result<Self, Error> {
-        let first = content.lines().next();
-
-        if let Some(line) = first {
+        // header is the first non-empty line of template
+        let first = content
+            .lines()
+            .enumerate()
+            .find(|(_, line)| !line.trim().is_empty());
+        let number = first.map_or(1, |(index, _)| index + 1);
+        let header_error = |message: String| Error::CodegenFileHeaderParseError {
+            file: relative.clone(),
+            line: number,
+            message,
+        };
+
+        if let Some((_, line)) = first {
             let mut first_line = line.to_string();
 
             let last_hash = first_line
@@ -234,10 +408,14 @@ impl Template {
                 return Err(Error::CodegenFileSkipped);
             }
 
-            let params = super::format(first_line.trim_matches(&['{', '}', '#', ' '] as &[_]))?;
+            let params = super::format(first_line.trim_matches(&['{', '}', '#', ' '] as &[_]))
+                .map_err(header_error)?;
+
+            let type_ = validate_header(&params).map_err(header_error)?;
 
             if let Some(serde_json::Value::String(min_version)) = params.get("min_version") {
-                let min = semver::Version::parse(min_version).map_err(Error::SemVersion)?;
+                let min = semver::Version::parse(min_version)
+                    .map_err(|e| header_error(format!("invalid min_version {min_version}: {e}")))?;
                 let current = semver::Version::parse(crate::VERSION).unwrap();
 
                 let req = semver::VersionReq::parse(
@@ -251,45 +429,32 @@ impl Template {
                 return Err(Error::MissingMinVersionError(relative));
             }
 
-            params
-                .get("type")
-                .ok_or_else(|| Error::CodegenFileHeaderRequired("type".to_string()))?
-                .as_str()
-                .map(|type_| match type_ {
-                    "endpoints" => EndpointsTemplate::from(PathBuf::from(relative), &params),
-                    "models" => ModelsTemplate::from(PathBuf::from(relative), &params),
-                    "tags" => TagsTemplate::from(PathBuf::from(relative), &params),
-                    "static" => StaticTemplate::from(PathBuf::from(relative), &params),
-                    _ => Err(Error::CodegenFileHeaderRequired("type".to_string())),
-                })
-                .unwrap()
+            match type_.as_str() {
+                "endpoints" => EndpointsTemplate::from(PathBuf::from(relative), &params),
+                "models" => ModelsTemplate::from(PathBuf::from(relative), &params),
+                "tags" => TagsTemplate::from(PathBuf::from(relative), &params),
+                "routes" => RoutesTemplate::from(PathBuf::from(relative), &params),
+                "tests" => TestsTemplate::from(PathBuf::from(relative), &params),
+                "commands" => CommandsTemplate::from(PathBuf::from(relative), &params),
+                "static" => StaticTemplate::from(PathBuf::from(relative), &params),
+                _ => Err(Error::CodegenFileHeaderRequired("type".to_string())),
+            }
         } else {
             Err(Error::CodegenFileSkipped)
         }
     }
 
-    pub fn format(&self, command: &str, files: Vec<String>) -> Result<(), Error> {
-        let parts = crate::tools::ArgumentsExtractor::new(command).collect::<Vec<String>>();
-
-        for file in files {
-            let mut cmd = Command::new(parts.first().unwrap());
-            for i in 1..parts.len() {
-                cmd.arg(parts.get(i).unwrap());
-            }
-
-            let output = cmd
-                .arg(file)
-                .output()
-                .map_err(Error::CodegenFormattingError)?;
-
-            if !output.status.success() {
-                return Err(Error::CodegenFormattingCommandError(
-                    String::from_utf8_lossy(&output.stderr).to_string(),
-                ));
-            }
+    pub fn hooks(&self) -> Hooks {
+        match self {
+            Template::Models(t) => t.hooks.clone(),
+            Template::Endpoints(t) => t.hooks.clone(),
+            Template::Tags(t) => t.hooks.clone(),
+            Template::Routes(t) => t.hooks.clone(),
+            Template::Tests(t) => t.hooks.clone(),
+            Template::Commands(t) => t.hooks.clone(),
+            Template::Static(t) => t.hooks.clone(),
+            Template::File(_) => Hooks::default(),
         }
-
-        Ok(())
     }
 }
 
@@ -313,6 +478,7 @@ impl StaticTemplate {
             relative,
             filename,
             condition,
+            hooks: Hooks::from_header(config)?,
         }))
     }
 
@@ -379,6 +545,7 @@ impl EndpointsTemplate {
             content_type,
             condition,
             group_by,
+            hooks: Hooks::from_header(config)?,
         }))
     }
 
@@ -391,18 +558,19 @@ impl EndpointsTemplate {
     ) -> Result<Vec<String>, Error> {
         let mut result = vec![];
 
-        for group in self.group_by.split(openapi) {
-            // prepare per group structures
-            let mut openapi = openapi.clone().set_content_type(&self.content_type);
+        let formats = serde_json::to_value(openapi.models.formats()).unwrap();
+
+        for group in self.group_by.split(openapi)? {
+            // prepare per group structures, models and endpoints of group are borrowed
+            let view = openapi.view(&self.content_type, |e| group.includes(e));
             let mut container = container.clone();
 
-            container.data.insert(
-                "formats".to_string(),
-                serde_json::to_value(openapi.models.formats()).unwrap(),
-            );
+            container
+                .data
+                .insert("formats".to_string(), formats.clone());
 
             // process group
-            group.process(&mut openapi, &mut container);
+            group.process(&mut container);
 
             if self
                 .condition
@@ -413,7 +581,7 @@ impl EndpointsTemplate {
                 // render
                 result.append(&mut process_render(
                     tera,
-                    openapi,
+                    &view,
                     PathBuf::from(format!(
                         "{}/{}",
                         target_dir,
@@ -457,6 +625,7 @@ impl TagsTemplate {
             filename,
             content_type,
             condition,
+            hooks: Hooks::from_header(config)?,
         }))
     }
 
@@ -470,7 +639,7 @@ impl TagsTemplate {
         let groups = TagGroup::produce(openapi);
 
         let mut tags: Vec<TagContainer> = vec![];
-        let mut processed = openapi.clone().set_content_type(&self.content_type);
+        let processed = openapi.view(&self.content_type, |_| false);
         let mut container = container.clone();
 
         for group in groups {
@@ -480,8 +649,6 @@ impl TagsTemplate {
             })
         }
 
-        processed.endpoints = vec![];
-
         container
             .data
             .insert("tags".to_string(), serde_json::to_value(tags).unwrap());
@@ -500,7 +667,149 @@ impl TagsTemplate {
             // render
             process_render(
                 tera,
-                processed,
+                &processed,
+                PathBuf::from(format!(
+                    "{}/{}",
+                    target_dir,
+                    self.filename.resolve(&container)?
+                )),
+                self.relative.clone(),
+                &container,
+            )
+        } else {
+            log::info!("Template skipped due to condition: {:?}", self.relative);
+            Ok(vec![])
+        }
+    }
+}
+
+impl RoutesTemplate {
+    pub fn from(relative: PathBuf, config: &HashMap<&str, Value>) -> Result<Template, Error> {
+        let filename = Filename::from(
+            config
+                .get("filename")
+                .ok_or_else(|| Error::CodegenFileHeaderRequired("filename".to_string()))?
+                .as_str()
+                .unwrap()
+                .to_string(),
+        );
+
+        let condition = config
+            .get("if")
+            .map(|s| Condition::from(s.as_str().unwrap()))
+            .map_or(Ok(None), |v| v.map(Some))?;
+
+        Ok(Template::Routes(Self {
+            relative,
+            filename,
+            condition,
+            hooks: Hooks::from_header(config)?,
+        }))
+    }
+
+    pub fn render(
+        &self,
+        tera: &Tera,
+        target_dir: &str,
+        openapi: &super::openapi::Openapi,
+        container: &super::CodegenContainer,
+    ) -> Result<Vec<String>, Error> {
+        if self
+            .condition
+            .as_ref()
+            .map(|s| s.check(container))
+            .unwrap_or(true)
+        {
+            // routing table only, models are not part of context
+            let routes = openapi
+                .endpoints
+                .iter()
+                .map(|e| e.route())
+                .collect::<Vec<_>>();
+
+            process_render(
+                tera,
+                serde_json::json!({ "routes": routes }),
+                PathBuf::from(format!(
+                    "{}/{}",
+                    target_dir,
+                    self.filename.resolve(container)?
+                )),
+                self.relative.clone(),
+                container,
+            )
+        } else {
+            log::info!("Template skipped due to condition: {:?}", self.relative);
+
+            Ok(vec![])
+        }
+    }
+}
+
+impl TestsTemplate {
+    pub fn from(relative: PathBuf, config: &HashMap<&str, Value>) -> Result<Template, Error> {
+        let filename = Filename::from(
+            config
+                .get("filename")
+                .ok_or_else(|| Error::CodegenFileHeaderRequired("filename".to_string()))?
+                .as_str()
+                .unwrap()
+                .to_string(),
+        );
+
+        let content_type = config
+            .get("content_type")
+            .map(|s| s.as_str().unwrap().to_string())
+            .unwrap_or_else(|| "application/json".to_string());
+
+        let condition = config
+            .get("if")
+            .map(|s| Condition::from(s.as_str().unwrap()))
+            .map_or(Ok(None), |v| v.map(Some))?;
+
+        Ok(Template::Tests(Self {
+            relative,
+            filename,
+            content_type,
+            condition,
+            hooks: Hooks::from_header(config)?,
+        }))
+    }
+
+    pub fn render(
+        &self,
+        tera: &Tera,
+        target_dir: &str,
+        openapi: &super::openapi::Openapi,
+        container: &super::CodegenContainer,
+    ) -> Result<Vec<String>, Error> {
+        let view = openapi.view(&self.content_type, |_| true);
+        let mut container = container.clone();
+
+        let tests = view
+            .endpoints
+            .iter()
+            .flat_map(|e| e.test_cases(&openapi.models, Some(view.content_type)))
+            .collect::<Vec<_>>();
+
+        container
+            .data
+            .insert("tests".to_string(), serde_json::to_value(tests).unwrap());
+
+        container.data.insert(
+            "formats".to_string(),
+            serde_json::to_value(openapi.models.formats()).unwrap(),
+        );
+
+        if self
+            .condition
+            .as_ref()
+            .map(|s| s.check(&container))
+            .unwrap_or(true)
+        {
+            process_render(
+                tera,
+                &view,
                 PathBuf::from(format!(
                     "{}/{}",
                     target_dir,
@@ -516,6 +825,77 @@ impl TagsTemplate {
     }
 }
 
+impl CommandsTemplate {
+    pub fn from(relative: PathBuf, config: &HashMap<&str, Value>) -> Result<Template, Error> {
+        let filename = Filename::from(
+            config
+                .get("filename")
+                .ok_or_else(|| Error::CodegenFileHeaderRequired("filename".to_string()))?
+                .as_str()
+                .unwrap()
+                .to_string(),
+        );
+
+        let content_type = config
+            .get("content_type")
+            .map(|s| s.as_str().unwrap().to_string())
+            .unwrap_or_else(|| "application/json".to_string());
+
+        let condition = config
+            .get("if")
+            .map(|s| Condition::from(s.as_str().unwrap()))
+            .map_or(Ok(None), |v| v.map(Some))?;
+
+        Ok(Template::Commands(Self {
+            relative,
+            filename,
+            content_type,
+            condition,
+            hooks: Hooks::from_header(config)?,
+        }))
+    }
+
+    pub fn render(
+        &self,
+        tera: &Tera,
+        target_dir: &str,
+        openapi: &super::openapi::Openapi,
+        container: &super::CodegenContainer,
+    ) -> Result<Vec<String>, Error> {
+        if self
+            .condition
+            .as_ref()
+            .map(|s| s.check(container))
+            .unwrap_or(true)
+        {
+            let view = openapi.view(&self.content_type, |_| true);
+
+            // subcommands and security only, models are flattened into flags
+            let commands = super::openapi::commands::commands(
+                view.endpoints,
+                &openapi.models,
+                Some(&self.content_type),
+            );
+
+            process_render(
+                tera,
+                serde_json::json!({ "commands": commands, "security": openapi.security }),
+                PathBuf::from(format!(
+                    "{}/{}",
+                    target_dir,
+                    self.filename.resolve(container)?
+                )),
+                self.relative.clone(),
+                container,
+            )
+        } else {
+            log::info!("Template skipped due to condition: {:?}", self.relative);
+
+            Ok(vec![])
+        }
+    }
+}
+
 impl ModelsTemplate {
     pub fn from(relative: PathBuf, config: &HashMap<&str, Value>) -> Result<Template, Error> {
         let filename = Filename::from(
@@ -536,6 +916,7 @@ impl ModelsTemplate {
             relative,
             filename,
             condition,
+            hooks: Hooks::from_header(config)?,
         }))
     }
 
@@ -572,8 +953,27 @@ impl ModelsTemplate {
 }
 
 impl FileTemplate {
-    pub fn copy(&self, target_dir: &str) -> Result<Vec<String>, Error> {
-        let target = PathBuf::from(format!("{}/{}", target_dir, self.relative));
+    pub fn copy(
+        &self,
+        target_dir: &str,
+        container: &super::CodegenContainer,
+    ) -> Result<Vec<String>, Error> {
+        if !self
+            .condition
+            .as_ref()
+            .map(|s| s.check(container))
+            .unwrap_or(true)
+        {
+            log::info!("File skipped due to condition: {:?}", self.relative);
+
+            return Ok(vec![]);
+        }
+
+        let target = PathBuf::from(format!(
+            "{}/{}",
+            target_dir,
+            self.filename.resolve(container)?
+        ));
 
         log::info!("Copying: {:?}", target);
 
@@ -592,12 +992,17 @@ impl FileTemplate {
 
 pub fn get(discovered: Discovered) -> Result<Templates, Error> {
     let mut list: Vec<Template> = vec![];
+    let spec = super::options::OptionsSpec::from_discovered(&discovered)?.unwrap_or_default();
 
     for (relative, content) in discovered.templates {
         let result = Template::from_content(relative.clone(), content);
 
         match result {
             Ok(template) => {
+                if discovered.untrusted.contains(&relative) && !template.hooks().is_empty() {
+                    return Err(Error::CodegenUntrustedHooks(relative));
+                }
+
                 list.push(template);
             }
             Err(err) => match err {
@@ -611,7 +1016,12 @@ pub fn get(discovered: Discovered) -> Result<Templates, Error> {
     }
 
     for (relative, path) in discovered.files {
-        list.push(Template::from_file(relative, path))
+        if relative == super::options::OPTIONS_FILE {
+            continue;
+        }
+
+        let file = spec.files.get(&relative);
+        list.push(Template::from_file(relative, path, file)?)
     }
 
     if list.is_empty() {
@@ -621,40 +1031,571 @@ pub fn get(discovered: Discovered) -> Result<Templates, Error> {
     Ok(Templates { list })
 }
 
+fn validate_header(params: &HashMap<&str, Value>) -> Result<String, String> {
+    let type_ = match params.get("type") {
+        Some(Value::String(s)) if TEMPLATE_TYPES.contains(&s.as_str()) => s.clone(),
+        Some(Value::String(s)) => {
+            return Err(with_suggestion(
+                format!("unknown template type `{s}`"),
+                s,
+                TEMPLATE_TYPES.iter().copied(),
+            ))
+        }
+        Some(_) => return Err("option `type` has to be a single value".to_string()),
+        None => return Err("option `type` is required".to_string()),
+    };
+
+    let mut keys = params.keys().collect::<Vec<_>>();
+    keys.sort();
+
+    for key in keys {
+        let option = HEADER_OPTIONS.iter().find(|(name, _)| name == key);
+
+        match option {
+            None => {
+                return Err(with_suggestion(
+                    format!("unknown option `{key}`"),
+                    key,
+                    HEADER_OPTIONS.iter().map(|(name, _)| *name),
+                ))
+            }
+            Some((_, types)) if !types.is_empty() && !types.contains(&type_.as_str()) => {
+                return Err(format!(
+                    "option `{key}` is not supported by `{type_}` templates"
+                ))
+            }
+            _ => {}
+        }
+
+        if !params[key].is_string() && !MULTI_VALUE_OPTIONS.contains(key) {
+            return Err(format!("option `{key}` has to be a single value"));
+        }
+    }
+
+    if !params.contains_key("filename") {
+        return Err("option `filename` is required".to_string());
+    }
+
+    Ok(type_)
+}
+
+fn with_suggestion<'a>(
+    message: String,
+    value: &str,
+    candidates: impl Iterator<Item = &'a str>,
+) -> String {
+    match candidates
+        .map(|c| (distance(value, c), c))
+        .filter(|(d, _)| *d <= 2)
+        .min()
+    {
+        Some((_, suggestion)) => format!("{message}, did you mean `{suggestion}`?"),
+        None => message,
+    }
+}
+
+fn distance(left: &str, right: &str) -> usize {
+    let right = right.chars().collect::<Vec<_>>();
+    let mut previous = (0..=right.len()).collect::<Vec<_>>();
+
+    for (i, l) in left.chars().enumerate() {
+        let mut current = vec![i + 1];
+
+        for (j, r) in right.iter().enumerate() {
+            let cost = if l == *r { 0 } else { 1 };
+            current.push(
+                (previous[j] + cost)
+                    .min(previous[j + 1] + 1)
+                    .min(current[j] + 1),
+            );
+        }
+
+        previous = current;
+    }
+
+    previous[right.len()]
+}
+
 fn process_render(
     tera: &Tera,
-    data: (impl Serialize + Clone),
+    data: impl Serialize + Clone,
     target: PathBuf,
     relative: PathBuf,
     container: &super::CodegenContainer,
 ) -> Result<Vec<String>, Error> {
-    let mut ctx = Context::from_serialize(serde_json::to_value(data).unwrap()).unwrap();
+    span!(
+        "render",
+        template = %relative.display(),
+        target = %target.display()
+    );
 
-    let data = serde_json::to_value(container).unwrap();
-    for (key, value) in data.as_object().unwrap() {
-        ctx.insert(key, value);
-    }
+    let started = Instant::now();
+    let value = super::context::build(data, container)?;
+    let context_size = match container.profiler {
+        Some(_) => serde_json::to_string(&value).map(|s| s.len()).unwrap_or(0),
+        None => 0,
+    };
+
+    let ctx = Context::from_value(value).map_err(Error::CodegenTemplateError)?;
 
     let result = tera
         .render(&relative.to_string_lossy(), &ctx)
         .map_err(Error::CodegenTemplateError)?;
 
-    if result.trim().is_empty() {
-        return Ok(vec![]);
+    if let Some(profiler) = &container.profiler {
+        profiler.record(super::profile::RenderProfile {
+            template: relative.to_string_lossy().to_string(),
+            target: target.to_string_lossy().to_string(),
+            duration: started.elapsed(),
+            context_size,
+            output_size: result.len(),
+        });
     }
 
-    log::info!("Rendering: {:?}", target);
+    let mut files = vec![];
+
+    for (target, content) in split_output(target, &result)? {
+        if content.trim().is_empty() {
+            continue;
+        }
+
+        log::info!("Rendering: {:?}", target);
+
+        let mut directory = target.clone();
+        directory.pop();
+
+        std::fs::create_dir_all(directory).map_err(|e| Error::CodegenFileError(e.to_string()))?;
+
+        let content = match std::fs::read_to_string(&target) {
+            Ok(existing) => {
+                for name in super::regions::missing(&existing, &content) {
+                    log::warn!(
+                        "Region {} of {:?} is missing in generated output, its content is dropped",
+                        name,
+                        target
+                    );
+                }
+
+                super::regions::preserve(&existing, &content)
+            }
+            Err(_) => content,
+        };
+
+        let mut file =
+            File::create(target.clone()).map_err(|e| Error::CodegenFileError(e.to_string()))?;
+
+        file.write_all(content.as_bytes())
+            .map_err(|e| Error::CodegenFileError(e.to_string()))?;
 
+        files.push(target.to_string_lossy().to_string());
+    }
+
+    Ok(files)
+}
+
+// splits rendered output by file markers, paths are relative to directory of target file
+fn split_output(target: PathBuf, result: &str) -> Result<Vec<(PathBuf, String)>, Error> {
     let mut directory = target.clone();
     directory.pop();
 
-    std::fs::create_dir_all(directory).map_err(|e| Error::CodegenFileError(e.to_string()))?;
+    let mut files = vec![(target, String::new())];
+
+    for line in result.split_inclusive('\n') {
+        if let Some(path) = line.trim().strip_prefix(FILE_MARKER) {
+            let path = PathBuf::from(path.trim());
+
+            if path.as_os_str().is_empty()
+                || path
+                    .components()
+                    .any(|c| !matches!(c, std::path::Component::Normal(_)))
+            {
+                return Err(Error::CodegenFileError(format!(
+                    "Incorrect path in file marker: {}",
+                    path.to_string_lossy()
+                )));
+            }
+
+            files.push((directory.join(path), String::new()));
+        } else {
+            files.last_mut().unwrap().1.push_str(line);
+        }
+    }
+
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(content: &str) -> Result<Template, Error> {
+        Template::from_content(
+            "test.j2".to_string(),
+            format!("{{# {content},min_version={} #}}\n", crate::VERSION),
+        )
+    }
+
+    fn message(result: Result<Template, Error>) -> String {
+        match result {
+            Err(Error::CodegenFileHeaderParseError {
+                file,
+                line,
+                message,
+            }) => format!("{file}:{line}: {message}"),
+            r => panic!("unexpected result: {r:?}"),
+        }
+    }
+
+    #[test]
+    fn test_header_line_and_min_version() {
+        assert_eq!(
+            message(Template::from_content(
+                "models/test.j2".to_string(),
+                "\n\n{# type=models,filename=a.txt,min_version=x.y #}\n".to_string(),
+            )),
+            "models/test.j2:3: invalid min_version x.y: unexpected character 'x' while parsing major version number"
+        );
+    }
+
+    #[test]
+    fn test_header_valid() {
+        assert!(matches!(
+            header("type=endpoints,filename=e.go,group_by=tag,content_type=application/json"),
+            Ok(Template::Endpoints(_))
+        ));
+    }
 
-    let mut file =
-        File::create(target.clone()).map_err(|e| Error::CodegenFileError(e.to_string()))?;
+    #[test]
+    fn test_header_unknown_option() {
+        assert_eq!(
+            message(header("type=models,fielname=m.go")),
+            "test.j2:1: unknown option `fielname`, did you mean `filename`?"
+        );
+    }
+
+    #[test]
+    fn test_header_option_not_supported_by_type() {
+        assert_eq!(
+            message(header("type=models,filename=m.go,group_by=tag")),
+            "test.j2:1: option `group_by` is not supported by `models` templates"
+        );
+    }
 
-    file.write_all(result.as_bytes())
-        .map_err(|e| Error::CodegenFileError(e.to_string()))?;
+    #[test]
+    fn test_header_invalid_type() {
+        assert_eq!(
+            message(header("type=model,filename=m.go")),
+            "test.j2:1: unknown template type `model`, did you mean `models`?"
+        );
+        assert_eq!(
+            message(header("type=models;tags,filename=m.go")),
+            "test.j2:1: option `type` has to be a single value"
+        );
+    }
+
+    #[test]
+    fn test_split_output() {
+        let files = split_output(
+            PathBuf::from("out/models/mod.rs"),
+            "mod user;\n@schematools:file user.rs\nstruct User;\n  @schematools:file nested/pet.rs\nstruct Pet;\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            files,
+            vec![
+                (
+                    PathBuf::from("out/models/mod.rs"),
+                    "mod user;\n".to_string()
+                ),
+                (
+                    PathBuf::from("out/models/user.rs"),
+                    "struct User;\n".to_string()
+                ),
+                (
+                    PathBuf::from("out/models/nested/pet.rs"),
+                    "struct Pet;\n".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_output_rejects_parent_paths() {
+        assert!(split_output(
+            PathBuf::from("out/mod.rs"),
+            "@schematools:file ../escape.rs\n"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_file_copy_condition_and_placeholders() {
+        let source = std::env::temp_dir().join("schematools-file-template-source.toml");
+        std::fs::write(&source, "[package]").unwrap();
+
+        let target_dir = std::env::temp_dir().join("schematools-file-template");
+        let _ = std::fs::remove_dir_all(&target_dir);
+
+        let spec = super::super::options::FileSpec {
+            condition: Some("%options.crate%:true".to_string()),
+            target: None,
+        };
+        let template =
+            match Template::from_file("{{name}}/Cargo.toml".to_string(), source, Some(&spec))
+                .unwrap()
+            {
+                Template::File(t) => t,
+                _ => unreachable!(),
+            };
+
+        let mut container = super::super::create_container(&[
+            ("name".to_string(), Value::from("client")),
+            ("crate".to_string(), Value::from(false)),
+        ]);
+        assert!(template
+            .copy(&target_dir.to_string_lossy(), &container)
+            .unwrap()
+            .is_empty());
+
+        container
+            .options
+            .insert("crate".to_string(), Value::from(true));
+        let files = template
+            .copy(&target_dir.to_string_lossy(), &container)
+            .unwrap();
+
+        assert_eq!(files.len(), 1);
+        assert!(target_dir.join("client/Cargo.toml").exists());
+    }
+
+    #[test]
+    fn test_group_by_extension() {
+        let spec = |service: Option<&str>| {
+            let mut operation = serde_json::json!({
+                "operationId": "listUsers",
+                "responses": {"204": {"description": "ok"}}
+            });
+            if let Some(service) = service {
+                operation["x-service"] = serde_json::json!(service);
+            }
 
-    Ok(vec![target.to_string_lossy().to_string()])
+            crate::schema::Schema::from_json(serde_json::json!({
+                "openapi": "3.0.0",
+                "info": {"title": "test", "version": "1.0.0"},
+                "paths": {
+                    "/orders": {"get": {
+                        "operationId": "listOrders",
+                        "x-service": "orders",
+                        "responses": {"204": {"description": "ok"}}
+                    }},
+                    "/users": {"get": operation}
+                }
+            }))
+        };
+        let extract = |schema: &crate::schema::Schema| {
+            let client = crate::Client::new();
+            super::super::openapi::extract(
+                schema,
+                &crate::storage::SchemaStorage::new(schema, &client).unwrap(),
+                super::super::openapi::OpenapiExtractOptions::default(),
+            )
+            .unwrap()
+        };
+
+        let group_by = GroupBy::from("x-service").unwrap();
+        assert!(GroupBy::from("x-").is_err());
+
+        let openapi = extract(&spec(Some("users")));
+        let groups = group_by.split(&openapi).unwrap();
+        assert_eq!(groups.len(), 2);
+
+        let mut container = super::super::create_container(&[]);
+        groups[1].process(&mut container);
+        assert_eq!(container.data["service"], "users");
+        assert_eq!(
+            openapi
+                .endpoints
+                .iter()
+                .filter(|e| groups[1].includes(e))
+                .count(),
+            1
+        );
+
+        let openapi = extract(&spec(None));
+        assert!(matches!(
+            group_by.split(&openapi),
+            Err(Error::CodegenGroupByError(_))
+        ));
+    }
+
+    #[test]
+    fn test_routes() {
+        assert!(matches!(
+            header("type=routes,filename=routes.yaml"),
+            Ok(Template::Routes(_))
+        ));
+
+        let schema = crate::schema::Schema::from_json(serde_json::json!({
+            "openapi": "3.0.0",
+            "info": {"title": "test", "version": "1.0.0"},
+            "components": {"securitySchemes": {
+                "oauth": {"type": "oauth2"},
+                "key": {"type": "apiKey", "in": "header", "name": "X-Key"}
+            }},
+            "security": [{"key": []}],
+            "paths": {
+                "/orders/{id}": {"delete": {
+                    "operationId": "deleteOrder",
+                    "tags": ["orders"],
+                    "security": [{"oauth": ["orders:write", "admin"]}, {"oauth": ["admin"]}],
+                    "responses": {"204": {"description": "ok"}}
+                }},
+                "/health": {"get": {
+                    "responses": {"204": {"description": "ok"}}
+                }}
+            }
+        }));
+        let client = crate::Client::new();
+        let openapi = super::super::openapi::extract(
+            &schema,
+            &crate::storage::SchemaStorage::new(&schema, &client).unwrap(),
+            super::super::openapi::OpenapiExtractOptions::default(),
+        )
+        .unwrap();
+
+        let routes = openapi
+            .endpoints
+            .iter()
+            .map(|e| serde_json::to_value(e.route()).unwrap())
+            .collect::<Vec<_>>();
+
+        assert!(routes.contains(&serde_json::json!({
+            "method": "DELETE",
+            "path": "/orders/{id}",
+            "operation": "deleteOrder",
+            "tags": ["orders"],
+            "security": [
+                {"scheme": "oauth", "scopes": ["orders:write", "admin"]},
+                {"scheme": "oauth", "scopes": ["admin"]}
+            ],
+            "scopes": ["admin", "orders:write"]
+        })));
+        assert!(routes
+            .iter()
+            .any(|r| r["path"] == "/health" && r["security"][0]["scheme"] == "key"));
+    }
+
+    #[test]
+    fn test_commands() {
+        assert!(matches!(
+            header("type=commands,filename=cli.rs,content_type=application/json"),
+            Ok(Template::Commands(_))
+        ));
+        assert!(matches!(
+            header("type=commands,filename=cli.rs,group_by=tag"),
+            Err(Error::CodegenFileHeaderParseError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_tests() {
+        assert!(matches!(
+            header("type=tests,filename=tests.rs,content_type=application/json"),
+            Ok(Template::Tests(_))
+        ));
+
+        let schema = crate::schema::Schema::from_json(serde_json::json!({
+            "openapi": "3.0.0",
+            "info": {"title": "test", "version": "1.0.0"},
+            "components": {"schemas": {"Pet": {
+                "type": "object",
+                "required": ["name"],
+                "properties": {"name": {"type": "string", "examples": ["Rex"]}}
+            }}},
+            "paths": {"/pets/{id}": {"put": {
+                "operationId": "updatePet",
+                "parameters": [
+                    {"name": "id", "in": "path", "required": true, "schema": {"type": "string", "format": "uuid"}}
+                ],
+                "requestBody": {"content": {"application/json": {
+                    "schema": {"$ref": "#/components/schemas/Pet"}
+                }}},
+                "responses": {
+                    "200": {"description": "ok", "content": {"application/json": {
+                        "schema": {"$ref": "#/components/schemas/Pet"}
+                    }}},
+                    "404": {"description": "missing"}
+                }
+            }}}
+        }));
+        let client = crate::Client::new();
+        let openapi = super::super::openapi::extract(
+            &schema,
+            &crate::storage::SchemaStorage::new(&schema, &client).unwrap(),
+            super::super::openapi::OpenapiExtractOptions::default(),
+        )
+        .unwrap();
+
+        let cases =
+            serde_json::to_value(openapi.endpoints[0].test_cases(&openapi.models, None)).unwrap();
+
+        assert_eq!(
+            cases,
+            serde_json::json!([
+                {
+                    "operation": "updatePet",
+                    "method": "PUT",
+                    "path": "/pets/{id}",
+                    "status_code": 200,
+                    "description": "ok",
+                    "parameters": {"id": "00000000-0000-0000-0000-000000000000"},
+                    "request": {"content_type": "application/json", "valid": {"name": "Rex"}, "invalid": {}},
+                    "response": {"content_type": "application/json", "valid": {"name": "Rex"}, "invalid": {}}
+                },
+                {
+                    "operation": "updatePet",
+                    "method": "PUT",
+                    "path": "/pets/{id}",
+                    "status_code": 404,
+                    "description": "missing",
+                    "parameters": {"id": "00000000-0000-0000-0000-000000000000"},
+                    "request": {"content_type": "application/json", "valid": {"name": "Rex"}, "invalid": {}},
+                    "response": null
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn test_header_malformed() {
+        assert_eq!(
+            message(header("type=models,filename")),
+            "test.j2:1: cannot parse `filename`"
+        );
+    }
+
+    #[test]
+    fn test_untrusted_hooks() {
+        let content = format!(
+            "{{# type=models,filename=a.txt,post_hook=make,min_version={} #}}\n",
+            crate::VERSION
+        );
+        let discovered = Discovered {
+            templates: HashMap::from([("a.txt.j2".to_string(), content)]),
+            ..Discovered::default()
+        };
+
+        assert!(get(discovered.clone()).is_ok());
+
+        let untrusted = Discovered {
+            untrusted: ["a.txt.j2".to_string()].into(),
+            ..discovered
+        };
+
+        assert!(matches!(
+            get(untrusted),
+            Err(Error::CodegenUntrustedHooks(t)) if t == "a.txt.j2"
+        ));
+    }
 }
@@ -1,37 +1,155 @@
-use crate::error::Error;
-use std::{collections::HashMap, fs, path::PathBuf};
+use crate::{error::Error, Client};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
 use walkdir::WalkDir;
 
 #[derive(Debug, Default, Clone)]
 pub struct Discovered {
     pub templates: HashMap<String, String>,
     pub files: HashMap<String, PathBuf>,
+    // files of data directory readable by templates, never copied to output
+    pub data: HashMap<String, PathBuf>,
+    // templates as requested (local directories canonicalized), stable between runs
+    pub sources: Vec<String>,
+    // templates of remote packs not pinned by digest, hooks declared by them are rejected
+    pub untrusted: HashSet<String>,
 }
 
-#[derive(Debug, Default)]
+pub const IGNORE_FILE: &str = ".schematoolsignore";
+
+// directory of templates with auxiliary files read by templates
+pub const DATA_DIR: &str = "data/";
+
+// registry name of template packs embedded in binary, ex. builtin::axum-server
+pub const BUILTIN_REGISTRY: &str = "builtin";
+
+const BUILTIN_PACKS: &[(&str, &[(&str, &str)])] = &[(
+    "axum-server",
+    &[
+        (
+            "macros.j2",
+            include_str!("../resources/templates/axum-server/macros.j2"),
+        ),
+        (
+            "src/models.rs.j2",
+            include_str!("../resources/templates/axum-server/src/models.rs.j2"),
+        ),
+        (
+            "src/api.rs.j2",
+            include_str!("../resources/templates/axum-server/src/api.rs.j2"),
+        ),
+        (
+            "src/router.rs.j2",
+            include_str!("../resources/templates/axum-server/src/router.rs.j2"),
+        ),
+    ],
+)];
+
+#[derive(Debug, Default, Clone)]
 pub struct Discovery {
     registries: HashMap<String, Registry>,
+    follow_links: bool,
+    // hooks of remote packs not pinned by digest are allowed
+    allow_hooks: bool,
+    // remote packs are fetched again instead of being read from cache
+    no_cache: bool,
+    // client used to download remote template archives
+    client: Client,
 }
 
 impl Discovery {
+    pub fn new(client: &Client) -> Self {
+        Self {
+            client: client.clone(),
+            ..Self::default()
+        }
+    }
+
     pub fn register(&mut self, name: String, registry: Registry) {
         self.registries.insert(name, registry);
     }
 
+    pub fn get(&self, name: &str) -> Option<&Registry> {
+        self.registries.get(name)
+    }
+
+    /// Registered registries sorted by name
+    pub fn registries(&self) -> Vec<(&String, &Registry)> {
+        let mut list = self.registries.iter().collect::<Vec<_>>();
+        list.sort_by(|a, b| a.0.cmp(b.0));
+        list
+    }
+
+    pub fn with_follow_links(&mut self, value: bool) -> &mut Self {
+        self.follow_links = value;
+        self
+    }
+
+    pub fn with_allow_hooks(&mut self, value: bool) -> &mut Self {
+        self.allow_hooks = value;
+        self
+    }
+
+    pub fn with_no_cache(&mut self, value: bool) -> &mut Self {
+        self.no_cache = value;
+        self
+    }
+
     pub fn resolve(&self, tpls: &[String]) -> Result<Discovered, Error> {
         let mut templates: HashMap<String, String> = HashMap::new();
         let mut files: HashMap<String, PathBuf> = HashMap::new();
-
-        // -----------------------+
-        // formats:               |
-        // -----------------------+
-        // registry::.            |
-        // registry::/path/ .     |
-        // /path/                 |
-        // -----------------------+
+        let mut data: HashMap<String, PathBuf> = HashMap::new();
+        let mut sources: Vec<String> = vec![];
+        let mut untrusted: HashSet<String> = HashSet::new();
+
+        // -----------------------------------+
+        // formats:                           |
+        // -----------------------------------+
+        // registry::.                        |
+        // registry::/path/ .                 |
+        // /path/                             |
+        // https://host/pack.tar.gz::/path/   |
+        // git+https://host/repo.git#tag=v1   |
+        // builtin::axum-server               |
+        // -----------------------------------+
         for template in tpls {
             let parts = template.split("::").collect::<Vec<&str>>();
-            let realpath = if let [registry, path] = parts[..] {
+            sources.push(match parts[..] {
+                [path] if !path.contains("://") => fs::canonicalize(path)
+                    .map(|p| p.to_string_lossy().to_string())
+                    .unwrap_or_else(|_| path.to_string()),
+                _ => template.clone(),
+            });
+
+            // embedded packs unless registry of the same name was added
+            if let [BUILTIN_REGISTRY, pack] = parts[..] {
+                if !self.registries.contains_key(BUILTIN_REGISTRY) {
+                    let pack = pack.trim_matches('/');
+                    let (_, files) = BUILTIN_PACKS
+                        .iter()
+                        .find(|(name, _)| *name == pack)
+                        .ok_or_else(|| Error::DiscoveryNoRegistry(template.to_string()))?;
+
+                    for (relative, content) in files.iter() {
+                        templates.insert(relative.to_string(), content.to_string());
+                    }
+
+                    continue;
+                }
+            }
+
+            let remote = discover_remote(parts[0], self.no_cache, &self.client)?;
+            let trusted = remote.is_none() || self.allow_hooks || pinned(parts[0]);
+
+            let realpath = if let Some(registry) = remote {
+                let mut p = registry.path;
+                p.push(parts.get(1).unwrap_or(&"."));
+                p
+            } else if let [registry, path] = parts[..] {
                 let r = self
                     .registries
                     .get(registry)
@@ -47,9 +165,13 @@ impl Discovery {
             };
 
             let prefix = realpath.to_string_lossy();
+            let ignore = ignore_rules(&realpath)?;
+            let root = fs::canonicalize(&realpath).map_err(Error::DiscoveryReadFile)?;
+
             for entry in WalkDir::new(realpath.clone())
-                .follow_links(false)
+                .follow_links(self.follow_links)
                 .into_iter()
+                .filter_entry(|e| !ignore.matched(e.path(), e.file_type().is_dir()).is_ignore())
                 .filter_map(|e| e.ok())
                 .filter(|d| d.file_type().is_file() || d.file_type().is_symlink())
             {
@@ -59,7 +181,7 @@ impl Discovery {
                     .unwrap()
                     .to_string_lossy();
 
-                if relative.starts_with(".git") {
+                if relative.starts_with(".git") || relative == IGNORE_FILE {
                     continue;
                 }
 
@@ -72,6 +194,21 @@ impl Discovery {
                 if relative.ends_with(".j2") {
                     let content = fs::read_to_string(path).map_err(Error::DiscoveryReadFile)?;
                     templates.insert(relative.to_string(), content);
+
+                    if trusted {
+                        untrusted.remove(relative.as_ref());
+                    } else {
+                        untrusted.insert(relative.to_string());
+                    }
+                } else if let Some(name) = relative.strip_prefix(DATA_DIR) {
+                    // data is read by templates during render, links cannot leave templates
+                    let resolved =
+                        fs::canonicalize(entry.path()).map_err(Error::DiscoverySymlinkError)?;
+                    if !resolved.starts_with(&root) {
+                        return Err(Error::DiscoveryDataOutsideTemplates(relative.to_string()));
+                    }
+
+                    data.insert(name.to_string(), resolved);
                 } else {
                     // full path
                     files.insert(relative.to_string(), path);
@@ -79,13 +216,43 @@ impl Discovery {
             }
         }
 
-        Ok(Discovered { templates, files })
+        Ok(Discovered {
+            templates,
+            files,
+            data,
+            sources,
+            untrusted,
+        })
+    }
+}
+
+// gitignore syntax rules from root of templates directory
+fn ignore_rules(root: &Path) -> Result<Gitignore, Error> {
+    let mut builder = GitignoreBuilder::new(root);
+
+    let file = root.join(IGNORE_FILE);
+    if file.exists() {
+        if let Some(e) = builder.add(file) {
+            return Err(Error::DiscoveryIgnoreError(e.to_string()));
+        }
     }
+
+    builder
+        .build()
+        .map_err(|e| Error::DiscoveryIgnoreError(e.to_string()))
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Registry {
     pub path: PathBuf,
+    // uri registry was fetched from, ex. git repository
+    pub source: Option<String>,
+    // checked out tag, branch or revision
+    pub reference: Option<String>,
+    // resolved commit of git registry
+    pub commit: Option<String>,
+    // sha256 checksum of registry contents expected during add
+    pub lock: Option<String>,
 }
 
 pub enum GitCheckoutType {
@@ -94,9 +261,40 @@ pub enum GitCheckoutType {
     Tag(String),
 }
 
+/// Limits amount of data fetched from git registry
+#[derive(Debug, Default, Clone)]
+pub struct GitFetchOptions {
+    // history depth of shallow clone, full history if none
+    pub depth: Option<i32>,
+    // subdirectory checked out sparsely and used as root of registry
+    pub subdirectory: Option<String>,
+}
+
 impl Registry {
     pub fn new(path: PathBuf) -> Self {
-        Self { path }
+        Self {
+            path,
+            source: None,
+            reference: None,
+            commit: None,
+            lock: None,
+        }
+    }
+
+    pub fn with_source(mut self, source: &str, reference: Option<String>) -> Self {
+        self.source = Some(source.to_string());
+        self.reference = reference;
+        self
+    }
+
+    pub fn with_commit(mut self, commit: Option<String>) -> Self {
+        self.commit = commit;
+        self
+    }
+
+    pub fn with_lock(mut self, lock: Option<String>) -> Self {
+        self.lock = lock;
+        self
     }
 
     pub fn get_file(&self, path: &str) -> Result<String, Error> {
@@ -107,15 +305,177 @@ impl Registry {
     }
 }
 
+/// Fetches remote template pack into cache directory if location points to
+/// a tar.gz archive (optional `#sha256=` checksum) or git repository
+/// (`git+` prefix, optional `#tag=`, `#branch=` or `#rev=`)
+pub fn discover_remote(
+    location: &str,
+    no_cache: bool,
+    client: &Client,
+) -> Result<Option<Registry>, Error> {
+    // archives are downloaded only with http feature
+    #[cfg(not(feature = "http"))]
+    let _ = client;
+    #[cfg(not(any(feature = "http", feature = "git2")))]
+    let _ = no_cache;
+
+    if let Some(repository) = location.strip_prefix("git+") {
+        #[cfg(not(feature = "git2"))]
+        return Err(Error::DiscoveryRemoteError(format!(
+            "git2 feature not built, cannot fetch {repository}"
+        )));
+
+        #[cfg(feature = "git2")]
+        {
+            let (repository, source) = match repository.split_once('#') {
+                Some((repository, reference)) => (repository, git_reference(reference)),
+                None => (
+                    repository,
+                    GitCheckoutType::Rev("refs/remotes/origin/HEAD".to_string()),
+                ),
+            };
+
+            return discover_git(repository, source, no_cache).map(Some);
+        }
+    }
+
+    let url = location.split_once('#').map_or(location, |(url, _)| url);
+    if url.starts_with("http") && (url.ends_with(".tar.gz") || url.ends_with(".tgz")) {
+        #[cfg(not(feature = "http"))]
+        return Err(Error::DiscoveryRemoteError(format!(
+            "http feature not built, cannot fetch {location}"
+        )));
+
+        #[cfg(feature = "http")]
+        return discover_archive(location, no_cache, client).map(Some);
+    }
+
+    Ok(None)
+}
+
+/// Remote pack is pinned by digest if archive checksum or full git commit is given
+pub fn pinned(location: &str) -> bool {
+    match location.split_once('#') {
+        Some((_, fragment)) => match fragment.split_once('=') {
+            Some(("sha256", checksum)) => !checksum.is_empty(),
+            Some(("rev", rev)) => rev.len() == 40 && rev.chars().all(|c| c.is_ascii_hexdigit()),
+            _ => false,
+        },
+        None => false,
+    }
+}
+
+#[cfg(feature = "git2")]
+fn git_reference(reference: &str) -> GitCheckoutType {
+    match reference.split_once('=') {
+        Some(("tag", tag)) => GitCheckoutType::Tag(tag.to_string()),
+        Some(("branch", branch)) => GitCheckoutType::Branch(branch.to_string()),
+        Some((_, rev)) => GitCheckoutType::Rev(rev.to_string()),
+        None => GitCheckoutType::Rev(reference.to_string()),
+    }
+}
+
+#[cfg(feature = "http")]
+pub fn discover_archive(
+    location: &str,
+    no_cache: bool,
+    client: &Client,
+) -> Result<Registry, Error> {
+    use sha2::Digest;
+
+    // checksum is a part of cache key, so cached archive was verified already
+    let (url, checksum) = match location.split_once("#sha256=") {
+        Some((url, checksum)) => (url, Some(checksum.to_lowercase())),
+        None => (location, None),
+    };
+
+    let mut directory = std::env::temp_dir();
+    directory.push("schema-tools");
+    directory.push(format!("{:x}", md5::compute(location)));
+
+    if directory.exists() && no_cache {
+        fs::remove_dir_all(directory.as_path()).map_err(Error::DiscoveryCleanRegistryError)?;
+    } else if directory.exists() {
+        log::debug!("already exists: {:?}", directory);
+        return Ok(Registry::new(archive_root(directory))
+            .with_source(url, None)
+            .with_lock(checksum));
+    }
+
+    log::debug!("downloading: {} to {:?}", url, directory);
+
+    let response = client
+        .get(url)
+        .send()
+        .and_then(|r| r.error_for_status())
+        .and_then(|r| r.bytes())
+        .map_err(|e| Error::DiscoveryRemoteError(e.to_string()))?;
+
+    if let Some(checksum) = &checksum {
+        let calculated = format!("{:x}", sha2::Sha256::digest(&response));
+        if !calculated.eq(checksum) {
+            return Err(Error::DiscoveryInvalidLock(checksum.clone(), calculated));
+        }
+    }
+
+    // unpack to temporary directory first so interrupted download is not cached
+    let mut unpacked = directory.clone();
+    unpacked.set_extension("tmp");
+    if unpacked.exists() {
+        fs::remove_dir_all(&unpacked).map_err(Error::DiscoveryCleanRegistryError)?;
+    }
+    fs::create_dir_all(&unpacked).map_err(Error::DiscoveryCacheRegistryError)?;
+
+    tar::Archive::new(flate2::read::GzDecoder::new(&response[..]))
+        .unpack(&unpacked)
+        .map_err(|e| Error::DiscoveryRemoteError(e.to_string()))?;
+
+    fs::rename(&unpacked, &directory).map_err(Error::DiscoveryCacheRegistryError)?;
+
+    Ok(Registry::new(archive_root(directory))
+        .with_source(url, None)
+        .with_lock(checksum))
+}
+
+// archives usually contain single top level directory
+#[cfg(feature = "http")]
+fn archive_root(directory: PathBuf) -> PathBuf {
+    let entries = fs::read_dir(&directory)
+        .map(|r| r.filter_map(|e| e.ok()).collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    match &entries[..] {
+        [entry] if entry.path().is_dir() => entry.path(),
+        _ => directory,
+    }
+}
+
 #[cfg(feature = "git2")]
 pub fn discover_git(
     repository: &str,
     source: GitCheckoutType,
     no_cache: bool,
+) -> Result<Registry, Error> {
+    discover_git_with(repository, source, no_cache, &GitFetchOptions::default())
+}
+
+/// Checks out git registry, shallow and limited to subdirectory if requested
+#[cfg(feature = "git2")]
+pub fn discover_git_with(
+    repository: &str,
+    source: GitCheckoutType,
+    no_cache: bool,
+    options: &GitFetchOptions,
 ) -> Result<Registry, Error> {
     let mut directory = std::env::temp_dir();
     let mut refspecs: Vec<String> = vec![];
 
+    let reference = match &source {
+        GitCheckoutType::Tag(tag) => format!("tag={tag}"),
+        GitCheckoutType::Branch(branch) => format!("branch={branch}"),
+        GitCheckoutType::Rev(rev) => format!("rev={rev}"),
+    };
+
     let revparse = match source {
         GitCheckoutType::Tag(tag) => {
             refspecs.push(format!("refs/tags/{tag}:refs/remotes/origin/tags/{tag}"));
@@ -135,7 +495,16 @@ pub fn discover_git(
         }
     };
 
-    let digest = md5::compute(&revparse);
+    // sparse checkouts are cached separately from full ones
+    let subdirectory = options
+        .subdirectory
+        .as_deref()
+        .map(|s| s.trim_matches('/'))
+        .filter(|s| !s.is_empty());
+    let digest = match subdirectory {
+        Some(subdirectory) => md5::compute(format!("{repository}#{revparse}::{subdirectory}")),
+        None => md5::compute(format!("{repository}#{revparse}")),
+    };
     directory.push("schema-tools");
     directory.push(format!("{digest:x}"));
 
@@ -143,7 +512,18 @@ pub fn discover_git(
         fs::remove_dir_all(directory.as_path()).map_err(Error::DiscoveryCleanRegistryError)?;
     } else if directory.exists() {
         log::debug!("already exists: {:?}", directory);
-        return Ok(Registry::new(directory));
+
+        let commit = git2::Repository::open(&directory)
+            .and_then(|repo| {
+                repo.revparse_single(&revparse)?
+                    .peel_to_commit()
+                    .map(|commit| commit.id().to_string())
+            })
+            .ok();
+
+        return Ok(Registry::new(registry_root(directory, subdirectory))
+            .with_source(repository, Some(reference))
+            .with_commit(commit));
     } else {
         fs::create_dir_all(&directory).map_err(Error::DiscoveryCacheRegistryError)?;
     }
@@ -152,6 +532,9 @@ pub fn discover_git(
 
     let repo = git2::Repository::init(directory.clone()).map_err(Error::GitDiscoveryError)?;
     let mut opts = git2::FetchOptions::new();
+    if let Some(depth) = options.depth {
+        opts.depth(depth);
+    }
 
     repo.remote_anonymous(repository)
         .map_err(Error::GitDiscoveryError)?
@@ -162,10 +545,25 @@ pub fn discover_git(
         .revparse_single(&revparse)
         .map_err(Error::GitDiscoveryError)?;
 
-    repo.checkout_tree(&obj, None)
+    let mut checkout = git2::build::CheckoutBuilder::new();
+    if let Some(subdirectory) = subdirectory {
+        checkout.path(format!("{subdirectory}/"));
+    }
+
+    repo.checkout_tree(&obj, Some(&mut checkout))
         .map_err(Error::GitDiscoveryError)?;
 
-    Ok(Registry::new(directory))
+    Ok(Registry::new(registry_root(directory, subdirectory))
+        .with_source(repository, Some(reference))
+        .with_commit(obj.peel_to_commit().ok().map(|c| c.id().to_string())))
+}
+
+#[cfg(feature = "git2")]
+fn registry_root(directory: PathBuf, subdirectory: Option<&str>) -> PathBuf {
+    match subdirectory {
+        Some(subdirectory) => directory.join(subdirectory),
+        None => directory,
+    }
 }
 
 #[cfg(test)]
@@ -250,6 +648,118 @@ mod tests {
         assert!(result.files.contains_key("README.md"));
     }
 
+    #[test]
+    fn test_discover_remote_skips_local_paths() {
+        assert!(discover_remote("resources/test/", false, &Client::new())
+            .unwrap()
+            .is_none());
+        assert!(
+            discover_remote("https://example.com/schema.json", false, &Client::new())
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_pinned() {
+        assert!(pinned("https://host/pack.tar.gz#sha256=8f43"));
+        assert!(pinned(
+            "git+https://host/repo.git#rev=a279f3b54bc7b03af83162fbf027eb781db1e046"
+        ));
+        assert!(!pinned("https://host/pack.tar.gz"));
+        assert!(!pinned("git+https://host/repo.git#rev=a279f3b"));
+        assert!(!pinned("git+https://host/repo.git#tag=v1"));
+        assert!(!pinned("git+https://host/repo.git#branch=main"));
+    }
+
+    #[test]
+    fn test_git_reference() {
+        assert!(matches!(git_reference("tag=v1.0"), GitCheckoutType::Tag(t) if t == "v1.0"));
+        assert!(matches!(git_reference("branch=main"), GitCheckoutType::Branch(b) if b == "main"));
+        assert!(matches!(git_reference("a279f3b"), GitCheckoutType::Rev(r) if r == "a279f3b"));
+    }
+
+    #[test]
+    fn test_discovery_ignore_file() {
+        let discovery = Discovery::default();
+
+        let result = discovery
+            .resolve(&["./resources/test/discovery/test3/".to_string()])
+            .unwrap();
+
+        let mut files = result.files.keys().cloned().collect::<Vec<_>>();
+        files.sort();
+
+        assert_eq!(files, vec!["README.md".to_string()]);
+        assert!(result.templates.contains_key("test.j2"));
+    }
+
+    #[test]
+    fn test_discovery_data() {
+        let result = Discovery::default()
+            .resolve(&["./resources/test/codegen/".to_string()])
+            .unwrap();
+
+        assert!(result.data.contains_key("keywords.json"));
+        assert!(result.files.keys().all(|f| !f.starts_with(DATA_DIR)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_discovery_data_outside_templates() {
+        let dir = std::env::temp_dir().join("schematools-discovery-data-link");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("templates/data")).unwrap();
+        fs::write(dir.join("secret.json"), "{}").unwrap();
+        fs::write(dir.join("templates/shared.json"), "{}").unwrap();
+
+        std::os::unix::fs::symlink("../shared.json", dir.join("templates/data/shared.json"))
+            .unwrap();
+        let templates = dir.join("templates").to_string_lossy().to_string();
+        let result = Discovery::default()
+            .resolve(std::slice::from_ref(&templates))
+            .unwrap();
+
+        assert_eq!(
+            result.data["shared.json"],
+            fs::canonicalize(dir.join("templates/shared.json")).unwrap()
+        );
+
+        std::os::unix::fs::symlink("../../secret.json", dir.join("templates/data/secret.json"))
+            .unwrap();
+
+        assert!(matches!(
+            Discovery::default().resolve(&[templates]),
+            Err(Error::DiscoveryDataOutsideTemplates(f)) if f == "data/secret.json"
+        ));
+    }
+
+    #[test]
+    fn test_discovery_builtin() {
+        let result = Discovery::default()
+            .resolve(&["builtin::axum-server".to_string()])
+            .unwrap();
+
+        let mut templates = result.templates.keys().cloned().collect::<Vec<_>>();
+        templates.sort();
+
+        assert_eq!(
+            templates,
+            [
+                "macros.j2",
+                "src/api.rs.j2",
+                "src/models.rs.j2",
+                "src/router.rs.j2"
+            ]
+        );
+        assert!(result.files.is_empty());
+
+        assert!(matches!(
+            Discovery::default().resolve(&["builtin::missing".to_string()]),
+            Err(Error::DiscoveryNoRegistry(t)) if t == "builtin::missing"
+        ));
+    }
+
     #[test]
     fn test_discovery_file() {
         let discovery = Discovery::default();
@@ -271,6 +781,47 @@ mod tests {
         );
     }
 
+    #[test]
+    #[serial]
+    fn test_discover_git_subdirectory() {
+        let origin = std::env::temp_dir().join("schematools-sparse-origin");
+        let _ = fs::remove_dir_all(&origin);
+        fs::create_dir_all(origin.join("schemas")).unwrap();
+        fs::create_dir_all(origin.join("other")).unwrap();
+        fs::write(origin.join("schemas/a.json"), "{}").unwrap();
+        fs::write(origin.join("other/b.txt"), "b").unwrap();
+
+        let repo = git2::Repository::init(&origin).unwrap();
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("test", "test@example.com").unwrap();
+        let commit = repo
+            .commit(Some("HEAD"), &signature, &signature, "init", &tree, &[])
+            .unwrap();
+        repo.tag_lightweight("v1", &repo.find_object(commit, None).unwrap(), false)
+            .unwrap();
+
+        let registry = discover_git_with(
+            &format!("file://{}", origin.to_string_lossy()),
+            GitCheckoutType::Tag("v1".to_string()),
+            true,
+            &GitFetchOptions {
+                depth: Some(1),
+                subdirectory: Some("schemas/".to_string()),
+            },
+        )
+        .unwrap();
+
+        assert!(registry.path.ends_with("schemas"));
+        assert!(registry.path.join("a.json").exists());
+        assert!(!registry.path.join("../other").exists());
+        assert_eq!(registry.commit, Some(commit.to_string()));
+        assert_eq!(registry.reference.as_deref(), Some("tag=v1"));
+    }
+
     #[test]
     #[serial]
     fn test_discover_git_hash() {
@@ -42,12 +42,24 @@ pub enum Error {
     #[error("Discovery symlink error: {0}")]
     DiscoverySymlinkError(std::io::Error),
 
+    #[error("Data file {0} points outside of templates directory")]
+    DiscoveryDataOutsideTemplates(String),
+
     #[error("Discovery clean registry error: {0}")]
     DiscoveryCleanRegistryError(std::io::Error),
 
     #[error("Discovery cache registry error: {0}")]
     DiscoveryCacheRegistryError(std::io::Error),
 
+    #[error("Discovery ignore file error: {0}")]
+    DiscoveryIgnoreError(String),
+
+    #[error("Discovery remote templates error: {0}")]
+    DiscoveryRemoteError(String),
+
+    #[error("Cannot build http client: {0}")]
+    ClientError(String),
+
     #[error("Git url format should match git://repository#(branch|tag)?(#directory) pattern")]
     DiscoveryInvalidGitUrl,
 
@@ -81,22 +93,58 @@ pub enum Error {
     #[error("Codegen not allowed group by: {0}")]
     CodegenNotAllowedGroupBy(String),
 
+    #[error("Codegen cannot group endpoints: {0}")]
+    CodegenGroupByError(String),
+
     #[error("Codegen cannot retrieve name: {0}")]
     CodegenCannotRetrieveNameError(String),
 
     #[error("Codegen formatting command error: {0}")]
     CodegenFormattingCommandError(String),
 
+    #[error("Codegen options error: {0}")]
+    CodegenOptionsError(String),
+
+    #[error("Codegen hook error: {0}")]
+    CodegenHookError(String),
+
+    #[error("Template {0} of remote pack not pinned by digest declares hooks, use --allow-hooks to run them")]
+    CodegenUntrustedHooks(String),
+
     #[error("Codegen formatting error: {0}")]
     CodegenFormattingError(std::io::Error),
 
     #[error("Codegen file error: {0}")]
     CodegenFileError(String),
 
+    #[error("Manifest entry {0} is not a path inside target directory")]
+    CodegenManifestInvalidPath(String),
+
     #[error("Codegen template error: {0:?}")]
     #[cfg(feature = "tera")]
     CodegenTemplateError(tera::Error),
 
+    #[error("Codegen project error: {0}")]
+    CodegenProjectError(String),
+
+    #[error("Codegen requires at least one schema")]
+    CodegenMissingSchema,
+
+    #[error("Unsupported render context version {0}, current version is {1}")]
+    CodegenUnsupportedContextVersion(u32, u32),
+
+    #[error("Unsupported IR version {0}, current version is {1}")]
+    CodegenUnsupportedIrVersion(u32, u32),
+
+    #[error("Unsupported language of identifiers: {0}")]
+    CodegenUnsupportedLanguage(String),
+
+    #[error("Fixture {0} doesn't contain schema file")]
+    CodegenFixtureMissingSchema(String),
+
+    #[error("Render context has to be an object")]
+    CodegenInvalidContext,
+
     #[error("Cannot find required templates in directory")]
     CodegenMissingRequiredTemplates,
 
@@ -116,6 +164,9 @@ pub enum Error {
     #[error("Security scheme format is invalid")]
     CodegenInvalidSecuritySchemeFormat,
 
+    #[error("Invalid credential option {0}: {1}")]
+    CodegenInvalidCredential(String, String),
+
     #[error("Cannot find any templates which could be used to render files")]
     CodegenNoTemplatesFound,
 
@@ -125,8 +176,12 @@ pub enum Error {
     #[error("{0} is required in file header")]
     CodegenFileHeaderRequired(String),
 
-    #[error("Cannot parse header of codegen file: {0}")]
-    CodegenFileHeaderParseError(String),
+    #[error("Cannot parse header of codegen file {file}:{line}: {message}")]
+    CodegenFileHeaderParseError {
+        file: String,
+        line: usize,
+        message: String,
+    },
 
     #[error("Cannot get template from directory")]
     CodegenTemplatesDirectoryError,
@@ -137,6 +192,12 @@ pub enum Error {
     #[error("Schema invalid property type: {0}")]
     SchemaInvalidProperty(String),
 
+    #[error("Schema with name {0} already exists")]
+    SchemaRenameConflict(String),
+
+    #[error("Schema name {0} is not a valid component name")]
+    SchemaInvalidComponentName(String),
+
     #[error("Schema path - is reserved for stdin option and reference only")]
     SchemaAsReference,
 
@@ -149,6 +210,9 @@ pub enum Error {
     #[error("Json schema is invalid: {0}")]
     JsonSchemaInvalid(String),
 
+    #[error("{0}: {1}")]
+    JsonSchemaUnsupportedKeyword(String, String),
+
     #[error("Cannot validate schema {0}")]
     SchemaValidation(String),
 
@@ -161,6 +225,17 @@ pub enum Error {
     #[error("Cannot get remote schema: {url}, reason: {reason}")]
     SchemaHttpLoad { url: String, reason: String },
 
+    #[error("Cannot get remote schema: {url}, status: {status}")]
+    SchemaHttpStatus { url: String, status: u16 },
+
+    #[error("Cannot resolve {url} referenced by {document} at #{pointer}: {source}")]
+    SchemaReferenceLoad {
+        url: String,
+        document: String,
+        pointer: String,
+        source: Box<Error>,
+    },
+
     #[error("Schema is invalid: {url}, source: {scheme}")]
     SchemaLoadInvalidScheme { url: String, scheme: String },
 
@@ -185,6 +260,30 @@ pub enum Error {
     #[error("Cannot start logger: {0}")]
     LoggerStart(String),
 
+    #[error("Invalid reference: {0}")]
+    InvalidReference(String),
+
+    #[error("Cannot compose specification: {0}")]
+    ComposeError(String),
+
+    #[error("Cannot infer schema: {0}")]
+    InferError(String),
+
+    #[error("Cannot scaffold schema: {0}")]
+    ScaffoldError(String),
+
+    #[error("Cannot transform schema: {0}")]
+    TransformError(String),
+
+    #[error("Cannot load baseline {path}: {reason}")]
+    BaselineError { path: String, reason: String },
+
+    #[error("Cannot load boundary policy {path}: {reason}")]
+    BoundaryPolicyError { path: String, reason: String },
+
+    #[error("Cannot load annotations {path}: {reason}")]
+    AnnotateError { path: String, reason: String },
+
     #[error("Dereference critical issue: {0}")]
     DereferenceError(String),
 
@@ -1,3 +1,4 @@
+use serde_json::{Map, Value};
 use std::{
     fs::{self, File},
     io::{self, Read},
@@ -8,6 +9,33 @@ use walkdir::{DirEntry, WalkDir};
 use digest::{Digest, Output};
 
 use crate::error::Error;
+use crate::schema::Schema;
+use crate::storage::SchemaStorage;
+
+// keywords which only document schema and do not change its meaning
+const ANNOTATIONS: [&str; 5] = ["title", "description", "$comment", "examples", "example"];
+
+// keywords holding instance data, which is kept as is apart from keys order
+const DATA: [&str; 3] = ["default", "const", "enum"];
+
+// keywords which contain user defined names instead of schema keywords
+const NAMED: [&str; 15] = [
+    "properties",
+    "patternProperties",
+    "definitions",
+    "$defs",
+    "dependencies",
+    "dependentSchemas",
+    "paths",
+    "schemas",
+    "responses",
+    "parameters",
+    "requestBodies",
+    "headers",
+    "securitySchemes",
+    "content",
+    "mapping",
+];
 
 pub fn calculate<D: Digest>(path: &Path) -> Result<Output<D>, Error> {
     let metadata = fs::metadata(path).map_err(Error::HashCalculationError)?;
@@ -28,6 +56,127 @@ pub fn calculate<D: Digest>(path: &Path) -> Result<Output<D>, Error> {
     Ok(hash.finalize())
 }
 
+/// Calculates digest of normalized schema, so formatting, keys order and
+/// annotations like titles or descriptions do not affect the result
+pub fn canonical_digest<D: Digest>(schema: &Schema) -> Output<D> {
+    let mut hash = D::new();
+
+    hash.update(normalize(schema.get_body()).to_string());
+
+    hash.finalize()
+}
+
+/// Calculates digest of exact content of all files in storage, any change
+/// of root schema or referenced file changes the result
+pub fn storage_digest<D: Digest>(storage: &SchemaStorage) -> Output<D> {
+    let mut hash = D::new();
+
+    let mut urls = storage.schemas.keys().collect::<Vec<_>>();
+    urls.sort();
+
+    for url in urls {
+        hash.update(url.as_str());
+        hash.update(storage.schemas[url].get_body().to_string());
+    }
+
+    hash.finalize()
+}
+
+/// Returns canonical form of schema: sorted keys, without annotations
+/// and with internal references pointing directly to their final target
+pub fn normalize(root: &Value) -> Value {
+    normalize_node(root, root, false)
+}
+
+fn normalize_node(root: &Value, node: &Value, names: bool) -> Value {
+    match node {
+        Value::Object(map) => {
+            let mut keys = map
+                .keys()
+                .filter(|k| names || !ANNOTATIONS.contains(&k.as_str()))
+                .collect::<Vec<_>>();
+            keys.sort();
+
+            let mut result = Map::new();
+            for key in keys {
+                let value = match (names, key.as_str(), &map[key]) {
+                    (false, "$ref", Value::String(reference)) => {
+                        Value::String(resolve_trivial_ref(root, reference))
+                    }
+                    (false, keyword, value) if DATA.contains(&keyword) => sort_keys(value),
+                    (_, _, value) => {
+                        normalize_node(root, value, !names && NAMED.contains(&key.as_str()))
+                    }
+                };
+
+                result.insert(key.clone(), value);
+            }
+
+            Value::Object(result)
+        }
+        Value::Array(list) => Value::Array(
+            list.iter()
+                .map(|v| normalize_node(root, v, false))
+                .collect(),
+        ),
+        value => value.clone(),
+    }
+}
+
+/// Copy of value with keys of objects sorted recursively, maps built from hash maps have
+/// random order
+pub(crate) fn sort_keys(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries = map.iter().collect::<Vec<_>>();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(k, v)| (k.clone(), sort_keys(v)))
+                    .collect(),
+            )
+        }
+        Value::Array(list) => Value::Array(list.iter().map(sort_keys).collect()),
+        value => value.clone(),
+    }
+}
+
+// follows chain of internal references which point to nodes containing only another $ref
+fn resolve_trivial_ref(root: &Value, reference: &str) -> String {
+    let mut current = reference.to_string();
+    let mut visited: Vec<String> = vec![];
+
+    while let Some(pointer) = current.strip_prefix('#') {
+        if visited.contains(&current) {
+            break;
+        }
+
+        let next = root
+            .pointer(pointer)
+            .and_then(|v| v.as_object())
+            .and_then(|target| {
+                let mut keys = target.keys().filter(|k| !ANNOTATIONS.contains(&k.as_str()));
+
+                match (keys.next().map(|k| k.as_str()), keys.next()) {
+                    (Some("$ref"), None) => target["$ref"].as_str().map(|s| s.to_string()),
+                    _ => None,
+                }
+            });
+
+        match next {
+            Some(next) => {
+                visited.push(current);
+                current = next;
+            }
+            None => break,
+        }
+    }
+
+    current
+}
+
 fn calculate_file<D: Digest>(path: &Path, hash: &mut D) -> Result<(), Error> {
     let mut file = File::open(path).map_err(Error::HashCalculationError)?;
 
@@ -74,3 +223,105 @@ fn is_hidden(entry: &DirEntry, path: &Path) -> bool {
         .map(|s| s.starts_with('.'))
         .unwrap_or(false)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_normalize_ignores_keys_order_and_annotations() {
+        let first = json!({
+            "type": "object",
+            "title": "Pet",
+            "properties": {
+                "name": {"type": "string", "description": "Name of pet"},
+                "age": {"type": "integer"}
+            }
+        });
+
+        let second = json!({
+            "properties": {
+                "age": {"type": "integer"},
+                "name": {"description": "Pet name", "type": "string"}
+            },
+            "type": "object"
+        });
+
+        assert_eq!(
+            normalize(&first).to_string(),
+            normalize(&second).to_string()
+        );
+    }
+
+    #[test]
+    fn test_normalize_keeps_properties_named_like_annotations() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "title": {"type": "string", "title": "Title"},
+                "description": {"type": "string"}
+            }
+        });
+
+        assert_eq!(
+            normalize(&schema),
+            json!({
+                "properties": {
+                    "description": {"type": "string"},
+                    "title": {"type": "string"}
+                },
+                "type": "object"
+            })
+        );
+    }
+
+    #[test]
+    fn test_normalize_keeps_instance_data() {
+        let schema = |title: &str| {
+            json!({
+                "type": "object",
+                "default": {"title": title, "$ref": "#/a"},
+                "enum": [{"description": title}],
+                "const": {"b": 1, "a": 2}
+            })
+        };
+
+        assert_ne!(normalize(&schema("a")), normalize(&schema("b")));
+        assert_eq!(
+            normalize(&schema("a"))["default"],
+            json!({"$ref": "#/a", "title": "a"})
+        );
+        assert_eq!(
+            normalize(&schema("a"))["const"].to_string(),
+            r#"{"a":2,"b":1}"#
+        );
+    }
+
+    #[test]
+    fn test_normalize_resolves_trivial_refs() {
+        let schema = json!({
+            "definitions": {
+                "alias": {"$ref": "#/definitions/real", "description": "alias"},
+                "real": {"type": "string"},
+                "loop1": {"$ref": "#/definitions/loop2"},
+                "loop2": {"$ref": "#/definitions/loop1"}
+            },
+            "properties": {
+                "a": {"$ref": "#/definitions/alias"},
+                "b": {"$ref": "#/definitions/loop1"}
+            }
+        });
+
+        let result = normalize(&schema);
+
+        assert_eq!(
+            result.pointer("/properties/a/$ref"),
+            Some(&json!("#/definitions/real"))
+        );
+        assert_eq!(
+            result.pointer("/properties/b/$ref"),
+            Some(&json!("#/definitions/loop1"))
+        );
+    }
+}
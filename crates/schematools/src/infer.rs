@@ -0,0 +1,427 @@
+//! Json schema derived from example documents, a starting point for existing apis
+
+use std::collections::BTreeSet;
+
+use regex::Regex;
+use serde_json::{json, Map, Value};
+
+use crate::{error::Error, schema::Schema};
+
+pub struct Inferrer;
+
+pub struct InferrerOptions {
+    pub title: Option<String>,
+    pub enum_threshold: usize,
+    pub required_ratio: f64,
+    pub formats: bool,
+}
+
+impl Inferrer {
+    pub fn options() -> InferrerOptions {
+        InferrerOptions {
+            title: None,
+            enum_threshold: 0,
+            required_ratio: 1.0,
+            formats: true,
+        }
+    }
+}
+
+impl InferrerOptions {
+    pub fn with_title(&mut self, value: Option<String>) -> &mut Self {
+        self.title = value;
+        self
+    }
+
+    /// Strings with at most that many distinct, repeated values become enums, 0 disables enums
+    pub fn with_enum_threshold(&mut self, value: usize) -> &mut Self {
+        self.enum_threshold = value;
+        self
+    }
+
+    /// Properties present in at least that fraction of objects are required
+    pub fn with_required_ratio(&mut self, value: f64) -> &mut Self {
+        self.required_ratio = value;
+        self
+    }
+
+    /// Detects formats of strings (date-time, date, time, email, uuid, uri, ipv4)
+    pub fn with_formats(&mut self, value: bool) -> &mut Self {
+        self.formats = value;
+        self
+    }
+
+    /// Json schema every sample is valid against
+    pub fn process(&self, samples: &[Value]) -> Result<Schema, Error> {
+        if samples.is_empty() {
+            return Err(Error::InferError(
+                "at least one sample is required".to_string(),
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.required_ratio) {
+            return Err(Error::InferError(format!(
+                "required ratio {} has to be between 0 and 1",
+                self.required_ratio
+            )));
+        }
+
+        let mut shape = Shape::default();
+        for sample in samples {
+            shape.observe(sample, self.enum_threshold);
+        }
+
+        let mut root = Map::new();
+        root.insert(
+            "$schema".to_string(),
+            json!("http://json-schema.org/draft-07/schema#"),
+        );
+        if let Some(title) = &self.title {
+            root.insert("title".to_string(), Value::String(title.clone()));
+        }
+
+        if let Value::Object(body) = self.schema(&shape) {
+            root.extend(body);
+        }
+
+        Ok(Schema::from_json(Value::Object(root)))
+    }
+
+    fn schema(&self, shape: &Shape) -> Value {
+        let mut types = vec![];
+        let mut schema = Map::new();
+
+        if shape.booleans > 0 {
+            types.push("boolean");
+        }
+        if shape.numbers > 0 {
+            types.push("number");
+        } else if shape.integers > 0 {
+            types.push("integer");
+        }
+        if shape.strings > 0 {
+            types.push("string");
+
+            match &shape.format {
+                Some(Some(format)) if self.formats => {
+                    schema.insert("format".to_string(), json!(format));
+                }
+                _ => {
+                    let values = &shape.values;
+
+                    // enum only when values repeat, otherwise every string of a single sample is one
+                    if self.enum_threshold > 0
+                        && !shape.overflow
+                        && values.len() <= self.enum_threshold
+                        && shape.strings > values.len()
+                    {
+                        schema.insert("enum".to_string(), values.iter().cloned().collect());
+                    }
+                }
+            }
+        }
+        if shape.arrays > 0 {
+            types.push("array");
+
+            if let Some(items) = &shape.items {
+                schema.insert("items".to_string(), self.schema(items));
+            }
+        }
+        if shape.objects > 0 {
+            types.push("object");
+
+            let required: Vec<Value> = shape
+                .properties
+                .iter()
+                .filter(|(_, p)| p.count as f64 >= self.required_ratio * shape.objects as f64)
+                .map(|(name, _)| json!(name))
+                .collect();
+
+            schema.insert(
+                "properties".to_string(),
+                shape
+                    .properties
+                    .iter()
+                    .map(|(name, p)| (name.clone(), self.schema(p)))
+                    .collect::<Map<_, _>>()
+                    .into(),
+            );
+
+            if !required.is_empty() {
+                schema.insert("required".to_string(), Value::Array(required));
+            }
+        }
+        if shape.nulls > 0 {
+            types.push("null");
+        }
+
+        if let Some(Value::Array(values)) = schema.get_mut("enum") {
+            if shape.nulls > 0 {
+                values.push(Value::Null);
+            }
+        }
+
+        match types[..] {
+            [] => {}
+            [kind] => {
+                schema.insert("type".to_string(), json!(kind));
+            }
+            _ => {
+                schema.insert("type".to_string(), json!(types));
+            }
+        }
+
+        // type first, it reads better
+        let mut ordered = Map::new();
+        if let Some(kind) = schema.remove("type") {
+            ordered.insert("type".to_string(), kind);
+        }
+        ordered.extend(schema);
+
+        Value::Object(ordered)
+    }
+}
+
+// statistics of values observed at the same place of samples
+#[derive(Default)]
+struct Shape {
+    count: usize,
+    nulls: usize,
+    booleans: usize,
+    integers: usize,
+    numbers: usize,
+    strings: usize,
+    // distinct strings, collection stops above enum threshold
+    values: BTreeSet<String>,
+    overflow: bool,
+    // format shared by all strings, Some(None) once they differ
+    format: Option<Option<&'static str>>,
+    arrays: usize,
+    items: Option<Box<Shape>>,
+    objects: usize,
+    properties: Vec<(String, Shape)>,
+}
+
+impl Shape {
+    fn observe(&mut self, value: &Value, threshold: usize) {
+        self.count += 1;
+
+        match value {
+            Value::Null => self.nulls += 1,
+            Value::Bool(_) => self.booleans += 1,
+            Value::Number(n) if n.is_i64() || n.is_u64() => self.integers += 1,
+            Value::Number(_) => self.numbers += 1,
+            Value::String(s) => {
+                self.strings += 1;
+
+                if !self.overflow {
+                    self.values.insert(s.clone());
+                    if self.values.len() > threshold {
+                        self.overflow = true;
+                        self.values.clear();
+                    }
+                }
+
+                let format = detect_format(s);
+                self.format = match self.format {
+                    None => Some(format),
+                    Some(f) if f == format => Some(f),
+                    Some(_) => Some(None),
+                };
+            }
+            Value::Array(a) => {
+                self.arrays += 1;
+
+                for item in a {
+                    self.items
+                        .get_or_insert_with(Default::default)
+                        .observe(item, threshold);
+                }
+            }
+            Value::Object(o) => {
+                self.objects += 1;
+
+                for (name, value) in o {
+                    let position = match self.properties.iter().position(|(n, _)| n == name) {
+                        Some(position) => position,
+                        None => {
+                            self.properties.push((name.clone(), Shape::default()));
+                            self.properties.len() - 1
+                        }
+                    };
+
+                    self.properties[position].1.observe(value, threshold);
+                }
+            }
+        }
+    }
+}
+
+fn detect_format(value: &str) -> Option<&'static str> {
+    lazy_static! {
+        static ref FORMATS: Vec<(&'static str, Regex)> = vec![
+            (
+                "date-time",
+                Regex::new(
+                    r"^\d{4}-\d{2}-\d{2}[Tt ]\d{2}:\d{2}:\d{2}(\.\d+)?([Zz]|[+-]\d{2}:\d{2})$"
+                )
+                .unwrap()
+            ),
+            ("date", Regex::new(r"^\d{4}-\d{2}-\d{2}$").unwrap()),
+            (
+                "time",
+                Regex::new(r"^\d{2}:\d{2}:\d{2}(\.\d+)?([Zz]|[+-]\d{2}:\d{2})?$").unwrap()
+            ),
+            (
+                "uuid",
+                Regex::new(
+                    r"^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$"
+                )
+                .unwrap()
+            ),
+            ("email", Regex::new(r"^[^@\s]+@[^@\s]+\.[^@\s]+$").unwrap()),
+            (
+                "uri",
+                Regex::new(r"^[a-zA-Z][a-zA-Z0-9+.-]*://\S+$").unwrap()
+            ),
+            (
+                "ipv4",
+                Regex::new(
+                    r"^((25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)\.){3}(25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)$"
+                )
+                .unwrap()
+            ),
+        ];
+    }
+
+    FORMATS
+        .iter()
+        .find(|(_, re)| re.is_match(value))
+        .map(|(format, _)| *format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_infer() {
+        let samples = vec![
+            json!({
+                "id": "8c3e2b4a-1d2f-4e5a-9b6c-7d8e9f0a1b2c",
+                "status": "new",
+                "total": 10,
+                "createdAt": "2024-01-02T10:00:00Z",
+                "customer": {"email": "a@example.com", "name": "Ann"},
+                "items": [{"sku": "A1", "quantity": 1}, {"sku": "B2", "quantity": 2, "note": null}],
+                "tags": []
+            }),
+            json!({
+                "id": "1b2c8c3e-1d2f-4e5a-9b6c-7d8e9f0a2b4a",
+                "status": "paid",
+                "total": 12.5,
+                "createdAt": "2024-01-03T11:00:00+02:00",
+                "customer": {"email": "b@example.com", "name": null},
+                "items": [{"sku": "C3", "quantity": 3, "note": "gift"}],
+                "tags": ["vip", 1]
+            }),
+            json!({
+                "id": "2b4a1b2c-1d2f-4e5a-9b6c-7d8e9f0a8c3e",
+                "status": "new",
+                "total": 7,
+                "createdAt": "2024-01-04",
+                "customer": {"email": "c@example.com"},
+                "items": []
+            }),
+        ];
+
+        let schema = Inferrer::options()
+            .with_title(Some("Order".to_string()))
+            .with_enum_threshold(3)
+            .process(&samples)
+            .unwrap();
+
+        assert_eq!(
+            schema.get_body(),
+            &json!({
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "title": "Order",
+                "type": "object",
+                "properties": {
+                    "id": {"type": "string", "format": "uuid"},
+                    "status": {"type": "string", "enum": ["new", "paid"]},
+                    "total": {"type": "number"},
+                    "createdAt": {"type": "string"},
+                    "customer": {
+                        "type": "object",
+                        "properties": {
+                            "email": {"type": "string", "format": "email"},
+                            "name": {"type": ["string", "null"]}
+                        },
+                        "required": ["email"]
+                    },
+                    "items": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "sku": {"type": "string"},
+                                "quantity": {"type": "integer"},
+                                "note": {"type": ["string", "null"]}
+                            },
+                            "required": ["sku", "quantity"]
+                        }
+                    },
+                    "tags": {"type": "array", "items": {"type": ["integer", "string"]}}
+                },
+                "required": ["id", "status", "total", "createdAt", "customer", "items"]
+            })
+        );
+    }
+
+    #[test]
+    fn test_infer_options() {
+        let samples = vec![
+            json!({"a": "2024-01-02", "b": 1}),
+            json!({"a": "2024-01-03"}),
+        ];
+
+        let schema = Inferrer::options()
+            .with_required_ratio(0.5)
+            .with_formats(false)
+            .process(&samples)
+            .unwrap();
+
+        assert_eq!(schema.get_body()["required"], json!(["a", "b"]));
+        assert_eq!(
+            schema.get_body()["properties"]["a"],
+            json!({"type": "string"})
+        );
+
+        let colors =
+            ["red", "green", "blue", "red", "green", "blue", "black"].map(|c| json!({"color": c}));
+        let enums = |threshold| {
+            Inferrer::options()
+                .with_enum_threshold(threshold)
+                .process(&colors)
+                .unwrap()
+                .get_body()["properties"]["color"]
+                .clone()
+        };
+        assert_eq!(enums(3), json!({"type": "string"}));
+        assert_eq!(
+            enums(4),
+            json!({"type": "string", "enum": ["black", "blue", "green", "red"]})
+        );
+
+        assert!(matches!(
+            Inferrer::options().process(&[]),
+            Err(Error::InferError(_))
+        ));
+        assert!(matches!(
+            Inferrer::options()
+                .with_required_ratio(2.0)
+                .process(&samples),
+            Err(Error::InferError(_))
+        ));
+    }
+}
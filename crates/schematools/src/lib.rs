@@ -1,23 +1,36 @@
 #[macro_use]
 extern crate lazy_static;
 
+/// Enters trace span lasting until the end of current block, no-op without `tracing` feature
+macro_rules! span {
+    ($($args:tt)*) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!($($args)*).entered();
+    };
+}
+
+pub mod client;
 #[cfg(feature = "codegen")]
 pub mod codegen;
 pub mod discovery;
 pub mod error;
 pub mod hash;
+pub mod infer;
 pub mod process;
 pub mod resolver;
+pub mod scaffold;
 pub mod schema;
 pub mod scope;
 pub mod storage;
 pub mod tools;
 pub mod validate;
+pub mod workspace;
 
 #[cfg(feature = "http")]
 pub use reqwest::blocking::Client;
 /// A dummy client to be used when the http feature is disabled
 #[cfg(not(feature = "http"))]
+#[derive(Debug, Default, Clone)]
 pub struct Client;
 #[cfg(not(feature = "http"))]
 impl Client {
@@ -0,0 +1,183 @@
+use std::path::Path;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{error::Error, schema::Schema, tools::glob};
+
+pub struct Annotator;
+
+pub struct AnnotatorOptions {
+    pub mapping: Mapping,
+    pub overwrite: bool,
+}
+
+/// Extensions of components matched by glob of name, ex. `Pet*`, or of kind and name when glob
+/// contains slash, ex. `parameters/*`. All matching entries are applied in order of file
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct Mapping {
+    #[serde(default)]
+    pub components: serde_json::Map<String, Value>,
+}
+
+impl Mapping {
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let content = std::fs::read_to_string(path).map_err(|e| Error::AnnotateError {
+            path: path.display().to_string(),
+            reason: e.to_string(),
+        })?;
+
+        serde_yaml::from_str(&content).map_err(|e| Error::AnnotateError {
+            path: path.display().to_string(),
+            reason: e.to_string(),
+        })
+    }
+}
+
+impl Annotator {
+    pub fn options(mapping: Mapping) -> AnnotatorOptions {
+        AnnotatorOptions {
+            mapping,
+            overwrite: false,
+        }
+    }
+}
+
+impl AnnotatorOptions {
+    pub fn with_overwrite(&mut self, value: bool) -> &mut Self {
+        self.overwrite = value;
+        self
+    }
+
+    /// Backfills extensions of components, existing values are kept unless overwrite is set,
+    /// returns number of added extensions
+    pub fn process(&self, schema: &mut Schema) -> Result<usize, Error> {
+        let patterns = self
+            .mapping
+            .components
+            .iter()
+            .map(|(pattern, extensions)| {
+                let extensions = extensions
+                    .as_object()
+                    .ok_or_else(|| Error::IncorrectFilterError(pattern.clone()))?;
+                glob(pattern)
+                    .map(|re| (pattern.contains('/'), re, extensions))
+                    .map_err(|_| Error::IncorrectFilterError(pattern.clone()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let Some(components) = schema
+            .get_body_mut()
+            .get_mut("components")
+            .and_then(|c| c.as_object_mut())
+        else {
+            return Ok(0);
+        };
+
+        let mut count = 0;
+        for (kind, entries) in components.iter_mut() {
+            let Some(entries) = entries.as_object_mut() else {
+                continue;
+            };
+
+            for (name, component) in entries.iter_mut() {
+                let Some(component) = component.as_object_mut() else {
+                    continue;
+                };
+                if component.contains_key("$ref") {
+                    continue;
+                }
+
+                let mut assigned: Vec<&String> = vec![];
+                for (qualified, re, extensions) in &patterns {
+                    let subject = match qualified {
+                        true => format!("{kind}/{name}"),
+                        false => name.clone(),
+                    };
+                    if !re.is_match(&subject) {
+                        continue;
+                    }
+
+                    for (key, value) in extensions.iter() {
+                        // first matching entry wins, existing values only with overwrite
+                        let exists = component.contains_key(key);
+                        if assigned.contains(&key) || (exists && !self.overwrite) {
+                            continue;
+                        }
+
+                        if !exists {
+                            count += 1;
+                        }
+                        component.insert(key.clone(), value.clone());
+                        assigned.push(key);
+                    }
+                }
+            }
+        }
+
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_annotate() {
+        let mapping: Mapping = serde_yaml::from_str(
+            r#"
+components:
+  Pet*:
+    x-owner: pets
+    x-pii: false
+  parameters/*:
+    x-owner: platform
+  "*":
+    x-owner: unknown
+    x-classification: internal
+"#,
+        )
+        .unwrap();
+
+        let mut schema = Schema::from_json(json!({"components": {
+            "schemas": {
+                "Pet": {"type": "object", "x-pii": true},
+                "Owner": {"type": "object"},
+                "Alias": {"$ref": "#/components/schemas/Pet"}
+            },
+            "parameters": {
+                "Limit": {"in": "query", "name": "limit"}
+            }
+        }}));
+
+        let count = Annotator::options(mapping.clone())
+            .process(&mut schema)
+            .unwrap();
+
+        assert_eq!(count, 6);
+        assert_eq!(
+            schema.get_body(),
+            &json!({"components": {
+                "schemas": {
+                    "Pet": {"type": "object", "x-pii": true, "x-owner": "pets", "x-classification": "internal"},
+                    "Owner": {"type": "object", "x-owner": "unknown", "x-classification": "internal"},
+                    "Alias": {"$ref": "#/components/schemas/Pet"}
+                },
+                "parameters": {
+                    "Limit": {"in": "query", "name": "limit", "x-owner": "platform", "x-classification": "internal"}
+                }
+            }})
+        );
+
+        Annotator::options(mapping)
+            .with_overwrite(true)
+            .process(&mut schema)
+            .unwrap();
+        assert_eq!(
+            schema.get_body().pointer("/components/schemas/Pet/x-pii"),
+            Some(&json!(false))
+        );
+    }
+}
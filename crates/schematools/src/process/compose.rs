@@ -0,0 +1,261 @@
+use serde_json::Value;
+use url::Url;
+
+use crate::{error::Error, schema::Schema, storage::ref_to_url, Client};
+
+pub struct Composer;
+
+pub struct ComposerOptions {
+    pub keyword: String,
+}
+
+impl Composer {
+    pub fn options() -> ComposerOptions {
+        ComposerOptions {
+            keyword: "$include".to_string(),
+        }
+    }
+}
+
+impl ComposerOptions {
+    /// Directive holding path (or list of paths) of included fragments
+    pub fn with_keyword(&mut self, value: String) -> &mut Self {
+        self.keyword = value;
+        self
+    }
+
+    /// Replaces every `{"$include": "paths/orders.yaml"}` with content of fragment, keys of
+    /// listed fragments and siblings of directive are merged and have to be unique. Fragments
+    /// may include other fragments, relative references of fragments are rewritten to be
+    /// relative to the schema file while local ones (`#/...`) point to composed document
+    ///
+    /// Returns number of included fragments
+    pub fn process(&self, schema: &mut Schema, client: &Client) -> Result<usize, Error> {
+        let base = schema.get_url().clone();
+        let mut stack = vec![base.clone()];
+
+        self.compose(&base, schema.get_body_mut(), client, &mut stack)
+    }
+
+    fn compose(
+        &self,
+        base: &Url,
+        node: &mut Value,
+        client: &Client,
+        stack: &mut Vec<Url>,
+    ) -> Result<usize, Error> {
+        let mut count = 0;
+
+        match node {
+            Value::Object(map) => {
+                if let Some(include) = map.remove(&self.keyword) {
+                    let paths = match include {
+                        Value::String(path) => vec![path],
+                        Value::Array(paths) => paths
+                            .into_iter()
+                            .map(|p| match p {
+                                Value::String(path) => Ok(path),
+                                p => Err(Error::ComposeError(format!("invalid path {p}"))),
+                            })
+                            .collect::<Result<Vec<_>, _>>()?,
+                        i => return Err(Error::ComposeError(format!("invalid path {i}"))),
+                    };
+
+                    let mut fragments = vec![];
+                    for path in &paths {
+                        let (fragment, nested) = self.fragment(base, path, client, stack)?;
+                        fragments.push(fragment);
+                        count += nested + 1;
+                    }
+
+                    if fragments.len() == 1 && map.is_empty() {
+                        *node = fragments.remove(0);
+                        return Ok(count);
+                    }
+
+                    for (path, fragment) in paths.iter().zip(fragments) {
+                        let Value::Object(fragment) = fragment else {
+                            return Err(Error::ComposeError(format!(
+                                "{path} has to be an object to be merged"
+                            )));
+                        };
+
+                        for (key, value) in fragment {
+                            if map.contains_key(&key) {
+                                return Err(Error::ComposeError(format!(
+                                    "{key} of {path} is already defined"
+                                )));
+                            }
+
+                            map.insert(key, value);
+                        }
+                    }
+                }
+
+                for (_, value) in map.iter_mut() {
+                    count += self.compose(base, value, client, stack)?;
+                }
+            }
+            Value::Array(a) => {
+                for value in a.iter_mut() {
+                    count += self.compose(base, value, client, stack)?;
+                }
+            }
+            _ => {}
+        }
+
+        Ok(count)
+    }
+
+    // fragment with nested includes resolved and references relative to base
+    fn fragment(
+        &self,
+        base: &Url,
+        path: &str,
+        client: &Client,
+        stack: &mut Vec<Url>,
+    ) -> Result<(Value, usize), Error> {
+        let url =
+            ref_to_url(base, path).ok_or_else(|| Error::InvalidReference(path.to_string()))?;
+
+        if stack.contains(&url) {
+            return Err(Error::ComposeError(format!("{url} includes itself")));
+        }
+
+        let mut body =
+            std::mem::take(Schema::load_url_with_client(url.clone(), client)?.get_body_mut());
+
+        stack.push(url.clone());
+        let count = self.compose(&url, &mut body, client, stack)?;
+        stack.pop();
+
+        rebase(&url, base, &mut body);
+
+        Ok((body, count))
+    }
+}
+
+fn rebase(from: &Url, to: &Url, node: &mut Value) {
+    match node {
+        Value::Object(map) => {
+            if let Some(Value::String(reference)) = map.get_mut("$ref") {
+                if !reference.starts_with('#')
+                    && Url::parse(reference) == Err(url::ParseError::RelativeUrlWithoutBase)
+                {
+                    if let Ok(absolute) = from.join(reference) {
+                        *reference = to
+                            .make_relative(&absolute)
+                            .unwrap_or_else(|| absolute.to_string());
+                    }
+                }
+            }
+
+            for (_, value) in map.iter_mut() {
+                rebase(from, to, value);
+            }
+        }
+        Value::Array(a) => {
+            for value in a.iter_mut() {
+                rebase(from, to, value);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::{fs, path::Path};
+
+    fn write(dir: &Path, file: &str, content: &str) {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_compose() {
+        let dir = std::env::temp_dir().join("schematools-compose");
+        write(
+            &dir,
+            "openapi.yaml",
+            "openapi: 3.0.0\npaths:\n  $include: [paths/orders.yaml, paths/users.yaml]\ncomponents:\n  schemas:\n    $include: components/schemas.yaml\n    Extra:\n      type: string\n",
+        );
+        write(
+            &dir,
+            "paths/orders.yaml",
+            "/orders:\n  get:\n    responses:\n      '200':\n        $ref: ../responses/orders.yaml\n",
+        );
+        write(
+            &dir,
+            "paths/users.yaml",
+            "/users:\n  $include: users/get.yaml\n",
+        );
+        write(
+            &dir,
+            "paths/users/get.yaml",
+            "get:\n  responses:\n    '200':\n      $ref: '#/components/responses/Users'\n",
+        );
+        write(&dir, "components/schemas.yaml", "Order:\n  type: object\n");
+
+        let client = Client::new();
+        let mut schema =
+            Schema::load_url(Url::from_file_path(dir.join("openapi.yaml")).unwrap()).unwrap();
+
+        let count = Composer::options().process(&mut schema, &client).unwrap();
+
+        assert_eq!(count, 4);
+        assert_eq!(
+            schema.get_body(),
+            &json!({
+                "openapi": "3.0.0",
+                "paths": {
+                    "/orders": {
+                        "get": {"responses": {"200": {"$ref": "responses/orders.yaml"}}}
+                    },
+                    "/users": {
+                        "get": {"responses": {"200": {"$ref": "#/components/responses/Users"}}}
+                    }
+                },
+                "components": {
+                    "schemas": {
+                        "Order": {"type": "object"},
+                        "Extra": {"type": "string"}
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_compose_conflicts_and_cycles() {
+        let dir = std::env::temp_dir().join("schematools-compose-invalid");
+        write(&dir, "a.yaml", "$include: b.yaml\n");
+        write(&dir, "b.yaml", "$include: a.yaml\n");
+        write(&dir, "duplicated.yaml", "Order:\n  type: object\n");
+
+        let client = Client::new();
+        let mut schema =
+            Schema::load_url(Url::from_file_path(dir.join("a.yaml")).unwrap()).unwrap();
+
+        assert!(matches!(
+            Composer::options().process(&mut schema, &client),
+            Err(Error::ComposeError(_))
+        ));
+
+        write(
+            &dir,
+            "openapi.yaml",
+            "Order:\n  type: string\n$include: duplicated.yaml\n",
+        );
+        let mut schema =
+            Schema::load_url(Url::from_file_path(dir.join("openapi.yaml")).unwrap()).unwrap();
+
+        assert!(matches!(
+            Composer::options().process(&mut schema, &client),
+            Err(Error::ComposeError(_))
+        ));
+    }
+}
@@ -58,14 +58,15 @@ impl DereferencerOptions {
         self
     }
 
-    pub fn process(&self, schema: &mut Schema, storage: &SchemaStorage) {
-        let original = schema.clone(); // todo: clone?
+    /// Infinite references and references which cannot be resolved are returned as error
+    pub fn process(&self, schema: &mut Schema, storage: &SchemaStorage) -> Result<(), Error> {
+        // resolver reads referenced nodes from storage, root is modified in place
+        let resolver = SchemaResolver::new(schema, storage);
         let mut dctx = DereferencerContext::new(schema.get_url());
 
         let root = schema.get_body_mut();
-        let resolver = SchemaResolver::new(&original, storage);
 
-        process_node(root, self, &mut dctx, &resolver);
+        process_node(root, self, &mut dctx, &resolver)
     }
 }
 
@@ -86,8 +87,12 @@ fn process_ref(
     options: &DereferencerOptions,
     ctx: &mut DereferencerContext,
     resolver: &SchemaResolver,
-) {
-    assert!(ctx.depth < 50, "Infinite reference occurred!");
+) -> Result<(), Error> {
+    if ctx.depth >= 50 {
+        return Err(Error::DereferenceError(
+            "Infinite reference occurred!".to_string(),
+        ));
+    }
 
     match ref_to_url(&ctx.base, &reference) {
         Some(mut url) => {
@@ -95,7 +100,7 @@ fn process_ref(
             url.set_fragment(None);
 
             if options.skip_root_internal_references && ctx.depth == 1 && ctx.base == url {
-                return;
+                return Ok(());
             }
 
             if options
@@ -103,7 +108,7 @@ fn process_ref(
                 .iter()
                 .any(|hostname| url.to_string().contains(hostname))
             {
-                return;
+                return Ok(());
             }
 
             // resolve
@@ -127,13 +132,13 @@ fn process_ref(
 
                             *root = serde_json::json!({ "$ref": format!("#{internal_path}") });
 
-                            return;
+                            return Ok(());
                         } else {
                             ctx.resolved.insert(reference, ctx.scope.to_string());
                         }
                     }
 
-                    process_node(&mut s, options, ctx, resolver);
+                    process_node(&mut s, options, ctx, resolver)?;
 
                     if let Some(result) = s.as_object_mut() {
                         for (key, value) in root.as_object().unwrap() {
@@ -152,6 +157,8 @@ fn process_ref(
         }
         None => log::warn!("Cannot parse reference: {}", ctx.scope),
     }
+
+    Ok(())
 }
 
 pub fn parse_url(reference: String) -> Result<(Option<String>, Option<String>), Error> {
@@ -177,18 +184,20 @@ fn process_node(
     options: &DereferencerOptions,
     ctx: &mut DereferencerContext,
     resolver: &SchemaResolver,
-) {
+) -> Result<(), Error> {
     match root {
         Value::Object(ref mut map) => {
             if let Some(Value::String(reference)) = map.get_mut("$ref") {
                 ctx.depth += 1;
-                process_ref(reference.clone(), root, options, ctx, resolver);
+                let result = process_ref(reference.clone(), root, options, ctx, resolver);
                 ctx.depth -= 1;
+
+                return result;
             } else {
                 for (property, value) in map.into_iter() {
                     ctx.scope.any(property);
 
-                    process_node(value, options, ctx, resolver);
+                    process_node(value, options, ctx, resolver)?;
 
                     if !options.skip_discriminators
                         && property == "discriminator"
@@ -204,12 +213,14 @@ fn process_node(
         Value::Array(a) => {
             for (index, x) in a.iter_mut().enumerate() {
                 ctx.scope.index(index);
-                process_node(x, options, ctx, resolver);
+                process_node(x, options, ctx, resolver)?;
                 ctx.scope.pop();
             }
         }
         _ => {}
     }
+
+    Ok(())
 }
 
 fn process_discriminator(root: &mut Value, ctx: &DereferencerContext) {
@@ -225,7 +236,7 @@ fn process_discriminator(root: &mut Value, ctx: &DereferencerContext) {
                         *reference = format!("#{}", d.clone());
                     } else {
                         for i in (0..=plain.len()).rev() {
-                            if plain.chars().nth(i).map_or(false, |i| i == '/') {
+                            if plain.chars().nth(i).is_some_and(|i| i == '/') {
                                 let prefix = &plain[0..i];
                                 if let Some(prefix) = ctx.resolved.get(prefix) {
                                     *reference =
@@ -253,17 +264,17 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "Infinite reference occurred!")]
     fn test_infinite_ref() {
         let mut spec = spec_from_file("resources/test/json-schemas/07-with-infinite-ref.json");
 
         let client = Client::new();
-        let ss = SchemaStorage::new(&spec, &client);
+        let ss = SchemaStorage::new(&spec, &client).unwrap();
 
-        Dereferencer::options()
+        let result = Dereferencer::options()
             .with_create_internal_references(false)
-            .with_skip_root_internal_references(false)
             .process(&mut spec, &ss);
+
+        assert!(matches!(result, Err(Error::DereferenceError(_))));
     }
 
     #[test]
@@ -271,9 +282,9 @@ mod tests {
         let mut spec = spec_from_file("resources/test/json-schemas/16-string-reference.json");
 
         let client = Client::new();
-        let ss = SchemaStorage::new(&spec, &client);
+        let ss = SchemaStorage::new(&spec, &client).unwrap();
 
-        Dereferencer::options().process(&mut spec, &ss);
+        Dereferencer::options().process(&mut spec, &ss).unwrap();
 
         let expected = json!({
             "$id": "https://example.com/arrays.schema.json",
@@ -303,12 +314,13 @@ mod tests {
         let mut spec = spec_from_file("resources/test/json-schemas/22-discriminator-root.json");
 
         let client = Client::new();
-        let ss = SchemaStorage::new(&spec, &client);
+        let ss = SchemaStorage::new(&spec, &client).unwrap();
 
         Dereferencer::options()
             .with_create_internal_references(true)
             .with_skip_root_internal_references(true)
-            .process(&mut spec, &ss);
+            .process(&mut spec, &ss)
+            .unwrap();
 
         let expected = json!({
           "$id": "https://example.com/arrays.schema.json",
@@ -373,8 +385,8 @@ mod tests {
         let mut spec = spec_from_file("resources/test/json-schemas/06-with-local-reference.json");
 
         let client = Client::new();
-        let ss = SchemaStorage::new(&spec, &client);
-        Dereferencer::options().process(&mut spec, &ss);
+        let ss = SchemaStorage::new(&spec, &client).unwrap();
+        Dereferencer::options().process(&mut spec, &ss).unwrap();
 
         let expected = json!({
           "$id": "https://example.com/arrays.schema.json",
@@ -423,12 +435,13 @@ mod tests {
         let mut spec = spec_from_file("resources/test/json-schemas/20-local-reference.json");
 
         let client = Client::new();
-        let ss = SchemaStorage::new(&spec, &client);
+        let ss = SchemaStorage::new(&spec, &client).unwrap();
 
         Dereferencer::options()
             .with_create_internal_references(true)
             .with_skip_root_internal_references(true)
-            .process(&mut spec, &ss);
+            .process(&mut spec, &ss)
+            .unwrap();
 
         let expected = json!({
           "$id": "https://example.com/arrays.schema.json",
@@ -527,9 +540,9 @@ mod tests {
             spec_from_file("resources/test/json-schemas/05-with-nested-remote-external-ref.json");
 
         let client = Client::new();
-        let ss = SchemaStorage::new(&spec, &client);
+        let ss = SchemaStorage::new(&spec, &client).unwrap();
 
-        Dereferencer::options().process(&mut spec, &ss);
+        Dereferencer::options().process(&mut spec, &ss).unwrap();
 
         let expected = json!({
           "$id": "https://example.com/arrays.schema.json",
@@ -558,9 +571,9 @@ mod tests {
             spec_from_file("resources/test/json-schemas/04-with-nested-external-ref.json");
 
         let client = Client::new();
-        let ss = SchemaStorage::new(&spec, &client);
+        let ss = SchemaStorage::new(&spec, &client).unwrap();
 
-        Dereferencer::options().process(&mut spec, &ss);
+        Dereferencer::options().process(&mut spec, &ss).unwrap();
 
         let expected = json!({
           "$id": "https://example.com/arrays.schema.json",
@@ -612,11 +625,12 @@ mod tests {
             spec_from_file("resources/test/json-schemas/05-with-nested-remote-external-ref.json");
 
         let client = Client::new();
-        let ss = SchemaStorage::new(&spec, &client);
+        let ss = SchemaStorage::new(&spec, &client).unwrap();
 
         Dereferencer::options()
             .with_skip_references(vec!["json.schemastore.org".to_string()])
-            .process(&mut spec, &ss);
+            .process(&mut spec, &ss)
+            .unwrap();
 
         let expected = json!({
           "$id": "https://example.com/arrays.schema.json",
@@ -641,9 +655,9 @@ mod tests {
             spec_from_file("resources/test/json-schemas/03-simple-with-external-ref.json");
 
         let client = Client::new();
-        let ss = SchemaStorage::new(&spec, &client);
+        let ss = SchemaStorage::new(&spec, &client).unwrap();
 
-        Dereferencer::options().process(&mut spec, &ss);
+        Dereferencer::options().process(&mut spec, &ss).unwrap();
 
         let expected = json!({
           "$id": "https://example.com/arrays.schema.json",
@@ -671,9 +685,9 @@ mod tests {
         let mut spec = spec_from_file("resources/test/json-schemas/02-simple-with-reference.json");
 
         let client = Client::new();
-        let ss = SchemaStorage::new(&spec, &client);
+        let ss = SchemaStorage::new(&spec, &client).unwrap();
 
-        Dereferencer::options().process(&mut spec, &ss);
+        Dereferencer::options().process(&mut spec, &ss).unwrap();
 
         let expected = json!({
             "$id": "https://example.com/arrays.schema.json",
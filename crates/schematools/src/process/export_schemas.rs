@@ -0,0 +1,269 @@
+use std::collections::BTreeMap;
+
+use serde_json::{Map, Value};
+
+use crate::{error::Error, schema::Schema, scope::SchemaScope, tools};
+
+const COMPONENTS: &str = "/any:components/any:schemas/definition:*";
+const PREFIX: &str = "#/components/schemas/";
+
+pub struct Exporter;
+
+pub struct ExporterOptions {
+    pub draft: Draft,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Draft {
+    Draft7,
+    Draft201909,
+    Draft202012,
+}
+
+impl Draft {
+    pub fn uri(&self) -> &'static str {
+        match self {
+            Self::Draft7 => "http://json-schema.org/draft-07/schema#",
+            Self::Draft201909 => "https://json-schema.org/draft/2019-09/schema",
+            Self::Draft202012 => "https://json-schema.org/draft/2020-12/schema",
+        }
+    }
+}
+
+impl Exporter {
+    pub fn options() -> ExporterOptions {
+        ExporterOptions {
+            draft: Draft::Draft7,
+        }
+    }
+}
+
+impl ExporterOptions {
+    pub fn with_draft(&mut self, value: Draft) -> &mut Self {
+        self.draft = value;
+        self
+    }
+
+    /// Returns standalone json schema for each of `components/schemas` entries,
+    /// references between components point to sibling `<name>.json` files
+    ///
+    /// Names are used as file names so only names allowed by openapi
+    /// (`^[a-zA-Z0-9.\-_]+$`) are accepted.
+    pub fn process(&self, schema: &Schema) -> Result<BTreeMap<String, Value>, Error> {
+        let mut result = BTreeMap::new();
+
+        tools::each_node(
+            schema.get_body(),
+            &mut SchemaScope::default(),
+            COMPONENTS,
+            |node, parts, _| {
+                if let [name] = parts {
+                    if !is_component_name(name) {
+                        return Err(Error::SchemaInvalidComponentName(name.clone()));
+                    }
+
+                    let mut exported = node.clone();
+                    convert(&mut exported, name);
+
+                    if let Value::Object(ref mut map) = exported {
+                        let mut with_schema = Map::new();
+                        with_schema.insert(
+                            "$schema".to_string(),
+                            Value::String(self.draft.uri().to_string()),
+                        );
+                        with_schema.extend(std::mem::take(map));
+                        *map = with_schema;
+                    }
+
+                    result.insert(name.clone(), exported);
+                }
+
+                Ok(())
+            },
+        )?;
+
+        Ok(result)
+    }
+}
+
+fn is_component_name(name: &str) -> bool {
+    name != "."
+        && name != ".."
+        && !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+}
+
+fn convert(node: &mut Value, current: &str) {
+    match node {
+        Value::Object(map) => {
+            if let Some(Value::String(reference)) = map.get_mut("$ref") {
+                if let Some(new) = rewrite_ref(reference, current) {
+                    *reference = new;
+                }
+            }
+
+            if let Some(Value::Bool(nullable)) = map.remove("nullable") {
+                if nullable {
+                    nullable_type(map);
+                }
+            }
+
+            // openapi 3.0 flags turn bounds exclusive, since draft 6 bounds are given directly
+            for (exclusive, bound) in [
+                ("exclusiveMinimum", "minimum"),
+                ("exclusiveMaximum", "maximum"),
+            ] {
+                if let Some(Value::Bool(flag)) = map.get(exclusive) {
+                    match (flag, map.get(bound).cloned()) {
+                        (true, Some(value)) => {
+                            map.remove(bound);
+                            map.insert(exclusive.to_string(), value);
+                        }
+                        _ => {
+                            map.remove(exclusive);
+                        }
+                    }
+                }
+            }
+
+            if let Some(example) = map.remove("example") {
+                map.entry("examples")
+                    .or_insert_with(|| Value::Array(vec![example]));
+            }
+
+            for (key, value) in map.iter_mut() {
+                // keys of properties are names, not keywords
+                if key == "properties" || key == "patternProperties" {
+                    if let Value::Object(properties) = value {
+                        properties.values_mut().for_each(|v| convert(v, current));
+                    }
+                } else if key != "enum" && key != "const" && key != "examples" {
+                    convert(value, current);
+                }
+            }
+        }
+        Value::Array(list) => list.iter_mut().for_each(|v| convert(v, current)),
+        _ => {}
+    }
+}
+
+fn nullable_type(map: &mut Map<String, Value>) {
+    let null = Value::String("null".to_string());
+
+    match map.get_mut("type") {
+        Some(Value::String(t)) => {
+            let t = Value::String(t.clone());
+            map.insert("type".to_string(), Value::Array(vec![t, null]));
+        }
+        Some(Value::Array(types)) => {
+            if !types.contains(&null) {
+                types.push(null);
+            }
+        }
+        _ => {
+            if let Some(Value::Array(list)) = map.get_mut("anyOf") {
+                list.push(serde_json::json!({"type": "null"}));
+            }
+        }
+    }
+
+    if let Some(Value::Array(values)) = map.get_mut("enum") {
+        if !values.contains(&Value::Null) {
+            values.push(Value::Null);
+        }
+    }
+}
+
+fn rewrite_ref(reference: &str, current: &str) -> Option<String> {
+    let pointer = reference.strip_prefix(PREFIX)?;
+    let (name, rest) = match pointer.split_once('/') {
+        Some((name, rest)) => (name, format!("#/{rest}")),
+        None => (pointer, String::new()),
+    };
+    let name = tools::unescape_key(name);
+
+    if name == current {
+        Some(if rest.is_empty() {
+            "#".to_string()
+        } else {
+            rest
+        })
+    } else {
+        Some(format!("{name}.json{rest}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_export_schemas() {
+        let schema = Schema::from_json(json!({
+            "components": {
+                "schemas": {
+                    "Pet": {
+                        "type": "object",
+                        "properties": {
+                            "category": {"$ref": "#/components/schemas/Category"},
+                            "tag": {"$ref": "#/components/schemas/Category/properties/tag"},
+                            "parent": {"$ref": "#/components/schemas/Pet"},
+                            "nullable": {"type": "string", "nullable": true, "example": "x"},
+                            "status": {"type": "string", "nullable": true, "enum": ["a", "b"]},
+                            "age": {"type": "integer", "minimum": 0, "exclusiveMinimum": true},
+                            "weight": {"type": "number", "maximum": 100, "exclusiveMaximum": false}
+                        }
+                    },
+                    "Category": {
+                        "type": "object",
+                        "properties": {
+                            "tag": {"type": "string"}
+                        }
+                    }
+                }
+            }
+        }));
+
+        let exported = Exporter::options()
+            .with_draft(Draft::Draft202012)
+            .process(&schema)
+            .unwrap();
+
+        assert_eq!(exported.keys().collect::<Vec<_>>(), vec!["Category", "Pet"]);
+        assert_eq!(
+            exported["Pet"],
+            json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "type": "object",
+                "properties": {
+                    "category": {"$ref": "Category.json"},
+                    "tag": {"$ref": "Category.json#/properties/tag"},
+                    "parent": {"$ref": "#"},
+                    "nullable": {"type": ["string", "null"], "examples": ["x"]},
+                    "status": {"type": ["string", "null"], "enum": ["a", "b", null]},
+                    "age": {"type": "integer", "exclusiveMinimum": 0},
+                    "weight": {"type": "number", "maximum": 100}
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_export_schemas_invalid_name() {
+        for name in ["../Pet", "Pet/Dog", ".."] {
+            let schema = Schema::from_json(json!({
+                "components": {"schemas": {name: {"type": "object"}}}
+            }));
+
+            let result = Exporter::options().process(&schema);
+
+            assert!(
+                matches!(result, Err(Error::SchemaInvalidComponentName(n)) if n == name),
+                "{name}"
+            );
+        }
+    }
+}
@@ -0,0 +1,257 @@
+use std::collections::HashSet;
+
+use serde_json::{Map, Value};
+
+use crate::{error::Error, schema::Schema, scope::SchemaScope, tools};
+
+pub struct Flattener;
+
+pub struct FlattenerOptions {
+    pub depth: usize,
+}
+
+impl Flattener {
+    pub fn options() -> FlattenerOptions {
+        FlattenerOptions { depth: 1 }
+    }
+}
+
+impl FlattenerOptions {
+    /// Inline objects nested deeper than given depth are promoted,
+    /// zero promotes every inline object
+    pub fn with_depth(&mut self, value: usize) -> &mut Self {
+        self.depth = value;
+        self
+    }
+
+    /// Moves nested inline objects to `components/schemas` (openapi) or `definitions`
+    /// (json schema) named after property path, ex. `User.address.geo` -> `UserAddressGeo`
+    pub fn process(&self, schema: &mut Schema) -> Result<(), Error> {
+        let body = schema.get_body_mut();
+        if !body.is_object() {
+            return Ok(());
+        }
+
+        let openapi = body.get("openapi").is_some();
+        let (pointer, prefix) = if openapi {
+            ("/components/schemas", "#/components/schemas/")
+        } else {
+            ("/definitions", "#/definitions/")
+        };
+
+        let mut promoted = Promoted {
+            prefix,
+            nodes: vec![],
+            names: HashSet::new(),
+        };
+
+        if let Some(Value::Object(definitions)) = body.pointer_mut(pointer) {
+            promoted.names.extend(definitions.keys().cloned());
+
+            for (name, definition) in definitions.iter_mut() {
+                if let Value::Object(definition) = definition {
+                    self.walk(definition, std::slice::from_ref(name), 0, &mut promoted);
+                }
+            }
+        }
+
+        if let Value::Object(root) = body {
+            if !openapi {
+                let path = root
+                    .get("title")
+                    .and_then(|t| t.as_str())
+                    .map(|t| vec![t.to_string()])
+                    .unwrap_or_default();
+
+                self.walk(root, &path, 0, &mut promoted);
+            }
+
+            if !promoted.nodes.is_empty() {
+                let mut container = root;
+                for key in pointer[1..].split('/') {
+                    container = match container
+                        .entry(key)
+                        .or_insert_with(|| Value::Object(Map::new()))
+                    {
+                        Value::Object(map) => map,
+                        _ => return Err(Error::InvalidOpenapiSchemaError),
+                    };
+                }
+
+                container.extend(promoted.nodes);
+            }
+        }
+
+        Ok(())
+    }
+
+    // children are promoted before parents so names follow the full property path
+    fn walk(
+        &self,
+        schema: &mut Map<String, Value>,
+        path: &[String],
+        depth: usize,
+        promoted: &mut Promoted,
+    ) {
+        if let Some(Value::Object(properties)) = schema.get_mut("properties") {
+            for (name, property) in properties.iter_mut() {
+                self.visit(property, &extend(path, name), depth + 1, promoted);
+            }
+        }
+
+        if let Some(items) = schema.get_mut("items") {
+            self.visit(items, &extend(path, "item"), depth, promoted);
+        }
+
+        if let Some(additional) = schema.get_mut("additionalProperties") {
+            self.visit(additional, &extend(path, "value"), depth, promoted);
+        }
+    }
+
+    fn visit(&self, node: &mut Value, path: &[String], depth: usize, promoted: &mut Promoted) {
+        if let Value::Object(schema) = node {
+            if schema.contains_key("$ref") {
+                return;
+            }
+
+            self.walk(schema, path, depth, promoted);
+
+            if depth > self.depth && matches!(schema.get("properties"), Some(Value::Object(_))) {
+                let reference = promoted.add(path, std::mem::take(node));
+                *node = serde_json::json!({ "$ref": reference });
+            }
+        }
+    }
+}
+
+struct Promoted {
+    prefix: &'static str,
+    nodes: Vec<(String, Value)>,
+    names: HashSet<String>,
+}
+
+impl Promoted {
+    // returns reference to promoted node
+    fn add(&mut self, path: &[String], node: Value) -> String {
+        let namer = SchemaScope::default().namer();
+        let mut name = path.iter().map(|p| namer.convert(p)).collect::<String>();
+
+        while self.names.contains(&name) {
+            name = tools::bump_suffix_number(&name);
+        }
+
+        log::info!("{}{}: promoted", self.prefix, name);
+
+        self.names.insert(name.clone());
+        self.nodes.push((name.clone(), node));
+
+        format!("{}{}", self.prefix, name)
+    }
+}
+
+fn extend(path: &[String], part: &str) -> Vec<String> {
+    let mut result = path.to_vec();
+    result.push(part.to_string());
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_flatten_openapi() {
+        let mut schema = Schema::from_json(json!({
+            "openapi": "3.0.0",
+            "components": {
+                "schemas": {
+                    "User": {
+                        "type": "object",
+                        "properties": {
+                            "home_address": {
+                                "type": "object",
+                                "properties": {
+                                    "geo": {
+                                        "type": "object",
+                                        "properties": {"lat": {"type": "number"}}
+                                    }
+                                }
+                            },
+                            "tags": {
+                                "type": "array",
+                                "items": {
+                                    "type": "object",
+                                    "properties": {
+                                        "meta": {
+                                            "type": "object",
+                                            "properties": {"key": {"type": "string"}}
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    },
+                    "UserHomeAddressGeo": {"type": "string"}
+                }
+            }
+        }));
+
+        Flattener::options().process(&mut schema).unwrap();
+
+        let schemas = &schema.get_body()["components"]["schemas"];
+        assert_eq!(
+            schemas["User"]["properties"]["home_address"]["properties"]["geo"],
+            json!({"$ref": "#/components/schemas/UserHomeAddressGeo2"})
+        );
+        assert_eq!(
+            schemas["UserHomeAddressGeo2"]["properties"]["lat"],
+            json!({"type": "number"})
+        );
+        assert_eq!(
+            schemas["User"]["properties"]["tags"]["items"]["properties"]["meta"],
+            json!({"$ref": "#/components/schemas/UserTagsItemMeta"})
+        );
+        assert!(schemas["User"]["properties"]["home_address"]
+            .get("$ref")
+            .is_none());
+    }
+
+    #[test]
+    fn test_flatten_jsonschema_with_zero_depth() {
+        let mut schema = Schema::from_json(json!({
+            "title": "Order",
+            "type": "object",
+            "properties": {
+                "customer": {
+                    "type": "object",
+                    "properties": {"name": {"type": "string"}}
+                },
+                "note": {"type": "string"}
+            }
+        }));
+
+        Flattener::options()
+            .with_depth(0)
+            .process(&mut schema)
+            .unwrap();
+
+        assert_eq!(
+            schema.get_body(),
+            &json!({
+                "title": "Order",
+                "type": "object",
+                "properties": {
+                    "customer": {"$ref": "#/definitions/OrderCustomer"},
+                    "note": {"type": "string"}
+                },
+                "definitions": {
+                    "OrderCustomer": {
+                        "type": "object",
+                        "properties": {"name": {"type": "string"}}
+                    }
+                }
+            })
+        );
+    }
+}
@@ -0,0 +1,358 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde_json::Value;
+
+use crate::{error::Error, schema::Schema, scope::SchemaScope, storage::ref_to_url, tools};
+
+const CONTAINERS: [&str; 3] = [
+    "/any:components/any:schemas/definition:*",
+    "/any:definitions/definition:*",
+    "/any:$defs/definition:*",
+];
+
+pub struct Grapher;
+
+pub struct GrapherOptions {
+    pub external: bool,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GraphFormat {
+    Dot,
+    Mermaid,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphNode {
+    pub name: String,
+    pub file: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct Graph {
+    pub nodes: BTreeMap<String, GraphNode>,
+    pub edges: BTreeSet<(String, String)>,
+}
+
+impl GrapherOptions {
+    pub fn with_external(&mut self, value: bool) -> &mut Self {
+        self.external = value;
+        self
+    }
+
+    pub fn process(&self, schema: &Schema) -> Result<Graph, Error> {
+        let root = schema.get_body();
+        let mut graph = Graph::default();
+        let mut components: Vec<(String, Vec<String>)> = vec![];
+
+        for container in CONTAINERS {
+            let prefix = container
+                .trim_end_matches("/definition:*")
+                .split('/')
+                .filter_map(|s| s.split(':').nth(1))
+                .collect::<Vec<_>>()
+                .join("/");
+
+            tools::each_node(
+                root,
+                &mut SchemaScope::default(),
+                container,
+                |node, parts, _| {
+                    if let [name] = parts {
+                        let id = format!("#/{}/{}", prefix, tools::escape_key(name));
+
+                        graph.nodes.insert(
+                            id.clone(),
+                            GraphNode {
+                                name: name.clone(),
+                                file: None,
+                            },
+                        );
+
+                        let mut references = vec![];
+                        collect_refs(node, &mut references);
+
+                        components.push((id, references));
+                    }
+
+                    Ok(())
+                },
+            )?;
+        }
+
+        for (id, references) in &components {
+            for reference in references {
+                if let Some(target) = self.target(schema, &mut graph, reference) {
+                    if &target != id {
+                        graph.edges.insert((id.clone(), target));
+                    }
+                }
+            }
+        }
+
+        Ok(graph)
+    }
+
+    fn target(&self, schema: &Schema, graph: &mut Graph, reference: &str) -> Option<String> {
+        if let Some(pointer) = reference.strip_prefix('#') {
+            return Some(internal_target(graph, pointer));
+        }
+
+        let base = schema.get_url();
+        let mut url = ref_to_url(base, reference)?;
+        let fragment = url.fragment().map(|s| s.to_string());
+        url.set_fragment(None);
+
+        let mut document = base.clone();
+        document.set_fragment(None);
+
+        if url == document {
+            return Some(internal_target(graph, &fragment.unwrap_or_default()));
+        }
+
+        if !self.external {
+            return None;
+        }
+
+        let file = base.make_relative(&url).unwrap_or_else(|| url.to_string());
+        let id = format!("{}#{}", file, fragment.clone().unwrap_or_default());
+
+        graph.nodes.entry(id.clone()).or_insert_with(|| GraphNode {
+            name: fragment
+                .as_ref()
+                .and_then(|f| f.rsplit('/').find(|s| !s.is_empty()))
+                .map(tools::unescape_key)
+                .unwrap_or_else(|| file.clone()),
+            file: Some(file.clone()),
+        });
+
+        Some(id)
+    }
+}
+
+impl Grapher {
+    pub fn options() -> GrapherOptions {
+        GrapherOptions { external: true }
+    }
+}
+
+impl Graph {
+    pub fn render(&self, format: GraphFormat) -> String {
+        match format {
+            GraphFormat::Dot => self.to_dot(),
+            GraphFormat::Mermaid => self.to_mermaid(),
+        }
+    }
+
+    fn files(&self) -> BTreeMap<&str, Vec<(&String, &GraphNode)>> {
+        let mut files: BTreeMap<&str, Vec<(&String, &GraphNode)>> = BTreeMap::new();
+
+        for (id, node) in &self.nodes {
+            if let Some(file) = &node.file {
+                files.entry(file).or_default().push((id, node));
+            }
+        }
+
+        files
+    }
+
+    fn to_dot(&self) -> String {
+        let quote = |s: &str| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+        let mut lines = vec!["digraph schema {".to_string()];
+
+        for (id, node) in self.nodes.iter().filter(|(_, n)| n.file.is_none()) {
+            lines.push(format!("  {} [label={}];", quote(id), quote(&node.name)));
+        }
+
+        for (file, nodes) in self.files() {
+            lines.push(format!(
+                "  subgraph {} {{",
+                quote(&format!("cluster_{file}"))
+            ));
+            lines.push(format!("    label={};", quote(file)));
+
+            for (id, node) in nodes {
+                lines.push(format!("    {} [label={}];", quote(id), quote(&node.name)));
+            }
+
+            lines.push("  }".to_string());
+        }
+
+        for (from, to) in &self.edges {
+            lines.push(format!("  {} -> {};", quote(from), quote(to)));
+        }
+
+        lines.push("}".to_string());
+        lines.join("\n")
+    }
+
+    fn to_mermaid(&self) -> String {
+        let quote = |s: &str| format!("\"{}\"", s.replace('"', "#quot;"));
+        let ids = self
+            .nodes
+            .keys()
+            .enumerate()
+            .map(|(i, id)| (id, format!("n{i}")))
+            .collect::<BTreeMap<_, _>>();
+
+        let mut lines = vec!["graph LR".to_string()];
+
+        for (id, node) in self.nodes.iter().filter(|(_, n)| n.file.is_none()) {
+            lines.push(format!("  {}[{}]", ids[id], quote(&node.name)));
+        }
+
+        for (i, (file, nodes)) in self.files().into_iter().enumerate() {
+            lines.push(format!("  subgraph f{}[{}]", i, quote(file)));
+
+            for (id, node) in nodes {
+                lines.push(format!("    {}[{}]", ids[id], quote(&node.name)));
+            }
+
+            lines.push("  end".to_string());
+        }
+
+        for (from, to) in &self.edges {
+            lines.push(format!("  {} --> {}", ids[from], ids[to]));
+        }
+
+        lines.join("\n")
+    }
+}
+
+fn internal_target(graph: &mut Graph, pointer: &str) -> String {
+    let id = format!("#{pointer}");
+
+    let component = graph
+        .nodes
+        .keys()
+        .find(|k| id == **k || id.starts_with(&format!("{k}/")))
+        .cloned();
+
+    component.unwrap_or_else(|| {
+        graph.nodes.insert(
+            id.clone(),
+            GraphNode {
+                name: pointer
+                    .rsplit('/')
+                    .find(|s| !s.is_empty())
+                    .map(tools::unescape_key)
+                    .unwrap_or_else(|| "#".to_string()),
+                file: None,
+            },
+        );
+
+        id
+    })
+}
+
+fn collect_refs(node: &Value, references: &mut Vec<String>) {
+    match node {
+        Value::Object(map) => {
+            if let Some(Value::String(reference)) = map.get("$ref") {
+                references.push(reference.clone());
+            }
+
+            for (key, value) in map {
+                if key == "discriminator" {
+                    if let Some(Value::Object(mapping)) = value.get("mapping") {
+                        references.extend(
+                            mapping
+                                .values()
+                                .filter_map(|v| v.as_str().map(|s| s.to_string())),
+                        );
+                    }
+                } else {
+                    collect_refs(value, references);
+                }
+            }
+        }
+        Value::Array(list) => {
+            for value in list {
+                collect_refs(value, references);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn openapi() -> Schema {
+        Schema::from_json(json!({
+            "components": {
+                "schemas": {
+                    "Pet": {
+                        "type": "object",
+                        "properties": {
+                            "category": {"$ref": "#/components/schemas/Category"},
+                            "owner": {"$ref": "common.yaml#/definitions/Owner"},
+                            "tags": {
+                                "type": "array",
+                                "items": {"$ref": "#/components/schemas/Category/properties/tag"}
+                            }
+                        }
+                    },
+                    "Category": {
+                        "type": "object",
+                        "properties": {
+                            "tag": {"type": "string"}
+                        }
+                    }
+                }
+            }
+        }))
+    }
+
+    #[test]
+    fn test_graph_edges() {
+        let graph = Grapher::options().process(&openapi()).unwrap();
+
+        assert_eq!(
+            graph.edges.into_iter().collect::<Vec<_>>(),
+            vec![
+                (
+                    "#/components/schemas/Pet".to_string(),
+                    "#/components/schemas/Category".to_string()
+                ),
+                (
+                    "#/components/schemas/Pet".to_string(),
+                    "common.yaml#/definitions/Owner".to_string()
+                )
+            ]
+        );
+    }
+
+    #[test]
+    fn test_graph_without_external() {
+        let graph = Grapher::options()
+            .with_external(false)
+            .process(&openapi())
+            .unwrap();
+
+        assert_eq!(graph.nodes.len(), 2);
+        assert_eq!(graph.edges.len(), 1);
+    }
+
+    #[test]
+    fn test_graph_render_mermaid() {
+        let graph = Grapher::options().process(&openapi()).unwrap();
+
+        assert_eq!(
+            graph.render(GraphFormat::Mermaid),
+            [
+                "graph LR",
+                "  n0[\"Category\"]",
+                "  n1[\"Pet\"]",
+                "  subgraph f0[\"common.yaml\"]",
+                "    n2[\"Owner\"]",
+                "  end",
+                "  n1 --> n0",
+                "  n1 --> n2",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -0,0 +1,307 @@
+use std::collections::BTreeMap;
+
+use serde_json::{Map, Value};
+
+use crate::{error::Error, schema::Schema, tools};
+
+const PREFIX: &str = "#/components/schemas/";
+const EXTENSIONS: [&str; 3] = [".json", ".yaml", ".yml"];
+
+// keywords holding data instead of schemas
+const DATA: [&str; 5] = ["enum", "const", "default", "example", "examples"];
+
+pub struct Importer;
+
+pub struct ImporterOptions {
+    pub convert: bool,
+    pub overwrite: bool,
+}
+
+impl Importer {
+    pub fn options() -> ImporterOptions {
+        ImporterOptions {
+            convert: false,
+            overwrite: false,
+        }
+    }
+}
+
+impl ImporterOptions {
+    pub fn with_convert(&mut self, value: bool) -> &mut Self {
+        self.convert = value;
+        self
+    }
+
+    pub fn with_overwrite(&mut self, value: bool) -> &mut Self {
+        self.overwrite = value;
+        self
+    }
+
+    /// Inserts standalone json schemas (by name) into `components/schemas`,
+    /// returns names which collided with different existing schemas
+    pub fn process(
+        &self,
+        schema: &mut Schema,
+        schemas: BTreeMap<String, Value>,
+    ) -> Result<Vec<String>, Error> {
+        let names = schemas.keys().cloned().collect::<Vec<_>>();
+
+        let root = schema
+            .get_body_mut()
+            .as_object_mut()
+            .ok_or(Error::InvalidOpenapiSchemaError)?;
+
+        let components = root
+            .entry("components")
+            .or_insert_with(|| Value::Object(Map::new()))
+            .as_object_mut()
+            .ok_or(Error::InvalidOpenapiSchemaError)?
+            .entry("schemas")
+            .or_insert_with(|| Value::Object(Map::new()))
+            .as_object_mut()
+            .ok_or(Error::InvalidOpenapiSchemaError)?;
+
+        let mut collisions = vec![];
+
+        for (name, mut body) in schemas {
+            if let Value::Object(ref mut map) = body {
+                map.remove("$schema");
+                map.remove("$id");
+            }
+
+            convert(&mut body, &name, &names, self.convert);
+
+            match components.get(&name) {
+                Some(existing) if *existing == body => {}
+                Some(_) => {
+                    collisions.push(name.clone());
+
+                    if self.overwrite {
+                        components.insert(name, body);
+                    }
+                }
+                None => {
+                    components.insert(name, body);
+                }
+            }
+        }
+
+        Ok(collisions)
+    }
+}
+
+fn convert(node: &mut Value, current: &str, names: &[String], downgrade: bool) {
+    match node {
+        Value::Object(map) => {
+            if let Some(Value::String(reference)) = map.get_mut("$ref") {
+                if let Some(new) = rewrite_ref(reference, current, names) {
+                    *reference = new;
+                }
+            }
+
+            if downgrade {
+                downgrade_keywords(map);
+            }
+
+            for (key, value) in map.iter_mut() {
+                // keys of properties are names, not keywords
+                if key == "properties" || key == "patternProperties" {
+                    if let Value::Object(properties) = value {
+                        properties
+                            .values_mut()
+                            .for_each(|v| convert(v, current, names, downgrade));
+                    }
+                } else if !DATA.contains(&key.as_str()) {
+                    convert(value, current, names, downgrade);
+                }
+            }
+        }
+        Value::Array(list) => list
+            .iter_mut()
+            .for_each(|v| convert(v, current, names, downgrade)),
+        _ => {}
+    }
+}
+
+// converts draft keywords which are not supported by openapi 3.0
+fn downgrade_keywords(map: &mut Map<String, Value>) {
+    if let Some(Value::Array(types)) = map.get("type").cloned() {
+        let null = Value::String("null".to_string());
+        let rest = types
+            .iter()
+            .filter(|t| **t != null)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if rest.len() < types.len() {
+            map.insert("nullable".to_string(), Value::Bool(true));
+        }
+
+        match rest.as_slice() {
+            [single] => {
+                map.insert("type".to_string(), single.clone());
+            }
+            [] => {
+                map.remove("type");
+            }
+            _ => {
+                map.remove("type");
+                map.insert(
+                    "anyOf".to_string(),
+                    Value::Array(
+                        rest.into_iter()
+                            .map(|t| Value::Object(Map::from_iter([("type".to_string(), t)])))
+                            .collect(),
+                    ),
+                );
+            }
+        }
+    }
+
+    if let Some(Value::Array(examples)) = map.remove("examples") {
+        if let Some(first) = examples.into_iter().next() {
+            map.entry("example").or_insert(first);
+        }
+    }
+
+    if let Some(value) = map.remove("const") {
+        map.insert("enum".to_string(), Value::Array(vec![value]));
+    }
+
+    // since draft 6 exclusive bounds are numbers, openapi 3.0 flags inclusive bounds,
+    // inclusive bound is kept when it is stricter (sign orders bounds the same way)
+    for (exclusive, bound, sign) in [
+        ("exclusiveMinimum", "minimum", 1.0),
+        ("exclusiveMaximum", "maximum", -1.0),
+    ] {
+        if let Some(Value::Number(value)) = map.get(exclusive).cloned() {
+            let inclusive = map.get(bound).and_then(Value::as_f64);
+
+            match (inclusive, value.as_f64()) {
+                (Some(inclusive), Some(value)) if inclusive * sign > value * sign => {
+                    map.remove(exclusive);
+                }
+                _ => {
+                    map.insert(bound.to_string(), Value::Number(value));
+                    map.insert(exclusive.to_string(), Value::Bool(true));
+                }
+            }
+        }
+    }
+}
+
+fn rewrite_ref(reference: &str, current: &str, names: &[String]) -> Option<String> {
+    let (file, fragment) = match reference.split_once('#') {
+        Some((file, fragment)) => (file, fragment),
+        None => (reference, ""),
+    };
+
+    let name = if file.is_empty() {
+        current
+    } else {
+        let stem = EXTENSIONS
+            .iter()
+            .find_map(|e| file.trim_start_matches("./").strip_suffix(e))?;
+
+        names.iter().find(|n| *n == stem)?
+    };
+
+    Some(format!(
+        "{}{}{}",
+        PREFIX,
+        tools::escape_key(name),
+        fragment.trim_end_matches('/')
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn openapi() -> Schema {
+        Schema::from_json(json!({
+            "openapi": "3.0.0",
+            "components": {
+                "schemas": {
+                    "Category": {"type": "string"}
+                }
+            }
+        }))
+    }
+
+    #[test]
+    fn test_import_schemas() {
+        let mut schema = openapi();
+        let schemas = BTreeMap::from([
+            (
+                "Pet".to_string(),
+                json!({
+                    "$schema": "https://json-schema.org/draft/2020-12/schema",
+                    "type": "object",
+                    "properties": {
+                        "category": {"$ref": "Category.json"},
+                        "parent": {"$ref": "#"},
+                        "name": {"type": ["string", "null"], "examples": ["Rex"]},
+                        "kind": {"const": "dog"},
+                        "age": {"type": "integer", "exclusiveMinimum": 0, "default": {"type": ["string", "null"]}},
+                        "weight": {"type": "number", "minimum": 10, "exclusiveMinimum": 5, "exclusiveMaximum": 100},
+                        "external": {"$ref": "common.json#/Owner"}
+                    }
+                }),
+            ),
+            ("Category".to_string(), json!({"type": "string"})),
+        ]);
+
+        let collisions = Importer::options()
+            .with_convert(true)
+            .process(&mut schema, schemas)
+            .unwrap();
+
+        assert!(collisions.is_empty());
+        assert_eq!(
+            schema
+                .get_body()
+                .pointer("/components/schemas/Pet")
+                .unwrap(),
+            &json!({
+                "type": "object",
+                "properties": {
+                    "category": {"$ref": "#/components/schemas/Category"},
+                    "parent": {"$ref": "#/components/schemas/Pet"},
+                    "name": {"type": "string", "nullable": true, "example": "Rex"},
+                    "kind": {"enum": ["dog"]},
+                    "age": {"type": "integer", "minimum": 0, "exclusiveMinimum": true, "default": {"type": ["string", "null"]}},
+                    "weight": {"type": "number", "minimum": 10, "maximum": 100, "exclusiveMaximum": true},
+                    "external": {"$ref": "common.json#/Owner"}
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_import_schemas_collisions() {
+        let mut schema = openapi();
+        let schemas = BTreeMap::from([("Category".to_string(), json!({"type": "integer"}))]);
+
+        let collisions = Importer::options()
+            .process(&mut schema, schemas.clone())
+            .unwrap();
+
+        assert_eq!(collisions, vec!["Category".to_string()]);
+        assert_eq!(
+            schema.get_body().pointer("/components/schemas/Category"),
+            Some(&json!({"type": "string"}))
+        );
+
+        Importer::options()
+            .with_overwrite(true)
+            .process(&mut schema, schemas)
+            .unwrap();
+
+        assert_eq!(
+            schema.get_body().pointer("/components/schemas/Category"),
+            Some(&json!({"type": "integer"}))
+        );
+    }
+}
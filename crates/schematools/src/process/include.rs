@@ -0,0 +1,180 @@
+use serde_json::{Map, Value};
+use url::Url;
+
+use crate::{
+    error::Error,
+    schema::Schema,
+    storage::{ref_to_url, SchemaStorage},
+};
+
+const ASSET_EXTENSIONS: [&str; 3] = ["md", "markdown", "txt"];
+
+pub struct Includer;
+
+pub struct IncluderOptions {
+    pub keyword: String,
+    pub target: String,
+}
+
+impl Includer {
+    pub fn options() -> IncluderOptions {
+        IncluderOptions {
+            keyword: "x-include".to_string(),
+            target: "description".to_string(),
+        }
+    }
+}
+
+impl IncluderOptions {
+    /// Extension holding path of included file
+    pub fn with_keyword(&mut self, value: String) -> &mut Self {
+        self.keyword = value;
+        self
+    }
+
+    /// Field receiving content of included file
+    pub fn with_target(&mut self, value: String) -> &mut Self {
+        self.target = value;
+        self
+    }
+
+    /// Inlines external documentation files into descriptions, both
+    /// `{"x-include": "docs/intro.md"}` and `{"description": {"$ref": "docs/intro.md"}}`
+    /// forms are supported, paths are relative to the schema file
+    ///
+    /// Returns number of included files
+    pub fn process(&self, schema: &mut Schema, storage: &SchemaStorage) -> Result<usize, Error> {
+        let base = schema.get_url().clone();
+
+        self.include(&base, schema.get_body_mut(), storage)
+    }
+
+    fn include(
+        &self,
+        base: &Url,
+        node: &mut Value,
+        storage: &SchemaStorage,
+    ) -> Result<usize, Error> {
+        let mut count = 0;
+
+        match node {
+            Value::Object(map) => {
+                if let Some(url) = self.source(base, map)? {
+                    let content = storage.read(&url)?;
+                    map.remove(&self.keyword);
+                    map.insert(self.target.clone(), Value::String(content));
+                    count += 1;
+                }
+
+                for (_, value) in map.iter_mut() {
+                    count += self.include(base, value, storage)?;
+                }
+            }
+            Value::Array(a) => {
+                for value in a.iter_mut() {
+                    count += self.include(base, value, storage)?;
+                }
+            }
+            _ => {}
+        }
+
+        Ok(count)
+    }
+
+    fn source(&self, base: &Url, map: &Map<String, Value>) -> Result<Option<Url>, Error> {
+        let path = match (map.get(&self.keyword), map.get(&self.target)) {
+            (Some(Value::String(path)), _) => path,
+            (_, Some(Value::Object(target))) if target.len() == 1 => match target.get("$ref") {
+                Some(Value::String(path)) => path,
+                _ => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+
+        ref_to_url(base, path)
+            .filter(|url| map.contains_key(&self.keyword) || is_asset(url))
+            .map(Some)
+            .ok_or_else(|| Error::InvalidReference(path.clone()))
+    }
+}
+
+/// Documentation file which is inlined instead of being resolved as a schema
+pub fn is_asset(url: &Url) -> bool {
+    url.path_segments()
+        .and_then(|mut s| s.next_back())
+        .and_then(|name| name.rsplit_once('.'))
+        .is_some_and(|(_, extension)| ASSET_EXTENSIONS.contains(&extension))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Client;
+    use serde_json::json;
+
+    fn schema(body: Value) -> Schema {
+        let dir = std::env::temp_dir().join("schematools-include");
+        std::fs::create_dir_all(dir.join("docs")).unwrap();
+        std::fs::write(dir.join("docs/intro.md"), "# Intro\n\nLong *story*.\n").unwrap();
+        std::fs::write(dir.join("docs/status.txt"), "Status of order").unwrap();
+
+        let path = dir.join("openapi.json");
+        std::fs::write(&path, body.to_string()).unwrap();
+
+        Schema::load_url(Url::from_file_path(path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_include() {
+        let mut schema = schema(json!({
+            "info": {"title": "test", "x-include": "docs/intro.md"},
+            "components": {
+                "schemas": {
+                    "Order": {
+                        "type": "object",
+                        "properties": {
+                            "status": {
+                                "type": "string",
+                                "description": {"$ref": "./docs/status.txt"}
+                            }
+                        }
+                    }
+                }
+            }
+        }));
+
+        let client = Client::new();
+        let storage = SchemaStorage::new(&schema, &client).unwrap();
+        let count = Includer::options().process(&mut schema, &storage).unwrap();
+
+        assert_eq!(count, 2);
+        assert_eq!(
+            schema.get_body(),
+            &json!({
+                "info": {"title": "test", "description": "# Intro\n\nLong *story*.\n"},
+                "components": {
+                    "schemas": {
+                        "Order": {
+                            "type": "object",
+                            "properties": {
+                                "status": {
+                                    "type": "string",
+                                    "description": "Status of order"
+                                }
+                            }
+                        }
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_is_asset() {
+        assert!(is_asset(&Url::parse("file:///docs/intro.md").unwrap()));
+        assert!(!is_asset(
+            &Url::parse("file:///schemas/order.yaml").unwrap()
+        ));
+        assert!(!is_asset(&Url::parse("file:///docs/").unwrap()));
+    }
+}
@@ -41,6 +41,28 @@ impl Merger {
     }
 }
 
+// allOf is removed after merge so inline nodes are moved out, only referenced ones are copied
+fn take_resolved(
+    node: &mut Value,
+    options: &MergerOptions,
+    scope: &mut SchemaScope,
+    resolver: &SchemaResolver,
+) -> Value {
+    if node.get("$ref").is_none() {
+        let mut node = std::mem::take(node);
+        process_node(&mut node, options, scope, resolver);
+        return node;
+    }
+
+    resolver
+        .resolve(node, scope, |v, ss| {
+            let mut node = v.clone();
+            process_node(&mut node, options, ss, resolver);
+            Ok(node)
+        })
+        .unwrap()
+}
+
 fn process_merge(
     root: &mut Value,
     options: &MergerOptions,
@@ -61,26 +83,16 @@ fn process_merge(
 
             let first = if size == 1 {
                 log::warn!("allOf with one element, skipping");
-                schemas.get_mut(0).unwrap().clone()
+                std::mem::take(schemas.get_mut(0).unwrap())
             } else {
                 log::debug!("{}.allOf", scope);
 
-                let mut first = resolver
-                    .resolve(schemas.get_mut(0).unwrap(), scope, |v, ss| {
-                        let mut node = v.clone();
-                        process_node(&mut node, options, ss, resolver);
-                        Ok(node)
-                    })
-                    .unwrap();
+                let mut first =
+                    take_resolved(schemas.get_mut(0).unwrap(), options, scope, resolver);
 
                 for n in 1..size {
-                    let value = resolver
-                        .resolve(schemas.get_mut(n).unwrap(), scope, |v, ss| {
-                            let mut node = v.clone();
-                            process_node(&mut node, options, ss, resolver);
-                            Ok(node)
-                        })
-                        .unwrap();
+                    let value =
+                        take_resolved(schemas.get_mut(n).unwrap(), options, scope, resolver);
                     merge_values(&mut first, value);
                 }
 
@@ -244,7 +256,7 @@ mod tests {
         let mut schema = Schema::from_json(value);
 
         let client = Client::new();
-        let ss = SchemaStorage::new(&schema, &client);
+        let ss = SchemaStorage::new(&schema, &client).unwrap();
 
         Merger::options().process(&mut schema, &ss);
 
@@ -298,7 +310,7 @@ mod tests {
         let mut schema = Schema::from_json(value);
 
         let client = Client::new();
-        let ss = SchemaStorage::new(&schema, &client);
+        let ss = SchemaStorage::new(&schema, &client).unwrap();
 
         Merger::options().process(&mut schema, &ss);
 
@@ -340,7 +352,7 @@ mod tests {
         let mut schema = Schema::from_json(value);
 
         let client = Client::new();
-        let ss = SchemaStorage::new(&schema, &client);
+        let ss = SchemaStorage::new(&schema, &client).unwrap();
 
         Merger::options().process(&mut schema, &ss);
 
@@ -392,7 +404,7 @@ mod tests {
         let mut schema = Schema::from_json(value);
 
         let client = Client::new();
-        let ss = SchemaStorage::new(&schema, &client);
+        let ss = SchemaStorage::new(&schema, &client).unwrap();
 
         Merger::options().process(&mut schema, &ss);
 
@@ -464,7 +476,7 @@ mod tests {
         let mut schema = Schema::from_json(value);
 
         let client = Client::new();
-        let ss = SchemaStorage::new(&schema, &client);
+        let ss = SchemaStorage::new(&schema, &client).unwrap();
 
         Merger::options().process(&mut schema, &ss);
 
@@ -589,7 +601,7 @@ mod tests {
         let mut schema = Schema::from_json(value);
 
         let client = Client::new();
-        let ss = SchemaStorage::new(&schema, &client);
+        let ss = SchemaStorage::new(&schema, &client).unwrap();
 
         Merger::options().process(&mut schema, &ss);
 
@@ -633,7 +645,7 @@ mod tests {
         let mut schema = Schema::from_json(value);
 
         let client = Client::new();
-        let ss = SchemaStorage::new(&schema, &client);
+        let ss = SchemaStorage::new(&schema, &client).unwrap();
 
         Merger::options().process(&mut schema, &ss);
 
@@ -1,12 +1,27 @@
 #![allow(dead_code)]
 
+pub mod annotate;
 #[cfg(feature = "semver")]
 pub mod bump_openapi;
+pub mod compose;
 pub mod dereference;
+pub mod export_schemas;
+pub mod flatten;
+pub mod graph;
+pub mod import_schemas;
+pub mod include;
 pub mod merge_allof;
 pub mod merge_openapi;
 pub mod name;
+pub mod normalize;
 pub mod patch;
+pub mod redact;
+pub mod rename_schema;
+pub mod servers;
+pub mod sort;
+pub mod strict;
+pub mod transform;
+pub mod usages;
 
 use serde_json::Value;
 use url::Url;
@@ -0,0 +1,255 @@
+use crate::error::Error;
+use crate::process::name::jsonschema;
+use crate::{schema::Schema, scope::SchemaNamingStrategy, scope::SchemaScope, tools};
+use serde_json::Value;
+
+pub struct ComponentsNamer;
+
+pub struct ComponentsNamerOptions {
+    pub overwrite: bool,
+    pub overwrite_ambiguous: bool,
+    pub naming_strategy: SchemaNamingStrategy,
+}
+
+impl ComponentsNamer {
+    pub fn options() -> ComponentsNamerOptions {
+        ComponentsNamerOptions {
+            overwrite: false,
+            overwrite_ambiguous: false,
+            naming_strategy: SchemaNamingStrategy::Default,
+        }
+    }
+}
+
+impl ComponentsNamerOptions {
+    pub fn with_overwrite(&mut self, value: bool) -> &mut Self {
+        self.overwrite = value;
+        self
+    }
+
+    pub fn with_overwrite_ambiguous(&mut self, value: bool) -> &mut Self {
+        self.overwrite_ambiguous = value;
+        self
+    }
+
+    pub fn with_naming_strategy(&mut self, value: SchemaNamingStrategy) -> &mut Self {
+        self.naming_strategy = value;
+        self
+    }
+
+    pub fn process(&self, schema: &mut Schema) -> Result<(), Error> {
+        let mut scope = SchemaScope::new(self.naming_strategy.clone());
+
+        self.process_root(schema.get_body_mut(), &mut scope)
+    }
+
+    /// Titles untitled inline schemas of schemas, parameters, headers,
+    /// responses and request bodies components
+    pub fn process_root(&self, root: &mut Value, scope: &mut SchemaScope) -> Result<(), Error> {
+        let options = jsonschema::NamerOptions {
+            overwrite: self.overwrite,
+            overwrite_ambiguous: self.overwrite_ambiguous,
+            base_name: None,
+        };
+
+        tools::each_node_mut(
+            root,
+            scope,
+            "/any:components/any:schemas/definition:*",
+            |node, parts, ctx| {
+                if let [key] = parts {
+                    ctx.glue(key);
+                    jsonschema::name_schema(node, ctx, &options)?;
+                    ctx.pop();
+                }
+
+                Ok(())
+            },
+        )?;
+
+        tools::each_node_mut(
+            root,
+            scope,
+            "/any:components/any:responses/definition:*/any:content/any:*/any:schema",
+            |node, parts, ctx| {
+                if let [key, _] = parts {
+                    ctx.glue(key).glue("response");
+                    jsonschema::name_schema(node, ctx, &options)?;
+                    ctx.reduce(2);
+                }
+
+                Ok(())
+            },
+        )?;
+
+        tools::each_node_mut(
+            root,
+            scope,
+            "/any:components/any:responses/definition:*/any:headers/definition:*",
+            |node, parts, ctx| {
+                if let [key, header] = parts {
+                    ctx.glue(key).glue(header).glue("header");
+                    name_parameter_schemas(node, ctx, &options)?;
+                    ctx.reduce(3);
+                }
+
+                Ok(())
+            },
+        )?;
+
+        tools::each_node_mut(
+            root,
+            scope,
+            "/any:components/any:requestBodies/definition:*/any:content/any:*/any:schema",
+            |node, parts, ctx| {
+                if let [key, _] = parts {
+                    ctx.glue(key).glue("request");
+                    jsonschema::name_schema(node, ctx, &options)?;
+                    ctx.reduce(2);
+                }
+
+                Ok(())
+            },
+        )?;
+
+        // same glue as parameters extracted during codegen: name + location
+        tools::each_node_mut(
+            root,
+            scope,
+            "/any:components/any:parameters/definition:*",
+            |node, parts, ctx| {
+                if let [key] = parts {
+                    let name = node
+                        .get("name")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or(key)
+                        .to_string();
+                    let kind = node
+                        .get("in")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("parameter")
+                        .to_string();
+
+                    ctx.glue(&name).glue(&kind);
+                    name_parameter_schemas(node, ctx, &options)?;
+                    ctx.reduce(2);
+                }
+
+                Ok(())
+            },
+        )?;
+
+        tools::each_node_mut(
+            root,
+            scope,
+            "/any:components/any:headers/definition:*",
+            |node, parts, ctx| {
+                if let [key] = parts {
+                    ctx.glue(key).glue("header");
+                    name_parameter_schemas(node, ctx, &options)?;
+                    ctx.reduce(2);
+                }
+
+                Ok(())
+            },
+        )?;
+
+        Ok(())
+    }
+}
+
+// parameter or header object keeps schema directly or in content map
+fn name_parameter_schemas(
+    node: &mut Value,
+    scope: &mut SchemaScope,
+    options: &jsonschema::NamerOptions,
+) -> Result<(), Error> {
+    if let Some(schema) = node.get_mut("schema") {
+        jsonschema::name_schema(schema, scope, options)?;
+    }
+
+    if let Some(Value::Object(content)) = node.get_mut("content") {
+        for media in content.values_mut() {
+            if let Some(schema) = media.get_mut("schema") {
+                jsonschema::name_schema(schema, scope, options)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_name_components() {
+        let mut schema = Schema::from_json(json!({
+            "paths": {
+                "/users": {"get": {}}
+            },
+            "components": {
+                "parameters": {
+                    "Filter": {
+                        "in": "query",
+                        "name": "filter",
+                        "schema": {
+                            "type": "object",
+                            "properties": {"name": {"type": "string"}}
+                        }
+                    }
+                },
+                "headers": {
+                    "RateLimit": {
+                        "schema": {
+                            "type": "object",
+                            "properties": {"remaining": {"type": "integer"}}
+                        }
+                    }
+                },
+                "responses": {
+                    "Error": {
+                        "content": {
+                            "application/json": {
+                                "schema": {
+                                    "oneOf": [
+                                        {"type": "object", "properties": {"code": {"type": "string"}}},
+                                        {"type": "object", "properties": {"message": {"type": "string"}}}
+                                    ]
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }));
+
+        ComponentsNamer::options()
+            .with_overwrite_ambiguous(true)
+            .process(&mut schema)
+            .unwrap();
+
+        let body = schema.get_body();
+        assert_eq!(
+            body.pointer("/components/parameters/Filter/schema/title"),
+            Some(&json!("FilterQuery"))
+        );
+        assert_eq!(
+            body.pointer("/components/headers/RateLimit/schema/title"),
+            Some(&json!("RateLimitHeader"))
+        );
+        assert_eq!(
+            body.pointer("/components/responses/Error/content/application~1json/schema/title"),
+            Some(&json!("ErrorResponse"))
+        );
+        assert_eq!(
+            body.pointer(
+                "/components/responses/Error/content/application~1json/schema/oneOf/0/title"
+            ),
+            Some(&json!("ErrorResponseOption1"))
+        );
+        assert!(body.pointer("/paths/~1users/get/operationId").is_none());
+    }
+}
@@ -5,11 +5,14 @@ use inflector::Inflector;
 use regex::Regex;
 
 pub struct Endpoint {
-    original: String,
     version: Option<String>,
     method: String,
+    // custom method of rpc-style path, ex. search in /users:search
+    verb: Option<String>,
     resources: Vec<String>,
     identifiers: Vec<String>,
+    // path ends with identifier of single resource
+    single: bool,
 }
 
 impl Endpoint {
@@ -29,33 +32,47 @@ impl Endpoint {
             return Err(Error::EndpointValidation { method, path });
         }
 
-        let mut parts = path.split('/').collect::<Vec<&str>>();
+        // custom method is separated with colon from last segment
+        let (path, verb) = match path.rsplit_once(':') {
+            Some((p, v)) if !v.contains('/') && !v.contains('}') && !v.is_empty() => {
+                (p.to_string(), Some(v.to_string()))
+            }
+            _ => (path, None),
+        };
+
+        let mut parts = path
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<&str>>();
         let mut version = None;
 
-        if VERSION.is_match(parts.first().unwrap()) {
+        if parts.first().is_some_and(|p| VERSION.is_match(p)) {
             version = Some(parts.first().unwrap().to_string());
             parts = parts.drain(1..).collect(); // shift vectors
         }
 
-        // todo: optimize
-        let resources: Vec<String> = parts
-            .clone()
-            .into_iter()
-            .filter(|s| !s.starts_with('{'))
-            .map(|s| s.to_string())
-            .collect();
-        let identifiers: Vec<String> = parts
-            .into_iter()
-            .filter(|s| s.starts_with('{'))
-            .map(|s| s.to_string())
-            .collect();
+        let mut resources: Vec<String> = vec![];
+        let mut identifiers: Vec<String> = vec![];
+
+        for part in &parts {
+            let (resource, identifier) = split_segment(part);
+
+            if let Some(resource) = resource {
+                resources.push(resource);
+            }
+
+            if let Some(identifier) = identifier {
+                identifiers.push(identifier);
+            }
+        }
 
         Ok(Endpoint {
-            original: original_path,
             version,
             method,
+            verb,
             resources,
             identifiers,
+            single: parts.last().is_some_and(|p| p.contains('{')),
         })
     }
 
@@ -66,10 +83,11 @@ impl Endpoint {
             parts.push(v);
         }
 
-        parts.push(
-            match self.method.as_str() {
+        parts.push(match &self.verb {
+            Some(verb) => verb.to_camel_case(),
+            None => match self.method.as_str() {
                 "get" => {
-                    if !self.original.ends_with('}') {
+                    if !self.single {
                         "list"
                     } else {
                         "get"
@@ -80,7 +98,7 @@ impl Endpoint {
                 m => m,
             }
             .to_string(),
-        );
+        });
 
         let mut resources: Vec<String> = vec![];
         for (i, resource) in self.resources.iter().enumerate() {
@@ -91,6 +109,8 @@ impl Endpoint {
                     if i < self.identifiers.len() {
                         // has identifier
                         singularize(processed)
+                    } else if self.verb.is_some() {
+                        processed
                     } else {
                         match self.method.as_str() {
                             "post" => singularize(processed),
@@ -120,6 +140,30 @@ impl Endpoint {
     }
 }
 
+// splits segment into literal resource name and identifier,
+// ex. {id}, report-{year} or {name}.json
+fn split_segment(segment: &str) -> (Option<String>, Option<String>) {
+    lazy_static! {
+        static ref IDENTIFIER: Regex = Regex::new(r"\{[^}]*\}").unwrap();
+    }
+
+    let identifier = IDENTIFIER.find(segment).map(|m| m.as_str().to_string());
+    let literal = IDENTIFIER.replace_all(segment, " ");
+    let literal = literal
+        .split(|c: char| c.is_whitespace() || c == '.' || c == '-' || c == '_')
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>();
+
+    // extension like {name}.json is not a resource
+    let resource = match (&identifier, literal.as_slice()) {
+        (_, []) => None,
+        (Some(_), _) if segment.starts_with('{') => None,
+        _ => Some(literal.join("-")),
+    };
+
+    (resource, identifier)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -163,4 +207,20 @@ mod tests {
             expected
         );
     }
+
+    #[test_case( "post".to_string(), "/users:search".to_string(), "searchUsers".to_string(); "endpoint name rpc test 1" )]
+    #[test_case( "post".to_string(), "/v1/users/{id}:activate".to_string(), "v1ActivateUser".to_string(); "endpoint name rpc test 2" )]
+    #[test_case( "get".to_string(), "/users:batchGet".to_string(), "batchGetUsers".to_string(); "endpoint name rpc test 3" )]
+    #[test_case( "get".to_string(), "/orgs/{orgId}/repos/{repoId}/issues".to_string(), "listOrgRepoIssues".to_string(); "endpoint name nested test 1" )]
+    #[test_case( "get".to_string(), "/orgs/{orgId}/repos/{repoId}/issues/{issueId}".to_string(), "getOrgRepoIssue".to_string(); "endpoint name nested test 2" )]
+    #[test_case( "get".to_string(), "/user.profiles/{id}".to_string(), "getUserProfile".to_string(); "endpoint name dots test 1" )]
+    #[test_case( "get".to_string(), "/files/{name}.json".to_string(), "getFile".to_string(); "endpoint name dots test 2" )]
+    #[test_case( "get".to_string(), "/users/{id}/avatar-{size}".to_string(), "getUserAvatar".to_string(); "endpoint name dashes test 1" )]
+    #[test_case( "delete".to_string(), "/user_groups/{id}".to_string(), "deleteUserGroup".to_string(); "endpoint name underscores test 1" )]
+    fn test_operation_name_non_rest(method: String, path: String, expected: String) {
+        assert_eq!(
+            Endpoint::new(method, path).unwrap().get_operation_id(false),
+            expected
+        );
+    }
 }
@@ -1,8 +1,10 @@
+pub mod components;
 pub mod endpoint;
 pub mod jsonschema;
 pub mod openapi;
 pub mod word;
 
+pub use self::components::ComponentsNamer;
 pub use self::jsonschema::JsonSchemaNamer;
 pub use self::openapi::OpenapiNamer;
 
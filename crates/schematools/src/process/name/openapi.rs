@@ -1,9 +1,8 @@
 use crate::error::Error;
-use crate::process::name::jsonschema;
 use crate::{schema::Schema, scope::SchemaNamingStrategy, scope::SchemaScope, tools};
 use serde_json::Value;
 
-use super::endpoint;
+use super::{components::ComponentsNamer, endpoint};
 
 pub struct OpenapiNamer;
 
@@ -51,80 +50,10 @@ impl OpenapiNamerOptions {
 
         let mut scope = SchemaScope::new(self.naming_strategy.clone());
 
-        tools::each_node_mut(
-            root,
-            &mut scope,
-            "/any:components/any:schemas/definition:*",
-            |node, parts, ctx| {
-                if let [key] = parts {
-                    ctx.glue(key);
-
-                    jsonschema::name_schema(
-                        node,
-                        ctx,
-                        &jsonschema::NamerOptions {
-                            overwrite: self.overwrite,
-                            overwrite_ambiguous: self.overwrite_ambiguous,
-                            base_name: None,
-                        },
-                    )?;
-
-                    ctx.pop();
-                }
-
-                Ok(())
-            },
-        )?;
-
-        tools::each_node_mut(
-            root,
-            &mut scope,
-            "/any:components/any:responses/definition:*/any:content/any:*/any:schema",
-            |node, parts, ctx| {
-                if let [key, _] = parts {
-                    ctx.glue(key).glue("response");
-
-                    jsonschema::name_schema(
-                        node,
-                        ctx,
-                        &jsonschema::NamerOptions {
-                            overwrite: self.overwrite,
-                            overwrite_ambiguous: self.overwrite_ambiguous,
-                            base_name: None,
-                        },
-                    )?;
-
-                    ctx.reduce(2);
-                }
-
-                Ok(())
-            },
-        )?;
-
-        tools::each_node_mut(
-            root,
-            &mut scope,
-            "/any:components/any:requestBodies/definition:*/any:content/any:*/any:schema",
-            |node, parts, ctx| {
-                if let [key, _] = parts {
-                    ctx.glue(key).glue("request");
-
-                    jsonschema::name_schema(
-                        node,
-                        ctx,
-                        &jsonschema::NamerOptions {
-                            overwrite: self.overwrite,
-                            overwrite_ambiguous: self.overwrite_ambiguous,
-                            base_name: None,
-                        },
-                    )?;
-
-                    ctx.reduce(2);
-                }
-
-                Ok(())
-            },
-        )?;
+        ComponentsNamer::options()
+            .with_overwrite(self.overwrite)
+            .with_overwrite_ambiguous(self.overwrite_ambiguous)
+            .process_root(root, &mut scope)?;
 
         tools::each_node_mut(
             root,
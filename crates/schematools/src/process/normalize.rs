@@ -0,0 +1,216 @@
+use serde_json::{Map, Value};
+
+use crate::schema::Schema;
+
+// keywords holding data instead of schemas
+const DATA: [&str; 5] = ["enum", "const", "default", "example", "examples"];
+
+// keywords which only document schema and do not change its meaning
+const ANNOTATIONS: [&str; 4] = ["title", "description", "$comment", "deprecated"];
+
+pub struct Normalizer;
+
+pub struct NormalizerOptions;
+
+impl Normalizer {
+    pub fn options() -> NormalizerOptions {
+        NormalizerOptions
+    }
+}
+
+impl NormalizerOptions {
+    /// Rewrites optional enums written as `enum: [..., null]`, `type: [..., "null"]` or
+    /// `oneOf`/`anyOf` with null variant to single form: `type`, `enum` without null
+    /// and `nullable: true`, returns number of rewritten nodes
+    pub fn process(&self, schema: &mut Schema) -> usize {
+        walk(schema.get_body_mut())
+    }
+}
+
+fn walk(node: &mut Value) -> usize {
+    match node {
+        Value::Object(map) => {
+            let nested = map
+                .iter_mut()
+                .filter(|(k, _)| !DATA.contains(&k.as_str()) && !k.starts_with("x-"))
+                .map(|(_, v)| walk(v))
+                .sum::<usize>();
+
+            let collapsed = collapse_null_variant(map);
+            let normalized = normalize_enum(map, collapsed);
+
+            nested + usize::from(collapsed || normalized)
+        }
+        Value::Array(list) => list.iter_mut().map(walk).sum(),
+        _ => 0,
+    }
+}
+
+// oneOf: [{enum: [...]}, {type: null}] is replaced by the enum variant
+fn collapse_null_variant(map: &mut Map<String, Value>) -> bool {
+    for keyword in ["oneOf", "anyOf"] {
+        let enum_variant = match map.get(keyword) {
+            Some(Value::Array(variants)) if variants.len() == 2 => {
+                match (is_null(&variants[0]), is_null(&variants[1])) {
+                    (true, false) => 1,
+                    (false, true) => 0,
+                    _ => continue,
+                }
+            }
+            _ => continue,
+        };
+
+        let variant = match &map[keyword][enum_variant] {
+            Value::Object(variant) if variant.get("enum").is_some() => variant.clone(),
+            _ => continue,
+        };
+
+        map.remove(keyword);
+        for (key, value) in variant {
+            map.entry(key).or_insert(value);
+        }
+        map.insert("nullable".to_string(), Value::Bool(true));
+
+        return true;
+    }
+
+    false
+}
+
+fn normalize_enum(map: &mut Map<String, Value>, collapsed: bool) -> bool {
+    let values = match map.get("enum") {
+        Some(Value::Array(values)) if values.iter().any(|v| !v.is_null()) => values,
+        _ => return false,
+    };
+
+    let null_value = values.iter().any(|v| v.is_null());
+    let null_type = match map.get("type") {
+        Some(Value::Array(types)) => types.iter().any(|t| t == "null"),
+        _ => false,
+    };
+
+    if !null_value && !null_type && !matches!(map.get("type"), Some(Value::Array(_)) | None) {
+        return collapsed;
+    }
+
+    let values = values
+        .iter()
+        .filter(|v| !v.is_null())
+        .cloned()
+        .collect::<Vec<_>>();
+
+    let type_ = match map.get("type") {
+        Some(Value::String(type_)) => Some(type_.clone()),
+        Some(Value::Array(types)) => {
+            let types = types
+                .iter()
+                .filter_map(|t| t.as_str())
+                .filter(|t| *t != "null")
+                .collect::<Vec<_>>();
+
+            match types[..] {
+                [type_] => Some(type_.to_string()),
+                _ => infer_type(&values),
+            }
+        }
+        _ => infer_type(&values),
+    };
+
+    match type_ {
+        Some(type_) => map.insert("type".to_string(), Value::String(type_)),
+        None => map.remove("type"),
+    };
+    map.insert("enum".to_string(), Value::Array(values));
+
+    if null_value || null_type {
+        map.insert("nullable".to_string(), Value::Bool(true));
+    }
+
+    true
+}
+
+fn infer_type(values: &[Value]) -> Option<String> {
+    if values.iter().all(|v| v.is_string()) {
+        Some("string".to_string())
+    } else if values.iter().all(|v| v.is_i64() || v.is_u64()) {
+        Some("integer".to_string())
+    } else if values.iter().all(|v| v.is_number()) {
+        Some("number".to_string())
+    } else {
+        None
+    }
+}
+
+fn is_null(node: &Value) -> bool {
+    let map = match node {
+        Value::Object(map) => map,
+        _ => return false,
+    };
+
+    let mut keys = map.keys().filter(|k| !ANNOTATIONS.contains(&k.as_str()));
+
+    match (keys.next().map(|k| k.as_str()), keys.next()) {
+        (Some("type"), None) => map["type"] == "null",
+        (Some("const"), None) => map["const"].is_null(),
+        (Some("enum"), None) => map["enum"] == Value::Array(vec![Value::Null]),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_nullable_enum_idioms_are_normalized_equally() {
+        let mut schema = Schema::from_json(json!({
+            "definitions": {
+                "nullInEnum": {"type": "string", "enum": ["a", "b", null]},
+                "nullInType": {"type": ["string", "null"], "enum": ["a", "b", null]},
+                "nullable": {"type": "string", "enum": ["a", "b"], "nullable": true},
+                "oneOf": {"oneOf": [{"type": "null"}, {"type": "string", "enum": ["a", "b"]}]},
+                "anyOf": {"anyOf": [{"type": "string", "enum": ["a", "b"]}, {"const": null}]},
+                "untyped": {"enum": [null, "a", "b"]}
+            }
+        }));
+
+        let normalized = Normalizer::options().process(&mut schema);
+
+        assert_eq!(normalized, 5);
+
+        for (name, definition) in schema.get_body()["definitions"].as_object().unwrap() {
+            assert_eq!(
+                definition,
+                &json!({"type": "string", "enum": ["a", "b"], "nullable": true}),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_not_nullable_enum_and_data_are_kept() {
+        let original = json!({
+            "properties": {
+                "status": {"type": "string", "enum": ["a"]},
+                "choice": {"oneOf": [{"type": "null"}, {"type": "string"}]},
+                "mixed": {"enum": [1, "a", null]}
+            },
+            "examples": [{"enum": ["a", null]}]
+        });
+        let mut schema = Schema::from_json(original.clone());
+
+        let normalized = Normalizer::options().process(&mut schema);
+
+        assert_eq!(normalized, 1);
+        assert_eq!(
+            schema.get_body()["properties"]["mixed"],
+            json!({"enum": [1, "a"], "nullable": true})
+        );
+        assert_eq!(
+            schema.get_body()["properties"]["status"],
+            original["properties"]["status"]
+        );
+        assert_eq!(schema.get_body()["examples"], original["examples"]);
+    }
+}
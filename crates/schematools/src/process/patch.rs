@@ -1,12 +1,21 @@
-use crate::schema::Schema;
-use crate::{error::Error, schema::path_to_url};
+use std::collections::HashMap;
+
+use crate::error::Error;
+#[cfg(feature = "json-patch")]
+use crate::schema::{path_to_url, Schema};
+#[cfg(feature = "json-patch")]
+use crate::scope::SchemaScope;
+use crate::tools;
 
 #[cfg(feature = "json-patch")]
 use json_patch::{diff, patch, Patch};
 use serde::Serialize;
-use serde_json::{from_value, Value};
+#[cfg(feature = "json-patch")]
+use serde_json::from_value;
+use serde_json::Value;
 
 #[derive(Copy, Clone, Debug, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
 pub enum Operation {
     Add,
     Remove,
@@ -23,6 +32,9 @@ pub enum Action {
 
     /// Apply inline patch
     Inline(PatchInlineOpts),
+
+    /// Apply inline patch to each node matched by selector
+    Select(PatchSelectOpts),
 }
 
 #[derive(Clone, Debug)]
@@ -49,6 +61,21 @@ pub struct PatchInlineOpts {
     pub value: Option<Value>,
 }
 
+#[derive(Clone, Debug)]
+pub struct PatchSelectOpts {
+    /// Operation add/remove/replace
+    pub op: Operation,
+
+    /// Path of nodes to patch, ex. /paths/*/*[has=responses][missing=x-owner]
+    pub selector: String,
+
+    /// Json path relative to matched node
+    pub path: String,
+
+    /// Json value, strings may contain {{method}}, {{path}}, {{pointer}}, {{name}} or {{N}} placeholders
+    pub value: Option<Value>,
+}
+
 #[cfg(feature = "json-patch")]
 pub fn execute(schema: &mut Schema, action: &Action) -> Result<(), Error> {
     match action {
@@ -75,5 +102,173 @@ pub fn execute(schema: &mut Schema, action: &Action) -> Result<(), Error> {
 
             patch(schema.get_body_mut(), &p).map_err(Error::JsonPatchError)
         }
+        Action::Select(s) => tools::each_node_mut(
+            schema.get_body_mut(),
+            &mut SchemaScope::default(),
+            &s.selector,
+            |node, parts, _| {
+                let context = context(&s.selector, parts);
+                let value = s.value.as_ref().map(|v| render(v, &context)).transpose()?;
+
+                let p: Patch = from_value(serde_json::json!([{
+                    "op": s.op,
+                    "path": s.path,
+                    "value": value,
+                }]))
+                .map_err(Error::SerdeJsonError)?;
+
+                patch(node, &p).map_err(Error::JsonPatchError)
+            },
+        ),
+    }
+}
+
+// placeholders available for matched node: indexed wildcard parts, json pointer
+// and path/method of operation
+fn context(selector: &str, parts: &[String]) -> HashMap<String, String> {
+    let mut pointer = vec![];
+    let mut wildcards = parts.iter();
+
+    for segment in selector.trim_matches('/').split('/') {
+        let key = segment.split('[').next().unwrap_or_default();
+        let key = key.split_once(':').map(|(_, k)| k).unwrap_or(key);
+
+        match key {
+            "*" | "**" => {
+                if let Some(part) = wildcards.next() {
+                    if !part.is_empty() {
+                        pointer.push(if key == "*" {
+                            tools::escape_key(part)
+                        } else {
+                            part.clone()
+                        });
+                    }
+                }
+            }
+            k => pointer.push(k.to_string()),
+        }
+    }
+
+    let mut context = parts
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (i.to_string(), p.clone()))
+        .collect::<HashMap<_, _>>();
+
+    if let Some(name) = parts.last() {
+        context.insert("name".to_string(), name.clone());
+    }
+
+    if let [paths, path, method, ..] = pointer.as_slice() {
+        if paths == "paths" {
+            context.insert("path".to_string(), tools::unescape_key(path));
+            context.insert("method".to_string(), method.to_string());
+        }
+    }
+
+    context.insert("pointer".to_string(), format!("/{}", pointer.join("/")));
+
+    context
+}
+
+fn render(value: &Value, context: &HashMap<String, String>) -> Result<Value, Error> {
+    Ok(match value {
+        Value::String(s) => {
+            let mut result = String::new();
+            let mut rest = s.as_str();
+
+            while let Some((before, after)) = rest.split_once("{{") {
+                let (key, after) = after
+                    .split_once("}}")
+                    .ok_or_else(|| Error::CannotFillParameters(s.clone()))?;
+
+                result.push_str(before);
+                result.push_str(
+                    context
+                        .get(key.trim())
+                        .ok_or_else(|| Error::CannotFillParameters(key.trim().to_string()))?,
+                );
+                rest = after;
+            }
+
+            result.push_str(rest);
+
+            Value::String(result)
+        }
+        Value::Array(list) => Value::Array(
+            list.iter()
+                .map(|v| render(v, context))
+                .collect::<Result<_, _>>()?,
+        ),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| render(v, context).map(|v| (k.clone(), v)))
+                .collect::<Result<_, _>>()?,
+        ),
+        v => v.clone(),
+    })
+}
+
+#[cfg(test)]
+#[cfg(feature = "json-patch")]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_patch_select_with_template() {
+        let mut schema = Schema::from_json(json!({
+            "paths": {
+                "/users/{id}": {
+                    "parameters": [],
+                    "get": {"responses": {}},
+                    "delete": {"responses": {}, "x-owner": "admins"}
+                }
+            }
+        }));
+
+        execute(
+            &mut schema,
+            &Action::Select(PatchSelectOpts {
+                op: Operation::Add,
+                selector: "/paths/*/*[has=responses][missing=x-owner]".to_string(),
+                path: "/x-owner".to_string(),
+                value: Some(json!({"team": "users", "source": "{{method}} {{path}}"})),
+            }),
+        )
+        .unwrap();
+
+        assert_eq!(
+            schema.get_body(),
+            &json!({
+                "paths": {
+                    "/users/{id}": {
+                        "parameters": [],
+                        "get": {
+                            "responses": {},
+                            "x-owner": {"team": "users", "source": "get /users/{id}"}
+                        },
+                        "delete": {"responses": {}, "x-owner": "admins"}
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_patch_select_unknown_placeholder() {
+        let mut schema = Schema::from_json(json!({"definitions": {"a": {}}}));
+
+        let result = execute(
+            &mut schema,
+            &Action::Select(PatchSelectOpts {
+                op: Operation::Add,
+                selector: "/definitions/*".to_string(),
+                path: "/title".to_string(),
+                value: Some(json!("{{method}}")),
+            }),
+        );
+
+        assert!(result.is_err());
     }
 }
@@ -0,0 +1,239 @@
+use regex::Regex;
+use serde_json::{Map, Value};
+
+use crate::{error::Error, schema::Schema};
+
+const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+const MASK: &str = "***";
+
+pub struct Redactor;
+
+pub struct RedactorOptions {
+    pub extensions: Vec<Regex>,
+    pub descriptions: Vec<Regex>,
+    pub flag: String,
+    pub tags: Vec<String>,
+}
+
+impl Redactor {
+    pub fn options() -> RedactorOptions {
+        RedactorOptions {
+            extensions: vec![],
+            descriptions: vec![],
+            flag: "x-sensitive".to_string(),
+            tags: vec![],
+        }
+    }
+}
+
+impl RedactorOptions {
+    /// Extensions with names matching any of patterns are removed
+    pub fn with_extensions(&mut self, value: &[String]) -> Result<&mut Self, Error> {
+        self.extensions = compile(value)?;
+        Ok(self)
+    }
+
+    /// Descriptions matching any of patterns are removed
+    pub fn with_descriptions(&mut self, value: &[String]) -> Result<&mut Self, Error> {
+        self.descriptions = compile(value)?;
+        Ok(self)
+    }
+
+    /// Extension flagging properties which examples should be masked
+    pub fn with_flag(&mut self, value: String) -> &mut Self {
+        self.flag = value;
+        self
+    }
+
+    /// Operations with any of tags are removed
+    pub fn with_tags(&mut self, value: Vec<String>) -> &mut Self {
+        self.tags = value;
+        self
+    }
+
+    pub fn process(&self, schema: &mut Schema) -> Result<(), Error> {
+        let root = schema.get_body_mut();
+
+        if let Some(Value::Object(paths)) = root.get_mut("paths") {
+            self.remove_operations(paths);
+        }
+
+        self.redact(root);
+
+        Ok(())
+    }
+
+    fn remove_operations(&self, paths: &mut Map<String, Value>) {
+        if self.tags.is_empty() {
+            return;
+        }
+
+        for (path, item) in paths.iter_mut() {
+            if let Value::Object(item) = item {
+                for method in METHODS {
+                    let tagged = item
+                        .get(method)
+                        .and_then(|o| o.get("tags"))
+                        .and_then(|t| t.as_array())
+                        .is_some_and(|t| {
+                            t.iter().any(|t| {
+                                t.as_str().is_some_and(|t| self.tags.iter().any(|x| x == t))
+                            })
+                        });
+
+                    if tagged {
+                        log::info!("/paths/{}/{}: removed", path, method);
+                        item.remove(method);
+                    }
+                }
+            }
+        }
+
+        // path items without operations are removed too
+        paths.retain(|_, item| {
+            item.as_object()
+                .is_none_or(|i| METHODS.iter().any(|m| i.contains_key(*m)))
+        });
+    }
+
+    fn redact(&self, node: &mut Value) {
+        match node {
+            Value::Object(map) => {
+                if map.get(&self.flag) == Some(&Value::Bool(true)) {
+                    mask(map);
+                }
+
+                if map
+                    .get("description")
+                    .and_then(|d| d.as_str())
+                    .is_some_and(|d| self.descriptions.iter().any(|r| r.is_match(d)))
+                {
+                    map.remove("description");
+                }
+
+                map.retain(|key, _| {
+                    !(key.starts_with("x-") && self.extensions.iter().any(|r| r.is_match(key)))
+                });
+
+                for (key, value) in map.iter_mut() {
+                    // keys of properties are names, not keywords
+                    if key == "properties" {
+                        if let Value::Object(properties) = value {
+                            properties.values_mut().for_each(|v| self.redact(v));
+                        }
+                    } else {
+                        self.redact(value);
+                    }
+                }
+            }
+            Value::Array(list) => list.iter_mut().for_each(|v| self.redact(v)),
+            _ => {}
+        }
+    }
+}
+
+// string examples are masked, others are removed
+fn mask(map: &mut Map<String, Value>) {
+    for key in ["example", "default"] {
+        match map.get(key) {
+            Some(Value::String(_)) => {
+                map.insert(key.to_string(), Value::String(MASK.to_string()));
+            }
+            Some(_) => {
+                map.remove(key);
+            }
+            None => {}
+        }
+    }
+
+    if let Some(Value::Array(examples)) = map.get_mut("examples") {
+        examples.retain(|e| e.is_string());
+        examples
+            .iter_mut()
+            .for_each(|e| *e = Value::String(MASK.to_string()));
+    }
+}
+
+fn compile(patterns: &[String]) -> Result<Vec<Regex>, Error> {
+    patterns
+        .iter()
+        .map(|p| Regex::new(p).map_err(|_| Error::IncorrectFilterError(p.clone())))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_redact() {
+        let mut schema = Schema::from_json(json!({
+            "paths": {
+                "/users": {
+                    "get": {"tags": ["users"], "x-internal-owner": "team-a"},
+                    "delete": {"tags": ["internal"]}
+                },
+                "/admin": {
+                    "parameters": [],
+                    "post": {"tags": ["internal", "admin"]}
+                }
+            },
+            "components": {
+                "schemas": {
+                    "User": {
+                        "description": "TODO: internal use only",
+                        "x-go-type": "User",
+                        "properties": {
+                            "description": {"type": "string", "description": "Public"},
+                            "password": {
+                                "type": "string",
+                                "x-sensitive": true,
+                                "example": "secret",
+                                "examples": ["secret", 1]
+                            }
+                        }
+                    }
+                }
+            }
+        }));
+
+        Redactor::options()
+            .with_tags(vec!["internal".to_string()])
+            .with_extensions(&["^x-internal".to_string()])
+            .unwrap()
+            .with_descriptions(&["(?i)internal".to_string()])
+            .unwrap()
+            .process(&mut schema)
+            .unwrap();
+
+        assert_eq!(
+            schema.get_body(),
+            &json!({
+                "paths": {
+                    "/users": {
+                        "get": {"tags": ["users"]}
+                    }
+                },
+                "components": {
+                    "schemas": {
+                        "User": {
+                            "x-go-type": "User",
+                            "properties": {
+                                "description": {"type": "string", "description": "Public"},
+                                "password": {
+                                    "type": "string",
+                                    "x-sensitive": true,
+                                    "example": "***",
+                                    "examples": ["***"]
+                                }
+                            }
+                        }
+                    }
+                }
+            })
+        );
+    }
+}
@@ -0,0 +1,325 @@
+use std::path::Path;
+
+use serde_json::{Map, Value};
+
+use crate::{error::Error, schema::Schema, tools};
+
+const PREFIX: &str = "#/components/schemas/";
+
+// keywords holding data instead of schemas
+const DATA: [&str; 5] = ["enum", "const", "default", "example", "examples"];
+
+pub struct Renamer;
+
+pub struct RenamerOptions {
+    pub from: String,
+    pub to: String,
+}
+
+impl Renamer {
+    pub fn options(from: String, to: String) -> RenamerOptions {
+        RenamerOptions { from, to }
+    }
+}
+
+impl RenamerOptions {
+    /// Renames component and rewrites all internal `$ref` and `discriminator.mapping`
+    /// entries pointing to it, returns number of rewritten references
+    pub fn process(&self, schema: &mut Schema) -> Result<usize, Error> {
+        let schemas = schema
+            .get_body_mut()
+            .pointer_mut("/components/schemas")
+            .and_then(|s| s.as_object_mut())
+            .ok_or_else(|| Error::SchemaPropertyNotAvailable("components/schemas".to_string()))?;
+
+        if !schemas.contains_key(&self.from) {
+            return Err(Error::SchemaPropertyNotAvailable(format!(
+                "components/schemas/{}",
+                self.from
+            )));
+        }
+
+        if schemas.contains_key(&self.to) {
+            return Err(Error::SchemaRenameConflict(self.to.clone()));
+        }
+
+        // rebuild map to keep position of renamed component
+        *schemas = std::mem::take(schemas)
+            .into_iter()
+            .map(|(k, v)| {
+                if k == self.from {
+                    (self.to.clone(), v)
+                } else {
+                    (k, v)
+                }
+            })
+            .collect::<Map<_, _>>();
+
+        let mut count = 0;
+        self.rewrite(schema.get_body_mut(), &mut count);
+
+        Ok(count)
+    }
+
+    /// Rewrites references to component of `document` in text of file placed in `base`
+    /// directory, formatting of text is left intact
+    ///
+    /// File part of every reference is resolved against `base` and compared with canonical
+    /// path of `document`, so same named files in other directories are not affected.
+    pub fn process_text(&self, text: &str, base: &Path, document: &Path) -> (String, usize) {
+        let document = document.canonicalize().ok();
+
+        let needle = format!("{}{}", PREFIX, tools::escape_key(&self.from));
+        let replacement = format!("{}{}", PREFIX, tools::escape_key(&self.to));
+
+        let mut result = String::with_capacity(text.len());
+        let mut count = 0;
+        let mut rest = text;
+
+        while let Some(index) = rest.find(&needle) {
+            let (before, after) = rest.split_at(index);
+            let after = &after[needle.len()..];
+
+            // file part of reference starts after quote, whitespace or colon
+            let file = before
+                .rsplit(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | ':'))
+                .next()
+                .unwrap_or_default();
+
+            let boundary = after.chars().next().is_none_or(|c| !is_name_char(c));
+            let same_document = !file.is_empty()
+                && document.is_some()
+                && base.join(file).canonicalize().ok() == document;
+
+            result.push_str(before);
+            if boundary && same_document {
+                result.push_str(&replacement);
+                count += 1;
+            } else {
+                result.push_str(&needle);
+            }
+
+            rest = after;
+        }
+
+        result.push_str(rest);
+
+        (result, count)
+    }
+
+    fn rewrite(&self, node: &mut Value, count: &mut usize) {
+        match node {
+            Value::Object(map) => {
+                if let Some(Value::String(reference)) = map.get_mut("$ref") {
+                    if let Some(new) = self.reference(reference) {
+                        *reference = new;
+                        *count += 1;
+                    }
+                }
+
+                if let Some(Value::Object(mapping)) = map
+                    .get_mut("discriminator")
+                    .and_then(|d| d.get_mut("mapping"))
+                {
+                    for value in mapping.values_mut() {
+                        if let Value::String(reference) = value {
+                            // mapping accepts plain schema names too
+                            if *reference == self.from {
+                                reference.clone_from(&self.to);
+                                *count += 1;
+                            } else if let Some(new) = self.reference(reference) {
+                                *reference = new;
+                                *count += 1;
+                            }
+                        }
+                    }
+                }
+
+                for (key, value) in map.iter_mut() {
+                    if key != "discriminator" && !DATA.contains(&key.as_str()) {
+                        self.rewrite(value, count);
+                    }
+                }
+            }
+            Value::Array(list) => list.iter_mut().for_each(|v| self.rewrite(v, count)),
+            _ => {}
+        }
+    }
+
+    fn reference(&self, reference: &str) -> Option<String> {
+        let rest = reference
+            .strip_prefix(PREFIX)?
+            .strip_prefix(&tools::escape_key(&self.from))?;
+
+        if rest.is_empty() || rest.starts_with('/') {
+            Some(format!("{}{}{}", PREFIX, tools::escape_key(&self.to), rest))
+        } else {
+            None
+        }
+    }
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '.' | '-' | '_' | '~')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_rename_schema() {
+        let mut schema = Schema::from_json(json!({
+            "components": {
+                "schemas": {
+                    "Pet": {
+                        "oneOf": [
+                            {"$ref": "#/components/schemas/Dog"},
+                            {"$ref": "#/components/schemas/DogOwner"}
+                        ],
+                        "discriminator": {
+                            "mapping": {
+                                "dog": "#/components/schemas/Dog",
+                                "puppy": "Dog"
+                            }
+                        }
+                    },
+                    "Dog": {
+                        "properties": {
+                            "name": {"type": "string"},
+                            "alias": {"$ref": "#/components/schemas/Dog/properties/name"}
+                        }
+                    },
+                    "DogOwner": {"type": "string"}
+                }
+            }
+        }));
+
+        let count = Renamer::options("Dog".to_string(), "Hound".to_string())
+            .process(&mut schema)
+            .unwrap();
+
+        assert_eq!(count, 4);
+        assert_eq!(
+            schema.get_body(),
+            &json!({
+                "components": {
+                    "schemas": {
+                        "Pet": {
+                            "oneOf": [
+                                {"$ref": "#/components/schemas/Hound"},
+                                {"$ref": "#/components/schemas/DogOwner"}
+                            ],
+                            "discriminator": {
+                                "mapping": {
+                                    "dog": "#/components/schemas/Hound",
+                                    "puppy": "Hound"
+                                }
+                            }
+                        },
+                        "Hound": {
+                            "properties": {
+                                "name": {"type": "string"},
+                                "alias": {"$ref": "#/components/schemas/Hound/properties/name"}
+                            }
+                        },
+                        "DogOwner": {"type": "string"}
+                    }
+                }
+            })
+        );
+        assert_eq!(
+            schema
+                .get_body()
+                .pointer("/components/schemas")
+                .unwrap()
+                .as_object()
+                .unwrap()
+                .keys()
+                .collect::<Vec<_>>(),
+            vec!["Pet", "Hound", "DogOwner"]
+        );
+    }
+
+    #[test]
+    fn test_rename_schema_conflict() {
+        let mut schema = Schema::from_json(json!({
+            "components": {"schemas": {"Dog": {}, "Cat": {}}}
+        }));
+
+        assert!(Renamer::options("Dog".to_string(), "Cat".to_string())
+            .process(&mut schema)
+            .is_err());
+        assert!(Renamer::options("Cow".to_string(), "Bull".to_string())
+            .process(&mut schema)
+            .is_err());
+    }
+
+    #[test]
+    fn test_rename_schema_in_text() {
+        let dir = std::env::temp_dir().join("schematools-rename-schema-text");
+        let _ = std::fs::remove_dir_all(&dir);
+        for directory in ["specs", "common"] {
+            std::fs::create_dir_all(dir.join(directory)).unwrap();
+        }
+        for file in ["openapi.yaml", "other.yaml", "common/openapi.yaml"] {
+            std::fs::write(dir.join(file), "").unwrap();
+        }
+
+        let text = r#"
+pet:
+  $ref: '../openapi.yaml#/components/schemas/Dog'  # keep comment
+owner:
+  $ref: "../openapi.yaml#/components/schemas/DogOwner"
+other:
+  $ref: ../other.yaml#/components/schemas/Dog
+common:
+  $ref: ../common/openapi.yaml#/components/schemas/Dog
+"#;
+
+        let (result, count) = Renamer::options("Dog".to_string(), "Hound".to_string())
+            .process_text(text, &dir.join("specs"), &dir.join("openapi.yaml"));
+
+        assert_eq!(count, 1);
+        assert_eq!(
+            result,
+            r#"
+pet:
+  $ref: '../openapi.yaml#/components/schemas/Hound'  # keep comment
+owner:
+  $ref: "../openapi.yaml#/components/schemas/DogOwner"
+other:
+  $ref: ../other.yaml#/components/schemas/Dog
+common:
+  $ref: ../common/openapi.yaml#/components/schemas/Dog
+"#
+        );
+    }
+
+    #[test]
+    fn test_rename_schema_skips_data() {
+        let mut schema = Schema::from_json(json!({
+            "components": {
+                "schemas": {
+                    "Dog": {
+                        "type": "object",
+                        "example": {"$ref": "#/components/schemas/Dog"},
+                        "default": {"$ref": "#/components/schemas/Dog"}
+                    },
+                    "Pet": {"$ref": "#/components/schemas/Dog"}
+                }
+            }
+        }));
+
+        let count = Renamer::options("Dog".to_string(), "Hound".to_string())
+            .process(&mut schema)
+            .unwrap();
+
+        assert_eq!(count, 1);
+        assert_eq!(
+            schema.get_body()["components"]["schemas"]["Hound"]["example"],
+            json!({"$ref": "#/components/schemas/Dog"})
+        );
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{error::Error, schema::Schema};
+
+pub struct ServersSetter;
+
+pub struct ServersSetterOptions {
+    pub servers: Vec<Server>,
+    pub append: bool,
+    pub variables: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct Server {
+    pub url: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl ServersSetter {
+    pub fn options() -> ServersSetterOptions {
+        ServersSetterOptions {
+            servers: vec![],
+            append: false,
+            variables: BTreeMap::new(),
+        }
+    }
+}
+
+impl ServersSetterOptions {
+    pub fn with_servers(&mut self, value: Vec<Server>) -> &mut Self {
+        self.servers = value;
+        self
+    }
+
+    pub fn with_append(&mut self, value: bool) -> &mut Self {
+        self.append = value;
+        self
+    }
+
+    pub fn with_variables(&mut self, value: BTreeMap<String, String>) -> &mut Self {
+        self.variables = value;
+        self
+    }
+
+    /// Replaces (or appends to) `servers` of openapi specification, `{name}`
+    /// placeholders of given variables are substituted in url and description
+    pub fn process(&self, schema: &mut Schema) -> Result<(), Error> {
+        let root = schema
+            .get_body_mut()
+            .as_object_mut()
+            .ok_or(Error::InvalidOpenapiSchemaError)?;
+
+        let mut servers = match root.get("servers") {
+            Some(Value::Array(existing)) if self.append => existing.clone(),
+            _ => vec![],
+        };
+
+        for server in &self.servers {
+            let server = Server {
+                url: self.substitute(&server.url),
+                description: server.description.as_ref().map(|d| self.substitute(d)),
+            };
+
+            let value = serde_json::to_value(&server).map_err(Error::SerdeJsonError)?;
+
+            // appending same url again replaces previous entry
+            match servers
+                .iter()
+                .position(|s| s.get("url") == Some(&Value::String(server.url.clone())))
+            {
+                Some(index) => servers[index] = value,
+                None => servers.push(value),
+            }
+        }
+
+        root.insert("servers".to_string(), Value::Array(servers));
+
+        Ok(())
+    }
+
+    fn substitute(&self, phrase: &str) -> String {
+        self.variables
+            .iter()
+            .fold(phrase.to_string(), |result, (name, value)| {
+                result.replace(&format!("{{{name}}}"), value)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn openapi() -> Schema {
+        Schema::from_json(json!({
+            "openapi": "3.0.0",
+            "servers": [
+                {"url": "https://api.example.com", "description": "production"}
+            ]
+        }))
+    }
+
+    #[test]
+    fn test_set_servers() {
+        let mut schema = openapi();
+
+        ServersSetter::options()
+            .with_servers(vec![Server {
+                url: "https://api.{env}.example.com".to_string(),
+                description: Some("{env}".to_string()),
+            }])
+            .with_variables(BTreeMap::from([("env".to_string(), "staging".to_string())]))
+            .process(&mut schema)
+            .unwrap();
+
+        assert_eq!(
+            schema.get_body().get("servers"),
+            Some(&json!([
+                {"url": "https://api.staging.example.com", "description": "staging"}
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_append_servers() {
+        let mut schema = openapi();
+
+        ServersSetter::options()
+            .with_servers(vec![
+                Server {
+                    url: "http://localhost:8080".to_string(),
+                    description: None,
+                },
+                Server {
+                    url: "https://api.example.com".to_string(),
+                    description: Some("prod".to_string()),
+                },
+            ])
+            .with_append(true)
+            .process(&mut schema)
+            .unwrap();
+
+        assert_eq!(
+            schema.get_body().get("servers"),
+            Some(&json!([
+                {"url": "https://api.example.com", "description": "prod"},
+                {"url": "http://localhost:8080"}
+            ]))
+        );
+    }
+}
@@ -0,0 +1,312 @@
+use serde_json::{Map, Value};
+
+use crate::{error::Error, schema::Schema};
+
+const ROOT: [&str; 10] = [
+    "openapi",
+    "info",
+    "jsonSchemaDialect",
+    "servers",
+    "paths",
+    "webhooks",
+    "components",
+    "security",
+    "tags",
+    "externalDocs",
+];
+
+const PATH_ITEM: [&str; 13] = [
+    "$ref",
+    "summary",
+    "description",
+    "servers",
+    "parameters",
+    "get",
+    "put",
+    "post",
+    "delete",
+    "options",
+    "head",
+    "patch",
+    "trace",
+];
+
+const OPERATION: [&str; 12] = [
+    "tags",
+    "summary",
+    "description",
+    "externalDocs",
+    "operationId",
+    "parameters",
+    "requestBody",
+    "responses",
+    "callbacks",
+    "deprecated",
+    "security",
+    "servers",
+];
+
+const COMPONENTS: [&str; 10] = [
+    "schemas",
+    "responses",
+    "parameters",
+    "examples",
+    "requestBodies",
+    "headers",
+    "securitySchemes",
+    "links",
+    "callbacks",
+    "pathItems",
+];
+
+const SCHEMA: [&str; 21] = [
+    "$ref",
+    "title",
+    "description",
+    "type",
+    "format",
+    "enum",
+    "const",
+    "default",
+    "nullable",
+    "readOnly",
+    "writeOnly",
+    "deprecated",
+    "required",
+    "properties",
+    "additionalProperties",
+    "items",
+    "allOf",
+    "oneOf",
+    "anyOf",
+    "not",
+    "discriminator",
+];
+
+// keywords containing map of schemas, ex. properties or definitions
+const SCHEMA_MAPS: [&str; 4] = ["properties", "patternProperties", "definitions", "$defs"];
+
+// keywords containing schema or list of schemas
+const SCHEMA_NESTED: [&str; 6] = [
+    "additionalProperties",
+    "items",
+    "allOf",
+    "oneOf",
+    "anyOf",
+    "not",
+];
+
+pub struct Sorter;
+
+pub struct SorterOptions;
+
+impl Sorter {
+    pub fn options() -> SorterOptions {
+        SorterOptions
+    }
+}
+
+impl SorterOptions {
+    /// Orders paths alphabetically, methods canonically, components by name and
+    /// keys of known objects according to openapi specification, result of
+    /// sorting already sorted document is the same document
+    pub fn process(&self, schema: &mut Schema) -> Result<(), Error> {
+        let root = schema
+            .get_body_mut()
+            .as_object_mut()
+            .ok_or(Error::InvalidOpenapiSchemaError)?;
+
+        order(root, &ROOT);
+
+        if let Some(Value::Object(paths)) = root.get_mut("paths") {
+            sort_by_name(paths);
+            paths.values_mut().for_each(path_item);
+        }
+
+        if let Some(Value::Object(webhooks)) = root.get_mut("webhooks") {
+            sort_by_name(webhooks);
+            webhooks.values_mut().for_each(path_item);
+        }
+
+        if let Some(Value::Object(components)) = root.get_mut("components") {
+            order(components, &COMPONENTS);
+
+            for (kind, entries) in components.iter_mut() {
+                if let Value::Object(entries) = entries {
+                    sort_by_name(entries);
+
+                    match kind.as_str() {
+                        "schemas" => entries.values_mut().for_each(schema_object),
+                        "pathItems" => entries.values_mut().for_each(path_item),
+                        _ => entries.values_mut().for_each(nested_schemas),
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn path_item(node: &mut Value) {
+    if let Value::Object(item) = node {
+        order(item, &PATH_ITEM);
+
+        for (key, value) in item.iter_mut() {
+            if PATH_ITEM[5..].contains(&key.as_str()) {
+                operation(value);
+            } else {
+                nested_schemas(value);
+            }
+        }
+    }
+}
+
+fn operation(node: &mut Value) {
+    if let Value::Object(operation) = node {
+        order(operation, &OPERATION);
+
+        if let Some(Value::Object(responses)) = operation.get_mut("responses") {
+            // status codes ascending, default response at the end
+            let mut entries = std::mem::take(responses).into_iter().collect::<Vec<_>>();
+            entries.sort_by_key(|(code, _)| (code == "default", code.to_uppercase()));
+            *responses = entries.into_iter().collect();
+        }
+
+        operation.values_mut().for_each(nested_schemas);
+    }
+}
+
+// finds schemas nested in parameters, media types, headers etc.
+fn nested_schemas(node: &mut Value) {
+    match node {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if key == "schema" {
+                    schema_object(value);
+                } else {
+                    nested_schemas(value);
+                }
+            }
+        }
+        Value::Array(list) => list.iter_mut().for_each(nested_schemas),
+        _ => {}
+    }
+}
+
+// order of properties is meaningful and left intact
+fn schema_object(node: &mut Value) {
+    match node {
+        Value::Object(map) => {
+            order(map, &SCHEMA);
+
+            for (key, value) in map.iter_mut() {
+                if SCHEMA_MAPS.contains(&key.as_str()) {
+                    if let Value::Object(schemas) = value {
+                        schemas.values_mut().for_each(schema_object);
+                    }
+                } else if SCHEMA_NESTED.contains(&key.as_str()) {
+                    schema_object(value);
+                }
+            }
+        }
+        Value::Array(list) => list.iter_mut().for_each(schema_object),
+        _ => {}
+    }
+}
+
+// known keys first in given order, other keys keep original order
+fn order(map: &mut Map<String, Value>, known: &[&str]) {
+    let mut entries = std::mem::take(map).into_iter().collect::<Vec<_>>();
+    entries.sort_by_key(|(k, _)| known.iter().position(|x| x == k).unwrap_or(known.len()));
+    *map = entries.into_iter().collect();
+}
+
+fn sort_by_name(map: &mut Map<String, Value>) {
+    let mut entries = std::mem::take(map).into_iter().collect::<Vec<_>>();
+    entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+    *map = entries.into_iter().collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn keys(value: &Value) -> Vec<&str> {
+        value
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(|k| k.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_sort() {
+        let mut schema = Schema::from_json(json!({
+            "components": {
+                "schemas": {
+                    "User": {
+                        "properties": {"name": {"type": "string"}, "age": {"type": "integer"}},
+                        "type": "object",
+                        "x-go-type": "User"
+                    },
+                    "Group": {"type": "object", "title": "Group"}
+                }
+            },
+            "paths": {
+                "/users": {
+                    "post": {"responses": {"default": {}, "201": {}}, "operationId": "create"},
+                    "get": {
+                        "responses": {"4XX": {}, "200": {}},
+                        "parameters": [{"name": "x", "in": "query", "schema": {"type": "string", "description": "X"}}]
+                    }
+                },
+                "/groups": {}
+            },
+            "info": {"title": "Api"},
+            "openapi": "3.0.0"
+        }));
+
+        Sorter::options().process(&mut schema).unwrap();
+
+        let body = schema.get_body();
+        assert_eq!(keys(body), vec!["openapi", "info", "paths", "components"]);
+        assert_eq!(keys(&body["paths"]), vec!["/groups", "/users"]);
+        assert_eq!(keys(&body["paths"]["/users"]), vec!["get", "post"]);
+        assert_eq!(
+            keys(&body["paths"]["/users"]["post"]),
+            vec!["operationId", "responses"]
+        );
+        assert_eq!(
+            keys(&body["paths"]["/users"]["post"]["responses"]),
+            vec!["201", "default"]
+        );
+        assert_eq!(
+            keys(&body["paths"]["/users"]["get"]["responses"]),
+            vec!["200", "4XX"]
+        );
+        assert_eq!(
+            keys(&body["paths"]["/users"]["get"]["parameters"][0]["schema"]),
+            vec!["description", "type"]
+        );
+        assert_eq!(keys(&body["components"]["schemas"]), vec!["Group", "User"]);
+        assert_eq!(
+            keys(&body["components"]["schemas"]["User"]),
+            vec!["type", "properties", "x-go-type"]
+        );
+        assert_eq!(
+            keys(&body["components"]["schemas"]["User"]["properties"]),
+            vec!["name", "age"]
+        );
+
+        // idempotent
+        let sorted = body.clone();
+        Sorter::options().process(&mut schema).unwrap();
+        assert_eq!(
+            serde_json::to_string(schema.get_body()).unwrap(),
+            serde_json::to_string(&sorted).unwrap()
+        );
+    }
+}
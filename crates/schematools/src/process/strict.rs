@@ -0,0 +1,167 @@
+//! Strict mode of processors, documents using keywords which codegen cannot express are
+//! rejected with pointer of the node, see `--strict` of codegen
+
+use serde_json::{Map, Value};
+
+use crate::{error::Error, schema::Schema, tools};
+
+// keywords holding data instead of schemas
+const DATA: [&str; 5] = ["enum", "const", "default", "example", "examples"];
+
+// keywords holding schemas by name, names are not keywords
+const NAMED: [&str; 4] = ["properties", "patternProperties", "definitions", "schemas"];
+
+// keywords which change shape of data but are ignored by extraction
+const UNSUPPORTED_KEYWORDS: [&str; 10] = [
+    "if",
+    "then",
+    "else",
+    "not",
+    "anyOf",
+    "dependencies",
+    "dependentSchemas",
+    "unevaluatedProperties",
+    "unevaluatedItems",
+    "prefixItems",
+];
+
+pub(crate) fn unsupported_keyword(schema: &Map<String, Value>) -> Option<String> {
+    UNSUPPORTED_KEYWORDS
+        .iter()
+        .find(|k| schema.contains_key(**k) && required_groups(schema, k).is_none())
+        .map(|k| k.to_string())
+        .or_else(|| {
+            // patternProperties is extracted only when it is the only way to describe object
+            let combined = schema.contains_key("properties")
+                || matches!(schema.get("additionalProperties"), Some(Value::Object(_)));
+
+            (schema.contains_key("patternProperties") && combined)
+                .then(|| "patternProperties".to_string())
+        })
+        .or_else(|| {
+            matches!(schema.get("items"), Some(Value::Array(_))).then(|| "items".to_string())
+        })
+}
+
+// variants of oneOf/anyOf which only require fields, returned as lists of field names
+pub(crate) fn required_groups(schema: &Map<String, Value>, keyword: &str) -> Option<Value> {
+    let variants = schema.get(keyword)?.as_array()?;
+
+    let groups = variants
+        .iter()
+        .map(|variant| match variant.as_object()?.get("required") {
+            Some(Value::Array(fields))
+                if variant.as_object()?.len() == 1 && fields.iter().all(Value::is_string) =>
+            {
+                Some(Value::Array(fields.clone()))
+            }
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    (!groups.is_empty()).then_some(Value::Array(groups))
+}
+
+/// Fails on first node using keyword ignored by extraction
+pub fn check(schema: &Schema) -> Result<(), Error> {
+    walk(schema.get_body(), &mut String::new()).map_err(|(pointer, keyword)| {
+        Error::JsonSchemaUnsupportedKeyword(
+            format!("{}#{}", schema.get_url(), pointer),
+            format!("unsupported keyword {keyword}"),
+        )
+    })
+}
+
+fn walk(node: &Value, pointer: &mut String) -> Result<(), (String, String)> {
+    match node {
+        Value::Object(map) => {
+            if let Some(keyword) = unsupported_keyword(map) {
+                return Err((pointer.clone(), keyword));
+            }
+
+            for (key, value) in map {
+                if DATA.contains(&key.as_str()) || key.starts_with("x-") {
+                    continue;
+                }
+
+                let len = pointer.len();
+                pointer.push('/');
+                pointer.push_str(&tools::escape_key(key));
+
+                match value {
+                    Value::Object(named) if NAMED.contains(&key.as_str()) => each(named, pointer)?,
+                    value => walk(value, pointer)?,
+                }
+
+                pointer.truncate(len);
+            }
+
+            Ok(())
+        }
+        Value::Array(list) => {
+            for (i, value) in list.iter().enumerate() {
+                let len = pointer.len();
+                pointer.push_str(&format!("/{i}"));
+                walk(value, pointer)?;
+                pointer.truncate(len);
+            }
+
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+fn each(named: &Map<String, Value>, pointer: &mut String) -> Result<(), (String, String)> {
+    for (name, value) in named {
+        let len = pointer.len();
+        pointer.push('/');
+        pointer.push_str(&tools::escape_key(name));
+        walk(value, pointer)?;
+        pointer.truncate(len);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_unsupported_keyword_is_reported_with_pointer() {
+        let schema = Schema::from_json(json!({
+            "components": {
+                "schemas": {
+                    "not": {
+                        "type": "object",
+                        "properties": {
+                            "if": {"type": "string", "default": {"not": {}}},
+                            "a/b": {"type": "object", "not": {"required": ["c"]}}
+                        }
+                    }
+                }
+            }
+        }));
+
+        match check(&schema) {
+            Err(Error::JsonSchemaUnsupportedKeyword(location, message)) => {
+                assert!(location.ends_with("#/components/schemas/not/properties/a~1b"));
+                assert_eq!(message, "unsupported keyword not");
+            }
+            _ => panic!("unsupported keyword should be reported"),
+        }
+    }
+
+    #[test]
+    fn test_required_groups_are_supported() {
+        let schema = Schema::from_json(json!({
+            "type": "object",
+            "properties": {"a": {"type": "string"}, "b": {"type": "string"}},
+            "anyOf": [{"required": ["a"]}, {"required": ["b"]}]
+        }));
+
+        assert!(check(&schema).is_ok());
+    }
+}
@@ -0,0 +1,162 @@
+use jmespath::Expression;
+use serde_json::{Map, Value};
+
+use crate::{error::Error, schema::Schema};
+
+pub struct Transformer;
+
+pub struct TransformerOptions {
+    pub expressions: Vec<Expression<'static>>,
+}
+
+impl Transformer {
+    pub fn options() -> TransformerOptions {
+        TransformerOptions {
+            expressions: vec![],
+        }
+    }
+}
+
+impl TransformerOptions {
+    /// JMESPath expressions applied to schema body one after another
+    pub fn with_expressions(&mut self, value: &[String]) -> Result<&mut Self, Error> {
+        self.expressions = value
+            .iter()
+            .map(|e| jmespath::compile(e).map_err(|err| Error::TransformError(err.to_string())))
+            .collect::<Result<_, _>>()?;
+        Ok(self)
+    }
+
+    /// Replaces schema body with result of every expression, keys present in previous
+    /// body keep their order
+    pub fn process(&self, schema: &mut Schema) -> Result<(), Error> {
+        for expr in &self.expressions {
+            let result = expr
+                .search(schema.get_body())
+                .map_err(|e| Error::TransformError(e.to_string()))?;
+
+            if result.is_null() {
+                return Err(Error::TransformError(format!(
+                    "{}: produced null",
+                    expr.as_str()
+                )));
+            }
+
+            let result = serde_json::to_value(&*result).map_err(Error::SerdeJsonError)?;
+            *schema.get_body_mut() = keep_order(schema.get_body(), result);
+        }
+
+        Ok(())
+    }
+}
+
+// jmespath objects are sorted by keys
+fn keep_order(original: &Value, result: Value) -> Value {
+    match (original, result) {
+        (Value::Object(original), Value::Object(mut result)) => {
+            let mut ordered = Map::new();
+
+            for (key, value) in original {
+                if let Some(new) = result.remove(key) {
+                    ordered.insert(key.clone(), keep_order(value, new));
+                }
+            }
+            ordered.extend(result);
+
+            Value::Object(ordered)
+        }
+        (Value::Array(original), Value::Array(result)) => Value::Array(
+            result
+                .into_iter()
+                .enumerate()
+                .map(|(i, value)| match original.get(i) {
+                    Some(previous) => keep_order(previous, value),
+                    None => value,
+                })
+                .collect(),
+        ),
+        (_, result) => result,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn transform(body: Value, expressions: &[&str]) -> Result<Value, Error> {
+        let mut schema = Schema::from_json(body);
+        Transformer::options()
+            .with_expressions(
+                &expressions
+                    .iter()
+                    .map(|e| e.to_string())
+                    .collect::<Vec<_>>(),
+            )?
+            .process(&mut schema)?;
+
+        Ok(schema.get_body().clone())
+    }
+
+    #[test]
+    fn test_expressions_are_applied_in_order() {
+        let body = json!({
+            "openapi": "3.0.0",
+            "info": {"title": "Orders", "version": "1.0.0"},
+            "servers": [{"url": "https://a"}, {"url": "https://b"}],
+            "paths": {}
+        });
+
+        let result = transform(
+            body,
+            &[
+                "merge(@, {info: merge(info, {title: 'Orders (internal)'})})",
+                "merge(@, {servers: servers[:1]})",
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            result,
+            json!({
+                "openapi": "3.0.0",
+                "info": {"title": "Orders (internal)", "version": "1.0.0"},
+                "servers": [{"url": "https://a"}],
+                "paths": {}
+            })
+        );
+    }
+
+    #[test]
+    fn test_keys_keep_order_of_original() {
+        let body = json!({"paths": {"/b": {}, "/a": {}}, "info": {"version": 1}});
+
+        let result = transform(body, &["merge(@, {components: `{}`})"]).unwrap();
+
+        assert_eq!(
+            result.as_object().unwrap().keys().collect::<Vec<_>>(),
+            vec!["paths", "info", "components"]
+        );
+        assert_eq!(
+            result["paths"]
+                .as_object()
+                .unwrap()
+                .keys()
+                .collect::<Vec<_>>(),
+            vec!["/b", "/a"]
+        );
+        assert_eq!(result["info"]["version"], json!(1));
+    }
+
+    #[test]
+    fn test_invalid_expressions() {
+        assert!(matches!(
+            transform(json!({}), &["merge(@"]),
+            Err(Error::TransformError(_))
+        ));
+        assert!(matches!(
+            transform(json!({}), &["missing"]),
+            Err(Error::TransformError(_))
+        ));
+    }
+}
@@ -0,0 +1,243 @@
+use std::collections::BTreeSet;
+
+use serde_json::Value;
+
+use crate::{error::Error, schema::Schema, tools};
+
+pub struct Usages;
+
+pub struct UsagesOptions {
+    pub transitive: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Usage {
+    // json pointer of node containing reference
+    pub pointer: String,
+
+    // referenced json pointer
+    pub target: String,
+
+    // 0 for direct usages, n for usages of components depending on searched one
+    pub depth: usize,
+}
+
+impl Usages {
+    pub fn options() -> UsagesOptions {
+        UsagesOptions { transitive: false }
+    }
+}
+
+impl UsagesOptions {
+    pub fn with_transitive(&mut self, value: bool) -> &mut Self {
+        self.transitive = value;
+        self
+    }
+
+    /// Lists json pointers of all internal references to the node under pointer
+    /// (or any of its children)
+    pub fn process(&self, schema: &Schema, pointer: &str) -> Result<Vec<Usage>, Error> {
+        let root = schema.get_body();
+
+        if root.pointer(pointer).is_none() {
+            return Err(Error::SchemaPropertyNotAvailable(pointer.to_string()));
+        }
+
+        let mut references = vec![];
+        collect_refs(root, &mut String::new(), &mut references);
+
+        let mut result = vec![];
+        let mut visited = BTreeSet::from([pointer.to_string()]);
+        let mut current = vec![pointer.to_string()];
+        let mut depth = 0;
+
+        while !current.is_empty() {
+            let mut next = vec![];
+
+            for searched in &current {
+                for (at, target) in &references {
+                    if target != searched && !target.starts_with(&format!("{searched}/")) {
+                        continue;
+                    }
+
+                    result.push(Usage {
+                        pointer: at.clone(),
+                        target: target.clone(),
+                        depth,
+                    });
+
+                    if let Some(owner) = component(at) {
+                        if visited.insert(owner.clone()) {
+                            next.push(owner);
+                        }
+                    }
+                }
+            }
+
+            if !self.transitive {
+                break;
+            }
+
+            current = next;
+            depth += 1;
+        }
+
+        Ok(result)
+    }
+}
+
+// pointer of reusable component containing node
+fn component(pointer: &str) -> Option<String> {
+    let parts = pointer.split('/').skip(1).collect::<Vec<_>>();
+
+    let len = match parts.as_slice() {
+        ["components", _, _, ..] => 3,
+        ["definitions" | "$defs", _, ..] => 2,
+        _ => return None,
+    };
+
+    Some(format!("/{}", parts[..len].join("/")))
+}
+
+fn collect_refs(node: &Value, pointer: &mut String, references: &mut Vec<(String, String)>) {
+    match node {
+        Value::Object(map) => {
+            if let Some(Value::String(reference)) = map.get("$ref") {
+                if let Some(target) = reference.strip_prefix('#') {
+                    references.push((pointer.clone(), target.to_string()));
+                }
+            }
+
+            for (key, value) in map {
+                let len = pointer.len();
+                pointer.push('/');
+                pointer.push_str(&tools::escape_key(key));
+
+                if key == "discriminator" {
+                    if let Some(Value::Object(mapping)) = value.get("mapping") {
+                        for (name, reference) in mapping {
+                            if let Some(target) =
+                                reference.as_str().and_then(|s| s.strip_prefix('#'))
+                            {
+                                references.push((
+                                    format!("{}/mapping/{}", pointer, tools::escape_key(name)),
+                                    target.to_string(),
+                                ));
+                            }
+                        }
+                    }
+                } else {
+                    collect_refs(value, pointer, references);
+                }
+
+                pointer.truncate(len);
+            }
+        }
+        Value::Array(list) => {
+            for (i, value) in list.iter().enumerate() {
+                let len = pointer.len();
+                pointer.push_str(&format!("/{i}"));
+
+                collect_refs(value, pointer, references);
+
+                pointer.truncate(len);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn openapi() -> Schema {
+        Schema::from_json(json!({
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "responses": {
+                            "200": {
+                                "content": {
+                                    "application/json": {
+                                        "schema": {"$ref": "#/components/schemas/Pet"}
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "Pet": {
+                        "oneOf": [{"$ref": "#/components/schemas/Dog"}],
+                        "discriminator": {
+                            "mapping": {"dog": "#/components/schemas/Dog"}
+                        }
+                    },
+                    "Dog": {
+                        "type": "object",
+                        "properties": {
+                            "category": {"$ref": "#/components/schemas/Category/properties/name"}
+                        }
+                    },
+                    "Category": {
+                        "type": "object",
+                        "properties": {"name": {"type": "string"}}
+                    }
+                }
+            }
+        }))
+    }
+
+    #[test]
+    fn test_direct_usages() {
+        let usages = Usages::options()
+            .process(&openapi(), "/components/schemas/Dog")
+            .unwrap();
+
+        assert_eq!(
+            usages
+                .iter()
+                .map(|u| u.pointer.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "/components/schemas/Pet/oneOf/0",
+                "/components/schemas/Pet/discriminator/mapping/dog"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_transitive_usages() {
+        let usages = Usages::options()
+            .with_transitive(true)
+            .process(&openapi(), "/components/schemas/Category")
+            .unwrap();
+
+        assert_eq!(
+            usages
+                .iter()
+                .map(|u| (u.pointer.as_str(), u.depth))
+                .collect::<Vec<_>>(),
+            vec![
+                ("/components/schemas/Dog/properties/category", 0),
+                ("/components/schemas/Pet/oneOf/0", 1),
+                ("/components/schemas/Pet/discriminator/mapping/dog", 1),
+                (
+                    "/paths/~1pets/get/responses/200/content/application~1json/schema",
+                    2
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_missing_pointer() {
+        assert!(Usages::options()
+            .process(&openapi(), "/components/schemas/Missing")
+            .is_err());
+    }
+}
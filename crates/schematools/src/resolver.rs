@@ -38,7 +38,10 @@ impl<'a> SchemaResolver<'a> {
         match self.storage {
             Some(storage) => match node.as_object().unwrap().get("$ref").unwrap() {
                 Value::String(reference) => {
-                    let mut url = super::storage::ref_to_url(&self.url, reference).unwrap();
+                    span!("resolve", reference = %reference);
+
+                    let mut url = super::storage::ref_to_url(&self.url, reference)
+                        .ok_or_else(|| Error::InvalidReference(reference.to_string()))?;
 
                     let copy = url.clone();
                     let pointer = copy.fragment();
@@ -50,9 +53,10 @@ impl<'a> SchemaResolver<'a> {
                         Some(schema) => match pointer {
                             Some(p) => {
                                 if let Some(s) = schema.get_body().pointer(p) {
-                                    scope.reference(p);
+                                    scope.enter_file(url.as_str()).reference(p);
                                     let result = self.resolve(s, scope, f);
                                     scope.pop();
+                                    scope.leave_file();
                                     result
                                 } else {
                                     log::error!("Cannot resolve: {}", p);
@@ -95,7 +99,10 @@ impl<'a> SchemaResolver<'a> {
         match self.storage {
             Some(storage) => match node.as_object().unwrap().get("$ref").unwrap() {
                 Value::String(reference) => {
-                    let mut url = super::storage::ref_to_url(&self.url, reference).unwrap();
+                    span!("resolve", reference = %reference);
+
+                    let mut url = super::storage::ref_to_url(&self.url, reference)
+                        .ok_or_else(|| Error::InvalidReference(reference.to_string()))?;
 
                     let copy = url.clone();
                     let pointer = copy.fragment();
@@ -107,9 +114,10 @@ impl<'a> SchemaResolver<'a> {
                         Some(schema) => match pointer {
                             Some(p) => {
                                 if let Some(s) = schema.get_body().pointer(p) {
-                                    scope.reference(p);
+                                    scope.enter_file(url.as_str()).reference(p);
                                     let result = f(s, scope);
                                     scope.pop();
+                                    scope.leave_file();
                                     result
                                 } else {
                                     log::error!("Cannot resolve: {}", p);
@@ -0,0 +1,471 @@
+//! Starter documents following conventions expected by processors and codegen templates
+
+use inflector::Inflector;
+use regex::Regex;
+use serde_json::{json, Map, Value};
+
+use crate::{
+    error::Error,
+    process::name::{endpoint::Endpoint, word::singularize},
+    schema::Schema,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecurityScheme {
+    Bearer,
+    ApiKey,
+    Oauth2,
+}
+
+impl SecurityScheme {
+    fn name(&self) -> &'static str {
+        match self {
+            SecurityScheme::Bearer => "bearerAuth",
+            SecurityScheme::ApiKey => "apiKeyAuth",
+            SecurityScheme::Oauth2 => "oauth2",
+        }
+    }
+
+    fn definition(&self) -> Value {
+        match self {
+            SecurityScheme::Bearer => json!({
+                "type": "http",
+                "scheme": "bearer",
+                "bearerFormat": "JWT"
+            }),
+            SecurityScheme::ApiKey => json!({
+                "type": "apiKey",
+                "in": "header",
+                "name": "X-API-Key"
+            }),
+            SecurityScheme::Oauth2 => json!({
+                "type": "oauth2",
+                "flows": {
+                    "clientCredentials": {
+                        "tokenUrl": "https://example.com/oauth/token",
+                        "scopes": {}
+                    }
+                }
+            }),
+        }
+    }
+}
+
+pub struct Scaffold;
+
+pub struct ScaffoldOptions {
+    pub title: String,
+    pub version: String,
+    pub resources: Vec<String>,
+    pub security: Vec<SecurityScheme>,
+    pub servers: Vec<String>,
+}
+
+impl Scaffold {
+    pub fn options() -> ScaffoldOptions {
+        ScaffoldOptions {
+            title: "Service".to_string(),
+            version: "0.1.0".to_string(),
+            resources: vec![],
+            security: vec![SecurityScheme::Bearer],
+            servers: vec![],
+        }
+    }
+}
+
+impl ScaffoldOptions {
+    pub fn with_title(&mut self, value: String) -> &mut Self {
+        self.title = value;
+        self
+    }
+
+    pub fn with_version(&mut self, value: String) -> &mut Self {
+        self.version = value;
+        self
+    }
+
+    /// Plural names of resources, ex. `users` or `order-items`
+    pub fn with_resources(&mut self, value: Vec<String>) -> &mut Self {
+        self.resources = value;
+        self
+    }
+
+    /// Security schemes stubs, any of them is accepted by every operation
+    pub fn with_security(&mut self, value: Vec<SecurityScheme>) -> &mut Self {
+        self.security = value;
+        self
+    }
+
+    pub fn with_servers(&mut self, value: Vec<String>) -> &mut Self {
+        self.servers = value;
+        self
+    }
+
+    /// Openapi specification with collection (`list`, `create`) and item (`get`, `update`,
+    /// `delete`) endpoints of every resource, paginated list models, standard error schema
+    /// and responses, operationIds and titles are the ones `process name` would give
+    pub fn openapi(&self) -> Result<Schema, Error> {
+        lazy_static! {
+            static ref RESOURCE: Regex = Regex::new("^[a-z][a-z0-9]*([-_][a-z0-9]+)*$").unwrap();
+        }
+
+        let mut paths = Map::new();
+        let mut schemas = Map::new();
+        let mut parameters = Map::new();
+        let mut tags = vec![];
+
+        for resource in &self.resources {
+            if !RESOURCE.is_match(resource) {
+                return Err(Error::ScaffoldError(format!(
+                    "resource {resource} has to be lowercase plural noun, ex. order-items"
+                )));
+            }
+            if tags.iter().any(|t: &Value| t["name"] == *resource) {
+                return Err(Error::ScaffoldError(format!(
+                    "resource {resource} is duplicated"
+                )));
+            }
+
+            let model = singularize(resource.to_pascal_case());
+            let noun = model.to_sentence_case().to_lowercase();
+            let id = format!("{}Id", model.to_camel_case());
+            let collection = format!("/{resource}");
+            let item = format!("/{resource}/{{{id}}}");
+
+            let reference = |name: &str| json!({"$ref": format!("#/components/schemas/{name}")});
+            let content = |name: &str| json!({"application/json": {"schema": reference(name)}});
+            let operation = |method: &str,
+                             path: &str,
+                             summary: String|
+             -> Result<Map<String, Value>, Error> {
+                let operation_id =
+                    Endpoint::new(method.to_string(), path.to_string())?.get_operation_id(false);
+
+                let mut operation = Map::new();
+                operation.insert("operationId".to_string(), Value::String(operation_id));
+                operation.insert("summary".to_string(), Value::String(summary));
+                operation.insert("tags".to_string(), json!([resource]));
+                if path == item {
+                    operation.insert(
+                            "parameters".to_string(),
+                            json!([{"$ref": format!("#/components/parameters/{}", id.to_pascal_case())}]),
+                        );
+                }
+                Ok(operation)
+            };
+
+            let mut list = operation("get", &collection, format!("List {resource}"))?;
+            list.insert(
+                "parameters".to_string(),
+                json!([
+                    {"$ref": "#/components/parameters/Limit"},
+                    {"$ref": "#/components/parameters/Offset"}
+                ]),
+            );
+            list.insert(
+                "responses".to_string(),
+                json!({
+                    "200": {"description": format!("List of {resource}"), "content": content(&format!("{model}List"))},
+                    "default": {"$ref": "#/components/responses/Error"}
+                }),
+            );
+
+            let mut create = operation("post", &collection, format!("Create {noun}"))?;
+            create.insert(
+                "requestBody".to_string(),
+                json!({"required": true, "content": content(&model)}),
+            );
+            create.insert(
+                "responses".to_string(),
+                json!({
+                    "201": {"description": format!("Created {noun}"), "content": content(&model)},
+                    "400": {"$ref": "#/components/responses/BadRequest"},
+                    "default": {"$ref": "#/components/responses/Error"}
+                }),
+            );
+
+            let mut get = operation("get", &item, format!("Get {noun}"))?;
+            get.insert(
+                "responses".to_string(),
+                json!({
+                    "200": {"description": model.to_sentence_case(), "content": content(&model)},
+                    "404": {"$ref": "#/components/responses/NotFound"},
+                    "default": {"$ref": "#/components/responses/Error"}
+                }),
+            );
+
+            let mut update = operation("patch", &item, format!("Update {noun}"))?;
+            update.insert(
+                "requestBody".to_string(),
+                json!({"required": true, "content": content(&model)}),
+            );
+            update.insert(
+                "responses".to_string(),
+                json!({
+                    "200": {"description": format!("Updated {noun}"), "content": content(&model)},
+                    "400": {"$ref": "#/components/responses/BadRequest"},
+                    "404": {"$ref": "#/components/responses/NotFound"},
+                    "default": {"$ref": "#/components/responses/Error"}
+                }),
+            );
+
+            let mut delete = operation("delete", &item, format!("Delete {noun}"))?;
+            delete.insert(
+                "responses".to_string(),
+                json!({
+                    "204": {"description": format!("Deleted {noun}")},
+                    "404": {"$ref": "#/components/responses/NotFound"},
+                    "default": {"$ref": "#/components/responses/Error"}
+                }),
+            );
+
+            paths.insert(collection, json!({"get": list, "post": create}));
+            paths.insert(
+                item,
+                json!({
+                    "get": get,
+                    "patch": update,
+                    "delete": delete
+                }),
+            );
+
+            parameters.insert(
+                id.to_pascal_case(),
+                json!({
+                    "name": id,
+                    "in": "path",
+                    "required": true,
+                    "schema": {"type": "string", "format": "uuid"}
+                }),
+            );
+
+            schemas.insert(
+                model.clone(),
+                json!({
+                    "title": model,
+                    "type": "object",
+                    "required": ["id"],
+                    "properties": {
+                        "id": {"type": "string", "format": "uuid", "readOnly": true},
+                        "createdAt": {"type": "string", "format": "date-time", "readOnly": true},
+                        "updatedAt": {"type": "string", "format": "date-time", "readOnly": true}
+                    }
+                }),
+            );
+            schemas.insert(
+                format!("{model}List"),
+                json!({
+                    "title": format!("{model}List"),
+                    "type": "object",
+                    "required": ["items", "total"],
+                    "properties": {
+                        "items": {"type": "array", "items": reference(&model)},
+                        "total": {"type": "integer", "minimum": 0}
+                    }
+                }),
+            );
+
+            tags.push(json!({"name": resource}));
+        }
+
+        schemas.insert(
+            "Error".to_string(),
+            json!({
+                "title": "Error",
+                "type": "object",
+                "required": ["code", "message"],
+                "properties": {
+                    "code": {"type": "string"},
+                    "message": {"type": "string"},
+                    "details": {
+                        "type": "array",
+                        "items": {
+                            "title": "ErrorDetail",
+                            "type": "object",
+                            "required": ["message"],
+                            "properties": {
+                                "field": {"type": "string"},
+                                "message": {"type": "string"}
+                            }
+                        }
+                    }
+                }
+            }),
+        );
+
+        parameters.insert(
+            "Limit".to_string(),
+            json!({
+                "name": "limit",
+                "in": "query",
+                "schema": {"type": "integer", "minimum": 1, "maximum": 100, "default": 20}
+            }),
+        );
+        parameters.insert(
+            "Offset".to_string(),
+            json!({
+                "name": "offset",
+                "in": "query",
+                "schema": {"type": "integer", "minimum": 0, "default": 0}
+            }),
+        );
+
+        let error = |description: &str| {
+            json!({
+                "description": description,
+                "content": {"application/json": {"schema": {"$ref": "#/components/schemas/Error"}}}
+            })
+        };
+
+        let mut components = Map::new();
+        components.insert("schemas".to_string(), Value::Object(schemas));
+        components.insert("parameters".to_string(), Value::Object(parameters));
+        components.insert(
+            "responses".to_string(),
+            json!({
+                "BadRequest": error("Invalid request"),
+                "NotFound": error("Resource not found"),
+                "Error": error("Unexpected error")
+            }),
+        );
+
+        let mut body = Map::new();
+        body.insert("openapi".to_string(), json!("3.0.3"));
+        body.insert(
+            "info".to_string(),
+            json!({"title": self.title, "version": self.version}),
+        );
+
+        if !self.servers.is_empty() {
+            body.insert(
+                "servers".to_string(),
+                self.servers.iter().map(|url| json!({"url": url})).collect(),
+            );
+        }
+
+        if !self.security.is_empty() {
+            body.insert(
+                "security".to_string(),
+                self.security
+                    .iter()
+                    .map(|s| json!({s.name(): []}))
+                    .collect(),
+            );
+            components.insert(
+                "securitySchemes".to_string(),
+                self.security
+                    .iter()
+                    .map(|s| (s.name().to_string(), s.definition()))
+                    .collect::<Map<_, _>>()
+                    .into(),
+            );
+        }
+
+        body.insert("tags".to_string(), Value::Array(tags));
+        body.insert("paths".to_string(), Value::Object(paths));
+        body.insert("components".to_string(), Value::Object(components));
+
+        Ok(Schema::from_json(Value::Object(body)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::process::name::openapi::OpenapiNamer;
+
+    #[test]
+    fn test_openapi() {
+        let schema = Scaffold::options()
+            .with_title("Shop".to_string())
+            .with_resources(vec!["users".to_string(), "order-items".to_string()])
+            .with_security(vec![SecurityScheme::Bearer, SecurityScheme::ApiKey])
+            .openapi()
+            .unwrap();
+
+        let body = schema.get_body();
+
+        assert_eq!(body["info"], json!({"title": "Shop", "version": "0.1.0"}));
+        assert_eq!(
+            body["paths"]
+                .as_object()
+                .unwrap()
+                .keys()
+                .collect::<Vec<_>>(),
+            [
+                "/users",
+                "/users/{userId}",
+                "/order-items",
+                "/order-items/{orderItemId}"
+            ]
+        );
+
+        let ids = |body: &Value| {
+            body["paths"]
+                .as_object()
+                .unwrap()
+                .values()
+                .flat_map(|item| {
+                    item.as_object()
+                        .unwrap()
+                        .values()
+                        .filter_map(|o| o.get("operationId"))
+                        .cloned()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            ids(body),
+            [
+                "listUsers",
+                "createUser",
+                "getUser",
+                "updateUser",
+                "deleteUser",
+                "listOrderItems",
+                "createOrderItem",
+                "getOrderItem",
+                "updateOrderItem",
+                "deleteOrderItem"
+            ]
+        );
+
+        // naming is stable, so generated document is left untouched by namer
+        let mut named = schema.clone();
+        OpenapiNamer::options()
+            .with_overwrite(true)
+            .process(&mut named)
+            .unwrap();
+        assert_eq!(ids(named.get_body()), ids(body));
+
+        let components = &body["components"];
+        assert_eq!(components["schemas"]["OrderItem"]["title"], "OrderItem");
+        assert_eq!(
+            components["schemas"]["OrderItemList"]["properties"]["items"]["items"],
+            json!({"$ref": "#/components/schemas/OrderItem"})
+        );
+        assert_eq!(
+            components["parameters"]["OrderItemId"]["name"],
+            "orderItemId"
+        );
+        assert!(components["schemas"]["Error"].is_object());
+        assert_eq!(
+            body["security"],
+            json!([{"bearerAuth": []}, {"apiKeyAuth": []}])
+        );
+        assert_eq!(components["securitySchemes"]["apiKeyAuth"]["in"], "header");
+    }
+
+    #[test]
+    fn test_openapi_invalid_resources() {
+        for resources in [vec!["Users"], vec!["users/{id}"], vec!["users", "users"]] {
+            assert!(matches!(
+                Scaffold::options()
+                    .with_resources(resources.into_iter().map(String::from).collect())
+                    .openapi(),
+                Err(Error::ScaffoldError(_))
+            ));
+        }
+    }
+}
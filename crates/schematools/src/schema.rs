@@ -1,14 +1,16 @@
 use serde::Deserialize;
 use serde_json::Value;
-use std::{fs, path::PathBuf};
+use std::{fs, path::PathBuf, sync::Arc};
 use url::Url;
 
 use crate::error::Error;
-use crate::{process, Client};
+use crate::scope::SchemaScope;
+use crate::{process, tools, Client};
 
 #[derive(Debug, Clone)]
 pub struct Schema {
-    body: Value,
+    // shared by clones, ex. copies kept in storage and cache, until one of them is modified
+    body: Arc<Value>,
     url: Url,
 }
 
@@ -18,62 +20,15 @@ impl Schema {
         Self::load_url_with_client(url, &client)
     }
 
-    #[allow(unused_variables)]
     pub fn load_url_with_client(url: Url, client: &Client) -> Result<Schema, Error> {
         log::info!("loading: {}", url);
 
-        let (content_type, response) =
-            match url.scheme() {
-                "file" => {
-                    let path = if cfg!(windows) {
-                        let path = url.path();
-                        path[1..path.len()].to_string()
-                    } else {
-                        url.path().to_string()
-                    };
-
-                    let content = fs::read_to_string(&path).map_err(|_| Error::SchemaLoad {
-                        url: url.to_string(),
-                        path,
-                    })?;
-
-                    Ok((None::<String>, content))
-                }
-                #[cfg(feature = "http")]
-                "http" | "https" => {
-                    let response = client.get(url.to_string()).send().map_err(|error| {
-                        Error::SchemaHttpLoad {
-                            url: url.to_string(),
-                            reason: error.to_string(),
-                        }
-                    })?;
-
-                    let content_type = response
-                        .headers()
-                        .get("content-type")
-                        .ok_or_else(|| Error::SchemaHttpLoad {
-                            url: url.to_string(),
-                            reason: "Cannot get content-type header".to_string(),
-                        })?
-                        .to_str()
-                        .unwrap();
-
-                    Ok((Some(content_type.to_string()), response.text().unwrap()))
-                }
-                s => Err(Error::SchemaLoadInvalidScheme {
-                    url: url.to_string(),
-                    scheme: s.to_string(),
-                }),
-            }?;
+        let (content_type, response) = fetch(&url, client)?;
 
         let extension = url
             .path_segments()
-            .map(|c| c.collect::<Vec<_>>())
-            .unwrap()
-            .last()
-            .unwrap()
-            .split('.')
-            .last();
+            .and_then(|mut c| c.next_back())
+            .and_then(|name| name.split('.').next_back());
 
         let is_yaml_extension = if let Some(s) = extension {
             s.contains("yaml")
@@ -104,7 +59,10 @@ impl Schema {
             })?
         };
 
-        Ok(Schema { body, url })
+        Ok(Schema {
+            body: Arc::new(body),
+            url,
+        })
     }
 
     pub fn load_urls(urls: Vec<Url>) -> Result<Schema, Error> {
@@ -115,30 +73,36 @@ impl Schema {
 
     pub fn load_urls_with_client(urls: Vec<Url>, client: &Client) -> Result<Schema, Error> {
         if urls.len() == 1 {
-            return Self::load_url(urls.first().unwrap().clone());
+            return Self::load_url_with_client(urls[0].clone(), client);
         }
 
         let mut bodies: Vec<Value> = Vec::with_capacity(urls.len());
         for url in urls {
-            let data = Self::load_url_with_client(url.clone(), client)?.body;
+            let data = Self::load_url_with_client(url.clone(), client)?.into_body();
             bodies.push(process::rel_to_absolute_refs(&url, data));
         }
 
         Ok(Schema {
-            body: serde_json::json!(bodies),
+            body: Arc::new(serde_json::json!(bodies)),
             url: Url::parse("schema://inline").unwrap(),
         })
     }
 
     pub fn from_json(body: Value) -> Schema {
         Schema {
-            body,
+            body: Arc::new(body),
             url: Url::parse("schema://inline").unwrap(),
         }
     }
 
+    /// Body for modification, it's copied first when shared with other clones of schema
     pub fn get_body_mut(&mut self) -> &mut Value {
-        &mut self.body
+        Arc::make_mut(&mut self.body)
+    }
+
+    /// Owned body, copied only when shared with other clones of schema
+    pub fn into_body(self) -> Value {
+        Arc::try_unwrap(self.body).unwrap_or_else(|body| (*body).clone())
     }
 
     pub fn get_body(&self) -> &Value {
@@ -148,6 +112,117 @@ impl Schema {
     pub fn get_url(&self) -> &Url {
         &self.url
     }
+
+    /// Returns json pointers and values of all nodes matching path,
+    /// `*` matches every key of an object ex. `/components/schemas/*/properties/*`,
+    /// `**` matches any depth and predicates narrow results ex. `/**[has=discriminator]`
+    pub fn select(&self, path: &str) -> Result<Vec<(String, &Value)>, Error> {
+        let segments = path
+            .trim_matches('/')
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>();
+
+        let pattern = segments
+            .iter()
+            .map(|s| format!("any:{s}"))
+            .collect::<Vec<_>>()
+            .join("/");
+
+        if pattern.is_empty() {
+            return Ok(vec![(String::new(), &self.body)]);
+        }
+
+        let mut result = vec![];
+        tools::each_node(
+            &self.body,
+            &mut SchemaScope::default(),
+            &pattern,
+            |node, parts, _| {
+                let mut keys = parts.iter();
+                let pointer = segments
+                    .iter()
+                    .map(|s| match s.split('[').next().unwrap_or_default() {
+                        "*" => keys
+                            .next()
+                            .map(|k| tools::escape_key(k))
+                            .unwrap_or_default(),
+                        "**" => keys.next().cloned().unwrap_or_default(),
+                        s => s.to_string(),
+                    })
+                    .filter(|s| !s.is_empty())
+                    .collect::<Vec<_>>();
+
+                result.push((format!("/{}", pointer.join("/")), node));
+
+                Ok(())
+            },
+        )?;
+
+        Ok(result)
+    }
+}
+
+/// Raw content of file or remote document with its content type
+#[allow(unused_variables)]
+pub fn fetch(url: &Url, client: &Client) -> Result<(Option<String>, String), Error> {
+    match url.scheme() {
+        "file" => {
+            let path = if cfg!(windows) {
+                let path = url.path();
+                path[1..path.len()].to_string()
+            } else {
+                url.path().to_string()
+            };
+
+            let content = fs::read_to_string(&path).map_err(|_| Error::SchemaLoad {
+                url: url.to_string(),
+                path,
+            })?;
+
+            Ok((None::<String>, content))
+        }
+        #[cfg(feature = "http")]
+        "http" | "https" => {
+            let response =
+                client
+                    .get(url.to_string())
+                    .send()
+                    .map_err(|error| Error::SchemaHttpLoad {
+                        url: url.to_string(),
+                        reason: error.to_string(),
+                    })?;
+
+            if !response.status().is_success() {
+                return Err(Error::SchemaHttpStatus {
+                    url: url.to_string(),
+                    status: response.status().as_u16(),
+                });
+            }
+
+            let content_type = response
+                .headers()
+                .get("content-type")
+                .ok_or_else(|| Error::SchemaHttpLoad {
+                    url: url.to_string(),
+                    reason: "Cannot get content-type header".to_string(),
+                })?
+                .to_str()
+                .unwrap_or_default()
+                .to_string();
+
+            let content = response.text().map_err(|error| Error::SchemaHttpLoad {
+                url: url.to_string(),
+                reason: error.to_string(),
+            })?;
+
+            Ok((Some(content_type), content))
+        }
+        s => Err(Error::SchemaLoadInvalidScheme {
+            url: url.to_string(),
+            scheme: s.to_string(),
+        }),
+    }
 }
 
 pub fn path_to_url(path: String) -> Result<Url, Error> {
@@ -177,6 +252,7 @@ pub fn path_to_url(path: String) -> Result<Url, Error> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serde_json::json;
     use test_case::test_case;
 
     #[test]
@@ -308,6 +384,85 @@ mod tests {
         assert!(spec.is_ok());
     }
 
+    #[test]
+    fn test_clone_shares_body_until_modified() {
+        let schema = Schema::from_json(json!({"type": "string"}));
+        let mut cloned = schema.clone();
+        assert!(std::ptr::eq(schema.get_body(), cloned.get_body()));
+
+        cloned.get_body_mut()["type"] = json!("integer");
+        assert!(!std::ptr::eq(schema.get_body(), cloned.get_body()));
+        assert_eq!(schema.get_body(), &json!({"type": "string"}));
+
+        assert_eq!(cloned.into_body(), json!({"type": "integer"}));
+    }
+
+    #[test]
+    fn test_select_wildcards() {
+        let schema = Schema::from_json(serde_json::json!({
+            "components": {
+                "schemas": {
+                    "Pet": {
+                        "properties": {
+                            "name": {"type": "string"},
+                            "a/b": {"type": "number"}
+                        }
+                    },
+                    "Error": {
+                        "type": "string"
+                    }
+                }
+            }
+        }));
+
+        let result = schema
+            .select("/components/schemas/*/properties/*")
+            .unwrap()
+            .into_iter()
+            .map(|(pointer, value)| (pointer, value.clone()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            result,
+            vec![
+                (
+                    "/components/schemas/Pet/properties/name".to_string(),
+                    serde_json::json!({"type": "string"})
+                ),
+                (
+                    "/components/schemas/Pet/properties/a~1b".to_string(),
+                    serde_json::json!({"type": "number"})
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_select_root_and_missing() {
+        let schema = Schema::from_json(serde_json::json!({"type": "object"}));
+
+        assert_eq!(schema.select("/").unwrap().len(), 1);
+        assert!(schema.select("/properties/*").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_select_recursive() {
+        let schema = Schema::from_json(serde_json::json!({
+            "definitions": {
+                "a": {"oneOf": [{"discriminator": {}}]}
+            }
+        }));
+
+        let pointers = schema
+            .select("/**[has=discriminator]")
+            .unwrap()
+            .into_iter()
+            .map(|(pointer, _)| pointer)
+            .collect::<Vec<_>>();
+
+        assert_eq!(pointers, vec!["/definitions/a/oneOf/0".to_string()]);
+    }
+
     #[test_case( "./not-existing.json".to_string() ; "relative" )]
     #[test_case( "../not-existing.json".to_string() ; "relative2" )]
     #[test_case( "not-existing.json".to_string(); "relative3" )]
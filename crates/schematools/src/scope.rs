@@ -1,9 +1,9 @@
 use std::fmt;
 
 use regex::Regex;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::error::Error;
+use crate::{error::Error, tools};
 
 #[derive(Clone, Debug)]
 pub enum SchemaNamingStrategy {
@@ -16,9 +16,11 @@ pub struct SchemaScope {
     scope: Vec<SchemaScopeType>,
     naming_strategy: SchemaNamingStrategy,
     spaces: Vec<Space>,
+    // urls of files entered while resolving references, the last one is current
+    files: Vec<String>,
 }
 
-#[derive(Clone, Debug, Serialize, Eq, PartialEq)]
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
 pub enum Space {
     Tag(String),
     Operation(String),
@@ -58,7 +60,12 @@ impl BasicNamer {
             .collect::<Vec<_>>()
             .join("");
 
-        result
+        // names are used as identifiers which cannot start with digit
+        if result.starts_with(|c: char| c.is_ascii_digit()) {
+            format!("X{result}")
+        } else {
+            result
+        }
     }
 
     pub fn convert(&self, original: &str) -> String {
@@ -102,7 +109,7 @@ impl BasicNamer {
         match self
             .parts
             .iter()
-            .filter(|s| {
+            .rfind(|s| {
                 !matches!(
                     s,
                     SchemaScopeType::Form(_)
@@ -110,7 +117,6 @@ impl BasicNamer {
                         | SchemaScopeType::Reference(_)
                 )
             })
-            .last()
             .unwrap()
         {
             SchemaScopeType::Entity(name) => {
@@ -128,7 +134,7 @@ impl BasicNamer {
                         SchemaScopeType::Entity(t) => Some(t),
                         _ => None,
                     })
-                    .last();
+                    .next_back();
 
                 if let Some(name) = entity {
                     let parts = [&self.split(&name)[..], &self.split(last)[..]].concat();
@@ -164,8 +170,9 @@ impl BasicNamer {
 
     fn split(&self, phrase: &str) -> Vec<String> {
         // todo: refactor
+        let phrase = tools::transliterate(phrase);
         let re = Regex::new(r"[A-Z_]").unwrap();
-        let result = re.replace_all(phrase, " $0");
+        let result = re.replace_all(&phrase, " $0");
 
         let t = result
             .chars()
@@ -189,6 +196,7 @@ impl Default for SchemaScope {
         Self {
             scope: vec![],
             spaces: vec![],
+            files: vec![],
             naming_strategy: SchemaNamingStrategy::Default,
         }
     }
@@ -242,6 +250,39 @@ impl SchemaScope {
         self
     }
 
+    pub fn enter_file(&mut self, url: &str) -> &mut Self {
+        self.files.push(url.to_string());
+        self
+    }
+
+    pub fn leave_file(&mut self) -> &mut Self {
+        self.files.pop();
+        self
+    }
+
+    /// Url of file current node comes from, if known
+    pub fn file(&self) -> Option<&str> {
+        self.files.last().map(|s| s.as_str())
+    }
+
+    /// Json pointer of current node within its file
+    pub fn pointer(&self) -> String {
+        let start = self
+            .scope
+            .iter()
+            .rposition(|s| matches!(s, SchemaScopeType::Reference(_)));
+
+        let (base, rest) = match start.map(|i| (&self.scope[i], &self.scope[i + 1..])) {
+            Some((SchemaScopeType::Reference(reference), rest)) => (reference.clone(), rest),
+            _ => (String::new(), &self.scope[..]),
+        };
+
+        rest.iter()
+            .cloned()
+            .filter_map(scope_to_string)
+            .fold(base, |pointer, part| format!("{pointer}/{part}"))
+    }
+
     pub fn reference(&mut self, reference: &str) {
         self.scope
             .push(SchemaScopeType::Reference(reference.to_string()));
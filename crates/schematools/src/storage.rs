@@ -1,31 +1,184 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
 
-use crate::schema::Schema;
+use crate::error::Error;
+use crate::process::include;
+use crate::schema::{self, Schema};
+use crate::tools::escape_key;
 use crate::Client;
 use serde_json::Value;
 use url::Url;
 
+#[derive(Clone)]
 pub struct SchemaStorage {
     pub schemas: HashMap<Url, Schema>,
+    cache: ResolutionCache,
+    client: Client,
 }
 
-impl SchemaStorage {
-    pub fn new(schema: &Schema, client: &Client) -> Self {
+/// Retries of transient http failures, ex. connection errors or 5xx responses, delay
+/// doubles after every attempt
+#[derive(Debug, Clone)]
+pub struct RetryOptions {
+    retries: u32,
+    backoff: Duration,
+}
+
+impl Default for RetryOptions {
+    fn default() -> Self {
         Self {
-            // saves also schema to storage
-            // replaces all refs to absolutes
-            schemas: extract_schemas(&[schema], client),
+            retries: 0,
+            backoff: Duration::from_millis(500),
+        }
+    }
+}
+
+impl RetryOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_retries(&mut self, value: u32) -> &mut Self {
+        self.retries = value;
+        self
+    }
+
+    /// Delay before first retry
+    pub fn with_backoff(&mut self, value: Duration) -> &mut Self {
+        self.backoff = value;
+        self
+    }
+
+    fn run<T, F>(&self, url: &Url, mut load: F) -> Result<T, Error>
+    where
+        F: FnMut() -> Result<T, Error>,
+    {
+        let mut attempt = 0;
+
+        loop {
+            match load() {
+                Err(e) if attempt < self.retries && is_transient(&e) => {
+                    let delay = self.backoff.saturating_mul(2u32.saturating_pow(attempt));
+                    log::warn!("{url}: {e}, retrying in {delay:?}");
+
+                    thread::sleep(delay);
+                    attempt += 1;
+                }
+                result => return result,
+            }
         }
     }
+}
+
+fn is_transient(error: &Error) -> bool {
+    match error {
+        Error::SchemaHttpLoad { .. } => true,
+        Error::SchemaHttpStatus { status, .. } => *status == 429 || *status >= 500,
+        _ => false,
+    }
+}
 
-    pub fn new_multi(schemas: &[&Schema], client: &Client) -> Self {
+/// Files loaded by url, shared between storages so every file is read once per pipeline
+#[derive(Debug, Clone, Default)]
+pub struct ResolutionCache {
+    schemas: Arc<Mutex<HashMap<Url, Schema>>>,
+    retry: RetryOptions,
+}
+
+impl ResolutionCache {
+    /// Empty cache which retries transient failures of remote files
+    pub fn new(retry: RetryOptions) -> Self {
         Self {
-            schemas: extract_schemas(schemas, client),
+            retry,
+            ..Self::default()
+        }
+    }
+
+    pub fn load(&self, url: Url, client: &Client) -> Result<Schema, Error> {
+        self.get_or_load(url, |url| {
+            self.retry
+                .run(&url, || Schema::load_url_with_client(url.clone(), client))
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.schemas.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn get_or_load<F>(&self, url: Url, load: F) -> Result<Schema, Error>
+    where
+        F: FnOnce(Url) -> Result<Schema, Error>,
+    {
+        if let Some(schema) = self.schemas.lock().unwrap().get(&url) {
+            log::debug!("cached: {}", url);
+            return Ok(schema.clone());
         }
+
+        let schema = load(url.clone())?;
+        self.schemas.lock().unwrap().insert(url, schema.clone());
+
+        Ok(schema)
     }
 }
 
-fn extract_schemas(schemas: &[&Schema], client: &Client) -> HashMap<Url, Schema> {
+impl SchemaStorage {
+    /// Saves schemas with files they reference, error is returned when referenced
+    /// file cannot be loaded
+    pub fn new(schema: &Schema, client: &Client) -> Result<Self, Error> {
+        Self::new_multi(&[schema], client)
+    }
+
+    pub fn new_multi(schemas: &[&Schema], client: &Client) -> Result<Self, Error> {
+        Self::new_multi_with_cache(schemas, client, &ResolutionCache::default())
+    }
+
+    /// External files are read through given cache, ex. one owned by chain
+    pub fn new_multi_with_cache(
+        schemas: &[&Schema],
+        client: &Client,
+        cache: &ResolutionCache,
+    ) -> Result<Self, Error> {
+        Ok(Self {
+            schemas: extract_schemas(schemas, client, cache)?,
+            cache: cache.clone(),
+            client: client.clone(),
+        })
+    }
+
+    /// Loads additional file, ex. merged specification, through storage cache
+    pub fn load(&self, url: Url) -> Result<Schema, Error> {
+        self.cache.load(url, &self.client)
+    }
+
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    pub fn cache(&self) -> &ResolutionCache {
+        &self.cache
+    }
+
+    /// Reads content of non-schema file, ex. markdown documentation, bypassing cache
+    pub fn read(&self, url: &Url) -> Result<String, Error> {
+        self.cache
+            .retry
+            .run(url, || schema::fetch(url, &self.client).map(|(_, c)| c))
+    }
+}
+
+fn extract_schemas(
+    schemas: &[&Schema],
+    client: &Client,
+    cache: &ResolutionCache,
+) -> Result<HashMap<Url, Schema>, Error> {
     let mut resolved: HashMap<Url, Schema> = HashMap::new();
 
     // load everything we need
@@ -40,73 +193,107 @@ fn extract_schemas(schemas: &[&Schema], client: &Client) -> HashMap<Url, Schema>
         let schema = (*original).clone();
         resolved.insert(url.clone(), schema);
 
-        log::trace!("extracting: {}", url);
+        span!("load", url = %url);
 
         // resolve external references
         resolve_externals(
             &mut resolved,
             original.get_url(),
             original.get_body(),
+            "",
             client,
-        );
+            cache,
+        )?;
     }
 
     // absolutize refs
-    resolved
+    Ok(resolved
         .into_iter()
         .map(|(url, mut schema)| {
             absolutize_refs(&url, schema.get_body_mut());
 
             (url, schema)
         })
-        .collect()
+        .collect())
 }
 
 fn resolve_externals(
     resolved: &mut HashMap<Url, Schema>,
     base: &Url,
     schema: &Value,
+    pointer: &str,
     client: &Client,
-) {
+    cache: &ResolutionCache,
+) -> Result<(), Error> {
     match schema {
         Value::Object(ref map) => {
             if let Some(Value::String(reference)) = map.get("$ref") {
-                if let Some(file) = ref_to_file_url(base, reference) {
-                    try_resolve_external(resolved, file, client);
+                if let Some(file) =
+                    ref_to_file_url(base, reference).filter(|f| !include::is_asset(f))
+                {
+                    try_resolve_external(resolved, file.clone(), client, cache).map_err(
+                        |e| match e {
+                            // the innermost document is the most useful one
+                            Error::SchemaReferenceLoad { .. } => e,
+                            e => Error::SchemaReferenceLoad {
+                                url: file.to_string(),
+                                document: base.to_string(),
+                                pointer: format!("{pointer}/$ref"),
+                                source: Box::new(e),
+                            },
+                        },
+                    )?;
                 }
             } else {
-                for (_, value) in map.into_iter() {
-                    resolve_externals(resolved, base, value, client);
+                for (key, value) in map.into_iter() {
+                    let pointer = format!("{pointer}/{}", escape_key(key));
+                    resolve_externals(resolved, base, value, &pointer, client, cache)?;
                 }
             }
         }
         Value::Array(a) => {
-            for x in a.iter() {
-                resolve_externals(resolved, base, x, client);
+            for (i, x) in a.iter().enumerate() {
+                let pointer = format!("{pointer}/{i}");
+                resolve_externals(resolved, base, x, &pointer, client, cache)?;
             }
         }
         _ => {}
     };
+
+    Ok(())
 }
 
-fn try_resolve_external(resolved: &mut HashMap<Url, Schema>, file: Url, client: &Client) {
+fn try_resolve_external(
+    resolved: &mut HashMap<Url, Schema>,
+    file: Url,
+    client: &Client,
+    cache: &ResolutionCache,
+) -> Result<(), Error> {
     if resolved.contains_key(&file) {
-        return;
+        return Ok(());
     }
 
-    let schema = Schema::load_url_with_client(file.clone(), client).unwrap();
+    let schema = cache.load(file.clone(), client)?;
     resolved.insert(file, schema.clone());
 
-    resolve_externals(resolved, schema.get_url(), schema.get_body(), client);
+    resolve_externals(
+        resolved,
+        schema.get_url(),
+        schema.get_body(),
+        "",
+        client,
+        cache,
+    )
 }
 
 fn absolutize_refs(current: &Url, root: &mut Value) {
     match root {
         Value::Object(ref mut map) => {
             if let Some(Value::String(reference)) = map.get_mut("$ref") {
-                // todo: not sure about unwrap
-                let mut absolute = ref_to_url(current, reference).unwrap().to_string();
-                std::mem::swap(reference, &mut absolute);
+                // references which are not valid urls are left for resolver to report
+                if let Some(absolute) = ref_to_url(current, reference) {
+                    *reference = absolute.to_string();
+                }
             } else {
                 for (key, value) in map.into_iter() {
                     if key == "discriminator" && value["mapping"].is_object() {
@@ -130,8 +317,9 @@ fn process_discriminator(current: &Url, data: &mut Value) {
     if let Value::Object(mapping) = data {
         for (_, value) in mapping.into_iter() {
             if let Value::String(reference) = value {
-                let mut absolute = ref_to_url(current, reference).unwrap().to_string();
-                std::mem::swap(reference, &mut absolute);
+                if let Some(absolute) = ref_to_url(current, reference) {
+                    *reference = absolute.to_string();
+                }
             }
         }
     }
@@ -153,3 +341,123 @@ fn ref_to_file_url(base: &Url, reference: &str) -> Option<Url> {
         u
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_cache_shared_between_storages_loads_once() {
+        let cache = ResolutionCache::default();
+        let url = Url::parse("file:///specs/common.json").unwrap();
+        let mut loads = 0;
+
+        for _ in 0..2 {
+            let cloned = cache.clone();
+            let schema = cloned
+                .get_or_load(url.clone(), |_| {
+                    loads += 1;
+                    Ok(Schema::from_json(json!({"type": "string"})))
+                })
+                .unwrap();
+
+            assert_eq!(schema.get_body(), &json!({"type": "string"}));
+        }
+
+        assert_eq!(loads, 1);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_cache_hits_share_body() {
+        let cache = ResolutionCache::default();
+        let url = Url::parse("file:///specs/common.json").unwrap();
+        let load = |_| Ok(Schema::from_json(json!({"type": "string"})));
+
+        let first = cache.get_or_load(url.clone(), load).unwrap();
+        let second = cache.get_or_load(url, load).unwrap();
+
+        assert!(std::ptr::eq(first.get_body(), second.get_body()));
+    }
+
+    #[test]
+    fn test_new_reports_missing_reference() {
+        let schema = Schema::load_url(
+            crate::schema::path_to_url(format!(
+                "{}/resources/test/json-schemas/06-with-local-reference.json",
+                env!("CARGO_MANIFEST_DIR")
+            ))
+            .unwrap(),
+        )
+        .unwrap();
+        let client = Client::new();
+
+        assert!(SchemaStorage::new(&schema, &client).is_ok());
+
+        let broken = Schema::from_json(json!({
+            "properties": {"a/b": {"items": [{"$ref": "file:///missing/schema.json"}]}}
+        }));
+
+        match SchemaStorage::new(&broken, &client) {
+            Err(Error::SchemaReferenceLoad {
+                url,
+                document,
+                pointer,
+                source,
+            }) => {
+                assert_eq!(url, "file:///missing/schema.json");
+                assert_eq!(&document, broken.get_url().as_str());
+                assert_eq!(pointer, "/properties/a~1b/items/0/$ref");
+                assert!(matches!(*source, Error::SchemaLoad { .. }));
+            }
+            _ => panic!("missing reference should be reported with its document"),
+        }
+    }
+
+    #[test]
+    fn test_retry_of_transient_failures() {
+        let url = Url::parse("https://example.com/schema.json").unwrap();
+        let mut retry = RetryOptions::new();
+        retry.with_retries(2).with_backoff(Duration::ZERO);
+
+        let failure = |status| Error::SchemaHttpStatus {
+            url: url.to_string(),
+            status,
+        };
+
+        let mut attempts = 0;
+        let result = retry.run(&url, || {
+            attempts += 1;
+            match attempts {
+                1 => Err(failure(503)),
+                2 => Err(Error::SchemaHttpLoad {
+                    url: url.to_string(),
+                    reason: "connection reset".to_string(),
+                }),
+                _ => Ok(Schema::from_json(json!({"type": "string"}))),
+            }
+        });
+        assert!(result.is_ok());
+        assert_eq!(attempts, 3);
+
+        let mut attempts = 0;
+        let result = retry.run(&url, || -> Result<Schema, Error> {
+            attempts += 1;
+            Err(failure(503))
+        });
+        assert!(matches!(
+            result,
+            Err(Error::SchemaHttpStatus { status: 503, .. })
+        ));
+        assert_eq!(attempts, 3);
+
+        let mut attempts = 0;
+        let result = retry.run(&url, || -> Result<Schema, Error> {
+            attempts += 1;
+            Err(failure(404))
+        });
+        assert!(result.is_err());
+        assert_eq!(attempts, 1);
+    }
+}
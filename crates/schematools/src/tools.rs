@@ -6,6 +6,106 @@ use crate::scope::SchemaScope;
 use serde::Serialize;
 use serde_json::Value;
 
+// single part of each_node path: type:key[predicate][predicate]
+struct Segment {
+    type_: String,
+    key: String,
+    predicates: Vec<Predicate>,
+}
+
+enum Predicate {
+    Has(String),
+    Missing(String),
+    Eq(String, Value),
+    Neq(String, Value),
+}
+
+impl FromStr for Segment {
+    type Err = Error;
+
+    fn from_str(data: &str) -> Result<Self, Self::Err> {
+        let (head, tail) = data.split_at(data.find('[').unwrap_or(data.len()));
+        let (type_, key) = head.split_once(':').unwrap_or(("any", head));
+
+        let predicates = if tail.is_empty() {
+            vec![]
+        } else {
+            tail.strip_prefix('[')
+                .and_then(|s| s.strip_suffix(']'))
+                .ok_or_else(|| Error::IncorrectFilterError(data.to_string()))?
+                .split("][")
+                .map(Predicate::from_str)
+                .collect::<Result<Vec<_>, _>>()?
+        };
+
+        Ok(Self {
+            type_: type_.to_string(),
+            key: key.to_string(),
+            predicates,
+        })
+    }
+}
+
+impl Segment {
+    fn check(&self, node: &Value) -> bool {
+        self.predicates.iter().all(|p| p.check(node))
+    }
+}
+
+impl FromStr for Predicate {
+    type Err = Error;
+
+    fn from_str(data: &str) -> Result<Self, Self::Err> {
+        let value = |s: &str| serde_json::from_str(s).unwrap_or(Value::String(s.to_string()));
+
+        if let Some((key, expected)) = data.split_once("!=") {
+            Ok(Self::Neq(key.to_string(), value(expected)))
+        } else if let Some(("has", key)) = data.split_once('=') {
+            Ok(Self::Has(key.to_string()))
+        } else if let Some(("missing", key)) = data.split_once('=') {
+            Ok(Self::Missing(key.to_string()))
+        } else if let Some((key, expected)) = data.split_once('=') {
+            Ok(Self::Eq(key.to_string(), value(expected)))
+        } else {
+            Err(Error::IncorrectFilterError(data.to_string()))
+        }
+    }
+}
+
+impl Predicate {
+    fn check(&self, node: &Value) -> bool {
+        match self {
+            Self::Has(key) => node.get(key).is_some(),
+            Self::Missing(key) => node.get(key).is_none(),
+            Self::Eq(key, expected) => node.get(key) == Some(expected),
+            Self::Neq(key, expected) => node.get(key) != Some(expected),
+        }
+    }
+}
+
+fn parse_path(path: &str) -> Result<Vec<Segment>, Error> {
+    path.trim_matches('/')
+        .split('/')
+        .map(Segment::from_str)
+        .collect::<Result<Vec<_>, _>>()
+}
+
+pub(crate) fn escape_key(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+pub(crate) fn unescape_key(key: &str) -> String {
+    key.replace("~1", "/").replace("~0", "~")
+}
+
+/// Calls f for each node matching path built from `type:key` segments where key is:
+/// - name of property,
+/// - `*` matching all properties of object or items of array,
+/// - `**` matching current node and all nested objects and arrays.
+///
+/// Every segment may be narrowed by predicates: `[type=object]`, `[type!=array]`, `[has=discriminator]`,
+/// `[missing=x-owner]`.
+/// Keys matched by `*` are passed to f as parts, `**` adds relative json pointer of descent.
 pub fn each_node_mut<F>(
     root: &mut Value,
     context: &mut SchemaScope,
@@ -15,19 +115,15 @@ pub fn each_node_mut<F>(
 where
     F: FnMut(&mut Value, &[String], &mut SchemaScope) -> Result<(), Error>,
 {
-    let parts = path
-        .trim_matches('/')
-        .split('/')
-        .map(|s| s.to_string())
-        .collect::<Vec<String>>();
+    let segments = parse_path(path)?;
 
-    each_mut(root, context, &parts, 0, &mut vec![], &mut f)
+    each_mut(root, context, &segments, 0, &mut vec![], &mut f)
 }
 
 fn each_mut<F>(
     node: &mut Value,
     context: &mut SchemaScope,
-    path: &[String],
+    path: &[Segment],
     index: usize,
     parts: &mut Vec<String>,
     f: &mut F,
@@ -37,111 +133,238 @@ where
 {
     match path.get(index) {
         None => f(node, parts, context),
-        Some(search) => {
-            if let [type_, search_key] = &search.split(':').collect::<Vec<&str>>()[..] {
-                match *search_key {
-                    "*" => match node {
-                        Value::Object(ref mut map) => {
-                            for (key, value) in map {
-                                context.push_str(type_, key);
-
-                                parts.push(key.clone());
-                                each_mut(value, context, path, index + 1, parts, f)?;
-                                parts.pop();
-
-                                context.pop();
-                            }
+        Some(segment) => match segment.key.as_str() {
+            "**" => descend_mut(node, context, path, index, parts, &mut vec![], f),
+            "*" => match node {
+                Value::Object(ref mut map) => {
+                    for (key, value) in map.iter_mut().filter(|(_, v)| segment.check(v)) {
+                        context.push_str(&segment.type_, key);
 
-                            Ok(())
-                        }
-                        _ => Err(Error::NotImplemented),
-                    },
-                    real_path => {
-                        context.push_str(type_, real_path);
+                        parts.push(key.clone());
+                        each_mut(value, context, path, index + 1, parts, f)?;
+                        parts.pop();
 
-                        if let Some(ref mut found) = node.pointer_mut(&["/", real_path].join("")) {
-                            each_mut(found, context, path, index + 1, parts, f)?;
+                        context.pop();
+                    }
+
+                    Ok(())
+                }
+                Value::Array(ref mut list) => {
+                    for (i, value) in list.iter_mut().enumerate() {
+                        if !segment.check(value) {
+                            continue;
                         }
 
+                        context.index(i);
+
+                        parts.push(i.to_string());
+                        each_mut(value, context, path, index + 1, parts, f)?;
+                        parts.pop();
+
                         context.pop();
+                    }
 
-                        Ok(())
+                    Ok(())
+                }
+                _ => Err(Error::NotImplemented),
+            },
+            real_path => {
+                context.push_str(&segment.type_, real_path);
+
+                if let Some(found) = node.pointer_mut(&["/", real_path].join("")) {
+                    if segment.check(found) {
+                        each_mut(found, context, path, index + 1, parts, f)?;
                     }
                 }
-            } else {
-                panic!("Incorrect path: {}", search);
+
+                context.pop();
+
+                Ok(())
+            }
+        },
+    }
+}
+
+fn descend_mut<F>(
+    node: &mut Value,
+    context: &mut SchemaScope,
+    path: &[Segment],
+    index: usize,
+    parts: &mut Vec<String>,
+    trail: &mut Vec<String>,
+    f: &mut F,
+) -> Result<(), Error>
+where
+    F: FnMut(&mut Value, &[String], &mut SchemaScope) -> Result<(), Error>,
+{
+    if !node.is_object() && !node.is_array() {
+        return Ok(());
+    }
+
+    if path[index].check(node) {
+        parts.push(trail.join("/"));
+        each_mut(node, context, path, index + 1, parts, f)?;
+        parts.pop();
+    }
+
+    match node {
+        Value::Object(ref mut map) => {
+            for (key, value) in map {
+                context.push_str(&path[index].type_, key);
+                trail.push(escape_key(key));
+
+                descend_mut(value, context, path, index, parts, trail, f)?;
+
+                trail.pop();
+                context.pop();
             }
         }
+        Value::Array(ref mut list) => {
+            for (i, value) in list.iter_mut().enumerate() {
+                context.index(i);
+                trail.push(i.to_string());
+
+                descend_mut(value, context, path, index, parts, trail, f)?;
+
+                trail.pop();
+                context.pop();
+            }
+        }
+        _ => {}
     }
+
+    Ok(())
 }
 
-pub fn each_node<F>(
-    root: &Value,
+/// Same as [`each_node_mut`] but with read only access to nodes
+pub fn each_node<'a, F>(
+    root: &'a Value,
     context: &mut SchemaScope,
     path: &str,
     mut f: F,
 ) -> Result<(), Error>
 where
-    F: FnMut(&Value, &[String], &mut SchemaScope) -> Result<(), Error>,
+    F: FnMut(&'a Value, &[String], &mut SchemaScope) -> Result<(), Error>,
 {
-    let parts = path
-        .trim_matches('/')
-        .split('/')
-        .map(|s| s.to_string())
-        .collect::<Vec<String>>();
+    let segments = parse_path(path)?;
 
-    each(root, context, &parts, 0, &mut vec![], &mut f)
+    each(root, context, &segments, 0, &mut vec![], &mut f)
 }
 
-fn each<F>(
-    node: &Value,
+fn each<'a, F>(
+    node: &'a Value,
     context: &mut SchemaScope,
-    path: &[String],
+    path: &[Segment],
     index: usize,
     parts: &mut Vec<String>,
     f: &mut F,
 ) -> Result<(), Error>
 where
-    F: FnMut(&Value, &[String], &mut SchemaScope) -> Result<(), Error>,
+    F: FnMut(&'a Value, &[String], &mut SchemaScope) -> Result<(), Error>,
 {
     match path.get(index) {
         None => f(node, parts, context),
-        Some(search) => {
-            if let [type_, search_key] = &search.split(':').collect::<Vec<&str>>()[..] {
-                match *search_key {
-                    "*" => match node {
-                        Value::Object(ref map) => {
-                            for (key, value) in map {
-                                context.push_str(type_, key);
-
-                                parts.push(key.clone());
-                                each(value, context, path, index + 1, parts, f)?;
-                                parts.pop();
-
-                                context.pop();
-                            }
+        Some(segment) => match segment.key.as_str() {
+            "**" => descend(node, context, path, index, parts, &mut vec![], f),
+            "*" => match node {
+                Value::Object(map) => {
+                    for (key, value) in map.iter().filter(|(_, v)| segment.check(v)) {
+                        context.push_str(&segment.type_, key);
 
-                            Ok(())
-                        }
-                        _ => Err(Error::NotImplemented),
-                    },
-                    real_path => {
-                        context.push_str(type_, real_path);
+                        parts.push(key.clone());
+                        each(value, context, path, index + 1, parts, f)?;
+                        parts.pop();
+
+                        context.pop();
+                    }
 
-                        if let Some(ref mut found) = node.pointer(&["/", real_path].join("")) {
-                            each(found, context, path, index + 1, parts, f)?;
+                    Ok(())
+                }
+                Value::Array(list) => {
+                    for (i, value) in list.iter().enumerate() {
+                        if !segment.check(value) {
+                            continue;
                         }
 
+                        context.index(i);
+
+                        parts.push(i.to_string());
+                        each(value, context, path, index + 1, parts, f)?;
+                        parts.pop();
+
                         context.pop();
+                    }
 
-                        Ok(())
+                    Ok(())
+                }
+                _ => Err(Error::NotImplemented),
+            },
+            real_path => {
+                context.push_str(&segment.type_, real_path);
+
+                if let Some(found) = node.pointer(&["/", real_path].join("")) {
+                    if segment.check(found) {
+                        each(found, context, path, index + 1, parts, f)?;
                     }
                 }
-            } else {
-                panic!("Incorrect path: {}", search);
+
+                context.pop();
+
+                Ok(())
+            }
+        },
+    }
+}
+
+fn descend<'a, F>(
+    node: &'a Value,
+    context: &mut SchemaScope,
+    path: &[Segment],
+    index: usize,
+    parts: &mut Vec<String>,
+    trail: &mut Vec<String>,
+    f: &mut F,
+) -> Result<(), Error>
+where
+    F: FnMut(&'a Value, &[String], &mut SchemaScope) -> Result<(), Error>,
+{
+    if !node.is_object() && !node.is_array() {
+        return Ok(());
+    }
+
+    if path[index].check(node) {
+        parts.push(trail.join("/"));
+        each(node, context, path, index + 1, parts, f)?;
+        parts.pop();
+    }
+
+    match node {
+        Value::Object(map) => {
+            for (key, value) in map {
+                context.push_str(&path[index].type_, key);
+                trail.push(escape_key(key));
+
+                descend(value, context, path, index, parts, trail, f)?;
+
+                trail.pop();
+                context.pop();
             }
         }
+        Value::Array(list) => {
+            for (i, value) in list.iter().enumerate() {
+                context.index(i);
+                trail.push(i.to_string());
+
+                descend(value, context, path, index, parts, trail, f)?;
+
+                trail.pop();
+                context.pop();
+            }
+        }
+        _ => {}
     }
+
+    Ok(())
 }
 
 pub struct ArgumentsExtractor<'a> {
@@ -196,7 +419,7 @@ impl Iterator for ArgumentsExtractor<'_> {
     }
 }
 
-pub fn fill_parameters(phrase: &str, data: (impl Serialize + Clone)) -> Result<String, Error> {
+pub fn fill_parameters(phrase: &str, data: impl Serialize + Clone) -> Result<String, Error> {
     let chars = phrase.chars();
     let mut result = String::new();
 
@@ -213,6 +436,7 @@ pub fn fill_parameters(phrase: &str, data: (impl Serialize + Clone)) -> Result<S
                     result.push_str(&match value {
                         Value::String(s) => Ok(s.clone()),
                         Value::Number(n) => Ok(n.to_string()),
+                        Value::Bool(b) => Ok(b.to_string()),
                         _ => Err(Error::CannotFillParameters(path)),
                     }?);
 
@@ -256,6 +480,43 @@ pub fn bump_suffix_number(phrase: &str) -> String {
     }
 }
 
+// letters which are not decomposed to ascii letter with diacritic marks
+const LIGATURES: [(char, &str); 10] = [
+    ('ß', "ss"),
+    ('æ', "ae"),
+    ('Æ', "AE"),
+    ('œ', "oe"),
+    ('Œ', "OE"),
+    ('ø', "o"),
+    ('Ø', "O"),
+    ('ł', "l"),
+    ('Ł', "L"),
+    ('đ', "d"),
+];
+
+/// Converts phrase to ascii: diacritic marks are removed (`café` becomes `cafe`) and
+/// characters without ascii form (other scripts, emoji) become separate words with
+/// their code point (`名` becomes ` u540d `), so different names stay different
+pub fn transliterate(phrase: &str) -> String {
+    use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+    let mut result = String::with_capacity(phrase.len());
+
+    for c in phrase.nfkd().filter(|c| !is_combining_mark(*c)) {
+        if c.is_ascii() {
+            result.push(c);
+        } else if let Some((_, ascii)) = LIGATURES.iter().find(|(l, _)| *l == c) {
+            result.push_str(ascii);
+        } else if c.is_whitespace() {
+            result.push(' ');
+        } else {
+            result.push_str(&format!(" u{:x} ", c as u32));
+        }
+    }
+
+    result
+}
+
 #[derive(Default)]
 pub struct Filter {
     conditions: Vec<ConditionSet>,
@@ -280,10 +541,7 @@ impl FromStr for ConditionSet {
 
 impl ConditionSet {
     pub fn check(&self, data: &Value) -> bool {
-        self.conditions
-            .iter()
-            .map(|c| c.check(data))
-            .all(|result| result)
+        self.conditions.iter().all(|c| c.check(data))
     }
 }
 
@@ -374,17 +632,64 @@ impl Filter {
             return default;
         }
 
-        self.conditions
-            .iter()
-            .map(|c| c.check(data))
-            .any(|result| result)
+        self.conditions.iter().any(|c| c.check(data))
     }
 }
 
+// glob of names or paths as regex, `*` matches within one segment and `**` across them
+pub(crate) fn glob(pattern: &str) -> Result<regex::Regex, regex::Error> {
+    let mut expression = String::from("^");
+    let mut chars = pattern.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                expression.push_str(".*");
+            }
+            '*' => expression.push_str("[^/#]*"),
+            '?' => expression.push_str("[^/#]"),
+            c => expression.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    expression.push('$');
+
+    regex::Regex::new(&expression)
+}
+
+// formats unix time as rfc3339 utc date, see http://howardhinnant.github.io/date_algorithms.html
+pub(crate) fn timestamp(secs: u64) -> String {
+    let (days, rest) = ((secs / 86400) as i64, secs % 86400);
+
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        rest / 3600,
+        rest % 3600 / 60,
+        rest % 60
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_escape_key() {
+        assert_eq!(escape_key("a/b~c"), "a~1b~0c");
+        assert_eq!(unescape_key("a~1b~0c"), "a/b~c");
+        assert_eq!(unescape_key("~01"), "~1");
+    }
+
     #[test]
     fn test_extract_suffix_number_empty() {
         let result = bump_suffix_number("asd");
@@ -399,6 +704,14 @@ mod tests {
         assert_eq!(result, "asd544".to_string());
     }
 
+    #[test]
+    fn test_transliterate() {
+        assert_eq!(transliterate("Café Straße"), "Cafe Strasse");
+        assert_eq!(transliterate("Łódź"), "Lodz");
+        assert_eq!(transliterate("名前"), " u540d  u524d ");
+        assert_eq!(transliterate("ready🚀"), "ready u1f680 ");
+    }
+
     #[test]
     fn test_fill_parameters() {
         let given = serde_json::json!({
@@ -414,6 +727,100 @@ mod tests {
         assert_eq!(result, "some variable 10 ok 2".to_string());
     }
 
+    #[test]
+    fn test_each_node_recursive_with_predicates() {
+        let root = serde_json::json!({
+            "components": {
+                "schemas": {
+                    "Pet": {
+                        "type": "object",
+                        "properties": {
+                            "kind": {
+                                "oneOf": [{"type": "object", "discriminator": {}}]
+                            }
+                        }
+                    },
+                    "Name": {"type": "string", "discriminator": {}}
+                }
+            }
+        });
+
+        let mut found = vec![];
+        each_node(
+            &root,
+            &mut SchemaScope::default(),
+            "/any:components/any:schemas/definition:*/any:**[type=object][has=discriminator]",
+            |_, parts, _| {
+                found.push(parts.to_vec());
+                Ok(())
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            found,
+            vec![vec![
+                "Pet".to_string(),
+                "properties/kind/oneOf/0".to_string()
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_each_node_predicates_and_arrays() {
+        let mut root = serde_json::json!({
+            "definitions": {
+                "a": {"type": "object"},
+                "b": {"type": "string"},
+                "c": {"allOf": [{"type": "string"}, {"type": "object"}]}
+            }
+        });
+
+        let mut found = vec![];
+        each_node(
+            &root,
+            &mut SchemaScope::default(),
+            "/any:definitions/definition:*[type!=string]",
+            |_, parts, _| {
+                found.push(parts.join("/"));
+                Ok(())
+            },
+        )
+        .unwrap();
+
+        assert_eq!(found, vec!["a".to_string(), "c".to_string()]);
+
+        each_node_mut(
+            &mut root,
+            &mut SchemaScope::default(),
+            "/definitions/c/allOf/*[type=string]",
+            |node, _, _| {
+                node["format"] = serde_json::json!("uuid");
+                Ok(())
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            root.pointer("/definitions/c/allOf/0/format"),
+            Some(&serde_json::json!("uuid"))
+        );
+    }
+
+    #[test]
+    fn test_each_node_incorrect_predicate() {
+        let root = serde_json::json!({});
+
+        let result = each_node(
+            &root,
+            &mut SchemaScope::default(),
+            "/any:*[type]",
+            |_, _, _| Ok(()),
+        );
+
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_argument_extractor() {
         let given = "codegen openapi -f - --templates-dir codegen/ --format \"gofmt -w\" --target-dir pkg/client/ -o namespace=testing -o clientName=TestingClient";
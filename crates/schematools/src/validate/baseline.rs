@@ -0,0 +1,192 @@
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use super::Violation;
+use crate::error::Error;
+use crate::tools::timestamp;
+
+/// Acknowledged violations which don't fail validation until they expire
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Baseline {
+    #[serde(default)]
+    pub acknowledged: Vec<Acknowledgement>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Acknowledgement {
+    pub rule: String,
+    pub pointer: String,
+    // exact message of violation, any violation of rule at pointer when omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    pub justification: String,
+    // last day (yyyy-mm-dd) acknowledgement is valid
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<String>,
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let error = |reason: String| Error::BaselineError {
+            path: path.display().to_string(),
+            reason,
+        };
+
+        let content = std::fs::read_to_string(path).map_err(|e| error(e.to_string()))?;
+        let baseline: Baseline =
+            serde_yaml::from_str(&content).map_err(|e| error(e.to_string()))?;
+
+        for acknowledgement in &baseline.acknowledged {
+            if acknowledgement.justification.trim().is_empty() {
+                return Err(error(format!(
+                    "justification of {} at {} is empty",
+                    acknowledgement.rule, acknowledgement.pointer
+                )));
+            }
+
+            if let Some(expires) = &acknowledgement.expires {
+                if !is_date(expires) {
+                    return Err(error(format!("expiry {expires} is not yyyy-mm-dd date")));
+                }
+            }
+        }
+
+        Ok(baseline)
+    }
+
+    /// Violations which are not acknowledged or whose acknowledgement expired before `today`
+    pub fn apply(&self, violations: Vec<Violation>, today: &str) -> Vec<Violation> {
+        let mut used = vec![false; self.acknowledged.len()];
+
+        let remaining = violations
+            .into_iter()
+            .filter(|violation| {
+                let Some((i, acknowledgement)) = self
+                    .acknowledged
+                    .iter()
+                    .enumerate()
+                    .find(|(_, a)| a.matches(violation))
+                else {
+                    return true;
+                };
+                used[i] = true;
+
+                match &acknowledgement.expires {
+                    Some(expires) if expires.as_str() < today => {
+                        log::warn!("Acknowledgement of {violation} expired at {expires}");
+                        true
+                    }
+                    _ => {
+                        log::info!(
+                            "Acknowledged {violation}: {}",
+                            acknowledgement.justification
+                        );
+                        false
+                    }
+                }
+            })
+            .collect();
+
+        for (acknowledgement, _) in self.acknowledged.iter().zip(used).filter(|(_, u)| !u) {
+            log::warn!(
+                "Acknowledgement of {} at {} doesn't match any violation and can be removed",
+                acknowledgement.rule,
+                acknowledgement.pointer
+            );
+        }
+
+        remaining
+    }
+}
+
+impl Acknowledgement {
+    fn matches(&self, violation: &Violation) -> bool {
+        self.rule == violation.rule
+            && self.pointer == violation.pointer
+            && self
+                .message
+                .as_ref()
+                .is_none_or(|m| *m == violation.message)
+    }
+}
+
+/// Current utc date in yyyy-mm-dd format
+pub fn today() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+
+    timestamp(secs)[..10].to_string()
+}
+
+fn is_date(value: &str) -> bool {
+    value.len() == 10
+        && value.char_indices().all(|(i, c)| match i {
+            4 | 7 => c == '-',
+            _ => c.is_ascii_digit(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_baseline() {
+        let baseline: Baseline = serde_yaml::from_str(
+            r#"
+acknowledged:
+  - rule: backward
+    pointer: /components/schemas/Pet
+    message: property name became required
+    justification: no clients send pets without name
+  - rule: backward
+    pointer: /components/schemas/Owner
+    justification: owners are not exposed anymore
+    expires: 2024-01-31
+  - rule: forward
+    pointer: /components/schemas/Tag
+    justification: stale
+"#,
+        )
+        .unwrap();
+
+        let violations = vec![
+            Violation::new(
+                "backward",
+                "/components/schemas/Pet",
+                "property name became required".to_string(),
+            ),
+            Violation::new(
+                "backward",
+                "/components/schemas/Pet",
+                "property age became required".to_string(),
+            ),
+            Violation::new(
+                "backward",
+                "/components/schemas/Owner",
+                "schema Owner was removed".to_string(),
+            ),
+        ];
+
+        let remaining = baseline
+            .apply(violations.clone(), "2024-01-31")
+            .into_iter()
+            .map(|v| v.message)
+            .collect::<Vec<_>>();
+        assert_eq!(remaining, ["property age became required"]);
+
+        let remaining = baseline.apply(violations, "2024-02-01");
+        assert_eq!(remaining.len(), 2);
+    }
+
+    #[test]
+    fn test_is_date() {
+        assert!(is_date("2024-01-31"));
+        assert!(!is_date("31-01-2024"));
+        assert!(!is_date("2024-1-31"));
+    }
+}
@@ -0,0 +1,135 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+use crate::tools::glob;
+
+/// Dependency rules of references between files, ex. service may reference only shared schemas
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct BoundaryPolicy {
+    #[serde(default)]
+    pub boundaries: Vec<Boundary>,
+}
+
+/// Globs match file path (`*` within one directory, `**` across them), target globs containing
+/// `#` match json pointer too, ex. `billing/**#/components/schemas/Internal*`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Boundary {
+    pub from: String,
+    // when not empty only matching targets may be referenced
+    #[serde(default)]
+    pub allow: Vec<String>,
+    #[serde(default)]
+    pub deny: Vec<String>,
+}
+
+impl BoundaryPolicy {
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let error = |reason: String| Error::BoundaryPolicyError {
+            path: path.display().to_string(),
+            reason,
+        };
+
+        let content = std::fs::read_to_string(path).map_err(|e| error(e.to_string()))?;
+        let policy: BoundaryPolicy =
+            serde_yaml::from_str(&content).map_err(|e| error(e.to_string()))?;
+
+        for boundary in &policy.boundaries {
+            for pattern in std::iter::once(&boundary.from)
+                .chain(&boundary.allow)
+                .chain(&boundary.deny)
+            {
+                glob(pattern).map_err(|e| error(format!("{pattern}: {e}")))?;
+            }
+        }
+
+        Ok(policy)
+    }
+
+    /// Reason why reference from file `source` to `target` (file#pointer) is forbidden
+    pub fn check(&self, source: &str, target: &str) -> Option<String> {
+        let (file, _) = target.split_once('#').unwrap_or((target, ""));
+        let matches = |pattern: &String| {
+            let subject = match pattern.contains('#') {
+                true => target,
+                false => file,
+            };
+            glob(pattern).is_ok_and(|re| re.is_match(subject))
+        };
+
+        self.boundaries
+            .iter()
+            .filter(|b| glob(&b.from).is_ok_and(|re| re.is_match(source)))
+            .find_map(|boundary| {
+                if let Some(pattern) = boundary.deny.iter().find(|p| matches(p)) {
+                    return Some(format!(
+                        "{source} cannot reference {target}, denied by {pattern}"
+                    ));
+                }
+
+                (!boundary.allow.is_empty() && !boundary.allow.iter().any(matches)).then(|| {
+                    format!(
+                        "{source} cannot reference {target}, only {} allowed",
+                        boundary.allow.join(", ")
+                    )
+                })
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_boundaries() {
+        let policy: BoundaryPolicy = serde_yaml::from_str(
+            r#"
+boundaries:
+  - from: billing/**
+    allow: [billing/**, common/**]
+  - from: "**"
+    deny: ["**#/components/schemas/Internal*"]
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            policy.check(
+                "billing/openapi.yaml",
+                "common/types.yaml#/components/schemas/Money"
+            ),
+            None
+        );
+        assert_eq!(
+            policy.check(
+                "billing/openapi.yaml",
+                "customers/openapi.yaml#/components/schemas/Customer"
+            ),
+            Some("billing/openapi.yaml cannot reference customers/openapi.yaml#/components/schemas/Customer, only billing/**, common/** allowed".to_string())
+        );
+        assert!(policy
+            .check(
+                "orders/openapi.yaml",
+                "customers/openapi.yaml#/components/schemas/InternalCustomer"
+            )
+            .is_some());
+        assert_eq!(
+            policy.check(
+                "orders/openapi.yaml",
+                "customers/openapi.yaml#/components/schemas/Customer"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_glob() {
+        assert!(glob("billing/*.yaml").unwrap().is_match("billing/a.yaml"));
+        assert!(!glob("billing/*.yaml")
+            .unwrap()
+            .is_match("billing/v1/a.yaml"));
+        assert!(glob("billing/**").unwrap().is_match("billing/v1/a.yaml"));
+    }
+}
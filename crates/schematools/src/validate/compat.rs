@@ -0,0 +1,496 @@
+use std::collections::HashSet;
+
+use serde_json::Value;
+
+use super::paths::{resolve, METHODS};
+use super::Violation;
+use crate::schema::Schema;
+use crate::tools::{escape_key, unescape_key};
+
+pub struct Compat;
+
+pub struct CompatOptions {
+    pub against: Schema,
+    pub mode: CompatMode,
+    pub endpoints: bool,
+}
+
+/// Compatibility of evolution in terms of schema registries: backward means data of previous
+/// version is accepted by current one (consumers upgrade first), forward means data of current
+/// version is accepted by previous one (producers upgrade first), full requires both
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CompatMode {
+    Backward,
+    Forward,
+    Full,
+}
+
+impl std::str::FromStr for CompatMode {
+    type Err = ();
+
+    fn from_str(input: &str) -> Result<CompatMode, Self::Err> {
+        match input {
+            "backward" => Ok(CompatMode::Backward),
+            "forward" => Ok(CompatMode::Forward),
+            "full" => Ok(CompatMode::Full),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Compat {
+    pub fn options(against: Schema) -> CompatOptions {
+        CompatOptions {
+            against,
+            mode: CompatMode::Backward,
+            endpoints: false,
+        }
+    }
+}
+
+impl CompatOptions {
+    pub fn with_mode(&mut self, value: CompatMode) -> &mut Self {
+        self.mode = value;
+        self
+    }
+
+    pub fn with_endpoints(&mut self, value: bool) -> &mut Self {
+        self.endpoints = value;
+        self
+    }
+
+    /// Incompatible changes of schema compared to previous version given as `against`
+    pub fn check(&self, schema: &Schema) -> Vec<Violation> {
+        let current = schema.get_body();
+        let previous = self.against.get_body();
+
+        let mut violations = vec![];
+
+        if self.mode != CompatMode::Forward {
+            violations.extend(self.direction("backward", current, previous));
+        }
+        if self.mode != CompatMode::Backward {
+            violations.extend(self.direction("forward", previous, current));
+        }
+
+        violations
+    }
+
+    // reader is a spec which has to accept data of writer: server side of requests and
+    // consumer of components, writer is the other side
+    fn direction(&self, rule: &'static str, reader: &Value, writer: &Value) -> Vec<Violation> {
+        let mut check = Check {
+            rule,
+            visited: HashSet::new(),
+            violations: vec![],
+        };
+
+        if let Some(schemas) = writer
+            .pointer("/components/schemas")
+            .and_then(|s| s.as_object())
+        {
+            for (name, schema) in schemas {
+                let pointer = format!("/components/schemas/{}", escape_key(name));
+                match reader.pointer(&pointer) {
+                    Some(other) => {
+                        let reference = format!("#{pointer}");
+                        check.schema(
+                            &pointer,
+                            Side::new(reader, other).with_reference(&reference),
+                            Side::new(writer, schema).with_reference(&reference),
+                        )
+                    }
+                    // schemas which appear later are not a problem of previous version
+                    None if rule == "backward" => {
+                        check.push(&pointer, format!("schema {name} was removed"))
+                    }
+                    None => {}
+                }
+            }
+        }
+
+        if self.endpoints {
+            check.endpoints(reader, writer);
+        }
+
+        check.violations
+    }
+}
+
+#[derive(Clone)]
+struct Side<'a> {
+    spec: &'a Value,
+    node: &'a Value,
+    reference: Option<String>,
+}
+
+impl<'a> Side<'a> {
+    fn new(spec: &'a Value, node: &'a Value) -> Self {
+        Self {
+            spec,
+            node: resolve(spec, node),
+            reference: node
+                .get("$ref")
+                .and_then(|r| r.as_str())
+                .map(|r| r.to_string()),
+        }
+    }
+
+    fn with_reference(mut self, reference: &str) -> Self {
+        self.reference = Some(reference.to_string());
+        self
+    }
+
+    fn get(&self, key: &str) -> Option<&'a Value> {
+        self.node.get(key)
+    }
+
+    fn child(&self, key: &str) -> Option<Side<'a>> {
+        self.node.get(key).map(|n| Side::new(self.spec, n))
+    }
+
+    fn types(&self) -> Option<Vec<&'a str>> {
+        let mut types = match self.get("type")? {
+            Value::String(s) => vec![s.as_str()],
+            Value::Array(list) => list.iter().filter_map(|t| t.as_str()).collect(),
+            _ => return None,
+        };
+        if self.get("nullable").and_then(|n| n.as_bool()) == Some(true) {
+            types.push("null");
+        }
+        Some(types)
+    }
+}
+
+struct Check {
+    rule: &'static str,
+    visited: HashSet<(Option<String>, Option<String>)>,
+    violations: Vec<Violation>,
+}
+
+impl Check {
+    fn push(&mut self, pointer: &str, message: String) {
+        self.violations
+            .push(Violation::new(self.rule, pointer, message));
+    }
+
+    fn schema(&mut self, pointer: &str, reader: Side, writer: Side) {
+        // referenced schemas are compared once, which also stops recursion
+        let key = (reader.reference.clone(), writer.reference.clone());
+        if key != (None, None) && !self.visited.insert(key) {
+            return;
+        }
+
+        match (reader.types(), writer.types()) {
+            (Some(r), None) => self.push(pointer, format!("type was narrowed to {}", r.join("|"))),
+            (Some(r), Some(w)) => {
+                for type_ in w {
+                    let widened = type_ == "integer" && r.contains(&"number");
+                    if !r.contains(&type_) && !widened {
+                        self.push(pointer, format!("type {type_} is no longer accepted"));
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        if let Some(Value::Array(accepted)) = reader.get("enum") {
+            match writer.get("enum") {
+                Some(Value::Array(values)) => {
+                    for value in values.iter().filter(|v| !accepted.contains(v)) {
+                        self.push(pointer, format!("enum value {value} is no longer accepted"));
+                    }
+                }
+                _ => self.push(pointer, "enum was added".to_string()),
+            }
+        }
+
+        for key in ["maxLength", "maximum", "maxItems", "maxProperties"] {
+            if let Some(limit) = reader.get(key).and_then(|v| v.as_f64()) {
+                match writer.get(key).and_then(|v| v.as_f64()) {
+                    Some(other) if other <= limit => {}
+                    _ => self.push(pointer, format!("{key} was decreased to {limit}")),
+                }
+            }
+        }
+
+        for key in ["minLength", "minimum", "minItems", "minProperties"] {
+            if let Some(limit) = reader.get(key).and_then(|v| v.as_f64()) {
+                match writer.get(key).and_then(|v| v.as_f64()) {
+                    Some(other) if other >= limit => {}
+                    _ => self.push(pointer, format!("{key} was increased to {limit}")),
+                }
+            }
+        }
+
+        for key in ["pattern", "format"] {
+            if let Some(value) = reader.get(key) {
+                if writer.get(key) != Some(value) {
+                    self.push(pointer, format!("{key} was changed to {value}"));
+                }
+            }
+        }
+
+        let required = |side: &Side| -> Vec<String> {
+            side.get("required")
+                .and_then(|r| r.as_array())
+                .map(|r| {
+                    r.iter()
+                        .filter_map(|n| Some(n.as_str()?.to_string()))
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        let writer_required = required(&writer);
+        for name in required(&reader) {
+            if !writer_required.contains(&name) {
+                self.push(pointer, format!("property {name} became required"));
+            }
+        }
+
+        let closed = reader.get("additionalProperties") == Some(&Value::Bool(false));
+        if let Some(Value::Object(properties)) = writer.get("properties") {
+            for name in properties.keys() {
+                let path = format!("{pointer}/properties/{}", escape_key(name));
+                let reader_property = reader
+                    .get("properties")
+                    .and_then(|p| p.get(name))
+                    .map(|p| Side::new(reader.spec, p));
+
+                match reader_property {
+                    Some(reader_property) => {
+                        let writer_property = Side::new(writer.spec, &properties[name]);
+                        self.schema(&path, reader_property, writer_property)
+                    }
+                    None if closed => {
+                        self.push(pointer, format!("property {name} is no longer accepted"))
+                    }
+                    None => {}
+                }
+            }
+        }
+
+        match (
+            reader.child("additionalProperties"),
+            writer.child("additionalProperties"),
+        ) {
+            _ if closed && writer.get("additionalProperties") != Some(&Value::Bool(false)) => self
+                .push(
+                    pointer,
+                    "additional properties are no longer accepted".to_string(),
+                ),
+            (Some(r), Some(w)) if r.node.is_object() && w.node.is_object() => {
+                self.schema(&format!("{pointer}/additionalProperties"), r, w)
+            }
+            _ => {}
+        }
+
+        if let (Some(r), Some(w)) = (reader.child("items"), writer.child("items")) {
+            if r.node.is_object() && w.node.is_object() {
+                self.schema(&format!("{pointer}/items"), r, w);
+            }
+        }
+    }
+
+    // reader plays server (receives requests), writer plays client (reads responses)
+    fn endpoints(&mut self, reader: &Value, writer: &Value) {
+        let Some(paths) = writer.get("paths").and_then(|p| p.as_object()) else {
+            return;
+        };
+
+        for (path, item) in paths {
+            for method in METHODS {
+                let Some(operation) = item.get(method) else {
+                    continue;
+                };
+                let pointer = format!("/paths/{}/{method}", escape_key(path));
+                let name = format!("{} {path}", method.to_uppercase());
+
+                let Some(server) = reader.pointer(&pointer) else {
+                    // operations which appear later are not a problem of previous version
+                    if self.rule == "backward" {
+                        self.push(&pointer, format!("{name} is missing"));
+                    }
+                    continue;
+                };
+
+                let parameters = |spec: &Value, node: &Value| -> Vec<(String, String, bool)> {
+                    [item, node]
+                        .iter()
+                        .filter_map(|n| n.get("parameters")?.as_array())
+                        .flatten()
+                        .map(|p| resolve(spec, p))
+                        .filter_map(|p| {
+                            Some((
+                                p.get("name")?.as_str()?.to_string(),
+                                p.get("in")?.as_str()?.to_string(),
+                                p.get("required").and_then(|r| r.as_bool()) == Some(true),
+                            ))
+                        })
+                        .collect()
+                };
+                let client_parameters = parameters(writer, operation);
+                for (field, location, required) in parameters(reader, server) {
+                    let known = client_parameters
+                        .iter()
+                        .any(|(f, l, r)| *f == field && *l == location && (*r || !required));
+                    if required && !known {
+                        self.push(
+                            &pointer,
+                            format!("{name}: {location} parameter {field} became required"),
+                        );
+                    }
+                }
+
+                let body = "/requestBody/content/application~1json/schema";
+                if let (Some(r), Some(w)) = (
+                    resolve_pointer(reader, server, body),
+                    resolve_pointer(writer, operation, body),
+                ) {
+                    self.schema(&format!("{pointer}{body}"), r, w);
+                }
+
+                let Some(responses) = operation.get("responses").and_then(|r| r.as_object()) else {
+                    continue;
+                };
+                for status in responses.keys() {
+                    let response = format!(
+                        "/responses/{}/content/application~1json/schema",
+                        escape_key(status)
+                    );
+                    // responses are written by server
+                    if let (Some(r), Some(w)) = (
+                        resolve_pointer(writer, operation, &response),
+                        resolve_pointer(reader, server, &response),
+                    ) {
+                        self.schema(&format!("{pointer}{response}"), r, w);
+                    }
+                }
+            }
+        }
+    }
+}
+
+// follows references of every segment, ex. of requestBody or responses defined in components
+fn resolve_pointer<'a>(spec: &'a Value, node: &'a Value, pointer: &str) -> Option<Side<'a>> {
+    let mut current = resolve(spec, node);
+    for segment in pointer.split('/').skip(1) {
+        let key = unescape_key(segment);
+        current = resolve(spec, current.get(&key)?);
+    }
+    Some(Side::new(spec, current))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema(body: Value) -> Schema {
+        Schema::from_json(body)
+    }
+
+    fn messages(mode: CompatMode, current: Value, previous: Value) -> Vec<String> {
+        Compat::options(schema(previous))
+            .with_mode(mode)
+            .with_endpoints(true)
+            .check(&schema(current))
+            .into_iter()
+            .map(|v| format!("{}: {}", v.rule, v.message))
+            .collect()
+    }
+
+    #[test]
+    fn test_compat_of_components() {
+        let previous = json!({"components": {"schemas": {
+            "Pet": {
+                "type": "object",
+                "required": ["name"],
+                "properties": {
+                    "name": {"type": "string", "maxLength": 20},
+                    "kind": {"type": "string", "enum": ["cat", "dog"]},
+                    "tags": {"type": "array", "items": {"$ref": "#/components/schemas/Tag"}}
+                }
+            },
+            "Tag": {"type": "string"},
+            "Owner": {"type": "object"}
+        }}});
+        let current = json!({"components": {"schemas": {
+            "Pet": {
+                "type": "object",
+                "required": ["name", "kind"],
+                "properties": {
+                    "name": {"type": "string", "maxLength": 10},
+                    "kind": {"type": "string", "enum": ["cat", "dog", "fish"]},
+                    "tags": {"type": "array", "items": {"$ref": "#/components/schemas/Tag"}}
+                }
+            },
+            "Tag": {"type": "integer"}
+        }}});
+
+        assert_eq!(
+            messages(CompatMode::Backward, current.clone(), previous.clone()),
+            [
+                "backward: property kind became required",
+                "backward: maxLength was decreased to 10",
+                "backward: type string is no longer accepted",
+                "backward: schema Owner was removed",
+            ]
+        );
+        assert_eq!(
+            messages(CompatMode::Forward, current.clone(), previous.clone()),
+            [
+                "forward: enum value \"fish\" is no longer accepted",
+                "forward: type integer is no longer accepted",
+            ]
+        );
+        assert_eq!(messages(CompatMode::Full, current, previous).len(), 6);
+        assert!(messages(CompatMode::Full, json!({}), json!({})).is_empty());
+    }
+
+    #[test]
+    fn test_compat_of_endpoints() {
+        let previous = json!({"paths": {
+            "/pets": {
+                "get": {"responses": {"200": {"content": {"application/json": {"schema": {
+                    "type": "string", "enum": ["a", "b"]
+                }}}}}},
+                "post": {}
+            }
+        }});
+        let current = json!({"paths": {
+            "/pets": {
+                "get": {
+                    "parameters": [{"in": "query", "name": "limit", "required": true}],
+                    "responses": {"200": {"content": {"application/json": {"schema": {
+                        "type": "string", "enum": ["a", "b", "c"]
+                    }}}}}
+                }
+            }
+        }});
+
+        assert_eq!(
+            messages(CompatMode::Backward, current, previous),
+            [
+                "backward: GET /pets: query parameter limit became required",
+                "backward: enum value \"c\" is no longer accepted",
+                "backward: POST /pets is missing",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compat_of_added_endpoint() {
+        let previous = json!({"paths": {"/pets": {"get": {}}}});
+        let current = json!({"paths": {"/pets": {"get": {}, "post": {}}, "/owners": {"get": {}}}});
+
+        assert!(messages(CompatMode::Full, current.clone(), previous.clone()).is_empty());
+        assert_eq!(
+            messages(CompatMode::Full, previous, current),
+            [
+                "backward: POST /pets is missing",
+                "backward: GET /owners is missing"
+            ]
+        );
+    }
+}
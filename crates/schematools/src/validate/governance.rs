@@ -0,0 +1,88 @@
+use serde_json::Value;
+
+use super::Violation;
+use crate::tools::escape_key;
+
+/// Extensions every component has to carry by default
+pub const GOVERNANCE: [&str; 3] = ["x-owner", "x-pii", "x-classification"];
+
+/// Components (of every kind) missing any of required extensions, components which are only
+/// a reference are skipped as they get metadata of referenced one
+pub fn governance(spec: &Value, required: &[String]) -> Vec<Violation> {
+    let mut violations = vec![];
+
+    let Some(components) = spec.get("components").and_then(|c| c.as_object()) else {
+        return violations;
+    };
+
+    for (kind, entries) in components {
+        let Some(entries) = entries.as_object() else {
+            continue;
+        };
+
+        for (name, component) in entries {
+            let Some(component) = component.as_object() else {
+                continue;
+            };
+            if component.contains_key("$ref") {
+                continue;
+            }
+
+            let missing = required
+                .iter()
+                .filter(|e| !component.contains_key(e.as_str()))
+                .cloned()
+                .collect::<Vec<_>>();
+
+            if !missing.is_empty() {
+                violations.push(Violation::new(
+                    "governance",
+                    &format!("/components/{}/{}", escape_key(kind), escape_key(name)),
+                    format!("{kind} {name} misses {}", missing.join(", ")),
+                ));
+            }
+        }
+    }
+
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_governance() {
+        let spec = json!({"components": {
+            "schemas": {
+                "Pet": {"type": "object", "x-owner": "pets", "x-pii": false, "x-classification": "public"},
+                "Owner": {"type": "object", "x-owner": "pets"},
+                "Alias": {"$ref": "#/components/schemas/Pet"}
+            },
+            "parameters": {
+                "Limit": {"in": "query", "name": "limit"}
+            }
+        }});
+
+        let required = GOVERNANCE.map(String::from);
+        let violations = governance(&spec, &required)
+            .into_iter()
+            .map(|v| (v.pointer, v.message))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            violations,
+            [
+                (
+                    "/components/schemas/Owner".to_string(),
+                    "schemas Owner misses x-pii, x-classification".to_string()
+                ),
+                (
+                    "/components/parameters/Limit".to_string(),
+                    "parameters Limit misses x-owner, x-pii, x-classification".to_string()
+                )
+            ]
+        );
+    }
+}
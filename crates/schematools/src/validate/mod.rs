@@ -4,6 +4,47 @@ use serde_json::{from_slice, Value};
 use crate::error::Error;
 use crate::schema::Schema;
 
+pub mod baseline;
+pub mod boundaries;
+pub mod compat;
+pub mod governance;
+pub mod operations;
+pub mod paths;
+
+/// Issue of specification which is valid against json schema of openapi but is not consistent
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    pub rule: String,
+    // json pointer of offending node
+    pub pointer: String,
+    pub message: String,
+}
+
+impl Violation {
+    pub fn new(rule: &str, pointer: &str, message: String) -> Self {
+        Self {
+            rule: rule.to_string(),
+            pointer: pointer.to_string(),
+            message,
+        }
+    }
+}
+
+impl std::fmt::Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] {} ({})", self.rule, self.message, self.pointer)
+    }
+}
+
+/// Consistency rules of openapi specification which json schema cannot express
+pub fn openapi_rules(schema: &Schema) -> Vec<Violation> {
+    let spec = schema.get_body();
+
+    let mut violations = paths::path_parameters(spec);
+    violations.extend(operations::operations(spec));
+    violations
+}
+
 pub fn validate_openapi(schema: &Schema) -> Result<(), Error> {
     let value = schema.get_body();
 
@@ -26,7 +67,17 @@ pub fn validate_openapi(schema: &Schema) -> Result<(), Error> {
 
             Err(Error::SchemaValidation(schema.get_url().to_string()))
         }
-        _ => Ok(()),
+        _ => {
+            let violations = openapi_rules(schema);
+            for violation in &violations {
+                log::error!("{violation}");
+            }
+
+            match violations.is_empty() {
+                true => Ok(()),
+                false => Err(Error::SchemaValidation(schema.get_url().to_string())),
+            }
+        }
     }
 }
 
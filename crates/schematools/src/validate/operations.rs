@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use super::paths::{resolve, METHODS};
+use super::Violation;
+use crate::tools::escape_key;
+
+/// OperationIds have to be unique, paths differing only in names of parameters are ambiguous
+/// routes and parameter (unique by name and location) cannot be listed twice in one list.
+pub fn operations(spec: &Value) -> Vec<Violation> {
+    let mut violations = vec![];
+
+    let Some(paths) = spec.get("paths").and_then(|p| p.as_object()) else {
+        return violations;
+    };
+
+    let mut operation_ids: HashMap<&str, String> = HashMap::new();
+    let mut routes: HashMap<String, &str> = HashMap::new();
+
+    for (path, item) in paths {
+        let pointer = format!("/paths/{}", escape_key(path));
+
+        match routes.get(&route(path)) {
+            Some(other) => violations.push(Violation::new(
+                "route-conflict",
+                &pointer,
+                format!("path {path} is ambiguous with {other}"),
+            )),
+            None => {
+                routes.insert(route(path), path);
+            }
+        }
+
+        duplicate_parameters(spec, item, &pointer, path, &mut violations);
+
+        for method in METHODS {
+            let Some(operation) = item.get(method) else {
+                continue;
+            };
+            let location = format!("{pointer}/{method}");
+            let name = format!("{} {path}", method.to_uppercase());
+
+            duplicate_parameters(spec, operation, &location, &name, &mut violations);
+
+            if let Some(id) = operation.get("operationId").and_then(|o| o.as_str()) {
+                match operation_ids.get(id) {
+                    Some(other) => violations.push(Violation::new(
+                        "operation-id",
+                        &format!("{location}/operationId"),
+                        format!("{name}: operationId {id} is already used by {other}"),
+                    )),
+                    None => {
+                        operation_ids.insert(id, name);
+                    }
+                }
+            }
+        }
+    }
+
+    violations
+}
+
+fn duplicate_parameters(
+    spec: &Value,
+    node: &Value,
+    pointer: &str,
+    name: &str,
+    violations: &mut Vec<Violation>,
+) {
+    let Some(parameters) = node.get("parameters").and_then(|p| p.as_array()) else {
+        return;
+    };
+
+    let mut seen = vec![];
+    for (i, parameter) in parameters.iter().enumerate() {
+        let parameter = resolve(spec, parameter);
+        let (Some(field), Some(location)) = (
+            parameter.get("name").and_then(|n| n.as_str()),
+            parameter.get("in").and_then(|i| i.as_str()),
+        ) else {
+            continue;
+        };
+
+        // header names are case insensitive
+        let key = match location {
+            "header" => (field.to_lowercase(), location),
+            _ => (field.to_string(), location),
+        };
+
+        if seen.contains(&key) {
+            violations.push(Violation::new(
+                "duplicate-parameter",
+                &format!("{pointer}/parameters/{i}"),
+                format!("{name}: {location} parameter {field} is defined more than once"),
+            ));
+        } else {
+            seen.push(key);
+        }
+    }
+}
+
+// path with names of parameters removed, ex. /users/{}
+fn route(path: &str) -> String {
+    let mut result = String::new();
+    let mut rest = path;
+
+    while let Some((before, after)) = rest.split_once('{') {
+        result.push_str(before);
+        result.push_str("{}");
+        rest = after.split_once('}').map(|(_, a)| a).unwrap_or_default();
+    }
+    result.push_str(rest);
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_operations() {
+        let spec = json!({
+            "paths": {
+                "/users/{id}": {
+                    "get": {"operationId": "getUser"},
+                    "delete": {
+                        "operationId": "getUser",
+                        "parameters": [
+                            {"in": "header", "name": "X-Trace"},
+                            {"in": "header", "name": "x-trace"},
+                            {"in": "query", "name": "x-trace"}
+                        ]
+                    }
+                },
+                "/users/{name}": {
+                    "get": {"operationId": "getUserByName"}
+                }
+            }
+        });
+
+        let violations = operations(&spec)
+            .into_iter()
+            .map(|v| (v.rule, v.pointer))
+            .collect::<Vec<_>>();
+
+        assert_eq!(violations.len(), 3);
+        assert!(violations.contains(&(
+            "operation-id".to_string(),
+            "/paths/~1users~1{id}/delete/operationId".to_string()
+        )));
+        assert!(violations.contains(&(
+            "duplicate-parameter".to_string(),
+            "/paths/~1users~1{id}/delete/parameters/1".to_string()
+        )));
+        assert!(violations.iter().any(|(rule, _)| rule == "route-conflict"));
+    }
+
+    #[test]
+    fn test_route() {
+        assert_eq!(
+            route("/users/{id}/posts/{postId}.json"),
+            "/users/{}/posts/{}.json"
+        );
+        assert_eq!(route("/users"), "/users");
+    }
+}
@@ -0,0 +1,183 @@
+use serde_json::Value;
+
+use super::Violation;
+use crate::tools::escape_key;
+
+pub(crate) const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Every `{param}` of path has to be defined as required path parameter (on path item or
+/// operation) and every path parameter has to appear in path. Schemas of the same parameter
+/// have to agree on type and format across operations of path.
+pub fn path_parameters(spec: &Value) -> Vec<Violation> {
+    let mut violations = vec![];
+
+    let Some(paths) = spec.get("paths").and_then(|p| p.as_object()) else {
+        return violations;
+    };
+
+    for (path, item) in paths {
+        let pointer = format!("/paths/{}", escape_key(path));
+        let template = template_names(path);
+        let common = parameters(spec, item);
+
+        let operations = METHODS
+            .iter()
+            .filter_map(|method| Some((*method, item.get(*method)?)))
+            .collect::<Vec<_>>();
+
+        let mut schemas: Vec<(String, &str, Option<&Value>, Option<&Value>)> = vec![];
+
+        for (method, operation) in &operations {
+            let mut defined = common.clone();
+            for parameter in parameters(spec, operation) {
+                defined.retain(|(name, _)| *name != parameter.0);
+                defined.push(parameter);
+            }
+
+            let location = format!("{pointer}/{method}");
+            let operation_name = format!("{} {path}", method.to_uppercase());
+
+            for name in &template {
+                if !defined.iter().any(|(n, _)| n == name) {
+                    violations.push(Violation::new(
+                        "path-parameters",
+                        &location,
+                        format!("{operation_name}: parameter {name} is not defined"),
+                    ));
+                }
+            }
+
+            for (name, parameter) in &defined {
+                if !template.contains(name) {
+                    violations.push(Violation::new(
+                        "path-parameters",
+                        &location,
+                        format!("{operation_name}: parameter {name} is not part of path"),
+                    ));
+                    continue;
+                }
+
+                if parameter.get("required").and_then(|r| r.as_bool()) != Some(true) {
+                    violations.push(Violation::new(
+                        "path-parameters",
+                        &location,
+                        format!("{operation_name}: parameter {name} has to be required"),
+                    ));
+                }
+
+                let schema = parameter.get("schema").map(|s| resolve(spec, s));
+                let type_ = schema.and_then(|s| s.get("type"));
+                let format = schema.and_then(|s| s.get("format"));
+
+                match schemas.iter().find(|(n, ..)| n == name) {
+                    Some((_, other, t, f)) if (*t, *f) != (type_, format) => {
+                        violations.push(Violation::new(
+                            "path-parameters",
+                            &location,
+                            format!(
+                                "{operation_name}: parameter {name} has different type than in {} {path}",
+                                other.to_uppercase()
+                            ),
+                        ))
+                    }
+                    Some(_) => {}
+                    None => schemas.push((name.clone(), method, type_, format)),
+                }
+            }
+        }
+    }
+
+    violations
+}
+
+// path parameters of path item or operation with resolved local references
+fn parameters<'a>(spec: &'a Value, node: &'a Value) -> Vec<(String, &'a Value)> {
+    node.get("parameters")
+        .and_then(|p| p.as_array())
+        .map(|list| {
+            list.iter()
+                .map(|p| resolve(spec, p))
+                .filter(|p| p.get("in").and_then(|i| i.as_str()) == Some("path"))
+                .filter_map(|p| Some((p.get("name")?.as_str()?.to_string(), p)))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub(crate) fn resolve<'a>(spec: &'a Value, node: &'a Value) -> &'a Value {
+    match node
+        .get("$ref")
+        .and_then(|r| r.as_str())
+        .and_then(|r| r.strip_prefix('#'))
+        .and_then(|pointer| spec.pointer(pointer))
+    {
+        Some(resolved) if resolved != node => resolve(spec, resolved),
+        _ => node,
+    }
+}
+
+pub(crate) fn template_names(path: &str) -> Vec<String> {
+    path.split('{')
+        .skip(1)
+        .filter_map(|s| s.split_once('}'))
+        .map(|(name, _)| name.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_path_parameters() {
+        let spec = json!({
+            "components": {
+                "parameters": {
+                    "UserId": {"in": "path", "name": "userId", "required": true, "schema": {"type": "string"}}
+                }
+            },
+            "paths": {
+                "/users/{userId}": {
+                    "parameters": [{"$ref": "#/components/parameters/UserId"}],
+                    "get": {},
+                    "delete": {
+                        "parameters": [{"in": "path", "name": "userId", "required": true, "schema": {"type": "integer"}}]
+                    }
+                },
+                "/users/{userId}/posts/{postId}": {
+                    "get": {
+                        "parameters": [
+                            {"in": "path", "name": "userId", "schema": {"type": "string"}},
+                            {"in": "path", "name": "id", "required": true, "schema": {"type": "string"}}
+                        ]
+                    }
+                },
+                "/health": {"get": {}}
+            }
+        });
+
+        let messages = path_parameters(&spec)
+            .into_iter()
+            .map(|v| v.message)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            messages,
+            [
+                "DELETE /users/{userId}: parameter userId has different type than in GET /users/{userId}",
+                "GET /users/{userId}/posts/{postId}: parameter postId is not defined",
+                "GET /users/{userId}/posts/{postId}: parameter userId has to be required",
+                "GET /users/{userId}/posts/{postId}: parameter id is not part of path",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_template_names() {
+        assert_eq!(template_names("/a/{b}/c/{d}.json"), ["b", "d"]);
+        assert!(template_names("/a").is_empty());
+    }
+}
@@ -0,0 +1,299 @@
+use std::path::Path;
+
+use serde_json::Value;
+use url::Url;
+use walkdir::WalkDir;
+
+use crate::{
+    error::Error,
+    process::graph::{Graph, GraphNode, Grapher},
+    schema::{path_to_url, Schema},
+    storage::{ref_to_url, SchemaStorage},
+    tools,
+    validate::{self, boundaries::BoundaryPolicy, Violation},
+    Client,
+};
+
+const EXTENSIONS: [&str; 3] = ["yaml", "yml", "json"];
+
+/// Openapi specifications of one directory (ex. one per bounded context) referencing each other
+pub struct Workspace {
+    pub root: Url,
+    pub specs: Vec<Schema>,
+}
+
+impl Workspace {
+    /// Loads every yaml/json file with `openapi` key found under directory, hidden ones are skipped
+    pub fn load(dir: &Path, client: &Client) -> Result<Self, Error> {
+        let root = dir
+            .canonicalize()
+            .ok()
+            .and_then(|d| Url::from_directory_path(d).ok())
+            .ok_or_else(|| Error::SchemaInvalidPath {
+                path: dir.display().to_string(),
+            })?;
+
+        let mut specs = vec![];
+        for entry in WalkDir::new(dir)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|e| e.depth() == 0 || !e.file_name().to_string_lossy().starts_with('.'))
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let extension = entry.path().extension().and_then(|e| e.to_str());
+            if !extension.is_some_and(|e| EXTENSIONS.contains(&e)) {
+                continue;
+            }
+
+            let url = path_to_url(entry.path().display().to_string())?;
+            let schema = Schema::load_url_with_client(url, client)?;
+            if schema.get_body().get("openapi").is_some() {
+                specs.push(schema);
+            }
+        }
+
+        Ok(Self { root, specs })
+    }
+
+    /// Path of specification relative to workspace directory
+    pub fn name(&self, url: &Url) -> String {
+        self.root
+            .make_relative(url)
+            .unwrap_or_else(|| url.to_string())
+    }
+
+    /// Validation and consistency rules of every specification together with references between
+    /// them, pointers of violations are prefixed with specification path
+    pub fn validate(&self, storage: &SchemaStorage) -> Vec<Violation> {
+        let mut violations = vec![];
+
+        for spec in &self.specs {
+            let name = self.name(spec.get_url());
+
+            if let Err(e) = validate::validate_openapi(spec) {
+                violations.push(Violation::new(
+                    "openapi",
+                    &format!("{name}#"),
+                    e.to_string(),
+                ));
+                continue;
+            }
+
+            let mut references = vec![];
+            collect_refs(spec.get_body(), String::new(), &mut references);
+
+            for (pointer, reference) in references {
+                if reference.starts_with('#') {
+                    continue;
+                }
+
+                let location = format!("{name}#{pointer}");
+                let Some(mut url) = ref_to_url(spec.get_url(), &reference) else {
+                    violations.push(Violation::new(
+                        "reference",
+                        &location,
+                        format!("reference {reference} is invalid"),
+                    ));
+                    continue;
+                };
+                let fragment = url.fragment().unwrap_or_default().to_string();
+                url.set_fragment(None);
+
+                let document = match self.specs.iter().find(|s| *s.get_url() == url) {
+                    Some(document) => Ok(document.clone()),
+                    None => storage.load(url),
+                };
+
+                let message = match document {
+                    Err(e) => format!("reference {reference} cannot be loaded: {e}"),
+                    Ok(document) if document.get_body().pointer(&fragment).is_none() => {
+                        format!("reference {reference} doesn't point to existing node")
+                    }
+                    Ok(_) => continue,
+                };
+                violations.push(Violation::new("reference", &location, message));
+            }
+        }
+
+        violations
+    }
+
+    /// References between files forbidden by policy, internal references are always allowed
+    pub fn boundaries(&self, policy: &BoundaryPolicy) -> Vec<Violation> {
+        let mut violations = vec![];
+
+        for spec in &self.specs {
+            let name = self.name(spec.get_url());
+
+            let mut references = vec![];
+            collect_refs(spec.get_body(), String::new(), &mut references);
+
+            for (pointer, reference) in references {
+                let Some(mut url) = ref_to_url(spec.get_url(), &reference) else {
+                    continue;
+                };
+                let fragment = url.fragment().unwrap_or_default().to_string();
+                url.set_fragment(None);
+
+                let file = self.name(&url);
+                if file == name {
+                    continue;
+                }
+
+                if let Some(message) = policy.check(&name, &format!("{file}#{fragment}")) {
+                    violations.push(Violation::new(
+                        "boundary",
+                        &format!("{name}#{pointer}"),
+                        message,
+                    ));
+                }
+            }
+        }
+
+        violations
+    }
+
+    /// Dependency graph of components of all specifications, nodes are grouped by file
+    pub fn graph(&self) -> Result<Graph, Error> {
+        let mut combined = Graph::default();
+
+        for spec in &self.specs {
+            let graph = Grapher::options().with_external(true).process(spec)?;
+            let name = self.name(spec.get_url());
+
+            // ids of internal nodes have no file, external ones are relative to specification
+            let id = |id: &str, node: &GraphNode| -> (String, String) {
+                match &node.file {
+                    None => (format!("{name}{id}"), name.clone()),
+                    Some(_) => {
+                        let url = spec.get_url().join(id).ok();
+                        let file = url
+                            .as_ref()
+                            .map(|u| {
+                                let mut document = u.clone();
+                                document.set_fragment(None);
+                                self.name(&document)
+                            })
+                            .unwrap_or_default();
+                        let fragment = url
+                            .as_ref()
+                            .and_then(|u| u.fragment())
+                            .unwrap_or_default()
+                            .to_string();
+
+                        (format!("{file}#{fragment}"), file)
+                    }
+                }
+            };
+
+            for (from, to) in &graph.edges {
+                let (from, _) = id(from, &graph.nodes[from]);
+                let (to, _) = id(to, &graph.nodes[to]);
+                combined.edges.insert((from, to));
+            }
+
+            for (key, node) in &graph.nodes {
+                let (key, file) = id(key, node);
+                combined.nodes.entry(key).or_insert(GraphNode {
+                    name: node.name.clone(),
+                    file: Some(file),
+                });
+            }
+        }
+
+        Ok(combined)
+    }
+}
+
+fn collect_refs(node: &Value, pointer: String, references: &mut Vec<(String, String)>) {
+    match node {
+        Value::Object(map) => {
+            if let Some(Value::String(reference)) = map.get("$ref") {
+                references.push((pointer.clone(), reference.clone()));
+            }
+
+            for (key, value) in map {
+                let key = tools::escape_key(key);
+                collect_refs(value, format!("{pointer}/{key}"), references);
+            }
+        }
+        Value::Array(list) => {
+            for (i, value) in list.iter().enumerate() {
+                collect_refs(value, format!("{pointer}/{i}"), references);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::ResolutionCache;
+    use std::fs;
+
+    fn write(dir: &Path, file: &str, content: &str) {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_workspace() {
+        let dir = std::env::temp_dir().join("schematools-workspace");
+        let _ = fs::remove_dir_all(&dir);
+
+        let info = "info:\n  title: t\n  version: '1'\n";
+        write(
+            &dir,
+            "billing/openapi.yaml",
+            &format!("openapi: 3.0.0\n{info}paths: {{}}\ncomponents:\n  schemas:\n    Invoice:\n      type: object\n      properties:\n        customer:\n          $ref: ../customers/openapi.yaml#/components/schemas/Customer\n        missing:\n          $ref: ../customers/openapi.yaml#/components/schemas/Missing\n"),
+        );
+        write(
+            &dir,
+            "customers/openapi.yaml",
+            &format!("openapi: 3.0.0\n{info}paths: {{}}\ncomponents:\n  schemas:\n    Customer:\n      type: object\n"),
+        );
+        write(&dir, "config.yaml", "not: openapi\n");
+        write(&dir, ".hidden/openapi.yaml", "openapi: 3.0.0\n");
+
+        let client = Client::new();
+        let workspace = Workspace::load(&dir, &client).unwrap();
+
+        assert_eq!(
+            workspace
+                .specs
+                .iter()
+                .map(|s| workspace.name(s.get_url()))
+                .collect::<Vec<_>>(),
+            ["billing/openapi.yaml", "customers/openapi.yaml"]
+        );
+
+        let storage =
+            SchemaStorage::new_multi_with_cache(&[], &client, &ResolutionCache::default()).unwrap();
+        let violations = workspace.validate(&storage);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(
+            violations[0].pointer,
+            "billing/openapi.yaml#/components/schemas/Invoice/properties/missing"
+        );
+
+        let policy: BoundaryPolicy =
+            serde_yaml::from_str("boundaries:\n  - from: billing/**\n    deny: [customers/**]\n")
+                .unwrap();
+        let violations = workspace.boundaries(&policy);
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].rule, "boundary");
+
+        let graph = workspace.graph().unwrap();
+        assert!(graph.edges.contains(&(
+            "billing/openapi.yaml#/components/schemas/Invoice".to_string(),
+            "customers/openapi.yaml#/components/schemas/Customer".to_string()
+        )));
+        assert_eq!(
+            graph.nodes["customers/openapi.yaml#/components/schemas/Customer"].file,
+            Some("customers/openapi.yaml".to_string())
+        );
+    }
+}